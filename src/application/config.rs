@@ -0,0 +1,356 @@
+//! Application configuration.
+//!
+//! This module contains `AppConfig`, the user-tunable behavior switches read by
+//! application services. Values default to the standard TermiGroove behavior and
+//! can be overridden through `TERMIGROOVE_*` environment variables at startup.
+
+use crate::application::state::PadLayout;
+use crate::audio::{MetronomeConfig, VolumeCurve};
+use crate::domain::timing::{QuantizeGrid, TimeSignature};
+use std::path::PathBuf;
+
+/// Peak level below which audio counts as silence when trimming.
+pub const DEFAULT_TRIM_THRESHOLD: f32 = 0.01;
+
+/// Swing amount, in percent, Alt+S switches on when none is configured.
+pub const DEFAULT_SWING: u8 = 50;
+
+/// Pad grid column cap when none is configured.
+pub const DEFAULT_MAX_PAD_COLUMNS: u16 = 10;
+
+/// Rows PageUp/PageDown move in the selected-files list when none is
+/// configured.
+pub const DEFAULT_PAGE_STEP: usize = 10;
+
+/// Cursor glyph of the selected-files list when none is configured.
+pub const DEFAULT_HIGHLIGHT_SYMBOL: &str = "▶ ";
+
+/// Cursor color of the selected-files list when none is configured.
+pub const DEFAULT_HIGHLIGHT_COLOR: &str = "green";
+
+/// What Space does in Pads mode when no pads are mapped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmptyPadsSpace {
+    /// Show a hint instead of starting a count-in
+    #[default]
+    Hint,
+    /// Start recording an empty loop (useful as a metronome bed)
+    RecordEmptyLoop,
+}
+
+/// What happens to sounding pads when leaving Pads mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LeavePadsVoices {
+    /// Cut every voice, so held or looping pads cannot get stuck
+    #[default]
+    Stop,
+    /// Let voices ring out on their own
+    Ring,
+}
+
+/// User-tunable application behavior.
+#[derive(Debug, Clone, Default)]
+pub struct AppConfig {
+    /// Space behavior when the pad mapping is empty
+    pub empty_pads_space: EmptyPadsSpace,
+    /// Sounding pads when Esc leaves Pads mode
+    pub leave_pads: LeavePadsVoices,
+    /// Order pad keys are handed to selected samples in
+    pub pad_layout: PadLayout,
+    /// Flash the screen border on each bar downbeat during playback/recording
+    pub downbeat_flash: bool,
+    /// Show a target that pulses on the beat in Pads mode
+    pub beat_pulse: bool,
+    /// Mark beats and bars on the loop progress timeline
+    pub timeline_grid: bool,
+    /// Directory whose samples are selected on startup
+    pub default_kit_dir: Option<PathBuf>,
+    /// Enter Pads mode right after loading the default kit
+    pub kit_auto_pads: bool,
+    /// Maximum random timing offset for loop playback, in ms (0 = off)
+    pub humanize_ms: u16,
+    /// Fixed seed for humanize jitter; random per run when unset
+    pub humanize_seed: Option<u64>,
+    /// Pad debounce window in ms; `DEFAULT_DEBOUNCE_MS` when unset
+    pub debounce_ms: Option<u128>,
+    /// Trim leading/trailing silence from samples at preload
+    pub trim_silence: bool,
+    /// Silence threshold for trimming; `DEFAULT_TRIM_THRESHOLD` when unset
+    pub trim_threshold: Option<f32>,
+    /// Master output volume in 0.0..=1.0; unity when unset
+    pub master_gain: Option<f32>,
+    /// How volume controls map to output gain; linear by default
+    pub volume_curve: VolumeCurve,
+    /// Output device name for the metronome; main output when unset
+    pub metronome_device: Option<String>,
+    /// Metronome pitch, volume and downbeat accent; the plain 1 kHz tick
+    /// when unset
+    pub metronome: Option<MetronomeConfig>,
+    /// Column cap for the pad grid; `DEFAULT_MAX_PAD_COLUMNS` when unset
+    pub max_pad_columns: Option<u16>,
+    /// Fixed pad grid column count (e.g. 4 for a 4x4 grid); the grid fits
+    /// the pad count when unset
+    pub pad_grid_columns: Option<u16>,
+    /// Rows PageUp/PageDown move in the selected-files list;
+    /// `DEFAULT_PAGE_STEP` when unset
+    pub page_step: Option<usize>,
+    /// Cursor glyph of the selected-files list; `DEFAULT_HIGHLIGHT_SYMBOL`
+    /// when unset
+    pub highlight_symbol: Option<String>,
+    /// Cursor color name of the selected-files list (e.g. "yellow",
+    /// "#ffaa00"); `DEFAULT_HIGHLIGHT_COLOR` when unset
+    pub highlight_color: Option<String>,
+    /// Practice ratio as (cycles on, cycles off): the loop plays `on`
+    /// cycles then is silent for `off`; always plays when unset
+    pub practice_cycles: Option<(u32, u32)>,
+    /// Grid recorded hits snap to; hits stay as played when unset
+    pub quantize: Option<QuantizeGrid>,
+    /// Playback swing in percent (0..=100); straight when unset
+    pub swing: Option<u8>,
+    /// Scroll footer messages too long to fit instead of truncating them
+    pub marquee: bool,
+    /// Up/Down in the selected-files list wrap around at the ends
+    pub wrap_selection: bool,
+    /// Describe each pad hit in words (e.g. "Played kick on Q") for
+    /// screen readers
+    pub announce_pads: bool,
+    /// Leave a freshly recorded loop paused at its start instead of
+    /// playing it right away
+    pub hold_after_record: bool,
+    /// Let an overdub still going at the loop end lengthen the loop to the
+    /// next bar
+    pub extend_overdubs: bool,
+    /// Keep pad hits from the last count-in beat at the start of the take
+    pub buffer_count_in: bool,
+    /// Time signature for new loops; 4/4 when unset
+    pub time_signature: Option<TimeSignature>,
+}
+
+impl AppConfig {
+    /// Threshold to trim samples with, or `None` when trimming is off.
+    pub fn trim_silence_threshold(&self) -> Option<f32> {
+        self.trim_silence
+            .then(|| self.trim_threshold.unwrap_or(DEFAULT_TRIM_THRESHOLD))
+    }
+
+    /// Most columns the pad grid may use.
+    pub fn max_pad_columns(&self) -> u16 {
+        self.max_pad_columns.unwrap_or(DEFAULT_MAX_PAD_COLUMNS)
+    }
+
+    /// Rows PageUp/PageDown move in the selected-files list.
+    pub fn selection_page_step(&self) -> usize {
+        self.page_step.unwrap_or(DEFAULT_PAGE_STEP)
+    }
+
+    /// Cursor glyph for the selected-files list.
+    pub fn highlight_symbol(&self) -> &str {
+        self.highlight_symbol
+            .as_deref()
+            .unwrap_or(DEFAULT_HIGHLIGHT_SYMBOL)
+    }
+
+    /// Cursor color name for the selected-files list.
+    pub fn highlight_color(&self) -> &str {
+        self.highlight_color
+            .as_deref()
+            .unwrap_or(DEFAULT_HIGHLIGHT_COLOR)
+    }
+
+    /// Build a config from the process environment.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Build a config from a key lookup, falling back to defaults for
+    /// missing or unrecognized values.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let mut config = Self::default();
+        if let Some(value) = lookup("TERMIGROOVE_EMPTY_PADS_SPACE") {
+            match value.trim().to_ascii_lowercase().as_str() {
+                "hint" => config.empty_pads_space = EmptyPadsSpace::Hint,
+                "record" => config.empty_pads_space = EmptyPadsSpace::RecordEmptyLoop,
+                _ => {}
+            }
+        }
+        if let Some(value) = lookup("TERMIGROOVE_LEAVE_PADS") {
+            match value.trim().to_ascii_lowercase().as_str() {
+                "stop" => config.leave_pads = LeavePadsVoices::Stop,
+                "ring" => config.leave_pads = LeavePadsVoices::Ring,
+                _ => {}
+            }
+        }
+        if let Some(value) = lookup("TERMIGROOVE_PAD_LAYOUT") {
+            match value.trim().to_ascii_lowercase().as_str() {
+                "rows" => config.pad_layout = PadLayout::Rows,
+                "home" => config.pad_layout = PadLayout::HomeRow,
+                "columns" => config.pad_layout = PadLayout::Columns,
+                _ => {}
+            }
+        }
+        if let Some(on) = parse_flag(&lookup, "TERMIGROOVE_DOWNBEAT_FLASH") {
+            config.downbeat_flash = on;
+        }
+        if let Some(on) = parse_flag(&lookup, "TERMIGROOVE_BEAT_PULSE") {
+            config.beat_pulse = on;
+        }
+        if let Some(on) = parse_flag(&lookup, "TERMIGROOVE_TIMELINE_GRID") {
+            config.timeline_grid = on;
+        }
+        if let Some(value) = lookup("TERMIGROOVE_DEFAULT_KIT_DIR")
+            && !value.trim().is_empty()
+        {
+            config.default_kit_dir = Some(PathBuf::from(value.trim()));
+        }
+        if let Some(on) = parse_flag(&lookup, "TERMIGROOVE_KIT_AUTO_PADS") {
+            config.kit_auto_pads = on;
+        }
+        if let Some(ms) = lookup("TERMIGROOVE_HUMANIZE_MS").and_then(|v| v.trim().parse().ok()) {
+            config.humanize_ms = ms;
+        }
+        if let Some(ms) = lookup("TERMIGROOVE_DEBOUNCE_MS").and_then(|v| v.trim().parse().ok()) {
+            config.debounce_ms = Some(ms);
+        }
+        if let Some(seed) = lookup("TERMIGROOVE_HUMANIZE_SEED").and_then(|v| v.trim().parse().ok())
+        {
+            config.humanize_seed = Some(seed);
+        }
+        if let Some(on) = parse_flag(&lookup, "TERMIGROOVE_AUTO_PLAY") {
+            config.hold_after_record = !on;
+        }
+        if let Some(on) = parse_flag(&lookup, "TERMIGROOVE_EXTEND_OVERDUBS") {
+            config.extend_overdubs = on;
+        }
+        if let Some(on) = parse_flag(&lookup, "TERMIGROOVE_BUFFER_COUNT_IN") {
+            config.buffer_count_in = on;
+        }
+        if let Some(on) = parse_flag(&lookup, "TERMIGROOVE_MARQUEE") {
+            config.marquee = on;
+        }
+        if let Some(on) = parse_flag(&lookup, "TERMIGROOVE_WRAP_SELECTION") {
+            config.wrap_selection = on;
+        }
+        if let Some(on) = parse_flag(&lookup, "TERMIGROOVE_ANNOUNCE_PADS") {
+            config.announce_pads = on;
+        }
+        if let Some(on) = parse_flag(&lookup, "TERMIGROOVE_TRIM_SILENCE") {
+            config.trim_silence = on;
+        }
+        if let Some(threshold) = lookup("TERMIGROOVE_TRIM_THRESHOLD")
+            .and_then(|v| v.trim().parse::<f32>().ok())
+            .filter(|t| (0.0..1.0).contains(t))
+        {
+            config.trim_threshold = Some(threshold);
+        }
+        if let Some(gain) = lookup("TERMIGROOVE_MASTER_GAIN")
+            .and_then(|v| v.trim().parse::<f32>().ok())
+            .filter(|g| (0.0..=1.0).contains(g))
+        {
+            config.master_gain = Some(gain);
+        }
+        if let Some(value) = lookup("TERMIGROOVE_VOLUME_CURVE") {
+            match value.trim().to_ascii_lowercase().as_str() {
+                "linear" => config.volume_curve = VolumeCurve::Linear,
+                "perceptual" => config.volume_curve = VolumeCurve::Perceptual,
+                _ => {}
+            }
+        }
+        if let Some(name) = lookup("TERMIGROOVE_METRONOME_DEVICE")
+            && !name.trim().is_empty()
+        {
+            config.metronome_device = Some(name.trim().to_string());
+        }
+        // Each setting overrides the default tick on its own; the accent
+        // follows the beat pitch unless set
+        let hz = |key: &str| {
+            lookup(key)
+                .and_then(|v| v.trim().parse::<f32>().ok())
+                .filter(|f| (20.0..=20_000.0).contains(f))
+        };
+        let freq = hz("TERMIGROOVE_METRONOME_FREQ");
+        let accent_freq = hz("TERMIGROOVE_METRONOME_ACCENT_FREQ");
+        let volume = lookup("TERMIGROOVE_METRONOME_VOLUME")
+            .and_then(|v| v.trim().parse::<f32>().ok())
+            .filter(|v| (0.0..=1.0).contains(v));
+        if freq.is_some() || accent_freq.is_some() || volume.is_some() {
+            let default = MetronomeConfig::default();
+            let freq = freq.unwrap_or(default.freq);
+            config.metronome = Some(MetronomeConfig {
+                freq,
+                volume: volume.unwrap_or(default.volume),
+                accent_freq: accent_freq.unwrap_or(freq),
+            });
+        }
+        if let Some(columns) = lookup("TERMIGROOVE_MAX_PAD_COLUMNS")
+            .and_then(|v| v.trim().parse::<u16>().ok())
+            .filter(|c| *c > 0)
+        {
+            config.max_pad_columns = Some(columns);
+        }
+        if let Some(columns) = lookup("TERMIGROOVE_PAD_GRID_COLUMNS")
+            .and_then(|v| v.trim().parse::<u16>().ok())
+            .filter(|c| *c > 0)
+        {
+            config.pad_grid_columns = Some(columns);
+        }
+        if let Some(step) = lookup("TERMIGROOVE_PAGE_STEP")
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .filter(|s| *s > 0)
+        {
+            config.page_step = Some(step);
+        }
+        if let Some(symbol) = lookup("TERMIGROOVE_HIGHLIGHT_SYMBOL")
+            && !symbol.trim().is_empty()
+        {
+            // Keep the cursor apart from the file name
+            let symbol = symbol.trim_start();
+            config.highlight_symbol = Some(if symbol.ends_with(' ') {
+                symbol.to_string()
+            } else {
+                format!("{symbol} ")
+            });
+        }
+        // "on:off", e.g. "3:1" plays three cycles and leaves one silent
+        if let Some((on, off)) = lookup("TERMIGROOVE_PRACTICE").and_then(|v| {
+            let (on, off) = v.trim().split_once(':')?;
+            Some((
+                on.trim().parse::<u32>().ok()?,
+                off.trim().parse::<u32>().ok()?,
+            ))
+        }) && on > 0
+            && off > 0
+        {
+            config.practice_cycles = Some((on, off));
+        }
+        // "1/4", "1/8" or "1/16"; "off" and anything else keep hits as played
+        if let Some(value) = lookup("TERMIGROOVE_QUANTIZE") {
+            config.quantize = QuantizeGrid::parse(&value);
+        }
+        if let Some(percent) = lookup("TERMIGROOVE_SWING")
+            .and_then(|v| v.trim().trim_end_matches('%').parse::<u8>().ok())
+            .filter(|p| *p <= 100)
+        {
+            config.swing = Some(percent);
+        }
+        // "3/4", "6/8", ...; anything unparsable keeps 4/4
+        if let Some(value) = lookup("TERMIGROOVE_TIME_SIGNATURE") {
+            config.time_signature = TimeSignature::parse(&value);
+        }
+        if let Some(color) = lookup("TERMIGROOVE_HIGHLIGHT_COLOR")
+            && !color.trim().is_empty()
+        {
+            config.highlight_color = Some(color.trim().to_string());
+        }
+        config
+    }
+}
+
+/// Read an on/off flag: "1", "on" or "true" is on, "0", "off" or "false"
+/// is off, and anything else leaves the default.
+fn parse_flag(lookup: &impl Fn(&str) -> Option<String>, key: &str) -> Option<bool> {
+    match lookup(key)?.trim().to_ascii_lowercase().as_str() {
+        "1" | "on" | "true" => Some(true),
+        "0" | "off" | "false" => Some(false),
+        _ => None,
+    }
+}
@@ -27,6 +27,10 @@ pub enum KeyCode {
     Delete,
     /// Backspace key
     Backspace,
+    /// Page Up key
+    PageUp,
+    /// Page Down key
+    PageDown,
     /// Other unmapped key (for future-proofing)
     Other(String),
 }
@@ -52,7 +56,8 @@ pub enum InputAction {
         /// Modifier keys that were held
         modifiers: KeyModifiers,
     },
-    /// A key was released (for future use)
+    /// A key was released (only reported by terminals with the kitty
+    /// keyboard protocol)
     KeyReleased {
         /// The key that was released
         key: KeyCode,
@@ -82,6 +87,8 @@ impl From<ratatui::crossterm::event::KeyCode> for KeyCode {
             CrosstermKeyCode::Char(c) => KeyCode::Char(c),
             CrosstermKeyCode::Delete => KeyCode::Delete,
             CrosstermKeyCode::Backspace => KeyCode::Backspace,
+            CrosstermKeyCode::PageUp => KeyCode::PageUp,
+            CrosstermKeyCode::PageDown => KeyCode::PageDown,
             other => KeyCode::Other(format!("{:?}", other)),
         }
     }
@@ -43,6 +43,8 @@ pub struct LoopStateDto {
     pub was_recording: Option<bool>,
     /// Number of tracks in the loop
     pub track_count: usize,
+    /// Time left until the cycle wraps (for Recording/Playing states)
+    pub remaining: Option<Duration>,
 }
 
 impl<A: AudioBus, C: Clock> From<&LoopEngine<A, C>> for LoopStateDto {
@@ -50,6 +52,7 @@ impl<A: AudioBus, C: Clock> From<&LoopEngine<A, C>> for LoopStateDto {
         let state = engine.state();
         let track_count = engine.tracks_count();
         let now = engine.now();
+        let remaining = engine.time_remaining();
 
         match state {
             crate::domain::r#loop::LoopState::Idle => LoopStateDto {
@@ -60,6 +63,7 @@ impl<A: AudioBus, C: Clock> From<&LoopEngine<A, C>> for LoopStateDto {
                 saved_offset: None,
                 was_recording: None,
                 track_count,
+                remaining,
             },
             crate::domain::r#loop::LoopState::Ready {
                 ticks_remaining,
@@ -72,6 +76,7 @@ impl<A: AudioBus, C: Clock> From<&LoopEngine<A, C>> for LoopStateDto {
                 saved_offset: None,
                 was_recording: None,
                 track_count,
+                remaining,
             },
             crate::domain::r#loop::LoopState::Recording {
                 start_time,
@@ -86,6 +91,7 @@ impl<A: AudioBus, C: Clock> From<&LoopEngine<A, C>> for LoopStateDto {
                     saved_offset: None,
                     was_recording: None,
                     track_count,
+                    remaining,
                 }
             }
             crate::domain::r#loop::LoopState::Playing {
@@ -101,6 +107,7 @@ impl<A: AudioBus, C: Clock> From<&LoopEngine<A, C>> for LoopStateDto {
                     saved_offset: None,
                     was_recording: None,
                     track_count,
+                    remaining,
                 }
             }
             crate::domain::r#loop::LoopState::Paused {
@@ -116,6 +123,7 @@ impl<A: AudioBus, C: Clock> From<&LoopEngine<A, C>> for LoopStateDto {
                 saved_offset: Some(saved_offset),
                 was_recording: Some(was_recording),
                 track_count,
+                remaining,
             },
         }
     }
@@ -0,0 +1,63 @@
+//! Application errors.
+//!
+//! This module contains `AppError`, the failures application use cases report
+//! back to the caller. Each variant carries the data the presentation layer
+//! needs to format its message, so tests can match on the variant instead of
+//! the rendered text.
+
+use crate::application::state::SUPPORTED_AUDIO_EXTENSIONS;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Failure of an application use case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppError {
+    /// Pads mode was requested with nothing selected
+    NoSelection,
+    /// A selected file is not a supported audio format
+    UnsupportedFile(PathBuf),
+    /// Every selected file has disappeared from disk
+    FilesMissing(Vec<PathBuf>),
+    /// The audio output could not be opened
+    AudioUnavailable,
+    /// No pad is mapped to the key
+    PadUnmapped(char),
+    /// The key is outside the pad keyboard layout
+    NotPadKey(char),
+    /// Another pad already uses the key
+    PadKeyTaken(char),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::NoSelection => write!(f, "Select at least one file first"),
+            AppError::UnsupportedFile(path) => write!(
+                f,
+                "Unsupported file (only {}): {}",
+                SUPPORTED_AUDIO_EXTENSIONS.join("/"),
+                file_name(path)
+            ),
+            AppError::FilesMissing(paths) => {
+                let names = paths.iter().map(|p| file_name(p)).collect::<Vec<_>>();
+                write!(f, "Selected files no longer exist: {}", names.join(", "))
+            }
+            AppError::AudioUnavailable => write!(f, "Audio output unavailable"),
+            AppError::PadUnmapped(key) => write!(f, "No pad on {}", key_label(*key)),
+            AppError::NotPadKey(key) => write!(f, "{} is not a pad key", key_label(*key)),
+            AppError::PadKeyTaken(key) => {
+                write!(f, "{} already has a pad", key_label(*key))
+            }
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+fn key_label(key: char) -> char {
+    key.to_ascii_uppercase()
+}
+
+fn file_name(path: &Path) -> &str {
+    path.file_name().and_then(|s| s.to_str()).unwrap_or("?")
+}
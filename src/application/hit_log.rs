@@ -0,0 +1,66 @@
+//! Live pad-hit log.
+//!
+//! This module contains `HitLog`, a record of every live pad hit with its
+//! time since logging started, kept apart from the loop so hits are captured
+//! whether or not anything is recording. The log is written out as plain
+//! text, one `seconds<TAB>key` line per hit, for later review or
+//! transcription.
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// A live pad hit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoggedHit {
+    pub key: char,
+    /// Time since the log started
+    pub time: Duration,
+}
+
+/// Pad hits buffered while logging is on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HitLog {
+    started: Duration,
+    hits: Vec<LoggedHit>,
+}
+
+impl HitLog {
+    /// Start an empty log at clock time `started`.
+    pub fn new(started: Duration) -> Self {
+        Self {
+            started,
+            hits: Vec::new(),
+        }
+    }
+
+    /// Log a hit of `key` at clock time `now`.
+    pub fn record(&mut self, key: char, now: Duration) {
+        self.hits.push(LoggedHit {
+            key,
+            time: now.saturating_sub(self.started),
+        });
+    }
+
+    /// Hits in the order they were played.
+    pub fn hits(&self) -> &[LoggedHit] {
+        &self.hits
+    }
+
+    /// The log as text, one `seconds<TAB>key` line per hit.
+    pub fn to_text(&self) -> String {
+        self.hits
+            .iter()
+            .map(|hit| format!("{:.3}\t{}\n", hit.time.as_secs_f64(), hit.key))
+            .collect()
+    }
+
+    /// Write the log to `path`, creating its directory if needed.
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_text())?;
+        Ok(())
+    }
+}
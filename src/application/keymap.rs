@@ -0,0 +1,455 @@
+//! Key bindings as data.
+//!
+//! Each binding ties a key chord in a context to an action and a short
+//! description. The input dispatcher and the on-screen help both read this
+//! table, so behavior and docs cannot drift apart.
+
+use crate::application::dto::input_action::{KeyCode, KeyModifiers};
+use KeyAction::*;
+use KeyContext::{Arrange, Browse, Explorer, Pads, Selection};
+
+/// Where a binding applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    /// Browse mode, whichever pane is focused
+    Browse,
+    /// Browse mode with the file explorer focused
+    Explorer,
+    /// Browse mode with the selected files focused
+    Selection,
+    /// Pads mode (pad grid or arrange view without its own binding)
+    Pads,
+    /// Arrange (track list) view
+    Arrange,
+}
+
+/// Command a key binding triggers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAction {
+    SwitchPane,
+    CycleBodySplit,
+    ToggleHeader,
+    OpenSession,
+    EnterPads,
+    Quit,
+    NavigateFiles,
+    SelectFile,
+    PreviewFile,
+    OpenFilter,
+    ClearFilter,
+    SelectionUp,
+    SelectionDown,
+    SelectionPageUp,
+    SelectionPageDown,
+    FillPads,
+    Unselect,
+    BackToBrowse,
+    ClearLoop,
+    PanicReset,
+    ToggleMasterMute,
+    LockTempo,
+    UnlockTempo,
+    TapTempo,
+    ToggleArrange,
+    ToggleDownbeatFlash,
+    ToggleBeatPulse,
+    ToggleTimelineGrid,
+    PreviewLastPad,
+    ReplayLastPad,
+    BounceLoop,
+    ExportLoop,
+    SaveSession,
+    ToggleHitLog,
+    RemapPad,
+    ReversePads,
+    CyclePadLayout,
+    ToggleRatchetReverse,
+    TogglePadGate,
+    EditChokeGroup,
+    CyclePadBus,
+    ToggleBusMute,
+    BusVolumeUp,
+    BusVolumeDown,
+    ResetEffects,
+    ToggleHalfLength,
+    DoubleLoop,
+    ToggleExtendOverdubs,
+    ToggleSwing,
+    ToggleMetronome,
+    HalveLoop,
+    RemoveLastTrack,
+    DeleteNearestEvent,
+    UndoTempo,
+    LoopControl,
+    PanLeft,
+    PanRight,
+    CyclePanel,
+    FitBpm,
+    OpenTempoPopup,
+    CloseArrange,
+    SelectPrevTrack,
+    SelectNextTrack,
+    MoveTrackUp,
+    MoveTrackDown,
+    ToggleTrackMute,
+    RenameTrack,
+    ToggleRestBar,
+    DeleteTrack,
+    DuplicateTrack,
+    TrackVolumeUp,
+    TrackVolumeDown,
+}
+
+/// A key chord bound to an action in a context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub key: KeyCode,
+    /// Modifiers that must be held; others are ignored
+    pub modifiers: KeyModifiers,
+    pub context: KeyContext,
+    pub description: &'static str,
+    pub action: KeyAction,
+}
+
+const NONE: KeyModifiers = KeyModifiers {
+    control: false,
+    shift: false,
+    alt: false,
+};
+const CTRL: KeyModifiers = KeyModifiers {
+    control: true,
+    shift: false,
+    alt: false,
+};
+const SHIFT: KeyModifiers = KeyModifiers {
+    control: false,
+    shift: true,
+    alt: false,
+};
+const ALT: KeyModifiers = KeyModifiers {
+    control: false,
+    shift: false,
+    alt: true,
+};
+
+const fn bind(
+    context: KeyContext,
+    key: KeyCode,
+    modifiers: KeyModifiers,
+    description: &'static str,
+    action: KeyAction,
+) -> KeyBinding {
+    KeyBinding {
+        key,
+        modifiers,
+        context,
+        description,
+        action,
+    }
+}
+
+/// Every binding, in lookup and help order. Within a context the first
+/// match wins, so chords with modifiers come before the bare key.
+static BINDINGS: &[KeyBinding] = &[
+    bind(Explorer, KeyCode::Up, NONE, "navigate", NavigateFiles),
+    bind(Explorer, KeyCode::Down, NONE, "navigate", NavigateFiles),
+    bind(Explorer, KeyCode::Left, NONE, "navigate", NavigateFiles),
+    bind(Explorer, KeyCode::Right, NONE, "navigate", NavigateFiles),
+    bind(
+        Explorer,
+        KeyCode::Char(' '),
+        NONE,
+        "select file",
+        SelectFile,
+    ),
+    bind(
+        Explorer,
+        KeyCode::Char('p'),
+        NONE,
+        "preview loop",
+        PreviewFile,
+    ),
+    bind(
+        Explorer,
+        KeyCode::Char('/'),
+        NONE,
+        "filter names",
+        OpenFilter,
+    ),
+    bind(Explorer, KeyCode::Esc, NONE, "clear filter", ClearFilter),
+    bind(Selection, KeyCode::Up, NONE, "navigate", SelectionUp),
+    bind(Selection, KeyCode::Down, NONE, "navigate", SelectionDown),
+    bind(Selection, KeyCode::PageUp, NONE, "page", SelectionPageUp),
+    bind(
+        Selection,
+        KeyCode::PageDown,
+        NONE,
+        "page",
+        SelectionPageDown,
+    ),
+    bind(Selection, KeyCode::Char('f'), NONE, "fill pads", FillPads),
+    bind(Selection, KeyCode::Char(' '), NONE, "unselect", Unselect),
+    bind(Selection, KeyCode::Delete, NONE, "unselect", Unselect),
+    bind(Selection, KeyCode::Char('d'), NONE, "unselect", Unselect),
+    bind(Browse, KeyCode::Tab, NONE, "switch pane", SwitchPane),
+    bind(Browse, KeyCode::Enter, NONE, "to pads", EnterPads),
+    bind(
+        Browse,
+        KeyCode::Char('|'),
+        NONE,
+        "pane split",
+        CycleBodySplit,
+    ),
+    bind(
+        Browse,
+        KeyCode::Char('^'),
+        NONE,
+        "compact header",
+        ToggleHeader,
+    ),
+    bind(
+        Browse,
+        KeyCode::Char('o'),
+        NONE,
+        "open session",
+        OpenSession,
+    ),
+    bind(Browse, KeyCode::Char('q'), NONE, "quit", Quit),
+    bind(Pads, KeyCode::Esc, NONE, "browse", BackToBrowse),
+    bind(Pads, KeyCode::Char(' '), CTRL, "clear loop", ClearLoop),
+    bind(
+        Pads,
+        KeyCode::Char(' '),
+        NONE,
+        "record/play/pause",
+        LoopControl,
+    ),
+    bind(Pads, KeyCode::Char('r'), CTRL, "reset audio", PanicReset),
+    bind(
+        Pads,
+        KeyCode::Char('m'),
+        ALT,
+        "master mute",
+        ToggleMasterMute,
+    ),
+    bind(Pads, KeyCode::Char('l'), ALT, "lock tempo", LockTempo),
+    bind(Pads, KeyCode::Char('u'), ALT, "unlock tempo", UnlockTempo),
+    bind(Pads, KeyCode::Char('t'), ALT, "tap tempo", TapTempo),
+    bind(Pads, KeyCode::Char('p'), ALT, "beat pulse", ToggleBeatPulse),
+    bind(Pads, KeyCode::Char('s'), ALT, "swing", ToggleSwing),
+    bind(Pads, KeyCode::Char('c'), ALT, "metronome", ToggleMetronome),
+    bind(
+        Pads,
+        KeyCode::Char('g'),
+        ALT,
+        "timeline grid",
+        ToggleTimelineGrid,
+    ),
+    bind(
+        Pads,
+        KeyCode::Char('h'),
+        ALT,
+        "half-loop practice",
+        ToggleHalfLength,
+    ),
+    bind(Pads, KeyCode::Char(']'), NONE, "double loop", DoubleLoop),
+    bind(Pads, KeyCode::Char('['), NONE, "halve loop", HalveLoop),
+    bind(
+        Pads,
+        KeyCode::Char('e'),
+        ALT,
+        "extend overdubs",
+        ToggleExtendOverdubs,
+    ),
+    bind(
+        Pads,
+        KeyCode::Char('d'),
+        ALT,
+        "drop last layer",
+        RemoveLastTrack,
+    ),
+    bind(
+        Pads,
+        KeyCode::Char('u'),
+        CTRL,
+        "drop last layer",
+        RemoveLastTrack,
+    ),
+    bind(Pads, KeyCode::Char('t'), CTRL, "arrange", ToggleArrange),
+    bind(
+        Pads,
+        KeyCode::Char('f'),
+        CTRL,
+        "downbeat flash",
+        ToggleDownbeatFlash,
+    ),
+    bind(
+        Pads,
+        KeyCode::Char('p'),
+        CTRL,
+        "preview pad",
+        PreviewLastPad,
+    ),
+    bind(Pads, KeyCode::Char('l'), CTRL, "replay pad", ReplayLastPad),
+    bind(Pads, KeyCode::Char('b'), CTRL, "bounce loop", BounceLoop),
+    bind(Pads, KeyCode::Char('w'), CTRL, "export wav", ExportLoop),
+    bind(Pads, KeyCode::Char('s'), CTRL, "save session", SaveSession),
+    bind(Pads, KeyCode::Char('g'), CTRL, "hit log", ToggleHitLog),
+    bind(Pads, KeyCode::Char('k'), CTRL, "remap pad", RemapPad),
+    bind(Pads, KeyCode::Char('r'), ALT, "reverse pads", ReversePads),
+    bind(Pads, KeyCode::Char('y'), CTRL, "pad layout", CyclePadLayout),
+    bind(
+        Pads,
+        KeyCode::Char('e'),
+        CTRL,
+        "ratchet reverse",
+        ToggleRatchetReverse,
+    ),
+    bind(Pads, KeyCode::Char('o'), CTRL, "gate pad", TogglePadGate),
+    bind(
+        Pads,
+        KeyCode::Char('n'),
+        CTRL,
+        "choke group",
+        EditChokeGroup,
+    ),
+    bind(Pads, KeyCode::Char('a'), CTRL, "pad bus", CyclePadBus),
+    bind(Pads, KeyCode::Char('d'), CTRL, "bus mute", ToggleBusMute),
+    bind(Pads, KeyCode::Up, ALT, "bus volume", BusVolumeUp),
+    bind(Pads, KeyCode::Down, ALT, "bus volume", BusVolumeDown),
+    bind(
+        Pads,
+        KeyCode::Char('x'),
+        CTRL,
+        "reset effects",
+        ResetEffects,
+    ),
+    bind(Pads, KeyCode::Char('z'), CTRL, "undo tempo", UndoTempo),
+    bind(
+        Pads,
+        KeyCode::Backspace,
+        NONE,
+        "delete event",
+        DeleteNearestEvent,
+    ),
+    bind(
+        Pads,
+        KeyCode::Delete,
+        NONE,
+        "delete event",
+        DeleteNearestEvent,
+    ),
+    bind(Pads, KeyCode::Left, SHIFT, "pan", PanLeft),
+    bind(Pads, KeyCode::Right, SHIFT, "pan", PanRight),
+    bind(Pads, KeyCode::Tab, NONE, "next panel", CyclePanel),
+    bind(Pads, KeyCode::Enter, NONE, "edit tempo", OpenTempoPopup),
+    bind(Pads, KeyCode::Char('='), NONE, "fit bpm", FitBpm),
+    bind(Arrange, KeyCode::Esc, NONE, "back", CloseArrange),
+    bind(Arrange, KeyCode::Up, SHIFT, "move", MoveTrackUp),
+    bind(Arrange, KeyCode::Down, SHIFT, "move", MoveTrackDown),
+    bind(Arrange, KeyCode::Up, NONE, "select", SelectPrevTrack),
+    bind(Arrange, KeyCode::Down, NONE, "select", SelectNextTrack),
+    bind(Arrange, KeyCode::Char('m'), NONE, "mute", ToggleTrackMute),
+    bind(Arrange, KeyCode::Char('n'), NONE, "rename", RenameTrack),
+    bind(Arrange, KeyCode::Char('x'), NONE, "rest bar", ToggleRestBar),
+    bind(Arrange, KeyCode::Char('d'), NONE, "delete", DeleteTrack),
+    bind(Arrange, KeyCode::Delete, NONE, "delete", DeleteTrack),
+    bind(
+        Arrange,
+        KeyCode::Char('c'),
+        NONE,
+        "duplicate",
+        DuplicateTrack,
+    ),
+    bind(Arrange, KeyCode::Char('+'), NONE, "volume", TrackVolumeUp),
+    bind(Arrange, KeyCode::Char('='), NONE, "volume", TrackVolumeUp),
+    bind(Arrange, KeyCode::Char('-'), NONE, "volume", TrackVolumeDown),
+];
+
+/// All key bindings.
+pub fn bindings() -> &'static [KeyBinding] {
+    BINDINGS
+}
+
+/// Action bound to a key in a context, if any. A binding matches when its
+/// key is pressed with at least its modifiers held.
+pub fn lookup(context: KeyContext, key: &KeyCode, modifiers: KeyModifiers) -> Option<KeyAction> {
+    BINDINGS
+        .iter()
+        .filter(|binding| binding.context == context && binding.key == *key)
+        .find(|binding| {
+            (!binding.modifiers.control || modifiers.control)
+                && (!binding.modifiers.shift || modifiers.shift)
+                && (!binding.modifiers.alt || modifiers.alt)
+        })
+        .map(|binding| binding.action)
+}
+
+/// Display label for a binding's chord, e.g. "Ctrl+R" or "Shift+←".
+pub fn chord_label(binding: &KeyBinding) -> String {
+    let key = match &binding.key {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) if binding.modifiers == NONE => c.to_string(),
+        KeyCode::Char(c) => c.to_ascii_uppercase().to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Delete => "Del".to_string(),
+        KeyCode::Backspace => "Bksp".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::Other(name) => name.clone(),
+    };
+    let mut label = String::new();
+    for (held, name) in [
+        (binding.modifiers.control, "Ctrl+"),
+        (binding.modifiers.alt, "Alt+"),
+        (binding.modifiers.shift, "Shift+"),
+    ] {
+        if held {
+            label.push_str(name);
+        }
+    }
+    label + &key
+}
+
+/// Help entries for a context in table order, as (chords, description);
+/// chords sharing a description are joined, e.g. ("↑/↓", "select").
+pub fn help_entries(context: KeyContext) -> Vec<(String, &'static str)> {
+    help_entries_in(&[context])
+}
+
+/// Help entries for several contexts shown together, like
+/// [`help_entries`]; a chord bound the same way in two of them is listed
+/// once.
+pub fn help_entries_in(contexts: &[KeyContext]) -> Vec<(String, &'static str)> {
+    let mut entries: Vec<(&'static str, Vec<String>)> = Vec::new();
+    for binding in BINDINGS.iter().filter(|b| contexts.contains(&b.context)) {
+        let label = chord_label(binding);
+        match entries
+            .iter_mut()
+            .find(|(description, _)| *description == binding.description)
+        {
+            Some((_, labels)) if labels.contains(&label) => {}
+            Some((_, labels)) => labels.push(label),
+            None => entries.push((binding.description, vec![label])),
+        }
+    }
+    entries
+        .into_iter()
+        .map(|(description, labels)| (labels.join("/"), description))
+        .collect()
+}
+
+/// One-line help for a context, e.g. "↑/↓ select, m mute".
+pub fn help_line(context: KeyContext) -> String {
+    help_entries(context)
+        .into_iter()
+        .map(|(labels, description)| format!("{labels} {description}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -13,7 +13,12 @@
 //! - Depends on: Domain layer (`crate::domain`)
 //! - Does not depend on: Presentation layer (`crate::ui`), Infrastructure layer (`crate::audio`)
 
+pub mod config;
 pub mod dto;
+pub mod error;
+pub mod hit_log;
+pub mod keymap;
 pub mod ports;
 pub mod service;
+pub mod session;
 pub mod state;
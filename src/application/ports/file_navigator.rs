@@ -38,4 +38,12 @@ pub trait FileNavigator {
     /// * `Some(FileEntry)` if there is a selected item
     /// * `None` if no item is selected or the navigator is empty
     fn selected_entry(&self) -> Option<FileEntry>;
+
+    /// Keep the selection on an entry the name filter shows, moving to the
+    /// first match when the selected entry is filtered out.
+    ///
+    /// # Returns
+    /// * `true` if any entry in the current directory matches the filter
+    /// * `false` if the filter hides every entry
+    fn apply_filter(&mut self) -> bool;
 }
@@ -6,18 +6,42 @@
 //! The service receives state as parameters and returns effects, ensuring
 //! it remains reusable and testable without holding internal state.
 
+use crate::application::config::{EmptyPadsSpace, LeavePadsVoices};
 use crate::application::dto::input_action::{InputAction, KeyCode, KeyModifiers};
+use crate::application::error::AppError;
+use crate::application::keymap::{self, KeyAction, KeyContext};
 use crate::application::ports::FileNavigator;
 use crate::application::service::effect::Effect;
-use crate::application::state::ApplicationState;
-use crate::audio::AudioCommand;
+use crate::application::session::default_session_path;
+use crate::application::state::{ApplicationState, PadMute, PlayMode, is_supported_audio};
+use crate::audio::{AudioCommand, AudioEvent, VolumeCurve};
 use crate::domain::r#loop::LoopState;
-use crate::presentation::ViewModel;
+use crate::domain::tempo::{LoopLength, clamp_bars, clamp_bpm, clamp_count_in};
+use crate::presentation::{ConfirmPrompt, PadsPanel, ViewModel};
 use ratatui::crossterm::event::{
     Event, KeyCode as CrosstermKeyCode, KeyEvent, KeyModifiers as CrosstermModifiers,
 };
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::Sender;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Pan change applied per Shift+Left/Right press.
+const PAN_STEP: f32 = 0.1;
+
+/// Track volume change applied per +/- press in the arrange view.
+const TRACK_VOLUME_STEP: f32 = 0.1;
+
+/// Mixing buses a pad cycles through with Ctrl+A.
+const PAD_BUSES: u8 = 4;
+
+/// Bus volume change applied per Alt+Up/Down press.
+const BUS_VOLUME_STEP: f32 = 0.1;
+
+/// Times a file plays back to back when previewed from the browser.
+const PREVIEW_LOOP_REPEATS: usize = 4;
+
+/// How far from the playhead Backspace/Delete may reach for an event.
+const DELETE_EVENT_TOLERANCE: Duration = Duration::from_millis(150);
 
 /// Stateless application service that orchestrates use cases.
 ///
@@ -63,17 +87,153 @@ impl AppService {
             InputAction::KeyPressed { key, modifiers } => {
                 self.handle_key_pressed(app_state, view_model, key, modifiers, &mut effects)?;
             }
-            InputAction::KeyReleased { .. } => {
-                // Key release events are not currently handled
+            InputAction::KeyReleased { key } => {
+                // Only terminals with the kitty keyboard protocol send these
+                if view_model.mode == crate::presentation::Mode::Pads
+                    && let KeyCode::Char(c) = key
+                {
+                    let k = c.to_ascii_lowercase();
+                    app_state.pads.release(k);
+                    if app_state.pads.is_gated(k) {
+                        effects.push(Effect::AudioCommand(AudioCommand::Release { key: k }));
+                    }
+                }
             }
-            InputAction::Resize { .. } => {
-                // Resize events are handled by UI layer, no effects needed
+            InputAction::Resize { width, height } => {
+                // Layout recomputes on draw; only size-dependent choices live here
+                view_model.apply_terminal_size(width, height);
             }
         }
 
         Ok(effects)
     }
 
+    /// Applies startup configuration before the first frame.
+    ///
+    /// Selects the configured default kit and, if enabled, enters Pads mode.
+    /// A missing or empty kit directory only produces a warning and leaves
+    /// the app in Browse mode.
+    pub fn boot(
+        &self,
+        app_state: &mut ApplicationState,
+        view_model: &mut ViewModel,
+    ) -> Vec<Effect> {
+        let mut effects = Vec::new();
+        if let Some(threshold) = app_state.config.trim_silence_threshold() {
+            effects.push(Effect::AudioCommand(AudioCommand::SetTrimSilence(Some(
+                threshold,
+            ))));
+        }
+        if let Some(gain) = app_state.config.master_gain {
+            effects.push(Effect::AudioCommand(AudioCommand::SetMasterGain(gain)));
+        }
+        if app_state.config.volume_curve != VolumeCurve::Linear {
+            effects.push(Effect::AudioCommand(AudioCommand::SetVolumeCurve(
+                app_state.config.volume_curve,
+            )));
+        }
+        if let Some(name) = app_state.config.metronome_device.clone() {
+            effects.push(Effect::AudioCommand(AudioCommand::SetMetronomeDevice(
+                Some(name),
+            )));
+        }
+        if let Some(metronome) = app_state.config.metronome {
+            effects.push(Effect::AudioCommand(AudioCommand::ConfigureMetronome(
+                metronome,
+            )));
+        }
+        let Some(dir) = app_state.config.default_kit_dir.clone() else {
+            return effects;
+        };
+        match app_state.select_kit_dir(&dir) {
+            Ok(count) => {
+                if app_state.config.kit_auto_pads {
+                    self.enter_pads(app_state, view_model, &mut effects);
+                } else {
+                    effects.push(Effect::StatusMessage(format!(
+                        "Loaded {} files from {}",
+                        count,
+                        dir.display()
+                    )));
+                }
+            }
+            Err(e) => effects.push(Effect::StatusMessage(e.to_string())),
+        }
+        effects
+    }
+
+    /// Switch to Pads mode with the current selection, reporting the outcome.
+    fn enter_pads(
+        &self,
+        app_state: &mut ApplicationState,
+        view_model: &mut ViewModel,
+        effects: &mut Vec<Effect>,
+    ) {
+        match app_state.enter_pads() {
+            Ok(entry) => {
+                let warning = entry.skipped_warning();
+                effects.push(Effect::AudioCommand(entry.preload));
+                // Update mode in view model
+                view_model.mode = crate::presentation::Mode::Pads;
+                effects.push(Effect::StatusMessage(warning.unwrap_or_else(|| {
+                    "[Pads] Press Esc to go back. Press Q/W/…/< to trigger.".to_string()
+                })));
+            }
+            Err(e) => {
+                effects.push(Effect::StatusMessage(e.to_string()));
+            }
+        }
+    }
+
+    /// Restore a saved session and switch to Pads mode with it.
+    pub fn open_session(
+        &self,
+        app_state: &mut ApplicationState,
+        view_model: &mut ViewModel,
+        path: &Path,
+        effects: &mut Vec<Effect>,
+    ) {
+        match app_state.load_session(path) {
+            Ok(commands) => {
+                effects.extend(commands.into_iter().map(Effect::AudioCommand));
+                view_model.mode = crate::presentation::Mode::Pads;
+                effects.push(Effect::StatusMessage(format!(
+                    "Session restored: {} pads, {} tracks",
+                    app_state.pads.key_to_slot.len(),
+                    app_state.tracks_count()
+                )));
+            }
+            Err(err) => effects.push(Effect::StatusMessage(format!(
+                "Session load failed: {}",
+                err
+            ))),
+        }
+    }
+
+    /// Save the session to `path` and leave Pads mode. A failed save keeps
+    /// the loop and stays in Pads mode.
+    pub fn save_session_and_leave(
+        &self,
+        app_state: &mut ApplicationState,
+        view_model: &mut ViewModel,
+        path: &Path,
+        effects: &mut Vec<Effect>,
+    ) {
+        match app_state.save_session(path) {
+            Ok(()) => {
+                Self::back_to_browse(app_state, view_model, effects);
+                effects.push(Effect::StatusMessage(format!(
+                    "Session saved to {}",
+                    path.display()
+                )));
+            }
+            Err(err) => effects.push(Effect::StatusMessage(format!(
+                "Session save failed: {}",
+                err
+            ))),
+        }
+    }
+
     /// Updates the loop engine state.
     ///
     /// This method orchestrates loop update logic, mutating the provided
@@ -88,9 +248,49 @@ impl AppService {
     /// A vector of effects that need to be executed.
     pub fn update_loop(&self, app_state: &mut ApplicationState) -> Vec<Effect> {
         app_state.update_loop();
-        // Currently, update_loop doesn't produce effects, but this is where
-        // they would be added if needed (e.g., scheduled audio playback)
-        Vec::new()
+        // Metronome audition ticks from the tempo popup
+        app_state
+            .click_preview_commands()
+            .into_iter()
+            .map(Effect::AudioCommand)
+            .collect()
+    }
+
+    /// Apply a notification from the audio thread, returning any status it
+    /// warrants.
+    pub fn handle_audio_event(
+        &self,
+        app_state: &mut ApplicationState,
+        event: AudioEvent,
+    ) -> Vec<Effect> {
+        match event {
+            AudioEvent::PadLevel { key, level } => {
+                app_state.pads.apply_pad_level(key, level);
+                Vec::new()
+            }
+            AudioEvent::SampleLoaded { key, sample_rate } => app_state
+                .pads
+                .apply_sample_rate(key, sample_rate)
+                .map(Effect::StatusMessage)
+                .into_iter()
+                .collect(),
+            AudioEvent::LoadFailed { key, error } => vec![Effect::StatusMessage(format!(
+                "Pad {} is silent: {error}",
+                key.to_ascii_uppercase()
+            ))],
+            AudioEvent::PreloadProgress { loaded, total } => {
+                app_state.pads.apply_preload_progress(loaded, total);
+                Vec::new()
+            }
+            AudioEvent::ExportFailed { path, error } => vec![Effect::StatusMessage(format!(
+                "Export to {} failed: {error}",
+                path.display()
+            ))],
+            AudioEvent::AuditionEnded => {
+                app_state.auditioning = false;
+                Vec::new()
+            }
+        }
     }
 
     /// Handles a key press event.
@@ -102,6 +302,24 @@ impl AppService {
         modifiers: KeyModifiers,
         effects: &mut Vec<Effect>,
     ) -> anyhow::Result<()> {
+        // The help overlay swallows every key until it is closed
+        if view_model.help_open {
+            if matches!(key, KeyCode::Char('?') | KeyCode::Esc) {
+                view_model.help_open = false;
+            }
+            return Ok(());
+        }
+        // Text fields and popups keep `?` as an ordinary key
+        if key == KeyCode::Char('?')
+            && !view_model.text_capture
+            && !view_model.filter_open
+            && !view_model.is_bpm_popup_open()
+            && view_model.confirm.is_none()
+        {
+            view_model.help_open = true;
+            return Ok(());
+        }
+
         match view_model.mode {
             crate::presentation::Mode::Browse => {
                 self.handle_browse_mode_key(app_state, view_model, key, effects)?;
@@ -122,45 +340,69 @@ impl AppService {
         key: KeyCode,
         effects: &mut Vec<Effect>,
     ) -> anyhow::Result<()> {
-        match key {
-            KeyCode::Tab => {
+        // Any other key stops a running preview loop
+        if app_state.auditioning && key != KeyCode::Char('p') {
+            app_state.auditioning = false;
+            effects.push(Effect::AudioCommand(AudioCommand::StopAudition));
+        }
+        if view_model.filter_open {
+            return self.handle_filter_key(view_model, key, effects);
+        }
+
+        let pane = match view_model.focus {
+            crate::presentation::FocusPane::LeftExplorer => KeyContext::Explorer,
+            crate::presentation::FocusPane::RightSelected => KeyContext::Selection,
+        };
+        let modifiers = KeyModifiers::default();
+        let Some(action) = keymap::lookup(KeyContext::Browse, &key, modifiers)
+            .or_else(|| keymap::lookup(pane, &key, modifiers))
+        else {
+            return Ok(());
+        };
+
+        match action {
+            KeyAction::SwitchPane => {
                 view_model.toggle_focus();
                 effects.push(Effect::StatusMessage(view_model.focus_status_message()));
             }
-            KeyCode::Enter => {
-                match app_state.enter_pads() {
-                    Ok(preload_commands) => {
-                        // Convert preload commands to effects
-                        for cmd in preload_commands {
-                            effects.push(Effect::AudioCommand(cmd));
-                        }
-                        // Update mode in view model
-                        view_model.mode = crate::presentation::Mode::Pads;
-                        effects.push(Effect::StatusMessage(
-                            "[Pads] Press Esc to go back. Press Q/W/…/< to trigger.".to_string(),
-                        ));
-                    }
-                    Err(e) => {
-                        effects.push(Effect::StatusMessage(e.to_string()));
-                    }
-                }
+            KeyAction::CycleBodySplit => {
+                effects.push(Effect::StatusMessage(view_model.cycle_body_split()));
+            }
+            KeyAction::ToggleHeader => {
+                effects.push(Effect::StatusMessage(view_model.toggle_header()));
+            }
+            KeyAction::OpenSession => match default_session_path() {
+                Some(path) => self.open_session(app_state, view_model, &path, effects),
+                None => effects.push(Effect::StatusMessage(
+                    "No home directory to load a session from".to_string(),
+                )),
+            },
+            KeyAction::EnterPads => {
+                self.enter_pads(app_state, view_model, effects);
+            }
+            // The event loop exits on quit
+            KeyAction::Quit => {}
+            KeyAction::NavigateFiles => {
+                self.handle_file_explorer_navigation(view_model, key, effects)?;
+            }
+            KeyAction::SelectFile => {
+                self.handle_file_selection(app_state, view_model, effects)?;
+            }
+            KeyAction::PreviewFile => {
+                self.handle_preview_loop(app_state, view_model, effects);
+            }
+            KeyAction::OpenFilter => {
+                view_model.filter_open = true;
+                effects.push(Effect::StatusMessage(
+                    "Filter: type part of a name (Enter keeps, Esc clears)".to_string(),
+                ));
+            }
+            KeyAction::ClearFilter if !view_model.explorer_filter.value().is_empty() => {
+                view_model.clear_filter();
+                effects.push(Effect::StatusMessage("Filter cleared".to_string()));
             }
             _ => {
-                // Route keys based on focused pane
-                match view_model.focus {
-                    crate::presentation::FocusPane::LeftExplorer => match key {
-                        KeyCode::Char(' ') => {
-                            self.handle_file_selection(app_state, view_model, effects)?;
-                        }
-                        KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => {
-                            self.handle_file_explorer_navigation(view_model, key, effects)?;
-                        }
-                        _ => {}
-                    },
-                    crate::presentation::FocusPane::RightSelected => {
-                        self.handle_selection_management(app_state, view_model, key, effects)?;
-                    }
-                }
+                self.handle_selection_management(app_state, view_model, action, effects)?;
             }
         }
 
@@ -178,71 +420,868 @@ impl AppService {
     ) -> anyhow::Result<()> {
         // Handle popup if open
         if view_model.is_bpm_popup_open() {
-            return self.handle_popup_key(app_state, view_model, key, effects);
+            return self.handle_popup_key(app_state, view_model, key, modifiers, effects);
+        }
+        if let Some(prompt) = view_model.confirm {
+            self.handle_confirm_key(app_state, view_model, prompt, &key, effects);
+            return Ok(());
         }
 
-        match key {
-            KeyCode::Esc => {
-                app_state.cancel_loop();
-                view_model.mode = crate::presentation::Mode::Browse;
-                effects.push(Effect::StatusMessage("Back to browse".to_string()));
+        // A focused text field gets every key so letters don't trigger pads
+        if view_model.text_capture {
+            return self.handle_text_capture_key(app_state, view_model, key, effects);
+        }
+        if let Some(from) = view_model.remap_from {
+            self.handle_remap_key(app_state, view_model, from, key, effects);
+            return Ok(());
+        }
+        if let Some(pad) = view_model.choke_edit {
+            Self::handle_choke_key(app_state, view_model, pad, key, effects);
+            return Ok(());
+        }
+
+        // The focused track list takes its keys first; the rest fall through
+        if view_model.is_arrange_open()
+            && view_model.pads_panel_focus == PadsPanel::Tracks
+            && self.handle_arrange_key(app_state, view_model, &key, modifiers, effects)
+        {
+            return Ok(());
+        }
+
+        let Some(action) = keymap::lookup(KeyContext::Pads, &key, modifiers) else {
+            // Unbound characters trigger their pad; Shift+letter steps the
+            // pad's loop playback through mute and solo instead, and Alt+key
+            // focuses the pad for per-pad commands without playing it
+            if let KeyCode::Char(c) = key {
+                if modifiers.alt {
+                    let k = c.to_ascii_lowercase();
+                    if app_state.pads.key_to_slot.contains_key(&k) {
+                        app_state.pads.focused_pad = Some(k);
+                        effects.push(Effect::StatusMessage(format!(
+                            "Pad {} focused",
+                            k.to_ascii_uppercase()
+                        )));
+                    }
+                } else if modifiers.shift && c.is_ascii_alphabetic() {
+                    let k = c.to_ascii_lowercase();
+                    if let Some(mute) = app_state.cycle_pad_mute(k) {
+                        let label = k.to_ascii_uppercase();
+                        let message = match mute {
+                            PadMute::Muted => format!("Pad {} muted in the loop", label),
+                            PadMute::Soloed => format!("Pad {} soloed in the loop", label),
+                            PadMute::Normal => format!("Pad {} back in the loop", label),
+                        };
+                        effects.push(Effect::StatusMessage(message));
+                    }
+                } else {
+                    self.trigger_pad(app_state, c, effects);
+                }
+            }
+            return Ok(());
+        };
+
+        match action {
+            KeyAction::BackToBrowse if app_state.tracks_count() > 0 => {
+                // Leaving drops the recorded loop; ask first
+                view_model.confirm = Some(ConfirmPrompt::DiscardLoop);
             }
-            KeyCode::Char(' ') if modifiers.control => {
+            KeyAction::BackToBrowse => Self::back_to_browse(app_state, view_model, effects),
+            KeyAction::ClearLoop => {
                 app_state.clear_loop();
+                // Cut long samples still ringing from the cleared loop
+                effects.push(Effect::AudioCommand(AudioCommand::StopAll));
                 effects.push(Effect::StatusMessage("Loop cleared".to_string()));
             }
-            KeyCode::Char(' ') => {
+            KeyAction::PanicReset => {
+                // Panic reset: respawn the audio thread, then reload every mapped pad
+                effects.push(Effect::ResetAudio);
+                for cmd in app_state.pad_restore_commands() {
+                    effects.push(Effect::AudioCommand(cmd));
+                }
+                if app_state.master_muted {
+                    effects.push(Effect::AudioCommand(AudioCommand::SetMasterMute(true)));
+                }
+            }
+            KeyAction::LockTempo => {
+                app_state.tempo_locked = true;
+                effects.push(Effect::StatusMessage(
+                    "Tempo locked (Alt+U to unlock)".to_string(),
+                ));
+            }
+            KeyAction::UnlockTempo => {
+                app_state.tempo_locked = false;
+                effects.push(Effect::StatusMessage("Tempo unlocked".to_string()));
+            }
+            KeyAction::OpenTempoPopup
+            | KeyAction::FitBpm
+            | KeyAction::UndoTempo
+            | KeyAction::TapTempo
+            | KeyAction::DoubleLoop
+            | KeyAction::HalveLoop
+                if app_state.tempo_locked =>
+            {
+                effects.push(Effect::StatusMessage(
+                    "Tempo locked (Alt+U to unlock)".to_string(),
+                ));
+            }
+            KeyAction::TapTempo => {
+                let message = match app_state.tap_tempo() {
+                    Some(bpm) => format!("Tap tempo: {} bpm", bpm),
+                    None => "Tap again to set the tempo".to_string(),
+                };
+                effects.push(Effect::StatusMessage(message));
+            }
+            KeyAction::ToggleMasterMute => {
+                let cmd = app_state.toggle_master_mute();
+                let message = if app_state.master_muted {
+                    "Master muted"
+                } else {
+                    "Master unmuted"
+                };
+                effects.push(Effect::StatusMessage(message.to_string()));
+                effects.push(Effect::AudioCommand(cmd));
+            }
+            KeyAction::ToggleArrange => {
+                view_model.toggle_arrange();
+                let message = if view_model.is_arrange_open() {
+                    format!("Arrange: {}", keymap::help_line(KeyContext::Arrange))
+                } else {
+                    "Back to pads".to_string()
+                };
+                effects.push(Effect::StatusMessage(message));
+            }
+            KeyAction::ToggleDownbeatFlash => {
+                app_state.config.downbeat_flash = !app_state.config.downbeat_flash;
+                let message = if app_state.config.downbeat_flash {
+                    "Downbeat flash on"
+                } else {
+                    "Downbeat flash off"
+                };
+                effects.push(Effect::StatusMessage(message.to_string()));
+            }
+            KeyAction::RemapPad => match app_state.pads.target_pad() {
+                Some(key) => {
+                    view_model.remap_from = Some(key);
+                    effects.push(Effect::StatusMessage(format!(
+                        "Remap pad {}: press a free pad key (Esc cancels)",
+                        key.to_ascii_uppercase()
+                    )));
+                }
+                None => effects.push(Effect::StatusMessage(
+                    "Play a pad first to pick it for remapping".to_string(),
+                )),
+            },
+            KeyAction::EditChokeGroup => match app_state.pads.target_pad() {
+                Some(key) => {
+                    view_model.choke_edit = Some(key);
+                    effects.push(Effect::StatusMessage(format!(
+                        "Choke group for pad {}: press 1-9, 0 for none (Esc cancels)",
+                        key.to_ascii_uppercase()
+                    )));
+                }
+                None => effects.push(Effect::StatusMessage(
+                    "Play a pad first to pick its choke group".to_string(),
+                )),
+            },
+            KeyAction::ReversePads => {
+                let remap = app_state.reverse_pad_mapping();
+                effects.extend(remap.commands.into_iter().map(Effect::AudioCommand));
+                let mut message = "Pads reversed".to_string();
+                if remap.moved_hits > 0 {
+                    message.push_str(&format!(" ({} loop hits moved)", remap.moved_hits));
+                }
+                effects.push(Effect::StatusMessage(message));
+            }
+            KeyAction::CyclePadLayout => {
+                let remap = app_state.cycle_pad_layout();
+                effects.extend(remap.commands.into_iter().map(Effect::AudioCommand));
+                let mut message = format!("Pad layout: {}", app_state.config.pad_layout.name());
+                if remap.moved_hits > 0 {
+                    message.push_str(&format!(" ({} loop hits moved)", remap.moved_hits));
+                }
+                effects.push(Effect::StatusMessage(message));
+            }
+            KeyAction::ToggleBeatPulse => {
+                app_state.config.beat_pulse = !app_state.config.beat_pulse;
+                let message = if app_state.config.beat_pulse {
+                    "Beat pulse on"
+                } else {
+                    "Beat pulse off"
+                };
+                effects.push(Effect::StatusMessage(message.to_string()));
+            }
+            KeyAction::PreviewLastPad => match app_state.pads.target_pad() {
+                // Audition without recording or marking the pad as pressed
+                Some(k) => {
+                    effects.push(Effect::AudioCommand(AudioCommand::Preview {
+                        key: k,
+                        effects: app_state.pads.effects_for(k),
+                    }));
+                    effects.push(Effect::StatusMessage(format!(
+                        "Preview {}",
+                        k.to_ascii_uppercase()
+                    )));
+                }
+                None => effects.push(Effect::StatusMessage(
+                    "Trigger a pad first to preview it".to_string(),
+                )),
+            },
+            KeyAction::ReplayLastPad => {
+                // Replay the last triggered pad; nothing to do before the first hit
+                if let Some(k) = app_state.pads.last_triggered {
+                    effects.push(Effect::AudioCommand(AudioCommand::Play { key: k }));
+                }
+            }
+            KeyAction::RemoveLastTrack => {
+                let message = match app_state.remove_last_track() {
+                    Some(index) => {
+                        // Keep the arrange selection on an existing track
+                        view_model.arrange_cursor = view_model
+                            .arrange_cursor
+                            .min(app_state.tracks_count().saturating_sub(1));
+                        format!(
+                            "Track {} removed, {} left",
+                            index + 1,
+                            app_state.tracks_count()
+                        )
+                    }
+                    None => "No tracks to remove".to_string(),
+                };
+                effects.push(Effect::StatusMessage(message));
+            }
+            KeyAction::ToggleHalfLength => {
+                let message = if app_state.toggle_half_length() {
+                    "Practicing the first half of the loop"
+                } else {
+                    "Playing the full loop"
+                };
+                effects.push(Effect::StatusMessage(message.to_string()));
+            }
+            KeyAction::ToggleTimelineGrid => {
+                app_state.config.timeline_grid = !app_state.config.timeline_grid;
+                let message = if app_state.config.timeline_grid {
+                    "Timeline grid on"
+                } else {
+                    "Timeline grid off"
+                };
+                effects.push(Effect::StatusMessage(message.to_string()));
+            }
+            KeyAction::ToggleSwing => {
+                let message = match app_state.toggle_swing() {
+                    0 => "Swing off".to_string(),
+                    percent => format!("Swing {percent}%"),
+                };
+                effects.push(Effect::StatusMessage(message));
+            }
+            KeyAction::ToggleMetronome => {
+                let message = if app_state.toggle_metronome() {
+                    "Metronome on while recording and playing"
+                } else {
+                    "Metronome off"
+                };
+                effects.push(Effect::StatusMessage(message.to_string()));
+            }
+            KeyAction::ToggleExtendOverdubs => {
+                let message = if app_state.toggle_extend_overdubs() {
+                    "Overdubs past the loop end extend it"
+                } else {
+                    "Overdubs wrap at the loop end"
+                };
+                effects.push(Effect::StatusMessage(message.to_string()));
+            }
+            KeyAction::DoubleLoop | KeyAction::HalveLoop => {
+                let (factor, verb) = if action == KeyAction::DoubleLoop {
+                    (2.0, "doubled")
+                } else {
+                    (0.5, "halved")
+                };
+                let count = |n: u16, unit: &str| match n {
+                    1 => format!("1 {unit}"),
+                    n => format!("{n} {unit}s"),
+                };
+                let message = match app_state.scale_loop_length(factor) {
+                    Some(LoopLength::Bars(bars)) => {
+                        format!("Loop {verb} to {}", count(bars, "bar"))
+                    }
+                    Some(LoopLength::Beats(beats)) => {
+                        format!("Loop {verb} to {}", count(beats, "beat"))
+                    }
+                    None if app_state.loop_state() == LoopState::Idle => {
+                        "Record a loop first".to_string()
+                    }
+                    None => format!("Loop cannot be {verb} further"),
+                };
+                effects.push(Effect::StatusMessage(message));
+            }
+            KeyAction::ResetEffects => view_model.confirm = Some(ConfirmPrompt::ResetEffects),
+            KeyAction::CyclePadBus
+            | KeyAction::ToggleBusMute
+            | KeyAction::BusVolumeUp
+            | KeyAction::BusVolumeDown => {
+                let Some(k) = app_state.pads.target_pad() else {
+                    effects.push(Effect::StatusMessage(
+                        "Play a pad first to pick its bus".to_string(),
+                    ));
+                    return Ok(());
+                };
+                let bus = app_state.pads.bus_for(k);
+                let (cmd, message) = match action {
+                    KeyAction::CyclePadBus => {
+                        let next = (bus + 1) % PAD_BUSES;
+                        (
+                            app_state.assign_pad_bus(k, next),
+                            format!("Pad {} on bus {next}", k.to_ascii_uppercase()),
+                        )
+                    }
+                    KeyAction::ToggleBusMute => {
+                        let cmd = app_state.toggle_bus_mute(bus);
+                        let state = if app_state.bus_level(bus).muted {
+                            "muted"
+                        } else {
+                            "unmuted"
+                        };
+                        (cmd, format!("Bus {bus} {state}"))
+                    }
+                    _ => {
+                        let delta = if action == KeyAction::BusVolumeDown {
+                            -BUS_VOLUME_STEP
+                        } else {
+                            BUS_VOLUME_STEP
+                        };
+                        let volume = app_state.bus_level(bus).volume + delta;
+                        let cmd = app_state.set_bus_volume(bus, volume);
+                        let percent = (app_state.bus_level(bus).volume * 100.0).round() as u32;
+                        (cmd, format!("Bus {bus} volume {percent}%"))
+                    }
+                };
+                effects.push(Effect::AudioCommand(cmd));
+                effects.push(Effect::StatusMessage(message));
+            }
+            KeyAction::ToggleRatchetReverse => {
+                if let Some(k) = app_state.pads.target_pad() {
+                    let message = if app_state.pads.toggle_ratchet_reverse(k) {
+                        format!("Ratchet reverse on for pad {}", k.to_ascii_uppercase())
+                    } else {
+                        format!("Ratchet reverse off for pad {}", k.to_ascii_uppercase())
+                    };
+                    effects.push(Effect::StatusMessage(message));
+                }
+            }
+            KeyAction::TogglePadGate => {
+                if let Some(k) = app_state.pads.target_pad()
+                    && let Some(mode) = app_state.pads.toggle_play_mode(k)
+                {
+                    let message = match mode {
+                        PlayMode::Gate => {
+                            format!("Pad {} plays while held", k.to_ascii_uppercase())
+                        }
+                        PlayMode::OneShot => {
+                            format!("Pad {} plays one-shot", k.to_ascii_uppercase())
+                        }
+                    };
+                    effects.push(Effect::StatusMessage(message));
+                }
+            }
+            KeyAction::BounceLoop => match app_state.bounce_loop() {
+                Ok((key, cmd)) => {
+                    effects.push(Effect::AudioCommand(cmd));
+                    effects.push(Effect::StatusMessage(format!(
+                        "Bounced loop to pad {}; record the next section",
+                        key.to_ascii_uppercase()
+                    )));
+                }
+                Err(err) => effects.push(Effect::StatusMessage(err.to_string())),
+            },
+            KeyAction::ExportLoop => {
+                let timestamp = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                let path = PathBuf::from(format!("termigroove-{timestamp}.wav"));
+                match app_state.export_loop(path.clone()) {
+                    Ok(cmd) => {
+                        effects.push(Effect::AudioCommand(cmd));
+                        effects.push(Effect::StatusMessage(format!(
+                            "Exporting loop to {}",
+                            path.display()
+                        )));
+                    }
+                    Err(err) => effects.push(Effect::StatusMessage(err.to_string())),
+                }
+            }
+            KeyAction::SaveSession => {
+                let message = match default_session_path() {
+                    Some(path) => match app_state.save_session(&path) {
+                        Ok(()) => format!("Session saved to {}", path.display()),
+                        Err(err) => format!("Session save failed: {}", err),
+                    },
+                    None => "No home directory to save the session in".to_string(),
+                };
+                effects.push(Effect::StatusMessage(message));
+            }
+            KeyAction::ToggleHitLog => {
+                let message = match app_state.stop_hit_log() {
+                    Some(log) => {
+                        let timestamp = SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs();
+                        let path = PathBuf::from(format!("termigroove-hits-{timestamp}.txt"));
+                        match log.write(&path) {
+                            Ok(()) => {
+                                format!("Saved {} hits to {}", log.hits().len(), path.display())
+                            }
+                            Err(err) => format!("Hit log save failed: {}", err),
+                        }
+                    }
+                    None => {
+                        app_state.start_hit_log();
+                        "Logging pad hits (Ctrl+G to save)".to_string()
+                    }
+                };
+                effects.push(Effect::StatusMessage(message));
+            }
+            KeyAction::DeleteNearestEvent => {
+                let message = if app_state.delete_nearest_event(DELETE_EVENT_TOLERANCE) {
+                    "Deleted event at playhead"
+                } else {
+                    "No event near the playhead"
+                };
+                effects.push(Effect::StatusMessage(message.to_string()));
+            }
+            KeyAction::UndoTempo => {
+                let message = if app_state.undo_tempo_change() {
+                    format!(
+                        "Tempo restored: {} bpm / {} bars",
+                        app_state.get_bpm(),
+                        app_state.get_bars()
+                    )
+                } else {
+                    "Nothing to undo".to_string()
+                };
+                effects.push(Effect::StatusMessage(message));
+            }
+            KeyAction::LoopControl
+                if app_state.pads.key_to_slot.is_empty()
+                    && matches!(app_state.loop_state(), LoopState::Idle)
+                    && app_state.config.empty_pads_space == EmptyPadsSpace::Hint =>
+            {
+                effects.push(Effect::StatusMessage("Map a pad first.".to_string()));
+            }
+            KeyAction::LoopControl => {
                 app_state.handle_loop_space();
                 // Status message update based on loop state would be handled elsewhere
             }
-            KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => {
-                view_model.focus_summary_box();
+            KeyAction::PanLeft | KeyAction::PanRight => match app_state.pads.target_pad() {
+                Some(k) => {
+                    let delta = if action == KeyAction::PanLeft {
+                        -PAN_STEP
+                    } else {
+                        PAN_STEP
+                    };
+                    let cmd = app_state.adjust_pad_pan(k, delta);
+                    effects.push(Effect::StatusMessage(format!(
+                        "Pan {}: {}",
+                        k.to_ascii_uppercase(),
+                        format_pan(app_state.pads.pan_for(k))
+                    )));
+                    effects.push(Effect::AudioCommand(cmd));
+                }
+                None => {
+                    effects.push(Effect::StatusMessage(
+                        "Trigger a pad first to pan it".to_string(),
+                    ));
+                }
+            },
+            KeyAction::CyclePanel => {
+                view_model.cycle_pads_panel();
+                effects.push(Effect::StatusMessage(
+                    view_model.pads_focus_status_message(),
+                ));
             }
-            KeyCode::Enter => {
-                if matches!(
-                    view_model.popup_focus(),
-                    crate::presentation::PopupFocus::SummaryBox
-                ) {
-                    view_model.open_bpm_bars_popup(app_state.get_bpm(), app_state.get_bars());
+            KeyAction::FitBpm => match app_state.pads.last_pressed_key() {
+                Some(k) => match app_state.fit_bpm_to_pad(k) {
+                    Ok(bpm) => effects.push(Effect::StatusMessage(format!(
+                        "BPM set to {} from {} over {} bars",
+                        bpm,
+                        k.to_ascii_uppercase(),
+                        app_state.get_bars()
+                    ))),
+                    Err(e) => effects.push(Effect::StatusMessage(e.to_string())),
+                },
+                None => effects.push(Effect::StatusMessage(
+                    "Trigger a pad first to take its tempo".to_string(),
+                )),
+            },
+            KeyAction::OpenTempoPopup if view_model.pads_panel_focus == PadsPanel::Summary => {
+                view_model.open_bpm_bars_popup(
+                    app_state.get_bpm(),
+                    app_state.loop_length_setting(),
+                    app_state.get_count_in(),
+                );
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Answer the open confirm popup: `y` runs the action, `n` or Esc keeps
+    /// things as they are, and `s` saves the session before discarding a
+    /// loop. Other keys leave the popup open.
+    fn handle_confirm_key(
+        &self,
+        app_state: &mut ApplicationState,
+        view_model: &mut ViewModel,
+        prompt: ConfirmPrompt,
+        key: &KeyCode,
+        effects: &mut Vec<Effect>,
+    ) {
+        match key {
+            KeyCode::Char('y' | 'Y') => {
+                view_model.confirm = None;
+                match prompt {
+                    ConfirmPrompt::DiscardLoop => {
+                        Self::back_to_browse(app_state, view_model, effects)
+                    }
+                    ConfirmPrompt::ResetEffects => {
+                        for cmd in app_state.pads.reset_effects() {
+                            effects.push(Effect::AudioCommand(cmd));
+                        }
+                        effects.push(Effect::StatusMessage("Pad effects reset".to_string()));
+                    }
                 }
             }
-            KeyCode::Char(c) => {
-                let k = c.to_ascii_lowercase();
-                if app_state.pads.key_to_slot.contains_key(&k) {
-                    // Check debounce
-                    let now_ms = SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_millis();
-                    if let Some(prev) = app_state.pads.last_press_ms.get(&k).cloned()
-                        && now_ms.saturating_sub(prev) < 100
-                    {
-                        return Ok(());
+            KeyCode::Char('s' | 'S') if prompt == ConfirmPrompt::DiscardLoop => {
+                view_model.confirm = None;
+                match default_session_path() {
+                    Some(path) => {
+                        self.save_session_and_leave(app_state, view_model, &path, effects)
                     }
-                    app_state.pads.last_press_ms.insert(k, now_ms);
-                    app_state.pads.active_keys.insert(k);
+                    None => effects.push(Effect::StatusMessage(
+                        "No home directory to save the session in".to_string(),
+                    )),
+                }
+            }
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+                view_model.confirm = None;
+                effects.push(Effect::StatusMessage(prompt.declined_message().to_string()));
+            }
+            _ => {}
+        }
+    }
 
-                    // Record loop event and potentially play audio
-                    let loop_state = app_state.loop_state();
-                    if !matches!(loop_state, LoopState::Recording { .. }) {
-                        effects.push(Effect::AudioCommand(AudioCommand::Play { key: k }));
-                    }
-                    app_state.record_loop_event(k);
+    fn back_to_browse(
+        app_state: &mut ApplicationState,
+        view_model: &mut ViewModel,
+        effects: &mut Vec<Effect>,
+    ) {
+        app_state.cancel_loop();
+        view_model.mode = crate::presentation::Mode::Browse;
+        // Pads held down while leaving never see their release in Browse
+        app_state.pads.active_keys.clear();
+        if app_state.config.leave_pads == LeavePadsVoices::Stop {
+            effects.push(Effect::AudioCommand(AudioCommand::StopAll));
+        }
+        effects.push(Effect::StatusMessage("Back to browse".to_string()));
+    }
+
+    /// Trigger a mapped pad from the keyboard: debounce, play (unless the
+    /// loop is recording) and record it into the loop.
+    fn trigger_pad(&self, app_state: &mut ApplicationState, c: char, effects: &mut Vec<Effect>) {
+        let k = c.to_ascii_lowercase();
+        if !app_state.pads.key_to_slot.contains_key(&k) {
+            return;
+        }
+        // Key repeats of a held gate pad must not restart it
+        if app_state.pads.is_gated(k)
+            && app_state.pads.reports_releases
+            && app_state.pads.active_keys.contains(&k)
+        {
+            return;
+        }
+        // Check debounce
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        if let Some(prev) = app_state.pads.last_press_ms.get(&k).cloned()
+            && now_ms.saturating_sub(prev) < app_state.debounce_ms()
+        {
+            return;
+        }
+        app_state.pads.last_press_ms.insert(k, now_ms);
+        app_state.pads.active_keys.insert(k);
+        app_state.pads.last_triggered = Some(k);
+        app_state.pads.focused_pad = None;
+        app_state.log_live_hit(k);
+
+        // Record loop event and potentially play audio
+        let reversed = app_state.pads.next_hit_reversed(k);
+        let loop_state = app_state.loop_state();
+        if !matches!(loop_state, LoopState::Recording { .. }) {
+            let cmd = if app_state.pads.is_gated(k) {
+                AudioCommand::PlayGated { key: k, reversed }
+            } else if reversed {
+                AudioCommand::PlayReversed { key: k, gain: 1.0 }
+            } else {
+                AudioCommand::Play { key: k }
+            };
+            effects.push(Effect::AudioCommand(cmd));
+        }
+        app_state.record_loop_hit(k, reversed);
+        if app_state.config.announce_pads {
+            effects.push(Effect::Announce(Self::describe_hit(
+                app_state, k, reversed, loop_state,
+            )));
+        }
+    }
+
+    /// Spoken-style description of a pad hit, e.g. "Recorded snare
+    /// reversed on W".
+    fn describe_hit(
+        app_state: &ApplicationState,
+        key: char,
+        reversed: bool,
+        loop_state: LoopState,
+    ) -> String {
+        let file_name = &app_state.pads.key_to_slot[&key].file_name;
+        let name = Path::new(file_name)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy())
+            .unwrap_or_else(|| file_name.into());
+        let verb = if matches!(loop_state, LoopState::Recording { .. }) {
+            "Recorded"
+        } else {
+            "Played"
+        };
+        let direction = if reversed { " reversed" } else { "" };
+        format!("{verb} {name}{direction} on {}", key.to_ascii_uppercase())
+    }
+
+    /// Handles key presses in the arrange view.
+    ///
+    /// Returns false for keys the arrange view does not own (Space and Ctrl
+    /// combos), so loop control keeps working while arranging.
+    fn handle_arrange_key(
+        &self,
+        app_state: &mut ApplicationState,
+        view_model: &mut ViewModel,
+        key: &KeyCode,
+        modifiers: KeyModifiers,
+        effects: &mut Vec<Effect>,
+    ) -> bool {
+        if modifiers.control || matches!(key, KeyCode::Char(' ') | KeyCode::Tab) {
+            return false;
+        }
+        let Some(action) = keymap::lookup(KeyContext::Arrange, key, modifiers) else {
+            return true;
+        };
+        let count = app_state.tracks_snapshot().len();
+        let cursor = view_model.arrange_cursor.min(count.saturating_sub(1));
+        view_model.arrange_cursor = cursor;
+        match action {
+            KeyAction::CloseArrange => {
+                view_model.close_arrange();
+                effects.push(Effect::StatusMessage("Back to pads".to_string()));
+            }
+            KeyAction::SelectPrevTrack
+            | KeyAction::SelectNextTrack
+            | KeyAction::MoveTrackUp
+            | KeyAction::MoveTrackDown => {
+                let up = matches!(action, KeyAction::SelectPrevTrack | KeyAction::MoveTrackUp);
+                let target = if up {
+                    cursor.saturating_sub(1)
+                } else {
+                    (cursor + 1).min(count.saturating_sub(1))
+                };
+                // Moving takes the selected track along with the cursor
+                let moving = matches!(action, KeyAction::MoveTrackUp | KeyAction::MoveTrackDown);
+                if !moving || app_state.move_track(cursor, target) {
+                    view_model.arrange_cursor = target;
+                }
+            }
+            KeyAction::ToggleTrackMute => {
+                if let Some(muted) = app_state.toggle_track_mute(cursor) {
+                    let state = if muted { "muted" } else { "unmuted" };
+                    effects.push(Effect::StatusMessage(format!(
+                        "Track {} {}",
+                        cursor + 1,
+                        state
+                    )));
+                }
+            }
+            KeyAction::RenameTrack if count > 0 => {
+                let name = app_state.tracks_snapshot()[cursor].name.clone();
+                view_model.begin_text_capture(name.as_deref().unwrap_or(""));
+                effects.push(Effect::StatusMessage(format!(
+                    "Rename track {}: Enter to save, Esc to cancel",
+                    cursor + 1
+                )));
+            }
+            KeyAction::ToggleRestBar => {
+                let message = match app_state.toggle_rest_bar() {
+                    Some(true) => "Bar marked as rest",
+                    Some(false) => "Rest removed",
+                    None => "Play the loop to mark rests",
+                };
+                effects.push(Effect::StatusMessage(message.to_string()));
+            }
+            KeyAction::DeleteTrack if count > 0 => {
+                app_state.remove_track(cursor);
+                view_model.arrange_cursor = cursor.min(count.saturating_sub(2));
+                effects.push(Effect::StatusMessage(format!(
+                    "Track {} deleted",
+                    cursor + 1
+                )));
+            }
+            KeyAction::DuplicateTrack if count > 0 => {
+                app_state.duplicate_track(cursor);
+                view_model.arrange_cursor = cursor + 1;
+                effects.push(Effect::StatusMessage(format!(
+                    "Track {} duplicated",
+                    cursor + 1
+                )));
+            }
+            KeyAction::TrackVolumeUp | KeyAction::TrackVolumeDown => {
+                let delta = if action == KeyAction::TrackVolumeDown {
+                    -TRACK_VOLUME_STEP
+                } else {
+                    TRACK_VOLUME_STEP
+                };
+                if let Some(volume) = app_state.adjust_track_volume(cursor, delta) {
+                    effects.push(Effect::StatusMessage(format!(
+                        "Track {} volume {}%",
+                        cursor + 1,
+                        (volume * 100.0).round() as u32
+                    )));
                 }
             }
             _ => {}
         }
+        true
+    }
+
+    /// Handles key presses while a text field has focus.
+    ///
+    /// Enter renames the selected arrange track (an empty name clears it),
+    /// Esc discards the edit and everything else edits the draft.
+    fn handle_text_capture_key(
+        &self,
+        app_state: &mut ApplicationState,
+        view_model: &mut ViewModel,
+        key: KeyCode,
+        effects: &mut Vec<Effect>,
+    ) -> anyhow::Result<()> {
+        match key {
+            KeyCode::Esc => {
+                view_model.end_text_capture();
+                effects.push(Effect::StatusMessage("Rename cancelled".to_string()));
+            }
+            KeyCode::Enter => {
+                let text = view_model.end_text_capture();
+                let name = Some(text.trim().to_string()).filter(|name| !name.is_empty());
+                let cursor = view_model.arrange_cursor;
+                if app_state.rename_track(cursor, name) {
+                    effects.push(Effect::StatusMessage(format!(
+                        "Track {} renamed",
+                        cursor + 1
+                    )));
+                }
+            }
+            _ => {
+                use tui_input::backend::crossterm::to_input_request;
 
+                if let Ok(event) = self.keycode_to_event(key)
+                    && let Some(req) = to_input_request(&event)
+                {
+                    let _ = view_model.text_draft.handle(req);
+                }
+            }
+        }
         Ok(())
     }
 
+    /// Handles the new key for a pad in mapping mode. Taken or non-pad keys
+    /// leave mapping mode on so another key can be tried.
+    fn handle_remap_key(
+        &self,
+        app_state: &mut ApplicationState,
+        view_model: &mut ViewModel,
+        from: char,
+        key: KeyCode,
+        effects: &mut Vec<Effect>,
+    ) {
+        let to = match key {
+            KeyCode::Esc => {
+                view_model.remap_from = None;
+                effects.push(Effect::StatusMessage("Remap cancelled".to_string()));
+                return;
+            }
+            KeyCode::Char(c) => c.to_ascii_lowercase(),
+            _ => return,
+        };
+        match app_state.remap_pad(from, to) {
+            Ok(remap) => {
+                view_model.remap_from = None;
+                effects.extend(remap.commands.into_iter().map(Effect::AudioCommand));
+                let mut message = format!(
+                    "Pad {} moved to {}",
+                    from.to_ascii_uppercase(),
+                    to.to_ascii_uppercase()
+                );
+                if remap.moved_hits > 0 {
+                    message.push_str(&format!(" ({} loop hits moved)", remap.moved_hits));
+                }
+                effects.push(Effect::StatusMessage(message));
+            }
+            Err(error) => effects.push(Effect::StatusMessage(error.to_string())),
+        }
+    }
+
+    /// Handles the group digit for a pad in pad-edit mode. Other keys leave
+    /// the mode on so a digit can still be pressed.
+    fn handle_choke_key(
+        app_state: &mut ApplicationState,
+        view_model: &mut ViewModel,
+        pad: char,
+        key: KeyCode,
+        effects: &mut Vec<Effect>,
+    ) {
+        let group = match key {
+            KeyCode::Esc => {
+                view_model.choke_edit = None;
+                effects.push(Effect::StatusMessage("Choke group unchanged".to_string()));
+                return;
+            }
+            KeyCode::Char('0') => None,
+            KeyCode::Char(c @ '1'..='9') => c.to_digit(10).map(|digit| digit as u8),
+            _ => return,
+        };
+        view_model.choke_edit = None;
+        let label = pad.to_ascii_uppercase();
+        let Some(cmd) = app_state.pads.set_choke_group(pad, group) else {
+            effects.push(Effect::StatusMessage(
+                AppError::PadUnmapped(pad).to_string(),
+            ));
+            return;
+        };
+        effects.push(Effect::AudioCommand(cmd));
+        let message = match group {
+            Some(group) => format!("Pad {label} in choke group {group}"),
+            None => format!("Pad {label} left its choke group"),
+        };
+        effects.push(Effect::StatusMessage(message));
+    }
+
     /// Handles key presses when popup is open.
     fn handle_popup_key(
         &self,
         app_state: &mut ApplicationState,
         view_model: &mut ViewModel,
         key: KeyCode,
-        _effects: &mut [Effect],
+        modifiers: KeyModifiers,
+        effects: &mut Vec<Effect>,
     ) -> anyhow::Result<()> {
         use crate::presentation::PopupFocus;
         use ratatui::crossterm::event::Event;
@@ -255,32 +1294,77 @@ impl AppService {
             }
             KeyCode::Enter => match view_model.popup_focus() {
                 PopupFocus::PopupOk => {
-                    // Apply popup changes
-                    let mut changed = false;
-                    if let Ok(bpm) = view_model.draft_bpm().value().parse::<u16>() {
-                        let before = app_state.get_bpm();
-                        app_state.set_bpm(bpm);
-                        if app_state.get_bpm() != before {
-                            changed = true;
-                        }
-                    }
-                    if let Ok(bars) = view_model.draft_bars().value().parse::<u16>() {
-                        let before = app_state.get_bars();
-                        app_state.set_bars(bars);
-                        if app_state.get_bars() != before {
-                            changed = true;
-                        }
+                    let bpm = view_model.draft_bpm().value().parse::<u16>();
+                    let length = LoopLength::parse(
+                        view_model.draft_bars().value(),
+                        app_state.time_signature().numerator,
+                    );
+                    let count_in = view_model.draft_count_in().value().parse::<u8>();
+                    // Keep the popup open on a bad field and send focus back
+                    // to it, so nothing is silently left unchanged
+                    let invalid = if bpm.is_err() {
+                        Some((PopupFocus::PopupFieldBpm, "BPM"))
+                    } else if length.is_none() {
+                        Some((PopupFocus::PopupFieldBars, "Bars"))
+                    } else if count_in.is_err() {
+                        Some((PopupFocus::PopupFieldCountIn, "Count-in"))
+                    } else {
+                        None
+                    };
+                    if let Some((field, label)) = invalid {
+                        let draft = match field {
+                            PopupFocus::PopupFieldBpm => view_model.draft_bpm(),
+                            PopupFocus::PopupFieldBars => view_model.draft_bars(),
+                            _ => view_model.draft_count_in(),
+                        };
+                        let message = if draft.value().trim().is_empty() {
+                            format!("{label} cannot be empty")
+                        } else {
+                            format!("{label} is not a valid number: {}", draft.value())
+                        };
+                        view_model.popup_focus = field;
+                        effects.push(Effect::StatusMessage(message));
+                        return Ok(());
                     }
-                    if changed {
-                        app_state.reset_loop_for_tempo();
+                    // Apply popup changes (resets the loop if anything changed)
+                    app_state.apply_tempo_length(bpm.ok(), length);
+                    if let Ok(count_in) = count_in {
+                        app_state.set_count_in(count_in);
                     }
                     view_model.close_bpm_bars_popup();
                 }
                 PopupFocus::PopupCancel => {
                     view_model.close_bpm_bars_popup();
                 }
+                PopupFocus::PopupClick => match view_model.draft_bpm().value().parse::<u16>() {
+                    Ok(bpm) => {
+                        let commands = app_state.start_click_preview(bpm);
+                        effects.extend(commands.into_iter().map(Effect::AudioCommand));
+                        effects.push(Effect::StatusMessage(format!(
+                            "Click preview at {} BPM",
+                            clamp_bpm(bpm)
+                        )));
+                    }
+                    Err(_) => effects.push(Effect::StatusMessage(format!(
+                        "BPM is not a valid number: {}",
+                        view_model.draft_bpm().value()
+                    ))),
+                },
                 _ => {}
             },
+            // Shift+Up/Down and +/- dial the focused field instead of moving focus
+            KeyCode::Up if modifiers.shift => {
+                Self::step_popup_field(app_state, view_model, 1);
+            }
+            KeyCode::Down if modifiers.shift => {
+                Self::step_popup_field(app_state, view_model, -1);
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                Self::step_popup_field(app_state, view_model, 1);
+            }
+            KeyCode::Char('-') => {
+                Self::step_popup_field(app_state, view_model, -1);
+            }
             KeyCode::Up => {
                 view_model.popup_focus_up();
             }
@@ -288,7 +1372,7 @@ impl AppService {
                 view_model.popup_focus_down();
             }
             KeyCode::Left | KeyCode::Right => {
-                view_model.popup_toggle_ok_cancel();
+                view_model.popup_cycle_buttons(key == KeyCode::Right);
             }
             _ => {
                 // Handle all other keys (including Char, Backspace, Delete, etc.) for text input
@@ -297,8 +1381,12 @@ impl AppService {
                 if let Ok(Event::Key(crossterm_key)) = self.keycode_to_event(key)
                     && let Some(req) = to_input_request(&Event::Key(crossterm_key))
                 {
-                    // Enforce digits-only input for InsertChar requests
+                    // Enforce digits-only input for InsertChar requests; the
+                    // length field also takes a `b` suffix for beats
                     let should_apply = match req {
+                        InputRequest::InsertChar('b' | 'B') => {
+                            matches!(view_model.popup_focus(), PopupFocus::PopupFieldBars)
+                        }
                         InputRequest::InsertChar(ch) => ch.is_ascii_digit(),
                         _ => true,
                     };
@@ -310,6 +1398,9 @@ impl AppService {
                             PopupFocus::PopupFieldBars => {
                                 let _ = view_model.draft_bars_mut().handle(req);
                             }
+                            PopupFocus::PopupFieldCountIn => {
+                                let _ = view_model.draft_count_in_mut().handle(req);
+                            }
                             _ => {}
                         }
                     }
@@ -317,9 +1408,57 @@ impl AppService {
             }
         }
 
+        // Closing the popup cuts a click preview short
+        if !view_model.is_popup_open {
+            app_state.stop_click_preview();
+        }
         Ok(())
     }
 
+    /// Step the focused popup field by `delta`, clamped to the tempo limits.
+    ///
+    /// An empty or unparsable draft steps from the current applied value.
+    fn step_popup_field(app_state: &ApplicationState, view_model: &mut ViewModel, delta: i32) {
+        use crate::presentation::PopupFocus;
+        use tui_input::Input as TextInput;
+
+        let step = |draft: &TextInput, current: u16, clamp: fn(u16) -> u16| {
+            let value = draft.value().parse::<u16>().unwrap_or(current);
+            let stepped = (value as i32 + delta).clamp(0, u16::MAX as i32) as u16;
+            TextInput::new(clamp(stepped).to_string())
+        };
+        match view_model.popup_focus() {
+            PopupFocus::PopupFieldBpm => {
+                let next = step(view_model.draft_bpm(), app_state.get_bpm(), clamp_bpm);
+                *view_model.draft_bpm_mut() = next;
+            }
+            PopupFocus::PopupFieldBars => {
+                let beats_per_bar = app_state.time_signature().numerator;
+                let next = match LoopLength::parse(view_model.draft_bars().value(), beats_per_bar) {
+                    // A length in beats steps by one beat
+                    Some(LoopLength::Beats(beats)) => {
+                        let stepped = (beats as i32 + delta).clamp(0, u16::MAX as i32) as u16;
+                        TextInput::new(LoopLength::from_beats(stepped, beats_per_bar).to_string())
+                    }
+                    _ => step(view_model.draft_bars(), app_state.get_bars(), clamp_bars),
+                };
+                *view_model.draft_bars_mut() = next;
+            }
+            PopupFocus::PopupFieldCountIn => {
+                let current = app_state.get_count_in();
+                let value = view_model
+                    .draft_count_in()
+                    .value()
+                    .parse()
+                    .unwrap_or(current);
+                let stepped = (value as i32 + delta).clamp(0, u8::MAX as i32) as u8;
+                *view_model.draft_count_in_mut() =
+                    TextInput::new(clamp_count_in(stepped).to_string());
+            }
+            _ => {}
+        }
+    }
+
     /// Temporary helper to convert KeyCode back to Event::Key for TextInput.
     /// TODO: Abstract TextInput behind a trait to avoid this dependency.
     fn keycode_to_event(&self, key: KeyCode) -> anyhow::Result<Event> {
@@ -334,6 +1473,8 @@ impl AppService {
             KeyCode::Char(c) => CrosstermKeyCode::Char(c),
             KeyCode::Delete => CrosstermKeyCode::Delete,
             KeyCode::Backspace => CrosstermKeyCode::Backspace,
+            KeyCode::PageUp => CrosstermKeyCode::PageUp,
+            KeyCode::PageDown => CrosstermKeyCode::PageDown,
             KeyCode::Other(_) => {
                 return Err(anyhow::anyhow!("Cannot convert Other key code to Event"));
             }
@@ -357,16 +1498,101 @@ impl AppService {
             navigator.handle_navigation_key(key)?;
         }
 
-        // Update current_left_item based on navigator selection
+        Self::sync_left_item(view_model);
+        Ok(())
+    }
+
+    /// Update current_left_item from the navigator selection; entries the
+    /// filter hides leave nothing to select.
+    fn sync_left_item(view_model: &mut ViewModel) {
         // Note: We need to get the navigator again after the mutable borrow ends
         let selected = view_model.as_navigator().selected_entry();
-        if let Some(entry) = selected {
-            view_model.current_left_item = Some(entry.path);
-            view_model.current_left_is_dir = entry.is_dir;
+        view_model.current_left_is_dir = selected.as_ref().is_some_and(|entry| entry.is_dir);
+        view_model.current_left_item = selected.map(|entry| entry.path);
+    }
+
+    /// Handles key presses while the explorer filter input is open.
+    ///
+    /// Typing narrows the explorer to matching names, Up/Down move between
+    /// matches, Enter keeps the filter and hands the keys back, and Esc
+    /// clears it.
+    fn handle_filter_key(
+        &self,
+        view_model: &mut ViewModel,
+        key: KeyCode,
+        effects: &mut Vec<Effect>,
+    ) -> anyhow::Result<()> {
+        match key {
+            KeyCode::Esc => {
+                view_model.clear_filter();
+                effects.push(Effect::StatusMessage("Filter cleared".to_string()));
+            }
+            KeyCode::Enter if view_model.explorer_filter.value().is_empty() => {
+                view_model.clear_filter();
+            }
+            KeyCode::Enter => {
+                view_model.filter_open = false;
+                effects.push(Effect::StatusMessage(format!(
+                    "Filter: {} (Esc clears)",
+                    view_model.explorer_filter.value()
+                )));
+            }
+            KeyCode::Up | KeyCode::Down => {
+                self.handle_file_explorer_navigation(view_model, key, effects)?;
+            }
+            _ => {
+                use tui_input::backend::crossterm::to_input_request;
+
+                if let Ok(event) = self.keycode_to_event(key)
+                    && let Some(req) = to_input_request(&event)
+                {
+                    let _ = view_model.explorer_filter.handle(req);
+                }
+                let found = view_model.as_navigator().apply_filter();
+                Self::sync_left_item(view_model);
+                let filter = view_model.explorer_filter.value();
+                effects.push(Effect::StatusMessage(if found {
+                    format!("Filter: {filter}")
+                } else {
+                    format!("No names match \"{filter}\"")
+                }));
+            }
         }
         Ok(())
     }
 
+    /// Audition the file under the explorer cursor a few times back to back,
+    /// replacing any preview already playing.
+    fn handle_preview_loop(
+        &self,
+        app_state: &mut ApplicationState,
+        view_model: &ViewModel,
+        effects: &mut Vec<Effect>,
+    ) {
+        let path = match &view_model.current_left_item {
+            Some(path) if !view_model.current_left_is_dir && is_supported_audio(path) => {
+                path.clone()
+            }
+            _ => {
+                effects.push(Effect::StatusMessage(
+                    "Move to an audio file to preview it".to_string(),
+                ));
+                return;
+            }
+        };
+        effects.push(Effect::AudioCommand(AudioCommand::StopAudition));
+        effects.push(Effect::AudioCommand(AudioCommand::PlayPath {
+            path: path.clone(),
+            repeats: PREVIEW_LOOP_REPEATS,
+        }));
+        app_state.auditioning = true;
+        effects.push(Effect::StatusMessage(format!(
+            "Previewing {} x{}",
+            path.file_name().and_then(|n| n.to_str()).unwrap_or("?"),
+            PREVIEW_LOOP_REPEATS
+        )));
+    }
+
     /// Handle file selection (Space key in left pane).
     fn handle_file_selection(
         &self,
@@ -385,22 +1611,51 @@ impl AppService {
         Ok(())
     }
 
-    /// Handle selection management (Up/Down/Delete in right pane).
+    /// Handle selection management actions (cursor, paging, fill and
+    /// unselect in the right pane).
     fn handle_selection_management(
         &self,
         app_state: &mut ApplicationState,
         _view_model: &mut ViewModel,
-        key: KeyCode,
+        action: KeyAction,
         effects: &mut Vec<Effect>,
     ) -> anyhow::Result<()> {
-        match key {
-            KeyCode::Up => {
+        match action {
+            KeyAction::SelectionUp if app_state.config.wrap_selection => {
+                app_state.selection.move_up_wrapping();
+            }
+            KeyAction::SelectionDown if app_state.config.wrap_selection => {
+                app_state.selection.move_down_wrapping();
+            }
+            KeyAction::SelectionUp => {
                 app_state.selection.move_up();
             }
-            KeyCode::Down => {
+            KeyAction::SelectionDown => {
                 app_state.selection.move_down();
             }
-            KeyCode::Char(' ') | KeyCode::Delete | KeyCode::Char('d') => {
+            KeyAction::SelectionPageUp => {
+                let step = app_state.config.selection_page_step();
+                app_state.selection.page_up(step);
+            }
+            KeyAction::SelectionPageDown => {
+                let step = app_state.config.selection_page_step();
+                app_state.selection.page_down(step);
+            }
+            KeyAction::FillPads => {
+                let message = match app_state.fill_selection_to_pads() {
+                    0 if app_state.selection.items.is_empty() => {
+                        "Select at least one file first".to_string()
+                    }
+                    0 => "Every pad already has a sample".to_string(),
+                    added => format!(
+                        "Filled pads: added {} copies ({} pads)",
+                        added,
+                        app_state.selection.items.len()
+                    ),
+                };
+                effects.push(Effect::StatusMessage(message));
+            }
+            KeyAction::Unselect => {
                 let before_len = app_state.selection.items.len();
                 app_state.selection.remove_at_cursor();
                 if app_state.selection.items.len() < before_len {
@@ -412,3 +1667,13 @@ impl AppService {
         Ok(())
     }
 }
+
+/// Format a pan value for display, e.g. "C", "L30", "R100".
+fn format_pan(pan: f32) -> String {
+    let percent = (pan * 100.0).round() as i32;
+    match percent {
+        0 => "C".to_string(),
+        p if p < 0 => format!("L{}", -p),
+        p => format!("R{}", p),
+    }
+}
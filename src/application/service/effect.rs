@@ -11,12 +11,18 @@ use crate::audio::AudioCommand;
 /// Effects are produced by application services and consumed by infrastructure
 /// layer components (e.g., audio thread, UI updates). This decouples the
 /// application layer from direct infrastructure dependencies.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Effect {
     /// Send an audio command to the audio thread
     AudioCommand(AudioCommand),
     /// Update the status message displayed in the UI footer
     StatusMessage(String),
+    /// Plain-language description of what just happened (e.g. "Played
+    /// kick on Q") for screen-reader users; only produced when
+    /// announcements are enabled
+    Announce(String),
+    /// Tear down and respawn the audio thread (subsequent audio commands go to the new thread)
+    ResetAudio,
     // Future effects can be added here:
     // - UiUpdate(UiUpdateCommand)
     // - Log(LogMessage)
@@ -0,0 +1,264 @@
+//! Saved sessions.
+//!
+//! This module contains `Session`, the serializable mirror of what is needed
+//! to pick up where a performance stopped: the pad mapping, bounced pads, the
+//! tempo and the recorded tracks with their rests. Offsets are stored as
+//! whole microseconds because the domain's `Duration`-based types do not
+//! serialize directly.
+
+use crate::audio::{Bounce, BounceHit};
+use crate::domain::r#loop::{LoopSnapshot, RecordedEvent, RestRegion, TrackSnapshot};
+use crate::domain::tempo::{LoopLength, clamp_bars};
+use crate::domain::timing::TimeSignature;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Session file location under the home directory.
+const SESSION_FILE: &str = ".termigroove/session.json";
+
+/// Default session file, `~/.termigroove/session.json`, or `None` when the
+/// home directory is unknown.
+pub fn default_session_path() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .filter(|home| !home.is_empty())
+        .map(|home| PathBuf::from(home).join(SESSION_FILE))
+}
+
+/// Everything a saved session restores.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub bpm: u16,
+    /// Loop length in whole bars (rounded up for beat lengths)
+    pub bars: u16,
+    /// Loop length in beats when it does not fill whole bars
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beats: Option<u16>,
+    /// Time signature as "3/4"; sessions saved before it was stored have
+    /// none and keep the current one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_signature: Option<String>,
+    /// File-backed pads, by key
+    pub pads: Vec<SessionPad>,
+    /// Bounced pads, in the order they were bounced so later bounces can
+    /// mix earlier ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bounces: Vec<SessionBounce>,
+    pub loop_length_us: u64,
+    pub tracks: Vec<SessionTrack>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rests: Vec<SessionRest>,
+}
+
+/// A pad and the sample file it plays.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionPad {
+    pub key: char,
+    pub path: PathBuf,
+}
+
+/// A pad playing a bounce of the loop, re-rendered from its hits on load.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionBounce {
+    pub key: char,
+    pub name: String,
+    pub length_us: u64,
+    pub hits: Vec<SessionBounceHit>,
+}
+
+/// A pad hit mixed into a bounce.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionBounceHit {
+    pub key: char,
+    pub offset_us: u64,
+    pub gain: f32,
+    #[serde(default)]
+    pub reversed: bool,
+}
+
+impl SessionBounce {
+    /// Capture a bounced pad.
+    pub fn new(key: char, name: String, bounce: &Bounce) -> Self {
+        Self {
+            key,
+            name,
+            length_us: bounce.length.as_micros() as u64,
+            hits: bounce
+                .hits
+                .iter()
+                .map(|hit| SessionBounceHit {
+                    key: hit.key,
+                    offset_us: hit.offset.as_micros() as u64,
+                    gain: hit.gain,
+                    reversed: hit.reversed,
+                })
+                .collect(),
+        }
+    }
+
+    /// Saved bounce, ready for `AudioCommand::Bounce`.
+    pub fn bounce(&self) -> Bounce {
+        Bounce {
+            length: Duration::from_micros(self.length_us),
+            hits: self
+                .hits
+                .iter()
+                .map(|hit| BounceHit {
+                    key: hit.key,
+                    offset: Duration::from_micros(hit.offset_us),
+                    gain: hit.gain,
+                    reversed: hit.reversed,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A span of the loop cycle kept silent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRest {
+    pub start_us: u64,
+    pub end_us: u64,
+}
+
+/// A recorded track.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionTrack {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub volume: f32,
+    pub muted: bool,
+    pub events: Vec<SessionEvent>,
+}
+
+/// A recorded pad hit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionEvent {
+    pub key: char,
+    pub offset_us: u64,
+    #[serde(default)]
+    pub reversed: bool,
+}
+
+impl Session {
+    /// Capture a session from its parts.
+    pub fn new(
+        bpm: u16,
+        length: LoopLength,
+        signature: TimeSignature,
+        pads: Vec<SessionPad>,
+        snapshot: &LoopSnapshot,
+        rests: &[RestRegion],
+    ) -> Self {
+        Self {
+            bpm,
+            bars: length.whole_bars(signature.numerator),
+            beats: match length {
+                LoopLength::Bars(_) => None,
+                LoopLength::Beats(beats) => Some(beats),
+            },
+            time_signature: Some(signature.to_string()),
+            pads,
+            bounces: Vec::new(),
+            loop_length_us: snapshot.loop_length.as_micros() as u64,
+            tracks: snapshot
+                .tracks
+                .iter()
+                .map(|track| SessionTrack {
+                    name: track.name.clone(),
+                    volume: track.volume,
+                    muted: track.muted,
+                    events: track
+                        .events
+                        .iter()
+                        .map(|event| SessionEvent {
+                            key: event.key,
+                            offset_us: event.offset.as_micros() as u64,
+                            reversed: event.reversed,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            rests: rests
+                .iter()
+                .map(|rest| SessionRest {
+                    start_us: rest.start.as_micros() as u64,
+                    end_us: rest.end.as_micros() as u64,
+                })
+                .collect(),
+        }
+    }
+
+    /// Add the bounced pads, in bounce order.
+    pub fn with_bounces(mut self, bounces: Vec<SessionBounce>) -> Self {
+        self.bounces = bounces;
+        self
+    }
+
+    /// Saved time signature, if the session has one.
+    pub fn signature(&self) -> Option<TimeSignature> {
+        self.time_signature
+            .as_deref()
+            .and_then(TimeSignature::parse)
+    }
+
+    /// Saved loop length setting, in bars of `beats_per_bar`.
+    pub fn length(&self, beats_per_bar: u16) -> LoopLength {
+        match self.beats {
+            Some(beats) => LoopLength::from_beats(beats, beats_per_bar),
+            None => LoopLength::Bars(clamp_bars(self.bars)),
+        }
+    }
+
+    /// Saved loop content, ready for `LoopEngine::restore`.
+    pub fn loop_snapshot(&self) -> LoopSnapshot {
+        LoopSnapshot {
+            loop_length: Duration::from_micros(self.loop_length_us),
+            tracks: self
+                .tracks
+                .iter()
+                .map(|track| TrackSnapshot {
+                    name: track.name.clone(),
+                    volume: track.volume,
+                    muted: track.muted,
+                    events: track
+                        .events
+                        .iter()
+                        .map(|event| RecordedEvent {
+                            key: event.key,
+                            offset: Duration::from_micros(event.offset_us),
+                            reversed: event.reversed,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Saved rest regions of the loop cycle.
+    pub fn rest_regions(&self) -> Vec<RestRegion> {
+        self.rests
+            .iter()
+            .map(|rest| RestRegion {
+                start: Duration::from_micros(rest.start_us),
+                end: Duration::from_micros(rest.end_us),
+            })
+            .collect()
+    }
+
+    /// Read a session file.
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Write the session as JSON, creating its directory if needed.
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
@@ -2,18 +2,41 @@
 //!
 //! This module contains `ApplicationState`, which holds:
 //! - Use case progress state (selection, pads)
-//! - Application configuration (bpm, bars)
+//! - Application configuration (bpm, bars, behavior switches)
 //! - Domain entities (LoopEngine)
 //!
 //! This state is managed by the application layer and can be mutated by
 //! application services. It does not contain presentation concerns.
 
-use crate::audio::{AudioCommand, SenderAudioBus, SystemClock};
-use crate::domain::r#loop::{LoopEngine, LoopState};
-use crate::domain::tempo::{clamp_bars, clamp_bpm};
+use crate::application::config::{AppConfig, DEFAULT_SWING};
+use crate::application::dto::loop_state::LoopStateDto;
+use crate::application::error::AppError;
+use crate::application::hit_log::HitLog;
+use crate::application::session::{Session, SessionBounce, SessionPad};
+use crate::audio::{
+    AudioCommand, Bounce, BounceHit, BusLevel, PadEffects, SenderAudioBus, SystemClock,
+    VolumeCurve, sample_duration,
+};
+use crate::domain::r#loop::{
+    Humanize, LoopEngine, LoopSnapshot, LoopState, PracticeCycles, RestRegion, TrackInfo,
+};
+use crate::domain::tempo::{
+    ClickPreview, LoopLength, TapTempo, bpm_from_beats, clamp_bars, clamp_bpm,
+};
+use crate::domain::timing::TimeSignature;
 use crate::selection::SelectionModel;
-use std::collections::{BTreeMap, HashSet};
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Maximum number of tempo changes that can be undone.
+const TEMPO_HISTORY_DEPTH: usize = 8;
+
+/// How fast a pad's level meter falls, in full-scale units per second.
+pub const LEVEL_DECAY_PER_SEC: f32 = 2.0;
+
+/// Repeat presses of a pad closer together than this are dropped, in ms.
+pub const DEFAULT_DEBOUNCE_MS: u128 = 100;
 
 /// Application state for use case progress and domain entities.
 #[derive(Debug)]
@@ -24,12 +47,50 @@ pub struct ApplicationState {
     pub pads: PadsState,
     /// Current BPM (beats per minute)
     bpm: u16,
-    /// Current bars (number of bars in loop)
-    bars: u16,
+    /// Current loop length (bars, or beats when entered as beats)
+    length: LoopLength,
+    /// User-tunable behavior switches
+    pub config: AppConfig,
+    /// Whether the master output is muted
+    pub master_muted: bool,
+    /// Whether tempo edits (popup, fit, undo) are blocked mid-performance
+    pub tempo_locked: bool,
+    /// Volume and mute per mixing bus; unset buses are at unity
+    pub bus_levels: BTreeMap<u8, BusLevel>,
+    /// Whether a browse-time preview loop is queued on the audio thread
+    pub auditioning: bool,
+    /// Undo history of applied tempo changes (oldest first)
+    tempo_history: Vec<TempoUndo>,
+    /// Recent taps for tap tempo
+    tap_tempo: TapTempo,
+    /// Metronome audition started from the tempo popup
+    click_preview: Option<ClickPreview>,
+    /// Live pad hits, while hit logging is on
+    hit_log: Option<HitLog>,
+    /// Minimum time between two counted presses of the same pad, in ms
+    debounce_ms: u128,
     /// Domain entity: loop engine
     loop_engine: LoopEngine<SenderAudioBus, SystemClock>,
 }
 
+/// Values and loop content captured before a tempo change.
+#[derive(Debug, Clone)]
+struct TempoUndo {
+    bpm: u16,
+    length: LoopLength,
+    snapshot: LoopSnapshot,
+}
+
+/// How a pad's recorded hits take part in loop playback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PadMute {
+    Normal,
+    /// Skipped in playback
+    Muted,
+    /// Played while every pad that is not soloed is skipped
+    Soloed,
+}
+
 /// Pads state containing key mappings and active keys.
 #[derive(Debug, Default, Clone)]
 pub struct PadsState {
@@ -39,6 +100,251 @@ pub struct PadsState {
     pub active_keys: HashSet<char>,
     /// Timestamp of last press for each key (milliseconds)
     pub last_press_ms: BTreeMap<char, u128>,
+    /// Stereo pan per key (-1.0 left .. 1.0 right); unset keys are centered
+    pub pan: BTreeMap<char, f32>,
+    /// Mixing bus per key; unset keys play on bus 0
+    pub bus: BTreeMap<char, u8>,
+    /// Pad most recently triggered from the keyboard
+    pub last_triggered: Option<char>,
+    /// Pad picked with Alt+key for per-pad commands, until a pad is played
+    pub focused_pad: Option<char>,
+    /// Loops bounced onto pads, in the order they were rendered
+    pub bounces: Vec<(char, Bounce)>,
+    /// Pads whose successive hits alternate forward and reversed playback
+    pub ratchet_reverse: HashSet<char>,
+    /// Ratchet pads whose next hit plays reversed
+    ratchet_phase: HashSet<char>,
+    /// Meter level per sounding pad (0.0..=1.0), decaying between reports
+    pub levels: BTreeMap<char, f32>,
+    /// Native sample rate per pad, as reported once its sample is decoded
+    pub sample_rates: BTreeMap<char, u32>,
+    /// Samples loaded and total while a batch preload is in flight
+    pub preload_progress: Option<(usize, usize)>,
+    /// Pads whose recorded hits are silent in loop playback
+    pub muted_keys: HashSet<char>,
+    /// Pads that alone play back from the loop while any are soloed
+    pub soloed_keys: HashSet<char>,
+    /// Whether the terminal has reported a key release, so `active_keys`
+    /// tracks held pads instead of only growing
+    pub reports_releases: bool,
+}
+
+impl PadsState {
+    /// Record how far a batch preload got; it is done once every sample
+    /// has loaded or failed.
+    pub fn apply_preload_progress(&mut self, loaded: usize, total: usize) {
+        self.preload_progress = (loaded < total).then_some((loaded, total));
+    }
+
+    /// Loop playback setting of a pad.
+    pub fn mute_of(&self, key: char) -> PadMute {
+        if self.muted_keys.contains(&key) {
+            PadMute::Muted
+        } else if self.soloed_keys.contains(&key) {
+            PadMute::Soloed
+        } else {
+            PadMute::Normal
+        }
+    }
+
+    /// Whether a pad was pressed within the last `window_ms` before
+    /// `now_ms`, inclusive. Presses stamped after `now_ms` count as active.
+    pub fn is_active(&self, key: char, now_ms: u128, window_ms: u128) -> bool {
+        self.last_press_ms
+            .get(&key)
+            .is_some_and(|pressed| now_ms.saturating_sub(*pressed) <= window_ms)
+    }
+
+    /// Mark a pad key as released.
+    pub fn release(&mut self, key: char) {
+        self.reports_releases = true;
+        self.active_keys.remove(&key);
+    }
+
+    /// Whether a pad should be drawn highlighted: while it is held on
+    /// terminals that report releases, otherwise for `window_ms` after a
+    /// press (see [`is_active`](Self::is_active)).
+    pub fn is_lit(&self, key: char, now_ms: u128, window_ms: u128) -> bool {
+        if self.reports_releases {
+            self.active_keys.contains(&key)
+        } else {
+            self.is_active(key, now_ms, window_ms)
+        }
+    }
+
+    /// Most recently pressed pad key, if any.
+    pub fn last_pressed_key(&self) -> Option<char> {
+        self.last_press_ms
+            .iter()
+            .max_by_key(|(_, t)| **t)
+            .map(|(k, _)| *k)
+    }
+
+    /// Pad per-pad commands (preview, pan, remap, ratchet) act on: the
+    /// focused pad, else the most recently pressed one.
+    pub fn target_pad(&self) -> Option<char> {
+        self.focused_pad.or_else(|| self.last_pressed_key())
+    }
+
+    /// Mixing bus a key plays on.
+    pub fn bus_for(&self, key: char) -> u8 {
+        self.bus.get(&key).copied().unwrap_or(0)
+    }
+
+    /// Current pan for a key (center when unset).
+    pub fn pan_for(&self, key: char) -> f32 {
+        self.pan.get(&key).copied().unwrap_or(0.0)
+    }
+
+    /// Effect chain currently configured for a key.
+    pub fn effects_for(&self, key: char) -> PadEffects {
+        PadEffects {
+            pan: self.pan_for(key),
+            reverse: false,
+        }
+    }
+
+    /// Take a level reported by the audio thread. Meters jump up to louder
+    /// levels and otherwise fall at `LEVEL_DECAY_PER_SEC`.
+    pub fn apply_pad_level(&mut self, key: char, level: f32) {
+        let level = level.clamp(0.0, 1.0);
+        let meter = self.levels.entry(key).or_insert(0.0);
+        *meter = meter.max(level);
+        if *meter <= 0.0 {
+            self.levels.remove(&key);
+        }
+    }
+
+    /// Take a pad's decoded sample rate. Once every mapped pad has reported,
+    /// returns a normalization hint if their rates are far apart.
+    pub fn apply_sample_rate(&mut self, key: char, sample_rate: u32) -> Option<String> {
+        if !self.key_to_slot.contains_key(&key) {
+            return None;
+        }
+        let first_report = self.sample_rates.insert(key, sample_rate).is_none();
+        if !first_report || self.sample_rates.len() < self.key_to_slot.len() {
+            return None;
+        }
+        let (low, high) = sample_rate_mismatch(self.sample_rates.values().copied())?;
+        Some(format!(
+            "Samples range from {} to {} Hz and are resampled on play; consider normalizing them",
+            low, high
+        ))
+    }
+
+    /// Let every meter fall for `elapsed`; silent pads are dropped.
+    pub fn decay_levels(&mut self, elapsed: Duration) {
+        let fall = LEVEL_DECAY_PER_SEC * elapsed.as_secs_f32();
+        self.levels.retain(|_, level| {
+            *level -= fall;
+            *level > 0.0
+        });
+    }
+
+    /// Current meter level of a pad (0.0 when silent).
+    pub fn level_for(&self, key: char) -> f32 {
+        self.levels.get(&key).copied().unwrap_or(0.0)
+    }
+
+    /// Clear every per-pad effect override (pan, ratchet reverse). Returns
+    /// the commands that put the audio thread back on the defaults.
+    pub fn reset_effects(&mut self) -> Vec<AudioCommand> {
+        let commands = std::mem::take(&mut self.pan)
+            .into_keys()
+            .map(|key| AudioCommand::SetPadPan { key, pan: 0.0 })
+            .collect();
+        self.ratchet_reverse.clear();
+        self.ratchet_phase.clear();
+        commands
+    }
+
+    /// Mapped keys in keyboard order, top row first, so the grid mirrors
+    /// where the keys sit; keys off the pad keyboard come last.
+    pub fn keys_in_layout_order(&self) -> Vec<char> {
+        let layout = default_pad_keys();
+        let mut keys: Vec<char> = self.key_to_slot.keys().copied().collect();
+        keys.sort_by_key(|key| layout.iter().position(|k| k == key).unwrap_or(layout.len()));
+        keys
+    }
+
+    /// Whether a key's pad plays only while held.
+    pub fn is_gated(&self, key: char) -> bool {
+        self.key_to_slot
+            .get(&key)
+            .is_some_and(|slot| slot.play_mode == PlayMode::Gate)
+    }
+
+    /// Switch a pad between one-shot and gate. Returns the new mode, or
+    /// `None` when no pad is mapped to the key.
+    pub fn toggle_play_mode(&mut self, key: char) -> Option<PlayMode> {
+        let slot = self.key_to_slot.get_mut(&key)?;
+        slot.play_mode = match slot.play_mode {
+            PlayMode::OneShot => PlayMode::Gate,
+            PlayMode::Gate => PlayMode::OneShot,
+        };
+        Some(slot.play_mode)
+    }
+
+    /// Put a pad in a choke group, or take it out with `None`. Returns the
+    /// command for the audio thread, or `None` when no pad is mapped to the
+    /// key.
+    pub fn set_choke_group(&mut self, key: char, group: Option<u8>) -> Option<AudioCommand> {
+        let slot = self.key_to_slot.get_mut(&key)?;
+        slot.choke_group = group;
+        Some(AudioCommand::SetPadChoke { key, group })
+    }
+
+    /// Toggle ratchet reverse for a key. Returns whether it is now enabled.
+    pub fn toggle_ratchet_reverse(&mut self, key: char) -> bool {
+        self.ratchet_phase.remove(&key);
+        if self.ratchet_reverse.remove(&key) {
+            false
+        } else {
+            self.ratchet_reverse.insert(key);
+            true
+        }
+    }
+
+    /// Direction of the next hit of a key: true when it should play
+    /// reversed. Ratchet pads flip on every call; other pads always play
+    /// forwards.
+    pub fn next_hit_reversed(&mut self, key: char) -> bool {
+        if !self.ratchet_reverse.contains(&key) {
+            return false;
+        }
+        if self.ratchet_phase.remove(&key) {
+            true
+        } else {
+            self.ratchet_phase.insert(key);
+            false
+        }
+    }
+}
+
+/// Outcome of entering Pads mode.
+#[derive(Debug, Clone)]
+pub struct PadsEntry {
+    /// Batch preload of the mapped pads, in pad order
+    pub preload: AudioCommand,
+    /// Selected files that no longer exist and were left unmapped
+    pub skipped: Vec<PathBuf>,
+}
+
+impl PadsEntry {
+    /// Warning listing the skipped files, if any.
+    pub fn skipped_warning(&self) -> Option<String> {
+        (!self.skipped.is_empty())
+            .then(|| format!("Skipped missing files: {}", file_names(&self.skipped)))
+    }
+}
+
+/// Outcome of moving a pad to another key.
+#[derive(Debug, Clone)]
+pub struct PadRemap {
+    /// Commands that stop the old key and load the pad under the new one
+    pub commands: Vec<AudioCommand>,
+    /// Recorded loop hits rewritten to the new key
+    pub moved_hits: usize,
 }
 
 /// Sample slot information.
@@ -46,6 +352,23 @@ pub struct PadsState {
 pub struct SampleSlot {
     /// File name of the sample
     pub file_name: String,
+    /// Full path the sample was loaded from
+    pub path: PathBuf,
+    /// Whether a live hit plays out or only while its key is held
+    pub play_mode: PlayMode,
+    /// Live hits cut any sounding pad of the same group
+    pub choke_group: Option<u8>,
+}
+
+/// How long a live pad hit sounds.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PlayMode {
+    /// Play the whole sample
+    #[default]
+    OneShot,
+    /// Play while the key is held. Terminals that do not report key
+    /// releases never stop the sound, so these pads play as one-shots.
+    Gate,
 }
 
 impl ApplicationState {
@@ -55,7 +378,17 @@ impl ApplicationState {
             selection: SelectionModel::default(),
             pads: PadsState::default(),
             bpm: 120,
-            bars: 16,
+            length: LoopLength::Bars(16),
+            config: AppConfig::default(),
+            master_muted: false,
+            tempo_locked: false,
+            bus_levels: BTreeMap::new(),
+            auditioning: false,
+            tempo_history: Vec::new(),
+            tap_tempo: TapTempo::default(),
+            click_preview: None,
+            hit_log: None,
+            debounce_ms: DEFAULT_DEBOUNCE_MS,
             loop_engine,
         }
     }
@@ -65,9 +398,129 @@ impl ApplicationState {
         self.loop_engine.state()
     }
 
+    /// Flattened loop state with the current cycle offset, for rendering.
+    pub fn loop_state_dto(&self) -> LoopStateDto {
+        LoopStateDto::from(&self.loop_engine)
+    }
+
+    /// Replace the config and push engine-level settings (humanize, practice
+    /// cycles, quantize) down.
+    pub fn set_config(&mut self, config: AppConfig) {
+        let humanize = (config.humanize_ms > 0).then(|| Humanize {
+            amount: Duration::from_millis(config.humanize_ms as u64),
+            seed: config.humanize_seed.unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_nanos() as u64
+            }),
+        });
+        self.loop_engine.set_humanize(humanize);
+        let practice = config
+            .practice_cycles
+            .map(|(on, off)| PracticeCycles { on, off });
+        self.loop_engine.set_practice(practice);
+        self.loop_engine.set_quantize(config.quantize);
+        self.loop_engine
+            .set_auto_play_after_record(!config.hold_after_record);
+        self.loop_engine.set_extend_overdubs(config.extend_overdubs);
+        self.loop_engine.set_buffer_count_in(config.buffer_count_in);
+        self.loop_engine.set_swing(config.swing.unwrap_or(0));
+        if let Some(signature) = config.time_signature {
+            self.set_time_signature(signature);
+        }
+        if let Some(ms) = config.debounce_ms {
+            self.set_debounce_ms(ms);
+        }
+        self.config = config;
+    }
+
+    /// Flip the master mute. Returns the command for the audio thread.
+    pub fn toggle_master_mute(&mut self) -> AudioCommand {
+        self.master_muted = !self.master_muted;
+        AudioCommand::SetMasterMute(self.master_muted)
+    }
+
+    /// Current level of a bus.
+    pub fn bus_level(&self, bus: u8) -> BusLevel {
+        self.bus_levels.get(&bus).copied().unwrap_or_default()
+    }
+
+    /// Route a pad to a bus. Returns the command for the audio thread.
+    pub fn assign_pad_bus(&mut self, key: char, bus: u8) -> AudioCommand {
+        if bus == 0 {
+            self.pads.bus.remove(&key);
+        } else {
+            self.pads.bus.insert(key, bus);
+        }
+        AudioCommand::SetPadBus { key, bus }
+    }
+
+    /// Set a bus volume (0.0..=1.0), keeping its mute state.
+    pub fn set_bus_volume(&mut self, bus: u8, volume: f32) -> AudioCommand {
+        let level = BusLevel {
+            volume: volume.clamp(0.0, 1.0),
+            ..self.bus_level(bus)
+        };
+        self.bus_levels.insert(bus, level);
+        AudioCommand::SetBusLevel { bus, level }
+    }
+
+    /// Flip a bus mute, keeping its volume.
+    pub fn toggle_bus_mute(&mut self, bus: u8) -> AudioCommand {
+        let mut level = self.bus_level(bus);
+        level.muted = !level.muted;
+        self.bus_levels.insert(bus, level);
+        AudioCommand::SetBusLevel { bus, level }
+    }
+
     /// Update loop engine (call on each frame).
     pub fn update_loop(&mut self) {
+        let before = self.loop_engine.loop_length();
         self.loop_engine.update();
+        // An overdub ran on and lengthened the loop to the next bar
+        if !before.is_zero() && self.loop_engine.loop_length() > before {
+            self.length = self
+                .length
+                .next_bar_boundary(self.time_signature().numerator);
+        }
+    }
+
+    /// Switch playback swing off, or back on at the configured amount
+    /// (`DEFAULT_SWING` when none is set). Returns the new percentage.
+    pub fn toggle_swing(&mut self) -> u8 {
+        let swing = if self.loop_engine.swing() > 0 {
+            0
+        } else {
+            self.config
+                .swing
+                .filter(|percent| *percent > 0)
+                .unwrap_or(DEFAULT_SWING)
+        };
+        self.loop_engine.set_swing(swing);
+        swing
+    }
+
+    /// Toggle the metronome ticking through recording and playback.
+    /// Returns whether it is now on.
+    pub fn toggle_metronome(&mut self) -> bool {
+        let enabled = !self.loop_engine.metronome_enabled();
+        self.loop_engine.set_metronome_enabled(enabled);
+        enabled
+    }
+
+    /// Whether the metronome ticks through recording and playback.
+    pub fn metronome_enabled(&self) -> bool {
+        self.loop_engine.metronome_enabled()
+    }
+
+    /// Toggle letting overdubs played past the loop end lengthen the loop.
+    /// Returns whether it is now on.
+    pub fn toggle_extend_overdubs(&mut self) -> bool {
+        self.config.extend_overdubs = !self.config.extend_overdubs;
+        self.loop_engine
+            .set_extend_overdubs(self.config.extend_overdubs);
+        self.config.extend_overdubs
     }
 
     /// Get current BPM.
@@ -75,9 +528,47 @@ impl ApplicationState {
         self.bpm
     }
 
-    /// Get current bars.
+    /// Get current bars; a loop entered in beats counts its partial last bar.
     pub fn get_bars(&self) -> u16 {
-        self.bars
+        self.length.whole_bars(self.time_signature().numerator)
+    }
+
+    /// Current loop length as entered (bars or beats).
+    pub fn loop_length_setting(&self) -> LoopLength {
+        self.length
+    }
+
+    /// Metronome ticks before recording starts.
+    pub fn get_count_in(&self) -> u8 {
+        self.loop_engine.count_in_beats()
+    }
+
+    /// Set the count-in length in beats (clamped to valid range); used from
+    /// the next count-in on.
+    pub fn set_count_in(&mut self, beats: u8) {
+        self.loop_engine.set_count_in_beats(beats);
+    }
+
+    /// Minimum time between two counted presses of the same pad, in ms.
+    pub fn debounce_ms(&self) -> u128 {
+        self.debounce_ms
+    }
+
+    /// Set the pad debounce window; 0 counts every press.
+    pub fn set_debounce_ms(&mut self, ms: u128) {
+        self.debounce_ms = ms;
+    }
+
+    /// Time signature new loops are counted in and measured by.
+    pub fn time_signature(&self) -> TimeSignature {
+        self.loop_engine.time_signature()
+    }
+
+    /// Switch the time signature for new loops. The count-in becomes one
+    /// bar of the new signature.
+    pub fn set_time_signature(&mut self, signature: TimeSignature) {
+        self.loop_engine.set_time_signature(signature);
+        self.set_count_in(u8::try_from(signature.numerator).unwrap_or(u8::MAX));
     }
 
     /// Set BPM (clamped to valid range).
@@ -85,19 +576,235 @@ impl ApplicationState {
         self.bpm = clamp_bpm(bpm);
     }
 
+    /// Register a tap-tempo tap now. Once the taps give a tempo it is
+    /// applied like a tempo edit (undoable, resetting the loop) and the new
+    /// BPM is returned.
+    pub fn tap_tempo(&mut self) -> Option<u16> {
+        let bpm = self.tap_tempo.tap(self.loop_engine.now())?;
+        self.apply_tempo(Some(bpm), None);
+        Some(self.bpm)
+    }
+
+    /// Start auditioning one bar of metronome ticks at `bpm` without
+    /// touching the loop. Returns the accented first tick, due now.
+    pub fn start_click_preview(&mut self, bpm: u16) -> Vec<AudioCommand> {
+        let beats = self.time_signature().numerator;
+        self.click_preview = Some(ClickPreview::new(bpm, beats, self.loop_engine.now()));
+        self.click_preview_commands()
+    }
+
+    /// Ticks of the running metronome audition that are now due. The
+    /// audition ends after its last tick.
+    pub fn click_preview_commands(&mut self) -> Vec<AudioCommand> {
+        let Some(preview) = self.click_preview.as_mut() else {
+            return Vec::new();
+        };
+        let commands = preview
+            .due(self.loop_engine.now())
+            .into_iter()
+            .map(|downbeat| AudioCommand::PlayMetronome { downbeat })
+            .collect();
+        if preview.is_done() {
+            self.click_preview = None;
+        }
+        commands
+    }
+
+    /// Drop the rest of a running metronome audition.
+    pub fn stop_click_preview(&mut self) {
+        self.click_preview = None;
+    }
+
+    /// Whether a metronome audition still has ticks to play.
+    pub fn is_click_previewing(&self) -> bool {
+        self.click_preview.is_some()
+    }
+
+    /// Start logging live pad hits, dropping any unsaved log.
+    pub fn start_hit_log(&mut self) {
+        self.hit_log = Some(HitLog::new(self.loop_engine.now()));
+    }
+
+    /// Stop logging live pad hits and hand back what was logged.
+    pub fn stop_hit_log(&mut self) -> Option<HitLog> {
+        self.hit_log.take()
+    }
+
+    /// The running hit log, if logging is on.
+    pub fn hit_log(&self) -> Option<&HitLog> {
+        self.hit_log.as_ref()
+    }
+
+    /// Log a live pad hit now; does nothing while logging is off.
+    pub fn log_live_hit(&mut self, key: char) {
+        let now = self.loop_engine.now();
+        if let Some(log) = &mut self.hit_log {
+            log.record(key, now);
+        }
+    }
+
     /// Set bars (clamped to valid range).
     pub fn set_bars(&mut self, bars: u16) {
-        self.bars = clamp_bars(bars);
+        self.length = LoopLength::Bars(clamp_bars(bars));
+    }
+
+    /// Apply new tempo values. When BPM or bars actually change, the current
+    /// values and loop are pushed to the undo history and the loop is reset.
+    /// Returns whether anything changed.
+    pub fn apply_tempo(&mut self, bpm: Option<u16>, bars: Option<u16>) -> bool {
+        self.apply_tempo_length(bpm, bars.map(LoopLength::Bars))
+    }
+
+    /// Like [`apply_tempo`](Self::apply_tempo), with the loop length given in
+    /// bars or beats.
+    pub fn apply_tempo_length(&mut self, bpm: Option<u16>, length: Option<LoopLength>) -> bool {
+        let (bpm_before, length_before) = (self.bpm, self.length);
+        if let Some(bpm) = bpm {
+            self.set_bpm(bpm);
+        }
+        match length {
+            Some(LoopLength::Bars(bars)) => self.set_bars(bars),
+            Some(LoopLength::Beats(beats)) => {
+                self.length = LoopLength::from_beats(beats, self.time_signature().numerator)
+            }
+            None => {}
+        }
+        if (self.bpm, self.length) == (bpm_before, length_before) {
+            return false;
+        }
+        self.tempo_history.push(TempoUndo {
+            bpm: bpm_before,
+            length: length_before,
+            snapshot: self.loop_engine.snapshot(),
+        });
+        if self.tempo_history.len() > TEMPO_HISTORY_DEPTH {
+            self.tempo_history.remove(0);
+        }
+        self.reset_loop_for_tempo();
+        true
+    }
+
+    /// Revert the most recent tempo change, restoring BPM, bars and the
+    /// recorded tracks. Returns false when there is nothing to undo.
+    pub fn undo_tempo_change(&mut self) -> bool {
+        let Some(undo) = self.tempo_history.pop() else {
+            return false;
+        };
+        self.bpm = undo.bpm;
+        self.length = undo.length;
+        self.loop_engine.restore(undo.snapshot, self.bpm);
+        true
+    }
+
+    /// Capture the current loop content.
+    pub fn loop_snapshot(&self) -> LoopSnapshot {
+        self.loop_engine.snapshot()
+    }
+
+    /// Replace the loop content with a snapshot.
+    pub fn restore_loop(&mut self, snapshot: LoopSnapshot) {
+        self.loop_engine.restore(snapshot, self.bpm);
+    }
+
+    /// Number of recorded tracks.
+    pub fn tracks_count(&self) -> usize {
+        self.loop_engine.tracks_count()
+    }
+
+    /// Summaries of the recorded tracks.
+    pub fn tracks_snapshot(&self) -> Vec<TrackInfo> {
+        self.loop_engine.tracks_snapshot()
+    }
+
+    /// Toggle a track's mute. Returns the new mute state, or None if out of range.
+    pub fn toggle_track_mute(&mut self, index: usize) -> Option<bool> {
+        let muted = !self.tracks_snapshot().get(index)?.muted;
+        self.loop_engine.set_track_muted(index, muted);
+        Some(muted)
+    }
+
+    /// Change a track's volume by `delta`. Returns the new volume, or None if out of range.
+    pub fn adjust_track_volume(&mut self, index: usize, delta: f32) -> Option<f32> {
+        let volume = self.tracks_snapshot().get(index)?.volume + delta;
+        // Round to one decimal so repeated steps land on clean values
+        let volume = ((volume * 10.0).round() / 10.0).clamp(0.0, 1.0);
+        self.loop_engine.set_track_volume(index, volume);
+        Some(volume)
+    }
+
+    /// Remove a recorded track.
+    pub fn remove_track(&mut self, index: usize) -> bool {
+        self.loop_engine.remove_track(index)
+    }
+
+    /// Remove the most recently recorded track, leaving the rest playing.
+    /// Returns its index, or `None` when there are no tracks.
+    pub fn remove_last_track(&mut self) -> Option<usize> {
+        let index = self.tracks_count().checked_sub(1)?;
+        self.loop_engine.undo_last_track().then_some(index)
+    }
+
+    /// Rename a recorded track; `None` clears the name.
+    pub fn rename_track(&mut self, index: usize, name: Option<String>) -> bool {
+        self.loop_engine.set_track_name(index, name)
+    }
+
+    /// Rest regions marked on the loop.
+    pub fn rest_regions(&self) -> &[RestRegion] {
+        self.loop_engine.rest_regions()
+    }
+
+    /// Double (`factor` 2.0) or halve (0.5) the loop, keeping the loop
+    /// length setting in step. Returns the new setting, or `None` when there
+    /// is no loop or the length would leave the valid range.
+    pub fn scale_loop_length(&mut self, factor: f32) -> Option<LoopLength> {
+        let length = self
+            .length
+            .scaled(factor, self.time_signature().numerator)?;
+        if !self.loop_engine.scale_loop_length(factor) {
+            return None;
+        }
+        self.length = length;
+        Some(length)
+    }
+
+    /// Toggle practicing the first half of the loop. Returns whether it is
+    /// now on.
+    pub fn toggle_half_length(&mut self) -> bool {
+        self.loop_engine.toggle_half_length()
+    }
+
+    /// Toggle a rest over the bar under the playhead.
+    pub fn toggle_rest_bar(&mut self) -> Option<bool> {
+        let beats = self.length.beats_per(self.time_signature().numerator);
+        self.loop_engine.toggle_rest_bar(beats)
+    }
+
+    /// Remove the recorded event nearest the playhead (within `tolerance`).
+    pub fn delete_nearest_event(&mut self, tolerance: Duration) -> bool {
+        self.loop_engine.delete_nearest_event(tolerance)
+    }
+
+    /// Duplicate a recorded track.
+    pub fn duplicate_track(&mut self, index: usize) -> bool {
+        self.loop_engine.duplicate_track(index)
+    }
+
+    /// Move a recorded track to a new position.
+    pub fn move_track(&mut self, from: usize, to: usize) -> bool {
+        self.loop_engine.move_track(from, to)
     }
 
     /// Reset loop engine for new tempo (when BPM or bars change).
     pub fn reset_loop_for_tempo(&mut self) {
-        self.loop_engine.reset_for_new_tempo(self.bpm, self.bars);
+        self.loop_engine
+            .reset_for_new_tempo(self.bpm, self.get_bars());
     }
 
     /// Handle space key press for loop control.
     pub fn handle_loop_space(&mut self) {
-        self.loop_engine.handle_space(self.bpm, self.bars);
+        let beats = self.length.beats_per(self.time_signature().numerator);
+        self.loop_engine.handle_space_beats(self.bpm, beats);
     }
 
     /// Record a loop event (pad press during recording).
@@ -105,6 +812,11 @@ impl ApplicationState {
         self.loop_engine.record_event(key);
     }
 
+    /// Record a pad hit that sounded forwards or reversed.
+    pub fn record_loop_hit(&mut self, key: char, reversed: bool) {
+        self.loop_engine.record_hit(key, reversed);
+    }
+
     /// Cancel the current loop operation.
     pub fn cancel_loop(&mut self) {
         self.loop_engine.handle_cancel();
@@ -115,59 +827,617 @@ impl ApplicationState {
         self.loop_engine.handle_control_space();
     }
 
+    /// Bounce the committed tracks onto the first free pad and clear them
+    /// for a new section at the same tempo. Returns the pad key and the
+    /// command that renders the bounce.
+    pub fn bounce_loop(&mut self) -> anyhow::Result<(char, AudioCommand)> {
+        if matches!(
+            self.loop_engine.state(),
+            LoopState::Ready { .. } | LoopState::Recording { .. }
+        ) {
+            anyhow::bail!("Finish recording before bouncing")
+        }
+        if self.tracks_count() == 0 {
+            anyhow::bail!("Nothing to bounce")
+        }
+        let Some(key) = self
+            .config
+            .pad_layout
+            .keys()
+            .iter()
+            .copied()
+            .find(|key| !self.pads.key_to_slot.contains_key(key))
+        else {
+            anyhow::bail!("No free pad for the bounce")
+        };
+        let bounce = self.loop_bounce();
+
+        let name = format!("Bounce {}", self.pads.bounces.len() + 1);
+        self.pads.key_to_slot.insert(
+            key,
+            SampleSlot {
+                file_name: name,
+                path: PathBuf::new(),
+                play_mode: PlayMode::OneShot,
+                choke_group: None,
+            },
+        );
+        self.pads.bounces.push((key, bounce.clone()));
+        self.refresh_pad_labels();
+        self.loop_engine.handle_control_space();
+        Ok((key, AudioCommand::Bounce { key, bounce }))
+    }
+
+    /// Render one cycle of the playing loop to a WAV file at `path`.
+    /// Returns the command that mixes and writes it.
+    pub fn export_loop(&self, path: PathBuf) -> anyhow::Result<AudioCommand> {
+        if !matches!(self.loop_engine.state(), LoopState::Playing { .. }) {
+            anyhow::bail!("Start loop playback before exporting")
+        }
+        if self.tracks_count() == 0 {
+            anyhow::bail!("Nothing to export")
+        }
+        Ok(AudioCommand::ExportWav {
+            path,
+            bounce: self.loop_bounce(),
+        })
+    }
+
+    /// Hits loop playback sounds over one cycle, in time order: those of
+    /// unmuted tracks on audible pads, outside every rest region.
+    fn loop_bounce(&self) -> Bounce {
+        let snapshot = self.loop_engine.snapshot();
+        let mut hits: Vec<BounceHit> = snapshot
+            .tracks
+            .iter()
+            .filter(|track| !track.muted)
+            .flat_map(|track| {
+                track
+                    .events
+                    .iter()
+                    .filter(|event| self.loop_engine.is_hit_audible(event.key, event.offset))
+                    .map(|event| BounceHit {
+                        key: event.key,
+                        offset: event.offset,
+                        gain: track.volume,
+                        reversed: event.reversed,
+                    })
+            })
+            .collect();
+        hits.sort_by_key(|hit| hit.offset);
+        Bounce {
+            length: snapshot.loop_length,
+            hits,
+        }
+    }
+
+    /// Commands that restore the audio thread's view of the pads:
+    /// output settings (silence trim, master gain, volume curve, metronome
+    /// device and sound) when configured, a `Preload` per file-backed key, re-rendered
+    /// bounces, then any non-center pans, bus routings, choke groups and
+    /// non-default bus levels.
+    pub fn pad_restore_commands(&self) -> Vec<AudioCommand> {
+        let mut commands: Vec<AudioCommand> = self
+            .config
+            .trim_silence_threshold()
+            .map(|threshold| AudioCommand::SetTrimSilence(Some(threshold)))
+            .into_iter()
+            .collect();
+        if let Some(gain) = self.config.master_gain {
+            commands.push(AudioCommand::SetMasterGain(gain));
+        }
+        if self.config.volume_curve != VolumeCurve::Linear {
+            commands.push(AudioCommand::SetVolumeCurve(self.config.volume_curve));
+        }
+        if let Some(name) = &self.config.metronome_device {
+            commands.push(AudioCommand::SetMetronomeDevice(Some(name.clone())));
+        }
+        if let Some(metronome) = self.config.metronome {
+            commands.push(AudioCommand::ConfigureMetronome(metronome));
+        }
+        commands.extend(
+            self.pads
+                .key_to_slot
+                .iter()
+                .filter(|(key, _)| !self.pads.bounces.iter().any(|(k, _)| k == *key))
+                .map(|(key, slot)| AudioCommand::Preload {
+                    key: *key,
+                    path: slot.path.clone(),
+                }),
+        );
+        commands.extend(
+            self.pads
+                .bounces
+                .iter()
+                .map(|(key, bounce)| AudioCommand::Bounce {
+                    key: *key,
+                    bounce: bounce.clone(),
+                }),
+        );
+        commands.extend(
+            self.pads
+                .pan
+                .iter()
+                .filter(|(_, pan)| **pan != 0.0)
+                .map(|(key, pan)| AudioCommand::SetPadPan {
+                    key: *key,
+                    pan: *pan,
+                }),
+        );
+        commands.extend(
+            self.pads
+                .bus
+                .iter()
+                .map(|(key, bus)| AudioCommand::SetPadBus {
+                    key: *key,
+                    bus: *bus,
+                }),
+        );
+        commands.extend(self.pads.key_to_slot.iter().filter_map(|(key, slot)| {
+            slot.choke_group.map(|group| AudioCommand::SetPadChoke {
+                key: *key,
+                group: Some(group),
+            })
+        }));
+        commands.extend(
+            self.bus_levels
+                .iter()
+                .filter(|(_, level)| **level != BusLevel::default())
+                .map(|(bus, level)| AudioCommand::SetBusLevel {
+                    bus: *bus,
+                    level: *level,
+                }),
+        );
+        commands
+    }
+
+    /// Set BPM so the pad's sample spans exactly the current loop length.
+    /// Resets the loop when the tempo changes. Returns the applied BPM.
+    pub fn fit_bpm_to_pad(&mut self, key: char) -> anyhow::Result<u16> {
+        let Some(slot) = self.pads.key_to_slot.get(&key) else {
+            anyhow::bail!("No sample mapped to {}", key.to_ascii_uppercase())
+        };
+        let duration = sample_duration(&slot.path)?;
+        // BPM counts quarter notes: stretch the duration by the beat's note
+        // value so 6/8 eighths fit as eighths
+        let signature = self.time_signature();
+        let beats = self.length.beats_per(signature.numerator);
+        let bpm = bpm_from_beats(duration * u32::from(signature.denominator) / 4, beats);
+        self.apply_tempo(Some(bpm), None);
+        Ok(self.bpm)
+    }
+
+    /// Shift the pan of a pad by `delta` (clamped to -1.0..=1.0).
+    /// Returns the audio command that applies the new pan.
+    pub fn adjust_pad_pan(&mut self, key: char, delta: f32) -> AudioCommand {
+        let pan = self.pads.pan_for(key) + delta;
+        // Round to one decimal so repeated steps land on clean values
+        let pan = ((pan * 10.0).round() / 10.0).clamp(-1.0, 1.0);
+        self.pads.pan.insert(key, pan);
+        AudioCommand::SetPadPan { key, pan }
+    }
+
+    /// Replace the selection with the supported samples in `dir`, sorted by
+    /// name and capped at the number of pads. Returns how many were selected.
+    pub fn select_kit_dir(&mut self, dir: &Path) -> anyhow::Result<usize> {
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| anyhow::anyhow!("Kit directory {} unavailable: {}", dir.display(), e))?;
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.is_file() && is_supported_audio(p))
+            .collect();
+        if files.is_empty() {
+            anyhow::bail!(
+                "Kit directory {} has no supported audio files (wav, flac, ogg, mp3)",
+                dir.display()
+            )
+        }
+        files.sort();
+        files.truncate(default_pad_keys().len());
+
+        self.selection = SelectionModel::default();
+        let count = files.len();
+        for path in files {
+            self.selection.add_file(path);
+        }
+        Ok(count)
+    }
+
     /// Attempt to enter Pads mode. Validates selection and builds pad mapping.
-    /// Returns effects (Preload commands) and error message if validation fails.
-    pub fn enter_pads(&mut self) -> anyhow::Result<Vec<AudioCommand>> {
+    /// Returns a batch preload plus any files skipped because they no longer
+    /// exist, or the reason validation failed.
+    pub fn enter_pads(&mut self) -> Result<PadsEntry, AppError> {
         if self.selection.items.is_empty() {
-            anyhow::bail!("Select at least one file first")
+            return Err(AppError::NoSelection);
+        }
+
+        // Validate all selected files are decodable audio (case-insensitive)
+        if let Some(invalid) = self
+            .selection
+            .items
+            .iter()
+            .find(|p| !is_supported_audio(p))
+            .cloned()
+        {
+            return Err(AppError::UnsupportedFile(invalid));
         }
 
-        // Validate all selected files are .wav (case-insensitive)
-        if let Some(invalid) = self.selection.items.iter().find(|p| !is_wav(p)).cloned() {
-            let name = file_name_str(&invalid);
-            anyhow::bail!("Unsupported file (only .wav): {}", name)
+        // Files may have been deleted since they were selected; map the rest
+        let (present, skipped): (Vec<PathBuf>, Vec<PathBuf>) = self
+            .selection
+            .items
+            .iter()
+            .cloned()
+            .partition(|p| p.exists());
+        if present.is_empty() {
+            return Err(AppError::FilesMissing(skipped));
         }
 
-        // Build mapping from selection order to default pad keys
-        let keys = default_pad_keys();
+        // Build mapping from selection order to the layout's pad keys
+        let keys = self.config.pad_layout.keys();
         let mut key_to_slot: BTreeMap<char, SampleSlot> = BTreeMap::new();
-        let mut preload_effects = Vec::new();
+        let mut preload_pads = Vec::new();
 
-        for (idx, path) in self.selection.items.iter().enumerate() {
+        for (idx, path) in present.iter().enumerate() {
             if idx >= keys.len() {
                 break; // ignore overflow for now
             }
             let key = keys[idx];
             let slot = SampleSlot {
                 file_name: file_name_str(path),
+                path: path.clone(),
+                play_mode: PlayMode::OneShot,
+                choke_group: None,
             };
             key_to_slot.insert(key, slot);
 
-            // Create Preload command for this key
-            preload_effects.push(AudioCommand::Preload {
-                key,
-                path: path.clone(),
-            });
+            preload_pads.push((key, path.clone()));
         }
 
+        self.map_pads(key_to_slot);
+        self.pads.preload_progress = Some((0, preload_pads.len()));
+
+        Ok(PadsEntry {
+            preload: AudioCommand::PreloadBatch { pads: preload_pads },
+            skipped,
+        })
+    }
+
+    /// Duplicate the selected files to fill every pad key. Returns how many
+    /// copies were added (0 when the selection is empty or already fills
+    /// the pads).
+    pub fn fill_selection_to_pads(&mut self) -> usize {
+        self.selection.fill_to(pad_capacity())
+    }
+
+    /// Start fresh pads state for a new key mapping.
+    fn map_pads(&mut self, key_to_slot: BTreeMap<char, SampleSlot>) {
         self.pads = PadsState {
             key_to_slot,
             active_keys: HashSet::new(),
             last_press_ms: BTreeMap::new(),
+            pan: BTreeMap::new(),
+            bus: BTreeMap::new(),
+            last_triggered: None,
+            focused_pad: None,
+            bounces: Vec::new(),
+            ratchet_reverse: HashSet::new(),
+            ratchet_phase: HashSet::new(),
+            levels: BTreeMap::new(),
+            sample_rates: BTreeMap::new(),
+            preload_progress: None,
+            muted_keys: HashSet::new(),
+            soloed_keys: HashSet::new(),
+            // A property of the terminal, not of the mapping
+            reports_releases: self.pads.reports_releases,
+        };
+        self.sync_key_mutes();
+        self.refresh_pad_labels();
+    }
+
+    /// Save the pads, tempo and recorded tracks with their rests to `path`.
+    /// Bounced pads are saved as the hits they mix, to re-render on load.
+    pub fn save_session(&self, path: &Path) -> anyhow::Result<()> {
+        let pads = self
+            .pads
+            .key_to_slot
+            .iter()
+            .filter(|(_, slot)| !slot.path.as_os_str().is_empty())
+            .map(|(&key, slot)| SessionPad {
+                key,
+                path: slot.path.clone(),
+            })
+            .collect();
+        let bounces = self
+            .pads
+            .bounces
+            .iter()
+            .map(|(key, bounce)| {
+                SessionBounce::new(*key, self.pads.key_to_slot[key].file_name.clone(), bounce)
+            })
+            .collect();
+        Session::new(
+            self.bpm,
+            self.length,
+            self.time_signature(),
+            pads,
+            &self.loop_engine.snapshot(),
+            self.loop_engine.rest_regions(),
+        )
+        .with_bounces(bounces)
+        .write(path)
+    }
+
+    /// Load a session saved with [`save_session`](Self::save_session):
+    /// remap its pads, restore tempo, tracks and rests, and return the
+    /// commands that preload the samples and re-render the bounces.
+    pub fn load_session(&mut self, path: &Path) -> anyhow::Result<Vec<AudioCommand>> {
+        let session = Session::read(path)?;
+        if session.pads.is_empty() && session.bounces.is_empty() {
+            anyhow::bail!("Session has no pads")
+        }
+        self.bpm = clamp_bpm(session.bpm);
+        if let Some(signature) = session.signature() {
+            self.set_time_signature(signature);
+        }
+        self.length = session.length(self.time_signature().numerator);
+        self.tempo_history.clear();
+
+        self.selection = SelectionModel::default();
+        let mut key_to_slot = BTreeMap::new();
+        for pad in session.pads.iter().cloned() {
+            self.selection.add_file(pad.path.clone());
+            key_to_slot.insert(
+                pad.key,
+                SampleSlot {
+                    file_name: file_name_str(&pad.path),
+                    path: pad.path,
+                    play_mode: PlayMode::OneShot,
+                    choke_group: None,
+                },
+            );
+        }
+        for bounce in &session.bounces {
+            key_to_slot.insert(
+                bounce.key,
+                SampleSlot {
+                    file_name: bounce.name.clone(),
+                    path: PathBuf::new(),
+                    play_mode: PlayMode::OneShot,
+                    choke_group: None,
+                },
+            );
+        }
+        self.map_pads(key_to_slot);
+        self.pads.bounces = session
+            .bounces
+            .iter()
+            .map(|bounce| (bounce.key, bounce.bounce()))
+            .collect();
+        self.loop_engine.handle_control_space();
+        self.loop_engine.restore(session.loop_snapshot(), self.bpm);
+        self.loop_engine.clear_rest_regions();
+        for rest in session.rest_regions() {
+            self.loop_engine.add_rest_region(rest.start, rest.end);
+        }
+        Ok(self.pad_restore_commands())
+    }
+
+    /// Step a pad's loop playback through muted, soloed and back to
+    /// normal. Returns the new setting, or `None` when the key has no pad.
+    pub fn cycle_pad_mute(&mut self, key: char) -> Option<PadMute> {
+        if !self.pads.key_to_slot.contains_key(&key) {
+            return None;
+        }
+        let next = match self.pads.mute_of(key) {
+            PadMute::Normal => PadMute::Muted,
+            PadMute::Muted => PadMute::Soloed,
+            PadMute::Soloed => PadMute::Normal,
         };
+        self.pads.muted_keys.remove(&key);
+        self.pads.soloed_keys.remove(&key);
+        match next {
+            PadMute::Muted => {
+                self.pads.muted_keys.insert(key);
+            }
+            PadMute::Soloed => {
+                self.pads.soloed_keys.insert(key);
+            }
+            PadMute::Normal => {}
+        }
+        self.sync_key_mutes();
+        Some(next)
+    }
+
+    /// Move the pad on `from` to the free pad key `to`, with its pan, bus,
+    /// mute and other per-pad settings. Recorded loop hits are rewritten to
+    /// the new key so the loop keeps playing the same sample.
+    pub fn remap_pad(&mut self, from: char, to: char) -> Result<PadRemap, AppError> {
+        if !self.pads.key_to_slot.contains_key(&from) {
+            return Err(AppError::PadUnmapped(from));
+        }
+        if !default_pad_keys().contains(&to) {
+            return Err(AppError::NotPadKey(to));
+        }
+        if self.pads.key_to_slot.contains_key(&to) {
+            return Err(AppError::PadKeyTaken(to));
+        }
+        Ok(self.rekey_pads(&BTreeMap::from([(from, to)])))
+    }
+
+    /// Switch to the next pad layout. Each pad moves to the key at its
+    /// position in the new layout, so samples keep their order; per-pad
+    /// settings and recorded loop hits follow their samples.
+    pub fn cycle_pad_layout(&mut self) -> PadRemap {
+        let from = self.config.pad_layout;
+        let to = from.next();
+        self.config.pad_layout = to;
+        let moves = from
+            .keys()
+            .iter()
+            .zip(to.keys())
+            .filter(|(old, new)| old != new && self.pads.key_to_slot.contains_key(old))
+            .map(|(old, new)| (*old, *new))
+            .collect();
+        self.rekey_pads(&moves)
+    }
+
+    /// Reassign the samples to the mapped pad keys in reverse keyboard
+    /// order, e.g. for a left-handed layout. Per-pad settings and recorded
+    /// loop hits follow their samples.
+    pub fn reverse_pad_mapping(&mut self) -> PadRemap {
+        let layout = self.config.pad_layout.keys();
+        let mut keys: Vec<char> = self.pads.key_to_slot.keys().copied().collect();
+        // Keys outside the layout (e.g. from a session) keep their order last
+        keys.sort_by_key(|key| layout.iter().position(|k| k == key).unwrap_or(layout.len()));
+        let moves = keys
+            .iter()
+            .zip(keys.iter().rev())
+            .filter(|(from, to)| from != to)
+            .map(|(from, to)| (*from, *to))
+            .collect();
+        self.rekey_pads(&moves)
+    }
+
+    /// Move pads to new keys all at once, so swaps do not collide. Every
+    /// per-pad setting moves with its sample.
+    fn rekey_pads(&mut self, moves: &BTreeMap<char, char>) -> PadRemap {
+        let rekey = |key: char| moves.get(&key).copied().unwrap_or(key);
+        let pads = &mut self.pads;
+        let old_chokes: BTreeMap<char, u8> = pads
+            .key_to_slot
+            .iter()
+            .filter_map(|(key, slot)| slot.choke_group.map(|group| (*key, group)))
+            .collect();
+        pads.key_to_slot = std::mem::take(&mut pads.key_to_slot)
+            .into_iter()
+            .map(|(key, slot)| (rekey(key), slot))
+            .collect();
+        pads.last_press_ms = std::mem::take(&mut pads.last_press_ms)
+            .into_iter()
+            .map(|(key, pressed)| (rekey(key), pressed))
+            .collect();
+        pads.pan = std::mem::take(&mut pads.pan)
+            .into_iter()
+            .map(|(key, pan)| (rekey(key), pan))
+            .collect();
+        pads.bus = std::mem::take(&mut pads.bus)
+            .into_iter()
+            .map(|(key, bus)| (rekey(key), bus))
+            .collect();
+        pads.sample_rates = std::mem::take(&mut pads.sample_rates)
+            .into_iter()
+            .map(|(key, rate)| (rekey(key), rate))
+            .collect();
+        for keys in [
+            &mut pads.ratchet_reverse,
+            &mut pads.ratchet_phase,
+            &mut pads.muted_keys,
+            &mut pads.soloed_keys,
+        ] {
+            *keys = keys.drain().map(rekey).collect();
+        }
+        pads.last_triggered = pads.last_triggered.map(rekey);
+        pads.focused_pad = pads.focused_pad.map(rekey);
+        pads.active_keys.retain(|key| !moves.contains_key(key));
+        pads.levels.retain(|key, _| !moves.contains_key(key));
+        // Bounces rendered from moved pads' hits re-render under the new keys
+        for (key, bounce) in &mut pads.bounces {
+            *key = rekey(*key);
+            for hit in &mut bounce.hits {
+                hit.key = rekey(hit.key);
+            }
+        }
+
+        let mut commands: Vec<AudioCommand> = moves
+            .keys()
+            .map(|&key| AudioCommand::Stop { key })
+            .collect();
+        for &key in moves.values() {
+            match pads.bounces.iter().find(|(k, _)| *k == key) {
+                Some((_, bounce)) => commands.push(AudioCommand::Bounce {
+                    key,
+                    bounce: bounce.clone(),
+                }),
+                None => commands.push(AudioCommand::Preload {
+                    key,
+                    path: pads.key_to_slot[&key].path.clone(),
+                }),
+            }
+            if let Some(&pan) = pads.pan.get(&key) {
+                commands.push(AudioCommand::SetPadPan { key, pan });
+            }
+            if let Some(&bus) = pads.bus.get(&key) {
+                commands.push(AudioCommand::SetPadBus { key, bus });
+            }
+        }
+        // Choke groups follow their pads, clearing the ones left behind
+        let touched: BTreeSet<char> = moves.keys().chain(moves.values()).copied().collect();
+        for key in touched {
+            let group = pads.key_to_slot.get(&key).and_then(|slot| slot.choke_group);
+            if group != old_chokes.get(&key).copied() {
+                commands.push(AudioCommand::SetPadChoke { key, group });
+            }
+        }
+
+        let moved_hits = self.loop_engine.rekey_events(moves);
+        self.sync_key_mutes();
+        self.refresh_pad_labels();
+        PadRemap {
+            commands,
+            moved_hits,
+        }
+    }
 
-        Ok(preload_effects)
+    /// Push the pads' mute and solo sets to the loop engine.
+    fn sync_key_mutes(&mut self) {
+        self.loop_engine
+            .set_key_mutes(self.pads.muted_keys.clone(), self.pads.soloed_keys.clone());
+    }
+
+    /// Name new tracks after the pads: file stems, or the bounce name.
+    fn refresh_pad_labels(&mut self) {
+        let labels = self
+            .pads
+            .key_to_slot
+            .iter()
+            .map(|(key, slot)| (*key, file_stem_str(Path::new(&slot.file_name))))
+            .collect();
+        self.loop_engine.set_pad_labels(labels);
     }
 }
 
-/// Check if path has .wav extension (case-insensitive).
-fn is_wav(p: &Path) -> bool {
+/// Ratio between the highest and lowest pad sample rate above which the
+/// mix is flagged for normalization.
+pub const SAMPLE_RATE_MISMATCH_RATIO: f64 = 1.25;
+
+/// Audio file extensions the decoder can load.
+pub const SUPPORTED_AUDIO_EXTENSIONS: [&str; 4] = ["wav", "flac", "ogg", "mp3"];
+
+/// Check if path has a supported audio extension (case-insensitive).
+pub fn is_supported_audio(p: &Path) -> bool {
     p.extension()
         .and_then(|e| e.to_str())
-        .map(|ext| ext.eq_ignore_ascii_case("wav"))
+        .map(|ext| {
+            SUPPORTED_AUDIO_EXTENSIONS
+                .iter()
+                .any(|supported| ext.eq_ignore_ascii_case(supported))
+        })
         .unwrap_or(false)
 }
 
+/// Lowest and highest of `rates` when they are far enough apart that
+/// resampling them on play is worth a hint (e.g. 22050 next to 48000 Hz);
+/// `None` for close rates such as 44100 and 48000.
+pub fn sample_rate_mismatch(rates: impl IntoIterator<Item = u32>) -> Option<(u32, u32)> {
+    let mut rates = rates.into_iter().filter(|rate| *rate > 0);
+    let first = rates.next()?;
+    let (low, high) = rates.fold((first, first), |(low, high), rate| {
+        (low.min(rate), high.max(rate))
+    });
+    (f64::from(high) / f64::from(low) > SAMPLE_RATE_MISMATCH_RATIO).then_some((low, high))
+}
+
 /// Get file name from path as string.
 fn file_name_str(p: &Path) -> String {
     p.file_name()
@@ -176,11 +1446,80 @@ fn file_name_str(p: &Path) -> String {
         .to_string()
 }
 
-/// Default pad keys for mapping samples (QWERTY row-first mapping).
+/// Comma-separated file names for status messages.
+fn file_names(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|p| file_name_str(p))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Get file name without extension from path as string.
+fn file_stem_str(p: &Path) -> String {
+    p.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("?")
+        .to_string()
+}
+
+/// Number of pads a selection can map to.
+pub fn pad_capacity() -> usize {
+    default_pad_keys().len()
+}
+
+/// Default pad keys for mapping samples (QWERTY row-first mapping). Every
+/// layout uses the same keys in a different order.
 fn default_pad_keys() -> &'static [char] {
-    const KEYS: &[char] = &[
-        'q', 'w', 'e', 'r', 't', 'y', 'u', 'i', 'o', 'p', 'a', 's', 'd', 'f', 'g', 'h', 'j', 'k',
-        'l', ';', 'z', 'x', 'c', 'v', 'b', 'n', 'm', ',', '.', '/',
-    ];
-    KEYS
+    PadLayout::default().keys()
+}
+
+/// Order pad keys are handed to selected samples in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PadLayout {
+    /// Keyboard rows top to bottom: Q W E … P, A S D … ;, Z X C … /
+    #[default]
+    Rows,
+    /// The home row first, then the top and bottom rows
+    HomeRow,
+    /// Keyboard columns left to right: Q A Z, W S X, …
+    Columns,
+}
+
+impl PadLayout {
+    /// Pad keys in mapping order.
+    pub fn keys(self) -> &'static [char] {
+        match self {
+            PadLayout::Rows => &[
+                'q', 'w', 'e', 'r', 't', 'y', 'u', 'i', 'o', 'p', 'a', 's', 'd', 'f', 'g', 'h',
+                'j', 'k', 'l', ';', 'z', 'x', 'c', 'v', 'b', 'n', 'm', ',', '.', '/',
+            ],
+            PadLayout::HomeRow => &[
+                'a', 's', 'd', 'f', 'g', 'h', 'j', 'k', 'l', ';', 'q', 'w', 'e', 'r', 't', 'y',
+                'u', 'i', 'o', 'p', 'z', 'x', 'c', 'v', 'b', 'n', 'm', ',', '.', '/',
+            ],
+            PadLayout::Columns => &[
+                'q', 'a', 'z', 'w', 's', 'x', 'e', 'd', 'c', 'r', 'f', 'v', 't', 'g', 'b', 'y',
+                'h', 'n', 'u', 'j', 'm', 'i', 'k', ',', 'o', 'l', '.', 'p', ';', '/',
+            ],
+        }
+    }
+
+    /// Short name for the footer and status messages.
+    pub fn name(self) -> &'static str {
+        match self {
+            PadLayout::Rows => "rows",
+            PadLayout::HomeRow => "home row",
+            PadLayout::Columns => "columns",
+        }
+    }
+
+    /// The layout after this one, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            PadLayout::Rows => PadLayout::HomeRow,
+            PadLayout::HomeRow => PadLayout::Columns,
+            PadLayout::Columns => PadLayout::Rows,
+        }
+    }
 }
@@ -1,24 +1,295 @@
-use rodio::{Decoder, OutputStream, Sink, Source, buffer::SamplesBuffer};
-use std::collections::BTreeMap;
+use anyhow::Context;
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source, buffer::SamplesBuffer};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::f32::consts::PI;
 use std::fs;
-use std::io::Cursor;
-use std::path::PathBuf;
-use std::sync::Arc;
-use std::sync::mpsc::{self, Sender};
+use std::io::{BufReader, Cursor};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 
 use crate::domain::ports::{AudioBus, Clock};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum AudioCommand {
-    Preload { key: char, path: PathBuf },
-    Play { key: char },
-    PlayLoop { key: char },
-    PlayMetronome,
+    Preload {
+        key: char,
+        path: PathBuf,
+    },
+    /// Preload several pads in order, decoding one per wake of the audio
+    /// thread and reporting `PreloadProgress` after each.
+    PreloadBatch {
+        pads: Vec<(char, PathBuf)>,
+    },
+    /// Preload a pad from an in-memory audio file (e.g. fetched remotely).
+    PreloadBytes {
+        key: char,
+        bytes: Vec<u8>,
+    },
+    Play {
+        key: char,
+    },
+    /// Live hit of a gate pad, played backwards when `reversed`.
+    /// [`Release`](Self::Release) cuts it without touching loop playback.
+    PlayGated {
+        key: char,
+        reversed: bool,
+    },
+    /// Scheduled loop playback at a track gain (1.0 = unity).
+    PlayLoop {
+        key: char,
+        gain: f32,
+    },
+    /// Play a pad backwards at a gain (1.0 = unity for live hits).
+    PlayReversed {
+        key: char,
+        gain: f32,
+    },
+    /// Metronome tick; the downbeat sounds at the accent frequency.
+    PlayMetronome {
+        downbeat: bool,
+    },
     PauseAll,
     ResumeAll,
+    /// Stereo position for a pad: -1.0 (left) .. 1.0 (right), 0.0 is center.
+    SetPadPan {
+        key: char,
+        pan: f32,
+    },
+    /// One-off audition of a pad with an explicit effect chain.
+    Preview {
+        key: char,
+        effects: PadEffects,
+    },
+    /// Audition a file outside the pad mapping `repeats` times back to back.
+    /// The file is decoded once and kept until another one is auditioned.
+    PlayPath {
+        path: PathBuf,
+        repeats: usize,
+    },
+    /// Stop and drop any queued audition.
+    StopAudition,
+    /// Cut every sounding voice of one pad immediately.
+    Stop {
+        key: char,
+    },
+    /// Cut the live gate voices of a pad when its key is released.
+    Release {
+        key: char,
+    },
+    /// Cut every sounding voice, including the metronome and auditions.
+    StopAll,
+    /// Silence all output without losing the master volume.
+    SetMasterMute(bool),
+    /// Master output volume, 0.0..=1.0 (1.0 = unity).
+    SetMasterGain(f32),
+    /// How volume controls (master, bus, track) map to sink gain.
+    SetVolumeCurve(VolumeCurve),
+    /// Route a pad to a mixing bus (all pads start on bus 0).
+    SetPadBus {
+        key: char,
+        bus: u8,
+    },
+    /// Put a pad in a choke group (`None` takes it out). Every later hit
+    /// of the pad, live or from the loop, first cuts the voices sounding in
+    /// its group, e.g. a closed hi-hat silencing the open one.
+    SetPadChoke {
+        key: char,
+        group: Option<u8>,
+    },
+    /// Volume and mute for a mixing bus.
+    SetBusLevel {
+        bus: u8,
+        level: BusLevel,
+    },
+    /// Trim leading/trailing silence below this peak level on later
+    /// preloads; `None` keeps samples whole.
+    SetTrimSilence(Option<f32>),
+    /// Output device (by name) for the metronome; `None` uses the main output.
+    SetMetronomeDevice(Option<String>),
+    /// Pitch and level of the metronome ticks.
+    ConfigureMetronome(MetronomeConfig),
+    /// Mix loop hits offline into a new one-shot sample cached under `key`.
+    Bounce {
+        key: char,
+        bounce: Bounce,
+    },
+    /// Mix loop hits offline, like [`Bounce`](Self::Bounce), and write the
+    /// result to a 16-bit stereo WAV file.
+    ExportWav {
+        path: PathBuf,
+        bounce: Bounce,
+    },
+    /// Stop the audio thread, dropping all sinks and the output stream.
+    Shutdown,
+}
+
+/// Sound of the metronome: sine frequencies in Hz for regular beats and the
+/// downbeat, and a peak volume in 0.0..=1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetronomeConfig {
+    pub freq: f32,
+    pub volume: f32,
+    pub accent_freq: f32,
+}
+
+impl Default for MetronomeConfig {
+    /// A plain 1 kHz tick with no accent.
+    fn default() -> Self {
+        Self {
+            freq: 1_000.0,
+            volume: 0.4,
+            accent_freq: 1_000.0,
+        }
+    }
+}
+
+/// Volume and mute of a mixing bus. Muting keeps the volume.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BusLevel {
+    pub volume: f32,
+    pub muted: bool,
+}
+
+impl Default for BusLevel {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            muted: false,
+        }
+    }
+}
+
+impl BusLevel {
+    /// Effective gain of the bus (0.0 when muted).
+    pub fn gain(&self) -> f32 {
+        if self.muted { 0.0 } else { self.volume }
+    }
+}
+
+/// Mapping from a 0.0..=1.0 volume control to the gain applied to sinks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VolumeCurve {
+    /// Gain equals the control value
+    #[default]
+    Linear,
+    /// Gain follows [`perceptual_gain`], so equal control steps sound like
+    /// roughly equal loudness steps
+    Perceptual,
+}
+
+impl VolumeCurve {
+    /// Gain for a control value.
+    pub fn gain(self, volume: f32) -> f32 {
+        match self {
+            VolumeCurve::Linear => volume,
+            VolumeCurve::Perceptual => perceptual_gain(volume),
+        }
+    }
+}
+
+/// Perceptual gain for a 0.0..=1.0 volume control: the cube of the value,
+/// a close fit to a 60 dB range that keeps 0 silent and 1 at unity.
+pub fn perceptual_gain(volume: f32) -> f32 {
+    volume.clamp(0.0, 1.0).powi(3)
+}
+
+/// How often the audio thread reports pad levels.
+const LEVEL_REPORT_INTERVAL: Duration = Duration::from_millis(40);
+
+/// Notification from the audio thread back to the UI.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AudioEvent {
+    /// Output level of a pad's sounding voices (0.0 once it falls silent).
+    PadLevel { key: char, level: f32 },
+    /// A preloaded sample finished decoding at its native sample rate.
+    SampleLoaded { key: char, sample_rate: u32 },
+    /// A pad's sample could not be read or decoded; the pad stays silent.
+    LoadFailed { key: char, error: String },
+    /// A batch preload finished `loaded` of its `total` samples, whether
+    /// they decoded or failed.
+    PreloadProgress { loaded: usize, total: usize },
+    /// A file audition played to its end without being stopped.
+    AuditionEnded,
+    /// A loop export could not be rendered or written.
+    ExportFailed { path: PathBuf, error: String },
+}
+
+/// Which output stream a command plays on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputTarget {
+    /// The main (default) device: pads and loop playback
+    Main,
+    /// The metronome device, when one is configured
+    Metronome,
+}
+
+/// Output a command is routed to.
+pub fn output_target(cmd: &AudioCommand) -> OutputTarget {
+    match cmd {
+        AudioCommand::PlayMetronome { .. } => OutputTarget::Metronome,
+        _ => OutputTarget::Main,
+    }
+}
+
+/// Pick the stream for a target, falling back to the main one when the
+/// metronome device is not configured or could not be opened.
+pub fn select_output<'a, H>(target: OutputTarget, main: &'a H, metronome: Option<&'a H>) -> &'a H {
+    match (target, metronome) {
+        (OutputTarget::Metronome, Some(handle)) => handle,
+        _ => main,
+    }
+}
+
+/// Open an output device by name, logging when it is unavailable.
+fn open_output_device(name: &str) -> Option<(OutputStream, OutputStreamHandle)> {
+    use rodio::cpal::traits::{DeviceTrait, HostTrait};
+
+    let device = rodio::cpal::default_host()
+        .output_devices()
+        .ok()?
+        .find(|device| device.name().map(|n| n == name).unwrap_or(false));
+    let Some(device) = device else {
+        eprintln!("[audio] Output device '{name}' not found; metronome stays on main output");
+        return None;
+    };
+    match OutputStream::try_from_device(&device) {
+        Ok(stream) => Some(stream),
+        Err(err) => {
+            eprintln!("[audio] Failed to open '{name}': {err:?}; metronome stays on main output");
+            None
+        }
+    }
+}
+
+/// Loop content to mix offline into a single pad sample.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Bounce {
+    pub length: Duration,
+    pub hits: Vec<BounceHit>,
+}
+
+/// A pad hit inside a [`Bounce`], at an offset from the loop start.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BounceHit {
+    pub key: char,
+    pub offset: Duration,
+    pub gain: f32,
+    /// Render the sample backwards
+    pub reversed: bool,
+}
+
+/// Per-pad effect chain applied whenever the pad sounds.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PadEffects {
+    /// Stereo position: -1.0 (left) .. 1.0 (right), 0.0 is center.
+    pub pan: f32,
+    /// Play the sample backwards.
+    pub reverse: bool,
 }
 
 #[derive(Clone)]
@@ -26,20 +297,118 @@ struct DecodedSample {
     channels: u16,
     sample_rate: u32,
     samples: Arc<Vec<f32>>, // decoded PCM in f32
+    /// Playable region of `samples` (sample indices, frame aligned)
+    range: Range<usize>,
 }
 
 impl DecodedSample {
+    fn new(channels: u16, sample_rate: u32, samples: Vec<f32>) -> Self {
+        let range = 0..samples.len();
+        Self {
+            channels,
+            sample_rate,
+            samples: Arc::new(samples),
+            range,
+        }
+    }
+
+    /// The PCM that actually plays, after trimming.
+    fn pcm(&self) -> &[f32] {
+        &self.samples[self.range.clone()]
+    }
+
+    /// Narrow the playable region to skip leading/trailing silence.
+    /// Fully silent samples are left untouched.
+    fn trim_silence(&mut self, threshold: f32) {
+        if let Some(bounds) = silence_bounds(self.pcm(), self.channels, threshold) {
+            let start = self.range.start;
+            self.range = start + bounds.start..start + bounds.end;
+        }
+    }
+
     fn to_source(&self) -> SamplesBuffer<f32> {
-        SamplesBuffer::new(self.channels, self.sample_rate, (*self.samples).clone())
+        SamplesBuffer::new(self.channels, self.sample_rate, self.pcm().to_vec())
     }
+
+    /// Build a source positioned in the stereo field. Center pan plays the
+    /// sample untouched; mono input is split to stereo, stereo is balanced.
+    fn to_panned_source(&self, pan: f32) -> SamplesBuffer<f32> {
+        if pan == 0.0 || self.channels > 2 {
+            return self.to_source();
+        }
+        let (left, right) = pan_gains(pan);
+        let data: Vec<f32> = if self.channels == 1 {
+            self.pcm()
+                .iter()
+                .flat_map(|&s| [s * left, s * right])
+                .collect()
+        } else {
+            self.pcm()
+                .chunks(2)
+                .flat_map(|frame| {
+                    let l = frame[0] * left;
+                    let r = frame.get(1).copied().unwrap_or(frame[0]) * right;
+                    [l, r]
+                })
+                .collect()
+        };
+        SamplesBuffer::new(2, self.sample_rate, data)
+    }
+
+    /// Build a source with a pad's effect chain applied. Live triggers,
+    /// loop playback and previews all go through here.
+    fn to_effected_source(&self, effects: &PadEffects) -> SamplesBuffer<f32> {
+        if effects.reverse {
+            self.reversed().to_panned_source(effects.pan)
+        } else {
+            self.to_panned_source(effects.pan)
+        }
+    }
+
+    /// Copy of the playable region with its frames in reverse order.
+    fn reversed(&self) -> DecodedSample {
+        let channels = self.channels.max(1) as usize;
+        let samples = self
+            .pcm()
+            .chunks(channels)
+            .rev()
+            .flatten()
+            .copied()
+            .collect();
+        DecodedSample::new(self.channels, self.sample_rate, samples)
+    }
+}
+
+/// Find the non-silent part of interleaved PCM.
+///
+/// Returns the sample index range from the first to the last frame whose
+/// peak exceeds `threshold`, aligned to whole frames, or `None` when every
+/// frame is at or below it.
+pub fn silence_bounds(samples: &[f32], channels: u16, threshold: f32) -> Option<Range<usize>> {
+    let channels = channels.max(1) as usize;
+    let loud = |frame: &[f32]| frame.iter().any(|s| s.abs() > threshold);
+    let mut frames = samples.chunks(channels);
+    let first = frames.position(loud)?;
+    let last = samples.chunks(channels).rposition(loud).unwrap_or(first);
+    Some(first * channels..((last + 1) * channels).min(samples.len()))
+}
+
+/// Compute (left, right) gains for a pan value in -1.0..=1.0.
+///
+/// Uses a balance law so that center (0.0) keeps both channels at unity,
+/// matching un-panned playback.
+pub fn pan_gains(pan: f32) -> (f32, f32) {
+    let pan = pan.clamp(-1.0, 1.0);
+    let left = (1.0 - pan).min(1.0);
+    let right = (1.0 + pan).min(1.0);
+    (left, right)
 }
 
 // Generate a short synthesized metronome tick (sine with quick decay).
-fn metronome_sample() -> DecodedSample {
+fn metronome_sample(freq: f32, volume: f32) -> DecodedSample {
     const SAMPLE_RATE: u32 = 44_100;
     const CHANNELS: u16 = 1;
     const DURATION_MS: u32 = 70;
-    const FREQ: f32 = 1_000.0;
 
     let total_samples = (SAMPLE_RATE as u64 * DURATION_MS as u64 / 1_000) as usize;
     let mut data = Vec::with_capacity(total_samples);
@@ -55,18 +424,586 @@ fn metronome_sample() -> DecodedSample {
         } else {
             1.0
         };
-        let sample = (2.0 * PI * FREQ * t).sin() * env * 0.4;
+        let sample = (2.0 * PI * freq * t).sin() * env * volume;
         data.push(sample);
     }
-    DecodedSample {
-        channels: CHANNELS,
-        sample_rate: SAMPLE_RATE,
-        samples: Arc::new(data),
+    DecodedSample::new(CHANNELS, SAMPLE_RATE, data)
+}
+
+/// Beat and downbeat ticks for a metronome config.
+fn metronome_ticks(config: MetronomeConfig) -> (DecodedSample, DecodedSample) {
+    let volume = config.volume.clamp(0.0, 1.0);
+    (
+        metronome_sample(config.freq, volume),
+        metronome_sample(config.accent_freq, volume),
+    )
+}
+
+/// Decode an audio file held in memory, trimming silence when a threshold
+/// is set.
+fn decode_sample(bytes: Vec<u8>, trim_threshold: Option<f32>) -> anyhow::Result<DecodedSample> {
+    let decoder = Decoder::new(Cursor::new(bytes))?;
+    let channels = decoder.channels();
+    let sample_rate = decoder.sample_rate();
+    let samples: Vec<f32> = decoder.convert_samples().collect();
+    let mut decoded = DecodedSample::new(channels, sample_rate, samples);
+    if let Some(threshold) = trim_threshold {
+        decoded.trim_silence(threshold);
+    }
+    Ok(decoded)
+}
+
+/// Read and decode an audio file, naming the file in any error.
+fn load_sample(path: &Path, trim_threshold: Option<f32>) -> anyhow::Result<DecodedSample> {
+    let name = path
+        .file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy();
+    let bytes = fs::read(path).with_context(|| format!("cannot read {name}"))?;
+    decode_sample(bytes, trim_threshold).with_context(|| format!("cannot decode {name}"))
+}
+
+/// Load a pad's sample into the cache, reporting the outcome.
+fn preload(
+    cache: &mut BTreeMap<char, DecodedSample>,
+    key: char,
+    path: &Path,
+    trim_threshold: Option<f32>,
+    events: Option<&Sender<AudioEvent>>,
+) {
+    match load_sample(path, trim_threshold) {
+        Ok(decoded) => {
+            report_sample_loaded(events, key, &decoded);
+            cache.insert(key, decoded);
+        }
+        Err(err) => {
+            eprintln!("[audio] Failed to load {}: {err:?}", path.display());
+            report_load_failed(events, key, &err);
+        }
+    }
+}
+
+/// Samples queued by `PreloadBatch`. One is decoded per wake so a large
+/// kit does not hold up playing voices; batches sent before the queue
+/// drains count towards the same total.
+#[derive(Debug, Default)]
+struct PreloadQueue {
+    pending: VecDeque<(char, PathBuf)>,
+    loaded: usize,
+    total: usize,
+}
+
+impl PreloadQueue {
+    fn push(&mut self, pads: Vec<(char, PathBuf)>) {
+        if self.pending.is_empty() {
+            self.loaded = 0;
+            self.total = 0;
+        }
+        self.total += pads.len();
+        self.pending.extend(pads);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Drop the queued samples for `key`, which a newer per-key command has
+    /// replaced; loading them later would overwrite it. The batch total
+    /// shrinks to match, and progress is reported again so a batch whose
+    /// last entry was dropped still finishes.
+    fn cancel(&mut self, key: char, events: Option<&Sender<AudioEvent>>) {
+        let before = self.pending.len();
+        self.pending.retain(|(pending, _)| *pending != key);
+        let dropped = before - self.pending.len();
+        if dropped == 0 {
+            return;
+        }
+        self.total -= dropped;
+        if let Some(events) = events {
+            let _ = events.send(AudioEvent::PreloadProgress {
+                loaded: self.loaded,
+                total: self.total,
+            });
+        }
+    }
+
+    /// Decode the next queued sample, then report how far the batch got.
+    fn load_next(
+        &mut self,
+        cache: &mut BTreeMap<char, DecodedSample>,
+        trim_threshold: Option<f32>,
+        events: Option<&Sender<AudioEvent>>,
+    ) {
+        let Some((key, path)) = self.pending.pop_front() else {
+            return;
+        };
+        preload(cache, key, &path, trim_threshold, events);
+        self.loaded += 1;
+        if let Some(events) = events {
+            let _ = events.send(AudioEvent::PreloadProgress {
+                loaded: self.loaded,
+                total: self.total,
+            });
+        }
+    }
+}
+
+/// Build the effected source for a cached pad sample, logging when missing.
+fn pad_source(
+    cache: &BTreeMap<char, DecodedSample>,
+    effects: &PadEffects,
+    key: char,
+) -> Option<SamplesBuffer<f32>> {
+    match cache.get(&key) {
+        Some(decoded) => Some(decoded.to_effected_source(effects)),
+        None => {
+            eprintln!("[audio] Play requested for key '{}' but not cached", key);
+            None
+        }
+    }
+}
+
+/// Mix the hits of a bounce into one stereo sample spanning its length.
+///
+/// Renders at the rate of the first cached hit; hits on pads at another rate
+/// are skipped and tails past the loop end are cut. Returns `None` when no
+/// hit has a cached sample.
+fn render_bounce(
+    cache: &BTreeMap<char, DecodedSample>,
+    effects: &BTreeMap<char, PadEffects>,
+    bounce: &Bounce,
+) -> Option<DecodedSample> {
+    let sample_rate = bounce
+        .hits
+        .iter()
+        .find_map(|hit| cache.get(&hit.key))?
+        .sample_rate;
+    let frames_at = |time: Duration| (time.as_secs_f64() * f64::from(sample_rate)).round() as usize;
+    let mut mix = vec![0.0f32; frames_at(bounce.length) * 2];
+    for hit in &bounce.hits {
+        let Some(decoded) = cache.get(&hit.key) else {
+            continue;
+        };
+        if decoded.sample_rate != sample_rate {
+            eprintln!("[audio] Bounce skips '{}': sample rate differs", hit.key);
+            continue;
+        }
+        let pan = effects.get(&hit.key).map(|e| e.pan).unwrap_or(0.0);
+        let (left, right) = if pan == 0.0 {
+            (1.0, 1.0)
+        } else {
+            pan_gains(pan)
+        };
+        let start = frames_at(hit.offset);
+        let channels = decoded.channels.max(1) as usize;
+        let reversed;
+        let decoded = if hit.reversed {
+            reversed = decoded.reversed();
+            &reversed
+        } else {
+            decoded
+        };
+        for (i, frame) in decoded.pcm().chunks(channels).enumerate() {
+            let at = (start + i) * 2;
+            let Some(out) = mix.get_mut(at..at + 2) else {
+                break;
+            };
+            out[0] += frame[0] * left * hit.gain;
+            out[1] += frame.get(1).copied().unwrap_or(frame[0]) * right * hit.gain;
+        }
+    }
+    for sample in &mut mix {
+        *sample = sample.clamp(-1.0, 1.0);
     }
+    Some(DecodedSample::new(2, sample_rate, mix))
+}
+
+/// Encode a decoded sample as a 16-bit PCM WAV file.
+fn encode_wav(decoded: &DecodedSample) -> Vec<u8> {
+    let pcm = decoded.pcm();
+    let data_len = (pcm.len() * 2) as u32;
+    let block_align = decoded.channels * 2;
+    let mut bytes = Vec::with_capacity(44 + pcm.len() * 2);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // integer PCM
+    bytes.extend_from_slice(&decoded.channels.to_le_bytes());
+    bytes.extend_from_slice(&decoded.sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(decoded.sample_rate * u32::from(block_align)).to_le_bytes());
+    bytes.extend_from_slice(&block_align.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in pcm {
+        let value = (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)).round() as i16;
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+/// A playing sink, the pad it sounds (`None` for the metronome), the gain it
+/// was started with, the bus it plays on (`None` for the metronome, which
+/// bypasses the buses), the choke group that can cut it, whether it is a
+/// live gate hit that stops when its key is released, and the meter of the
+/// samples it played.
+struct Voice {
+    sink: Sink,
+    key: Option<char>,
+    gain: f32,
+    bus: Option<u8>,
+    choke: Option<u8>,
+    gated: bool,
+    meter: PeakMeter,
+}
+
+/// Peak of the samples a voice played since the meter was last read,
+/// shared between the output mixer and the audio thread.
+#[derive(Debug, Clone, Default)]
+struct PeakMeter(Arc<AtomicU32>);
+
+impl PeakMeter {
+    fn record(&self, sample: f32) {
+        // Non-negative floats order like their bit patterns
+        self.0.fetch_max(sample.abs().to_bits(), Ordering::Relaxed);
+    }
+
+    /// Peak since the last call, resetting the meter.
+    fn take(&self) -> f32 {
+        f32::from_bits(self.0.swap(0, Ordering::Relaxed))
+    }
+}
+
+/// Source that feeds every sample it plays through a [`PeakMeter`].
+struct Metered<S> {
+    inner: S,
+    meter: PeakMeter,
+}
+
+impl<S: Iterator<Item = f32>> Iterator for Metered<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let sample = self.inner.next()?;
+        self.meter.record(sample);
+        Some(sample)
+    }
+}
+
+impl<S: Source<Item = f32>> Source for Metered<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        self.inner.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
+
+/// Master output level applied on top of every voice's gain.
+///
+/// Muting keeps `volume` so unmuting restores the previous level.
+/// `headroom` is the limiter's scale for the voices sounding right now.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Master {
+    volume: f32,
+    muted: bool,
+    headroom: f32,
+}
+
+impl Default for Master {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            muted: false,
+            headroom: 1.0,
+        }
+    }
+}
+
+impl Master {
+    fn level(&self, gain: f32) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            gain * self.volume * self.headroom
+        }
+    }
+}
+
+/// Limiter scale for `voices` pad voices playing at once: unity for a
+/// single voice, then 1/√n so dense hits do not sum far past full scale.
+pub fn headroom(voices: usize) -> f32 {
+    if voices <= 1 {
+        1.0
+    } else {
+        1.0 / (voices as f32).sqrt()
+    }
+}
+
+/// Gain chain of the audio thread: pad gain × bus gain × master, each
+/// control mapped through `curve`.
+#[derive(Debug, Default)]
+struct Mixer {
+    master: Master,
+    buses: BTreeMap<u8, BusLevel>,
+    pad_bus: BTreeMap<char, u8>,
+    curve: VolumeCurve,
+}
+
+impl Mixer {
+    /// Bus a pad is routed to.
+    fn bus_for(&self, key: char) -> u8 {
+        self.pad_bus.get(&key).copied().unwrap_or(0)
+    }
+
+    /// Output volume for a voice started at `gain` on `bus`.
+    fn level(&self, gain: f32, bus: Option<u8>) -> f32 {
+        let bus_gain = bus
+            .map(|b| self.buses.get(&b).copied().unwrap_or_default().gain())
+            .unwrap_or(1.0);
+        let master = Master {
+            volume: self.curve.gain(self.master.volume),
+            ..self.master
+        };
+        master.level(self.curve.gain(gain) * self.curve.gain(bus_gain))
+    }
+
+    /// Re-apply the gain chain to every playing voice.
+    fn apply(&self, voices: &[Voice]) {
+        for voice in voices {
+            voice.sink.set_volume(self.level(voice.gain, voice.bus));
+        }
+    }
+
+    /// Update the limiter for the pad voices still sounding and re-apply
+    /// the gain chain when it changed. The metronome does not count.
+    fn limit(&mut self, voices: &[Voice]) {
+        let sounding = voices
+            .iter()
+            .filter(|voice| voice.key.is_some() && !voice.sink.empty())
+            .count();
+        let headroom = headroom(sounding);
+        if headroom != self.master.headroom {
+            self.master.headroom = headroom;
+            self.apply(voices);
+        }
+    }
+}
+
+/// Play a source on a fresh sink and drop sinks that have finished.
+/// Returns the new voice, or `None` when no sink could be opened.
+fn start_sink<'a>(
+    stream_handle: &OutputStreamHandle,
+    voices: &'a mut Vec<Voice>,
+    mixer: &mut Mixer,
+    source: SamplesBuffer<f32>,
+    key: Option<char>,
+    gain: f32,
+    bus: Option<u8>,
+) -> Option<&'a mut Voice> {
+    match Sink::try_new(stream_handle) {
+        Ok(sink) => {
+            sink.set_volume(mixer.level(gain, bus));
+            let meter = PeakMeter::default();
+            sink.append(Metered {
+                inner: source,
+                meter: meter.clone(),
+            });
+            voices.push(Voice {
+                sink,
+                key,
+                gain,
+                bus,
+                choke: None,
+                gated: false,
+                meter,
+            });
+            voices.retain(|v| !v.sink.empty());
+            mixer.limit(voices);
+            voices.last_mut()
+        }
+        Err(err) => {
+            eprintln!("[audio] Failed to create Sink: {err:?}");
+            None
+        }
+    }
+}
+
+/// Play a pad voice on its bus after choking its group, tagging the voice
+/// so later hits in the group can cut it.
+fn start_pad_voice<'a>(
+    stream_handle: &OutputStreamHandle,
+    voices: &'a mut Vec<Voice>,
+    mixer: &mut Mixer,
+    chokes: &BTreeMap<char, u8>,
+    source: SamplesBuffer<f32>,
+    key: char,
+    gain: f32,
+) -> Option<&'a mut Voice> {
+    let group = chokes.get(&key).copied();
+    if let Some(group) = group {
+        choke_voices(voices, group);
+    }
+    let bus = Some(mixer.bus_for(key));
+    let voice = start_sink(stream_handle, voices, mixer, source, Some(key), gain, bus)?;
+    voice.choke = group;
+    Some(voice)
+}
+
+/// Cut the voices started in a choke group and drop them.
+fn choke_voices(voices: &mut Vec<Voice>, group: u8) {
+    voices.retain(|voice| {
+        let choked = voice.choke == Some(group);
+        if choked {
+            voice.sink.stop();
+        }
+        !choked
+    });
+}
+
+/// Cut the live gate voices of `key` and drop them, leaving its loop
+/// playback sounding.
+fn release_voices(voices: &mut Vec<Voice>, key: char) {
+    voices.retain(|voice| {
+        let released = voice.gated && voice.key == Some(key);
+        if released {
+            voice.sink.stop();
+        }
+        !released
+    });
+}
+
+/// Cut the voices of `key` (every voice when `None`) mid-playback and drop
+/// them.
+fn stop_voices(voices: &mut Vec<Voice>, key: Option<char>) {
+    voices.retain(|voice| {
+        let matches = key.is_none_or(|key| voice.key == Some(key));
+        if matches {
+            voice.sink.stop();
+        }
+        !matches
+    });
+}
+
+/// Loudest output peak per sounding pad since the last call: each voice's
+/// sample peak scaled by the gain it plays at.
+fn pad_levels(voices: &[Voice]) -> BTreeMap<char, f32> {
+    let mut levels = BTreeMap::new();
+    for voice in voices.iter().filter(|voice| !voice.sink.empty()) {
+        if let Some(key) = voice.key {
+            let level = levels.entry(key).or_insert(0.0f32);
+            *level = level.max((voice.meter.take() * voice.sink.volume()).min(1.0));
+        }
+    }
+    levels
+}
+
+/// Send the current pad levels, plus a zero for pads that went silent since
+/// the last report (`sounding` tracks them between calls).
+fn report_levels(voices: &[Voice], sounding: &mut BTreeSet<char>, events: &Sender<AudioEvent>) {
+    let levels = pad_levels(voices);
+    for key in sounding.iter().filter(|key| !levels.contains_key(key)) {
+        let _ = events.send(AudioEvent::PadLevel {
+            key: *key,
+            level: 0.0,
+        });
+    }
+    for (&key, &level) in &levels {
+        let _ = events.send(AudioEvent::PadLevel { key, level });
+    }
+    *sounding = levels.into_keys().collect();
+}
+
+/// Tell the app a pad's sample failed to load, with the error chain.
+fn report_load_failed(events: Option<&Sender<AudioEvent>>, key: char, err: &anyhow::Error) {
+    if let Some(events) = events {
+        let _ = events.send(AudioEvent::LoadFailed {
+            key,
+            error: format!("{err:#}"),
+        });
+    }
+}
+
+/// Tell the app a loop export to `path` failed, with the error chain.
+fn report_export_failed(events: Option<&Sender<AudioEvent>>, path: PathBuf, err: &anyhow::Error) {
+    if let Some(events) = events {
+        let _ = events.send(AudioEvent::ExportFailed {
+            path,
+            error: format!("{err:#}"),
+        });
+    }
+}
+
+/// Tell the app a pad's sample is decoded and at which rate.
+fn report_sample_loaded(events: Option<&Sender<AudioEvent>>, key: char, decoded: &DecodedSample) {
+    if let Some(events) = events {
+        let _ = events.send(AudioEvent::SampleLoaded {
+            key,
+            sample_rate: decoded.sample_rate,
+        });
+    }
+}
+
+/// Read the playback duration of an audio file.
+///
+/// Falls back to counting decoded frames when the decoder cannot report a
+/// total duration up front.
+pub fn sample_duration(path: &Path) -> anyhow::Result<Duration> {
+    let file = fs::File::open(path)?;
+    let decoder = Decoder::new(BufReader::new(file))?;
+    if let Some(duration) = decoder.total_duration() {
+        return Ok(duration);
+    }
+    let channels = decoder.channels().max(1) as f64;
+    let sample_rate = decoder.sample_rate().max(1) as f64;
+    let frames = decoder.count() as f64 / channels;
+    Ok(Duration::from_secs_f64(frames / sample_rate))
 }
 
 /// Spawn a background audio thread handling preload/play commands using rodio.
 pub fn spawn_audio_thread() -> Sender<AudioCommand> {
+    spawn_audio_thread_with_ready(None, None)
+}
+
+/// Spawn the audio thread, reporting pad levels on `events`.
+pub fn spawn_audio_thread_with_events(events: Sender<AudioEvent>) -> Sender<AudioCommand> {
+    spawn_audio_thread_with_ready(None, Some(events))
+}
+
+/// Spawn the audio thread and wait until its output stream is open.
+///
+/// Returns an error if the device could not be opened or the thread did not
+/// report back in time.
+pub fn try_spawn_audio_thread() -> anyhow::Result<Sender<AudioCommand>> {
+    try_spawn_audio_thread_with_events(None)
+}
+
+fn try_spawn_audio_thread_with_events(
+    events: Option<Sender<AudioEvent>>,
+) -> anyhow::Result<Sender<AudioCommand>> {
+    let (ready_tx, ready_rx) = mpsc::channel();
+    let tx = spawn_audio_thread_with_ready(Some(ready_tx), events);
+    match ready_rx.recv_timeout(Duration::from_secs(2)) {
+        Ok(Ok(())) => Ok(tx),
+        Ok(Err(err)) => anyhow::bail!("Failed to open output stream: {err}"),
+        Err(_) => anyhow::bail!("Audio thread did not start"),
+    }
+}
+
+fn spawn_audio_thread_with_ready(
+    ready: Option<Sender<Result<(), String>>>,
+    events: Option<Sender<AudioEvent>>,
+) -> Sender<AudioCommand> {
     let (tx, rx) = mpsc::channel::<AudioCommand>();
     thread::spawn(move || {
         // Keep output stream alive in thread scope
@@ -74,75 +1011,302 @@ pub fn spawn_audio_thread() -> Sender<AudioCommand> {
             Ok(v) => v,
             Err(err) => {
                 eprintln!("[audio] Failed to open output stream: {err:?}");
+                if let Some(ready) = ready {
+                    let _ = ready.send(Err(format!("{err}")));
+                }
                 return;
             }
         };
+        if let Some(ready) = ready {
+            let _ = ready.send(Ok(()));
+        }
 
         let mut cache: BTreeMap<char, DecodedSample> = BTreeMap::new();
-        let mut sinks: Vec<Sink> = Vec::new();
-        let metronome = metronome_sample();
+        let mut effects: BTreeMap<char, PadEffects> = BTreeMap::new();
+        let mut chokes: BTreeMap<char, u8> = BTreeMap::new();
+        let mut voices: Vec<Voice> = Vec::new();
+        let mut audition: Option<Sink> = None;
+        // Last auditioned file, so previewing it again skips the decode
+        let mut audition_sample: Option<(PathBuf, DecodedSample)> = None;
+        let mut mixer = Mixer::default();
+        let mut trim_threshold: Option<f32> = None;
+        // Keep the secondary stream alive alongside its handle
+        let mut metronome_out: Option<(OutputStream, OutputStreamHandle)> = None;
+        let mut metronome = MetronomeConfig::default();
+        let mut ticks = metronome_ticks(metronome);
+        let mut sounding: BTreeSet<char> = BTreeSet::new();
+        let mut last_report = Instant::now();
+        let mut preloads = PreloadQueue::default();
 
-        while let Ok(cmd) = rx.recv() {
+        loop {
+            // Wake up straight away while a batch preload is pending, and
+            // between commands to keep the level meters moving when anyone
+            // listens for them
+            let received = if !preloads.is_empty() {
+                rx.recv_timeout(Duration::ZERO)
+            } else if events.is_some() {
+                rx.recv_timeout(LEVEL_REPORT_INTERVAL)
+            } else {
+                rx.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            };
+            let cmd = match received {
+                Ok(cmd) => Some(cmd),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+            if let Some(events) = &events
+                && last_report.elapsed() >= LEVEL_REPORT_INTERVAL
+            {
+                report_levels(&voices, &mut sounding, events);
+                last_report = Instant::now();
+            }
+            if audition.as_ref().is_some_and(|sink| sink.empty()) {
+                audition = None;
+                if let Some(events) = &events {
+                    let _ = events.send(AudioEvent::AuditionEnded);
+                }
+            }
+            // Let the level back up as voices finish
+            mixer.limit(&voices);
+            preloads.load_next(&mut cache, trim_threshold, events.as_ref());
+            let Some(cmd) = cmd else {
+                continue;
+            };
+            let target = output_target(&cmd);
             match cmd {
-                AudioCommand::Preload { key, path } => match fs::read(&path) {
-                    Ok(bytes) => {
-                        let cursor = Cursor::new(bytes);
-                        match Decoder::new(cursor) {
-                            Ok(decoder) => {
-                                let channels = decoder.channels();
-                                let sample_rate = decoder.sample_rate();
-                                let samples: Vec<f32> = decoder.convert_samples().collect();
-                                cache.insert(
-                                    key,
-                                    DecodedSample {
-                                        channels,
-                                        sample_rate,
-                                        samples: Arc::new(samples),
-                                    },
-                                );
-                            }
+                AudioCommand::Preload { key, path } => {
+                    preloads.cancel(key, events.as_ref());
+                    preload(&mut cache, key, &path, trim_threshold, events.as_ref());
+                }
+                AudioCommand::PreloadBatch { pads } => preloads.push(pads),
+                AudioCommand::PreloadBytes { key, bytes } => {
+                    preloads.cancel(key, events.as_ref());
+                    match decode_sample(bytes, trim_threshold) {
+                        Ok(decoded) => {
+                            report_sample_loaded(events.as_ref(), key, &decoded);
+                            cache.insert(key, decoded);
+                        }
+                        Err(err) => {
+                            eprintln!(
+                                "[audio] Decoder error for in-memory sample '{key}': {err:?}"
+                            );
+                            report_load_failed(events.as_ref(), key, &err);
+                        }
+                    }
+                }
+                AudioCommand::Play { key } => {
+                    let chain = effects.get(&key).copied().unwrap_or_default();
+                    if let Some(source) = pad_source(&cache, &chain, key) {
+                        start_pad_voice(
+                            &stream_handle,
+                            &mut voices,
+                            &mut mixer,
+                            &chokes,
+                            source,
+                            key,
+                            1.0,
+                        );
+                    }
+                }
+                AudioCommand::PlayGated { key, reversed } => {
+                    let mut chain = effects.get(&key).copied().unwrap_or_default();
+                    chain.reverse ^= reversed;
+                    if let Some(source) = pad_source(&cache, &chain, key)
+                        && let Some(voice) = start_pad_voice(
+                            &stream_handle,
+                            &mut voices,
+                            &mut mixer,
+                            &chokes,
+                            source,
+                            key,
+                            1.0,
+                        )
+                    {
+                        voice.gated = true;
+                    }
+                }
+                AudioCommand::PlayLoop { key, gain } => {
+                    let chain = effects.get(&key).copied().unwrap_or_default();
+                    if let Some(source) = pad_source(&cache, &chain, key) {
+                        start_pad_voice(
+                            &stream_handle,
+                            &mut voices,
+                            &mut mixer,
+                            &chokes,
+                            source,
+                            key,
+                            gain,
+                        );
+                    }
+                }
+                AudioCommand::PlayReversed { key, gain } => {
+                    let mut chain = effects.get(&key).copied().unwrap_or_default();
+                    chain.reverse = !chain.reverse;
+                    if let Some(source) = pad_source(&cache, &chain, key) {
+                        start_pad_voice(
+                            &stream_handle,
+                            &mut voices,
+                            &mut mixer,
+                            &chokes,
+                            source,
+                            key,
+                            gain,
+                        );
+                    }
+                }
+                AudioCommand::PlayMetronome { downbeat } => {
+                    let (beat, accent) = &ticks;
+                    let tick = if downbeat { accent } else { beat };
+                    let handle = select_output(
+                        target,
+                        &stream_handle,
+                        metronome_out.as_ref().map(|(_, handle)| handle),
+                    );
+                    start_sink(
+                        handle,
+                        &mut voices,
+                        &mut mixer,
+                        tick.to_source(),
+                        None,
+                        1.0,
+                        None,
+                    );
+                }
+                AudioCommand::PlayPath { path, repeats } => {
+                    if audition_sample
+                        .as_ref()
+                        .is_none_or(|(cached, _)| *cached != path)
+                    {
+                        audition_sample = None;
+                        match fs::read(&path)
+                            .map_err(anyhow::Error::from)
+                            .and_then(|bytes| decode_sample(bytes, trim_threshold))
+                        {
+                            Ok(decoded) => audition_sample = Some((path.clone(), decoded)),
                             Err(err) => {
-                                eprintln!("[audio] Decoder error for {}: {err:?}", path.display());
+                                eprintln!("[audio] Cannot audition {}: {err:?}", path.display());
                             }
                         }
                     }
-                    Err(err) => {
-                        eprintln!("[audio] Failed to read {}: {err:?}", path.display());
-                    }
-                },
-                AudioCommand::Play { key } | AudioCommand::PlayLoop { key } => {
-                    if let Some(decoded) = cache.get(&key) {
-                        match Sink::try_new(&stream_handle) {
-                            Ok(sink) => {
+                    if let Some((_, decoded)) = &audition_sample {
+                        if audition.as_ref().is_none_or(|sink| sink.empty()) {
+                            audition = Sink::try_new(&stream_handle)
+                                .map_err(|err| eprintln!("[audio] Failed to create Sink: {err:?}"))
+                                .ok();
+                        }
+                        if let Some(sink) = &audition {
+                            sink.set_volume(mixer.level(1.0, None));
+                            for _ in 0..repeats {
                                 sink.append(decoded.to_source());
-                                sinks.push(sink);
-                                sinks.retain(|s| !s.empty());
                             }
-                            Err(err) => eprintln!("[audio] Failed to create Sink: {err:?}"),
                         }
-                    } else {
-                        eprintln!("[audio] Play requested for key '{}' but not cached", key);
                     }
                 }
-                AudioCommand::PlayMetronome => {
-                    if let Ok(sink) = Sink::try_new(&stream_handle) {
-                        sink.append(metronome.to_source());
-                        sinks.push(sink);
-                        sinks.retain(|s| !s.empty());
+                AudioCommand::StopAudition => {
+                    if let Some(sink) = audition.take() {
+                        sink.stop();
+                    }
+                }
+                AudioCommand::Stop { key } => stop_voices(&mut voices, Some(key)),
+                AudioCommand::Release { key } => release_voices(&mut voices, key),
+                AudioCommand::StopAll => {
+                    stop_voices(&mut voices, None);
+                    if let Some(sink) = audition.take() {
+                        sink.stop();
                     }
                 }
                 AudioCommand::PauseAll => {
-                    sinks.retain(|sink| {
-                        sink.pause();
+                    voices.retain(|voice| {
+                        voice.sink.pause();
                         true
                     });
                 }
                 AudioCommand::ResumeAll => {
-                    sinks.retain(|sink| {
-                        sink.play();
+                    voices.retain(|voice| {
+                        voice.sink.play();
                         true
                     });
                 }
+                AudioCommand::SetPadPan { key, pan } => {
+                    effects.entry(key).or_default().pan = pan.clamp(-1.0, 1.0);
+                }
+                AudioCommand::Preview {
+                    key,
+                    effects: chain,
+                } => {
+                    if let Some(source) = pad_source(&cache, &chain, key) {
+                        let bus = Some(mixer.bus_for(key));
+                        start_sink(
+                            &stream_handle,
+                            &mut voices,
+                            &mut mixer,
+                            source,
+                            Some(key),
+                            1.0,
+                            bus,
+                        );
+                    }
+                }
+                AudioCommand::SetMasterMute(muted) => {
+                    mixer.master.muted = muted;
+                    mixer.apply(&voices);
+                }
+                AudioCommand::SetMasterGain(gain) => {
+                    mixer.master.volume = gain.clamp(0.0, 1.0);
+                    mixer.apply(&voices);
+                }
+                AudioCommand::SetVolumeCurve(curve) => {
+                    mixer.curve = curve;
+                    mixer.apply(&voices);
+                }
+                AudioCommand::SetPadBus { key, bus } => {
+                    mixer.pad_bus.insert(key, bus);
+                }
+                AudioCommand::SetPadChoke { key, group } => match group {
+                    Some(group) => {
+                        chokes.insert(key, group);
+                    }
+                    None => {
+                        chokes.remove(&key);
+                    }
+                },
+                AudioCommand::SetBusLevel { bus, level } => {
+                    mixer.buses.insert(bus, level);
+                    mixer.apply(&voices);
+                }
+                AudioCommand::SetTrimSilence(threshold) => {
+                    trim_threshold = threshold;
+                }
+                AudioCommand::SetMetronomeDevice(name) => {
+                    metronome_out = name.as_deref().and_then(open_output_device);
+                }
+                AudioCommand::ConfigureMetronome(config) => {
+                    if config != metronome {
+                        metronome = config;
+                        ticks = metronome_ticks(config);
+                    }
+                }
+                AudioCommand::Bounce { key, bounce } => {
+                    preloads.cancel(key, events.as_ref());
+                    match render_bounce(&cache, &effects, &bounce) {
+                        Some(rendered) => {
+                            cache.insert(key, rendered);
+                        }
+                        None => eprintln!("[audio] Nothing cached to bounce into '{key}'"),
+                    }
+                }
+                AudioCommand::ExportWav { path, bounce } => {
+                    let written = match render_bounce(&cache, &effects, &bounce) {
+                        Some(rendered) => fs::write(&path, encode_wav(&rendered))
+                            .with_context(|| format!("cannot write {}", path.display())),
+                        None => Err(anyhow::anyhow!("no pad samples are loaded")),
+                    };
+                    if let Err(err) = written {
+                        eprintln!("[audio] Export failed: {err:?}");
+                        report_export_failed(events.as_ref(), path, &err);
+                    }
+                }
+                AudioCommand::Shutdown => break,
             }
         }
         eprintln!("[audio] receiver closed; audio thread exiting");
@@ -150,6 +1314,83 @@ pub fn spawn_audio_thread() -> Sender<AudioCommand> {
     tx
 }
 
+/// Replaceable handle to the audio thread's command channel.
+///
+/// Clones share the same underlying sender, so respawning the thread through
+/// any clone redirects every holder (effect handler, loop engine bus).
+#[derive(Clone, Debug)]
+pub struct AudioHandle {
+    tx: Arc<Mutex<Sender<AudioCommand>>>,
+    /// Level-event channel handed to respawned threads
+    events: Option<Sender<AudioEvent>>,
+    /// Set once a send finds the audio thread gone; cleared by `replace`
+    disconnected: Arc<AtomicBool>,
+}
+
+impl AudioHandle {
+    pub fn new(tx: Sender<AudioCommand>) -> Self {
+        Self {
+            tx: Arc::new(Mutex::new(tx)),
+            events: None,
+            disconnected: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Handle for a thread started with [`spawn_audio_thread_with_events`];
+    /// respawned threads keep reporting on `events`.
+    pub fn with_events(tx: Sender<AudioCommand>, events: Sender<AudioEvent>) -> Self {
+        Self {
+            tx: Arc::new(Mutex::new(tx)),
+            events: Some(events),
+            disconnected: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Send a command to the current audio thread. A send to a thread that
+    /// has exited (e.g. after a device error) marks the handle disconnected.
+    pub fn send(&self, cmd: AudioCommand) {
+        if let Ok(tx) = self.tx.lock()
+            && tx.send(cmd).is_err()
+        {
+            self.disconnected.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Whether the audio thread has stopped taking commands, so nothing
+    /// sent since has played. `respawn` recovers.
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected.load(Ordering::Relaxed)
+    }
+
+    /// Get a clone of the current sender.
+    pub fn sender(&self) -> Sender<AudioCommand> {
+        match self.tx.lock() {
+            Ok(tx) => tx.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Swap in a new sender, returning the previous one.
+    pub fn replace(&self, new_tx: Sender<AudioCommand>) -> Sender<AudioCommand> {
+        let mut guard = match self.tx.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        self.disconnected.store(false, Ordering::Relaxed);
+        std::mem::replace(&mut *guard, new_tx)
+    }
+
+    /// Tear down the current audio thread and start a fresh one.
+    ///
+    /// Cached samples are lost; callers must re-issue `Preload` commands.
+    pub fn respawn(&self) -> anyhow::Result<()> {
+        let new_tx = try_spawn_audio_thread_with_events(self.events.clone())?;
+        let old_tx = self.replace(new_tx);
+        let _ = old_tx.send(AudioCommand::Shutdown);
+        Ok(())
+    }
+}
+
 /// Infrastructure implementation of Clock trait using system time.
 #[derive(Clone)]
 pub struct SystemClock {
@@ -179,34 +1420,58 @@ impl Clock for SystemClock {
 /// Infrastructure implementation of AudioBus trait using channel sender.
 #[derive(Clone)]
 pub struct SenderAudioBus {
-    tx: std::sync::mpsc::Sender<AudioCommand>,
+    handle: AudioHandle,
 }
 
 impl SenderAudioBus {
     pub fn new(tx: std::sync::mpsc::Sender<AudioCommand>) -> Self {
-        Self { tx }
+        Self {
+            handle: AudioHandle::new(tx),
+        }
+    }
+
+    /// Create a bus that follows the given handle across audio thread respawns.
+    pub fn from_handle(handle: AudioHandle) -> Self {
+        Self { handle }
     }
 }
 
 impl AudioBus for SenderAudioBus {
     fn play_metronome_beep(&self) {
-        let _ = self.tx.send(AudioCommand::PlayMetronome);
+        self.play_metronome_tick(false);
+    }
+
+    fn play_metronome_tick(&self, downbeat: bool) {
+        self.handle.send(AudioCommand::PlayMetronome { downbeat });
     }
 
     fn play_pad(&self, key: char) {
-        let _ = self.tx.send(AudioCommand::Play { key });
+        self.handle.send(AudioCommand::Play { key });
     }
 
     fn play_scheduled(&self, key: char) {
-        let _ = self.tx.send(AudioCommand::PlayLoop { key });
+        self.play_scheduled_with_gain(key, 1.0);
+    }
+
+    fn play_scheduled_with_gain(&self, key: char, gain: f32) {
+        self.handle.send(AudioCommand::PlayLoop { key, gain });
+    }
+
+    fn play_pad_reversed(&self, key: char) {
+        self.handle
+            .send(AudioCommand::PlayReversed { key, gain: 1.0 });
+    }
+
+    fn play_scheduled_reversed(&self, key: char, gain: f32) {
+        self.handle.send(AudioCommand::PlayReversed { key, gain });
     }
 
     fn pause_all(&self) {
-        let _ = self.tx.send(AudioCommand::PauseAll);
+        self.handle.send(AudioCommand::PauseAll);
     }
 
     fn resume_all(&self) {
-        let _ = self.tx.send(AudioCommand::ResumeAll);
+        self.handle.send(AudioCommand::ResumeAll);
     }
 }
 
@@ -224,8 +1489,644 @@ mod tests {
         });
         // Play variants should not panic either
         let _ = tx.send(AudioCommand::Play { key: 'q' });
-        let _ = tx.send(AudioCommand::PlayLoop { key: 'q' });
-        let _ = tx.send(AudioCommand::PlayMetronome);
+        let _ = tx.send(AudioCommand::PlayLoop {
+            key: 'q',
+            gain: 1.0,
+        });
+        let _ = tx.send(AudioCommand::PlayMetronome { downbeat: true });
+        let _ = tx.send(AudioCommand::ConfigureMetronome(MetronomeConfig::default()));
         let _ = tx.send(AudioCommand::PauseAll);
+        let _ = tx.send(AudioCommand::SetPadPan {
+            key: 'q',
+            pan: -0.5,
+        });
+        let _ = tx.send(AudioCommand::Preview {
+            key: 'q',
+            effects: PadEffects {
+                pan: 0.5,
+                reverse: false,
+            },
+        });
+    }
+
+    #[test]
+    fn audio_handle_replace_redirects_all_clones() {
+        let (old_tx, old_rx) = mpsc::channel();
+        let (new_tx, new_rx) = mpsc::channel();
+        let handle = AudioHandle::new(old_tx);
+        let bus = SenderAudioBus::from_handle(handle.clone());
+
+        bus.play_pad('q');
+        let _ = handle.replace(new_tx);
+        bus.play_pad('w');
+
+        assert_eq!(old_rx.try_recv(), Ok(AudioCommand::Play { key: 'q' }));
+        assert!(old_rx.try_recv().is_err());
+        assert_eq!(new_rx.try_recv(), Ok(AudioCommand::Play { key: 'w' }));
+    }
+
+    #[test]
+    fn audio_handle_detects_a_closed_channel_until_replaced() {
+        let (old_tx, old_rx) = mpsc::channel();
+        let handle = AudioHandle::new(old_tx);
+        let bus = SenderAudioBus::from_handle(handle.clone());
+        bus.play_pad('q');
+        assert!(!handle.is_disconnected());
+
+        drop(old_rx);
+        bus.play_pad('w');
+        assert!(handle.is_disconnected());
+
+        let (new_tx, new_rx) = mpsc::channel();
+        let _ = handle.replace(new_tx);
+        assert!(!handle.is_disconnected());
+        bus.play_pad('e');
+        assert_eq!(new_rx.try_recv(), Ok(AudioCommand::Play { key: 'e' }));
+        assert!(!handle.is_disconnected());
+    }
+
+    #[test]
+    fn pan_gains_follow_balance_law() {
+        assert_eq!(pan_gains(0.0), (1.0, 1.0));
+        assert_eq!(pan_gains(-1.0), (1.0, 0.0));
+        assert_eq!(pan_gains(1.0), (0.0, 1.0));
+        assert_eq!(pan_gains(0.5), (0.5, 1.0));
+        assert_eq!(pan_gains(-0.25), (1.0, 0.75));
+        // Out-of-range values clamp to hard left/right
+        assert_eq!(pan_gains(3.0), (0.0, 1.0));
+    }
+
+    #[test]
+    fn panned_mono_source_splits_to_stereo() {
+        let sample = DecodedSample::new(1, 44_100, vec![1.0, 0.5]);
+        let source = sample.to_panned_source(-1.0);
+        assert_eq!(source.channels(), 2);
+        let data: Vec<f32> = source.collect();
+        assert_eq!(data, vec![1.0, 0.0, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn master_mute_zeroes_voices_and_restores_prior_levels() {
+        let voice = |gain| Voice {
+            sink: Sink::new_idle().0,
+            key: None,
+            gain,
+            bus: None,
+            choke: None,
+            gated: false,
+            meter: PeakMeter::default(),
+        };
+        let voices = vec![voice(1.0), voice(0.5)];
+        let mut mixer = Mixer::default();
+        mixer.master.volume = 0.8;
+        mixer.apply(&voices);
+        let levels = |voices: &[Voice]| voices.iter().map(|v| v.sink.volume()).collect::<Vec<_>>();
+        assert_eq!(levels(&voices), vec![0.8, 0.4]);
+
+        mixer.master.muted = true;
+        mixer.apply(&voices);
+        assert_eq!(levels(&voices), vec![0.0, 0.0]);
+        assert_eq!(mixer.master.volume, 0.8);
+
+        mixer.master.muted = false;
+        mixer.apply(&voices);
+        assert_eq!(levels(&voices), vec![0.8, 0.4]);
+    }
+
+    #[test]
+    fn mixer_multiplies_pad_bus_and_master_gains() {
+        let mut mixer = Mixer::default();
+        mixer.master.volume = 0.5;
+        mixer.pad_bus.insert('w', 1);
+        mixer.buses.insert(
+            1,
+            BusLevel {
+                volume: 0.5,
+                muted: false,
+            },
+        );
+
+        // Pads default to bus 0 at unity
+        assert_eq!(mixer.bus_for('q'), 0);
+        assert_eq!(mixer.level(0.8, Some(mixer.bus_for('q'))), 0.4);
+        assert_eq!(mixer.level(0.8, Some(mixer.bus_for('w'))), 0.2);
+        // The metronome bypasses the buses
+        assert_eq!(mixer.level(1.0, None), 0.5);
+    }
+
+    #[test]
+    fn perceptual_gain_keeps_endpoints_and_lowers_the_middle() {
+        assert_eq!(perceptual_gain(0.0), 0.0);
+        assert_eq!(perceptual_gain(1.0), 1.0);
+        assert_eq!(perceptual_gain(0.5), 0.125);
+        // Out-of-range controls are clamped
+        assert_eq!(perceptual_gain(-0.5), 0.0);
+        assert_eq!(perceptual_gain(2.0), 1.0);
+        assert_eq!(VolumeCurve::Linear.gain(0.5), 0.5);
+    }
+
+    #[test]
+    fn mixer_maps_every_control_through_the_volume_curve() {
+        let mut mixer = Mixer {
+            curve: VolumeCurve::Perceptual,
+            ..Mixer::default()
+        };
+        mixer.master.volume = 0.5;
+        mixer.buses.insert(
+            0,
+            BusLevel {
+                volume: 0.5,
+                muted: false,
+            },
+        );
+
+        assert_eq!(mixer.level(1.0, None), 0.125);
+        assert_eq!(mixer.level(0.5, Some(0)), 0.125 * 0.125 * 0.125);
+        // The limiter's headroom is not a control and stays linear
+        mixer.master.headroom = 0.5;
+        assert_eq!(mixer.level(1.0, None), 0.0625);
+    }
+
+    #[test]
+    fn limiter_lowers_pad_voices_as_more_sound_at_once() {
+        let voice = |key| {
+            let sink = Sink::new_idle().0;
+            sink.append(SamplesBuffer::new(1, 44_100, vec![0.5f32; 64]));
+            Voice {
+                sink,
+                key,
+                gain: 1.0,
+                bus: None,
+                choke: None,
+                gated: false,
+                meter: PeakMeter::default(),
+            }
+        };
+        let mut mixer = Mixer::default();
+        let mut voices = vec![voice(Some('q')), voice(None)];
+        mixer.limit(&voices);
+        assert_eq!(mixer.master.headroom, 1.0);
+
+        voices.extend([voice(Some('w')), voice(Some('e')), voice(Some('r'))]);
+        mixer.limit(&voices);
+        assert_eq!(mixer.master.headroom, 0.5);
+        assert!(voices.iter().all(|v| v.sink.volume() == 0.5));
+
+        // Finished voices give the level back
+        voices.truncate(2);
+        mixer.limit(&voices);
+        assert!(voices.iter().all(|v| v.sink.volume() == 1.0));
+        assert_eq!(headroom(0), 1.0);
+    }
+
+    #[test]
+    fn bus_mute_silences_every_routed_voice() {
+        let voice = |bus| Voice {
+            sink: Sink::new_idle().0,
+            key: None,
+            gain: 1.0,
+            bus,
+            choke: None,
+            gated: false,
+            meter: PeakMeter::default(),
+        };
+        let voices = vec![voice(Some(1)), voice(Some(0)), voice(Some(1)), voice(None)];
+        let mut mixer = Mixer::default();
+        let drums = BusLevel {
+            volume: 0.6,
+            muted: true,
+        };
+        mixer.buses.insert(1, drums);
+        mixer.apply(&voices);
+        let levels = |voices: &[Voice]| voices.iter().map(|v| v.sink.volume()).collect::<Vec<_>>();
+        assert_eq!(levels(&voices), vec![0.0, 1.0, 0.0, 1.0]);
+
+        mixer.buses.insert(
+            1,
+            BusLevel {
+                muted: false,
+                ..drums
+            },
+        );
+        mixer.apply(&voices);
+        assert_eq!(levels(&voices), vec![0.6, 1.0, 0.6, 1.0]);
+    }
+
+    #[test]
+    fn stop_cuts_only_the_matching_pad_and_stop_all_cuts_everything() {
+        let voice = |key| Voice {
+            sink: Sink::new_idle().0,
+            key,
+            gain: 1.0,
+            bus: None,
+            choke: None,
+            gated: false,
+            meter: PeakMeter::default(),
+        };
+        let mut voices = vec![
+            voice(Some('q')),
+            voice(Some('w')),
+            voice(Some('q')),
+            voice(None),
+        ];
+        let keys = |voices: &[Voice]| voices.iter().map(|v| v.key).collect::<Vec<_>>();
+
+        stop_voices(&mut voices, Some('q'));
+        assert_eq!(keys(&voices), vec![Some('w'), None]);
+
+        stop_voices(&mut voices, None);
+        assert!(voices.is_empty());
+    }
+
+    #[test]
+    fn metered_source_reports_the_peak_played_since_the_last_read() {
+        let meter = PeakMeter::default();
+        let mut source = Metered {
+            inner: SamplesBuffer::new(1, 44_100, vec![0.25, -0.75, 0.5, 0.1]),
+            meter: meter.clone(),
+        };
+        assert_eq!(meter.take(), 0.0);
+
+        source.next();
+        source.next();
+        assert_eq!(meter.take(), 0.75);
+        // Read again: only what played since counts
+        source.next();
+        assert_eq!(meter.take(), 0.5);
+        assert_eq!(meter.take(), 0.0);
+        assert_eq!(source.channels(), 1);
+    }
+
+    #[test]
+    fn release_cuts_only_live_gate_voices_of_the_key() {
+        let voice = |key, gated| Voice {
+            sink: Sink::new_idle().0,
+            key: Some(key),
+            gain: 1.0,
+            bus: None,
+            choke: None,
+            gated,
+            meter: PeakMeter::default(),
+        };
+        // A held gate hit of Q over its loop playback, and a gate hit of W
+        let mut voices = vec![voice('q', true), voice('q', false), voice('w', true)];
+        let tags = |voices: &[Voice]| voices.iter().map(|v| (v.key, v.gated)).collect::<Vec<_>>();
+
+        release_voices(&mut voices, 'q');
+        assert_eq!(tags(&voices), vec![(Some('q'), false), (Some('w'), true)]);
+    }
+
+    #[test]
+    fn choke_cuts_only_voices_of_the_same_group() {
+        let voice = |key, choke| Voice {
+            sink: Sink::new_idle().0,
+            key: Some(key),
+            gain: 1.0,
+            bus: None,
+            choke,
+            gated: false,
+            meter: PeakMeter::default(),
+        };
+        // Open hat and crash in group 1 and 2, the kick in none
+        let mut voices = vec![voice('o', Some(1)), voice('q', None), voice('c', Some(2))];
+        let keys = |voices: &[Voice]| voices.iter().map(|v| v.key).collect::<Vec<_>>();
+
+        choke_voices(&mut voices, 1);
+        assert_eq!(keys(&voices), vec![Some('q'), Some('c')]);
+
+        choke_voices(&mut voices, 3);
+        assert_eq!(voices.len(), 2);
+    }
+
+    #[test]
+    fn silence_bounds_skip_leading_and_trailing_quiet_frames() {
+        let mono = [0.0, 0.0, 0.001, 0.5, -0.2, 0.0, 0.3, 0.0, 0.0];
+        assert_eq!(silence_bounds(&mono, 1, 0.01), Some(3..7));
+
+        // Stereo bounds stay frame aligned even when only one channel is loud
+        let stereo = [0.0, 0.0, 0.0, 0.4, 0.2, 0.0, 0.0, 0.0];
+        assert_eq!(silence_bounds(&stereo, 2, 0.01), Some(2..6));
+
+        assert_eq!(silence_bounds(&[0.0, 0.005, -0.005], 1, 0.01), None);
+        assert_eq!(silence_bounds(&[], 1, 0.01), None);
+    }
+
+    #[test]
+    fn trim_silence_leaves_fully_silent_samples_whole() {
+        let mut silent = DecodedSample::new(1, 44_100, vec![0.0; 4]);
+        silent.trim_silence(0.01);
+        assert_eq!(silent.pcm().len(), 4);
+
+        let mut hit = DecodedSample::new(1, 44_100, vec![0.0, 0.0, 0.9, 0.1, 0.0]);
+        hit.trim_silence(0.01);
+        assert_eq!(hit.pcm(), &[0.9, 0.1]);
+        assert_eq!(hit.to_source().collect::<Vec<f32>>(), vec![0.9, 0.1]);
+    }
+
+    #[test]
+    fn only_the_metronome_targets_the_secondary_output() {
+        assert_eq!(
+            output_target(&AudioCommand::PlayMetronome { downbeat: false }),
+            OutputTarget::Metronome
+        );
+        for cmd in [
+            AudioCommand::Play { key: 'q' },
+            AudioCommand::PlayLoop {
+                key: 'q',
+                gain: 1.0,
+            },
+            AudioCommand::Preview {
+                key: 'q',
+                effects: PadEffects::default(),
+            },
+        ] {
+            assert_eq!(output_target(&cmd), OutputTarget::Main);
+        }
+    }
+
+    #[test]
+    fn select_output_falls_back_to_main_without_a_metronome_device() {
+        let (main, click) = ("main", "click");
+        assert_eq!(
+            *select_output(OutputTarget::Metronome, &main, Some(&click)),
+            "click"
+        );
+        assert_eq!(
+            *select_output(OutputTarget::Main, &main, Some(&click)),
+            "main"
+        );
+        assert_eq!(*select_output(OutputTarget::Metronome, &main, None), "main");
+    }
+
+    #[test]
+    fn render_bounce_mixes_hits_at_their_offsets_with_gain() {
+        let mut cache = BTreeMap::new();
+        cache.insert('q', DecodedSample::new(1, 4, vec![0.5, 0.25]));
+        let bounce = Bounce {
+            length: Duration::from_secs(1),
+            hits: vec![
+                BounceHit {
+                    key: 'q',
+                    offset: Duration::ZERO,
+                    gain: 1.0,
+                    reversed: false,
+                },
+                BounceHit {
+                    key: 'q',
+                    offset: Duration::from_millis(500),
+                    gain: 0.5,
+                    reversed: false,
+                },
+                // Not cached: ignored
+                BounceHit {
+                    key: 'w',
+                    offset: Duration::ZERO,
+                    gain: 1.0,
+                    reversed: false,
+                },
+                // Starts on the last frame: the tail is cut at the loop end
+                BounceHit {
+                    key: 'q',
+                    offset: Duration::from_millis(750),
+                    gain: 1.0,
+                    reversed: false,
+                },
+            ],
+        };
+
+        let rendered = render_bounce(&cache, &BTreeMap::new(), &bounce).expect("rendered");
+        assert_eq!(rendered.channels, 2);
+        assert_eq!(rendered.sample_rate, 4);
+        assert_eq!(
+            rendered.pcm(),
+            &[0.5, 0.5, 0.25, 0.25, 0.25, 0.25, 0.625, 0.625]
+        );
+    }
+
+    #[test]
+    fn render_bounce_needs_a_cached_hit() {
+        let bounce = Bounce {
+            length: Duration::from_secs(1),
+            hits: vec![BounceHit {
+                key: 'q',
+                offset: Duration::ZERO,
+                gain: 1.0,
+                reversed: false,
+            }],
+        };
+        assert!(render_bounce(&BTreeMap::new(), &BTreeMap::new(), &bounce).is_none());
+    }
+
+    /// Minimal 16-bit PCM WAV file.
+    fn wav_bytes(channels: u16, sample_rate: u32, samples: &[i16]) -> Vec<u8> {
+        let data_len = (samples.len() * 2) as u32;
+        let block_align = channels * 2;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&channels.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate * u32::from(block_align)).to_le_bytes());
+        bytes.extend_from_slice(&block_align.to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        for sample in samples {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn encoded_wav_decodes_back_to_the_same_frames() {
+        let rendered = DecodedSample::new(2, 8_000, vec![0.0, 0.5, -0.5, 1.0, 2.0, -2.0]);
+        let bytes = encode_wav(&rendered);
+        assert_eq!(bytes.len(), 44 + 6 * 2);
+
+        let decoded = decode_sample(bytes, None).expect("decode");
+        assert_eq!(decoded.channels, 2);
+        assert_eq!(decoded.sample_rate, 8_000);
+        let expected = [0.0, 0.5, -0.5, 1.0, 1.0, -1.0];
+        for (got, want) in decoded.pcm().iter().zip(expected) {
+            assert!((got - want).abs() < 0.001, "{got} != {want}");
+        }
+    }
+
+    #[test]
+    fn in_memory_wav_decodes_into_a_playable_pad() {
+        let bytes = wav_bytes(1, 8_000, &[0, i16::MAX / 2, i16::MIN / 2, 0]);
+        let decoded = decode_sample(bytes.clone(), None).expect("decode");
+        assert_eq!(decoded.channels, 1);
+        assert_eq!(decoded.sample_rate, 8_000);
+        assert_eq!(decoded.pcm().len(), 4);
+
+        let mut cache = BTreeMap::new();
+        cache.insert('q', decoded);
+        let source = pad_source(&cache, &PadEffects::default(), 'q').expect("playable");
+        assert_eq!(source.count(), 4);
+
+        // The audio thread accepts the same bytes without panicking
+        let tx = spawn_audio_thread();
+        let _ = tx.send(AudioCommand::PreloadBytes { key: 'q', bytes });
+        let _ = tx.send(AudioCommand::Play { key: 'q' });
+    }
+
+    #[test]
+    fn metronome_ticks_follow_the_configured_pitch_and_volume() {
+        let peak = |sample: &DecodedSample| sample.pcm().iter().fold(0.0f32, |m, v| m.max(v.abs()));
+        // Sign changes over the 70 ms tick track the sine frequency
+        let crossings = |sample: &DecodedSample| {
+            sample
+                .pcm()
+                .windows(2)
+                .filter(|pair| (pair[0] < 0.0) != (pair[1] < 0.0))
+                .count()
+        };
+        let (beat, accent) = metronome_ticks(MetronomeConfig {
+            freq: 800.0,
+            volume: 0.1,
+            accent_freq: 1_600.0,
+        });
+        assert!(peak(&beat) <= 0.1 && peak(&beat) > 0.09);
+        assert!(peak(&accent) <= 0.1);
+        let ratio = crossings(&accent) as f32 / crossings(&beat) as f32;
+        assert!((ratio - 2.0).abs() < 0.1, "accent/beat crossings {ratio}");
+
+        let (clamped, _) = metronome_ticks(MetronomeConfig {
+            volume: 3.0,
+            ..MetronomeConfig::default()
+        });
+        assert!(peak(&clamped) <= 1.0);
+    }
+
+    #[test]
+    fn undecodable_bytes_are_rejected() {
+        assert!(decode_sample(b"not audio".to_vec(), None).is_err());
+    }
+
+    #[test]
+    fn load_errors_name_the_file() {
+        let path = std::env::temp_dir().join("termigroove-corrupt.wav");
+        fs::write(&path, b"RIFF garbage").expect("write corrupt file");
+        let Err(err) = load_sample(&path, None) else {
+            panic!("corrupt file loaded");
+        };
+        assert!(format!("{err:#}").starts_with("cannot decode termigroove-corrupt.wav: "));
+        let _ = fs::remove_file(&path);
+
+        let Err(err) = load_sample(Path::new("/no/such/kick.wav"), None) else {
+            panic!("missing file loaded");
+        };
+        assert!(format!("{err:#}").starts_with("cannot read kick.wav: "));
+    }
+
+    #[test]
+    fn batch_preload_reports_progress_after_each_sample_in_order() {
+        let dir = std::env::temp_dir().join("termigroove-batch-preload");
+        fs::create_dir_all(&dir).expect("create dir");
+        let kick = dir.join("kick.wav");
+        let snare = dir.join("snare.wav");
+        fs::write(&kick, wav_bytes(1, 8_000, &[0, 100, 0])).expect("write kick");
+        fs::write(&snare, wav_bytes(1, 22_050, &[0, -100, 0])).expect("write snare");
+
+        let mut queue = PreloadQueue::default();
+        queue.push(vec![
+            ('q', kick),
+            ('w', snare),
+            ('e', dir.join("missing.wav")),
+        ]);
+        let (events, rx) = mpsc::channel();
+        let mut cache = BTreeMap::new();
+        while !queue.is_empty() {
+            queue.load_next(&mut cache, None, Some(&events));
+        }
+
+        let received: Vec<AudioEvent> = rx.try_iter().collect();
+        assert_eq!(received.len(), 6);
+        assert_eq!(
+            received[..4],
+            [
+                AudioEvent::SampleLoaded {
+                    key: 'q',
+                    sample_rate: 8_000
+                },
+                AudioEvent::PreloadProgress {
+                    loaded: 1,
+                    total: 3
+                },
+                AudioEvent::SampleLoaded {
+                    key: 'w',
+                    sample_rate: 22_050
+                },
+                AudioEvent::PreloadProgress {
+                    loaded: 2,
+                    total: 3
+                },
+            ]
+        );
+        assert!(matches!(
+            received[4],
+            AudioEvent::LoadFailed { key: 'e', .. }
+        ));
+        assert_eq!(
+            received[5],
+            AudioEvent::PreloadProgress {
+                loaded: 3,
+                total: 3
+            }
+        );
+        assert_eq!(cache.keys().copied().collect::<Vec<_>>(), vec!['q', 'w']);
+
+        // A new batch after the queue drains counts from zero again
+        queue.push(vec![('r', dir.join("kick.wav"))]);
+        queue.load_next(&mut cache, None, Some(&events));
+        assert_eq!(
+            rx.try_iter().last(),
+            Some(AudioEvent::PreloadProgress {
+                loaded: 1,
+                total: 1
+            })
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn per_key_command_drops_stale_batch_entries_for_that_key() {
+        let dir = std::env::temp_dir().join("termigroove-batch-preload-cancel");
+        fs::create_dir_all(&dir).expect("create dir");
+        let kick = dir.join("kick.wav");
+        let snare = dir.join("snare.wav");
+        fs::write(&kick, wav_bytes(1, 8_000, &[0, 100, 0])).expect("write kick");
+        fs::write(&snare, wav_bytes(1, 22_050, &[0, -100, 0])).expect("write snare");
+
+        let mut queue = PreloadQueue::default();
+        queue.push(vec![('q', kick.clone()), ('w', snare.clone())]);
+        let (events, rx) = mpsc::channel();
+        let mut cache = BTreeMap::new();
+        queue.load_next(&mut cache, None, Some(&events));
+
+        // W was remapped to the kick before its batch entry loaded
+        queue.cancel('w', Some(&events));
+        preload(&mut cache, 'w', &kick, None, Some(&events));
+        while !queue.is_empty() {
+            queue.load_next(&mut cache, None, Some(&events));
+        }
+
+        assert_eq!(cache[&'w'].sample_rate, 8_000);
+        let progress: Vec<AudioEvent> = rx
+            .try_iter()
+            .filter(|event| matches!(event, AudioEvent::PreloadProgress { .. }))
+            .collect();
+        assert_eq!(
+            progress.last(),
+            Some(&AudioEvent::PreloadProgress {
+                loaded: 1,
+                total: 1
+            })
+        );
+        let _ = fs::remove_dir_all(&dir);
     }
 }
@@ -8,11 +8,14 @@
 //! - Loop tracks and recorded events
 //! - Loop lifecycle management
 
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, HashSet, VecDeque};
 use std::time::Duration;
 
 use crate::domain::ports::{AudioBus, Clock};
-use crate::domain::timing::{beat_interval_ms, loop_length_from, normalize_offset};
+use crate::domain::tempo::{DEFAULT_COUNT_IN_BEATS, clamp_count_in};
+use crate::domain::timing::{
+    QuantizeGrid, TimeSignature, apply_swing, normalize_offset, quantize_offset,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LoopState {
@@ -41,6 +44,9 @@ pub enum LoopState {
 struct LoopTrack {
     events: Vec<RecordedEvent>,
     next_event_index: usize,
+    volume: f32,
+    muted: bool,
+    name: Option<String>,
 }
 
 impl LoopTrack {
@@ -48,6 +54,9 @@ impl LoopTrack {
         Self {
             events,
             next_event_index: 0,
+            volume: 1.0,
+            muted: false,
+            name: None,
         }
     }
 
@@ -56,10 +65,167 @@ impl LoopTrack {
     }
 }
 
-#[derive(Debug, Clone)]
-struct RecordedEvent {
-    key: char,
-    offset: Duration,
+/// A pad hit recorded at an offset within the loop cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordedEvent {
+    pub key: char,
+    pub offset: Duration,
+    /// Whether the hit sounded backwards (e.g. a ratchet-reverse pad)
+    pub reversed: bool,
+}
+
+/// Read-only summary of a committed track for display.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackInfo {
+    pub index: usize,
+    pub name: Option<String>,
+    pub event_count: usize,
+    pub volume: f32,
+    pub muted: bool,
+}
+
+/// Recorded content and mix settings of a single track inside a [`LoopSnapshot`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackSnapshot {
+    pub name: Option<String>,
+    pub events: Vec<RecordedEvent>,
+    pub volume: f32,
+    pub muted: bool,
+}
+
+impl From<Vec<RecordedEvent>> for TrackSnapshot {
+    fn from(events: Vec<RecordedEvent>) -> Self {
+        Self {
+            name: None,
+            events,
+            volume: 1.0,
+            muted: false,
+        }
+    }
+}
+
+/// Copy of the recorded loop content, detached from the playback position.
+///
+/// Used to restore a loop after destructive changes (e.g. tempo undo).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LoopSnapshot {
+    pub loop_length: Duration,
+    pub tracks: Vec<TrackSnapshot>,
+}
+
+/// Where the cycle was when a [`LoopEngineSnapshot`] was taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopPhase {
+    /// Nothing recorded, or a count-in that had not finished
+    Idle,
+    /// Playing back, `position` into the cycle
+    Playing { position: Duration },
+    /// Recording a take, `position` into the cycle
+    Recording { position: Duration },
+    /// Paused at `position`; Space resumes playing or recording
+    Paused {
+        position: Duration,
+        was_recording: bool,
+    },
+}
+
+/// Full engine state for session persistence and crash recovery.
+///
+/// Unlike [`LoopSnapshot`], this keeps the position in the cycle, whether
+/// the loop was playing, paused or recording, the unfinished take and the
+/// rests, so an imported engine schedules the same hits from the same
+/// point. Clock readings are stored relative to the cycle, not as times.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoopEngineSnapshot {
+    pub content: LoopSnapshot,
+    pub phase: LoopPhase,
+    /// Hits of a take still being recorded
+    pub take: Vec<RecordedEvent>,
+    /// Beat length of the tempo the loop was recorded at
+    pub beat: Duration,
+    pub cycle_count: u64,
+    pub rest_regions: Vec<RestRegion>,
+    pub half_length: bool,
+}
+
+/// A span of the loop cycle marked as a rest.
+///
+/// Playback skips events whose offset falls inside `start..end`; the recorded
+/// events themselves are kept, so removing the rest brings them back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestRegion {
+    pub start: Duration,
+    pub end: Duration,
+}
+
+impl RestRegion {
+    /// Whether an offset within the cycle falls inside the rest.
+    pub fn contains(&self, offset: Duration) -> bool {
+        self.start <= offset && offset < self.end
+    }
+}
+
+/// Call-and-response playback: the loop sounds for `on` cycles, then stays
+/// silent for `off` cycles so the player can fill the gap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PracticeCycles {
+    pub on: u32,
+    pub off: u32,
+}
+
+impl PracticeCycles {
+    /// Whether playback is suppressed on a cycle (0 = first after recording).
+    pub fn is_silent(&self, cycle: u64) -> bool {
+        let period = u64::from(self.on) + u64::from(self.off);
+        period > 0 && cycle % period >= u64::from(self.on)
+    }
+}
+
+/// Random timing offsets applied to scheduled playback.
+///
+/// Each event fires up to `amount` early or late. The jitter is derived from
+/// the seed, the cycle number and the event position, so it changes every
+/// cycle but is reproducible for a given seed. Stored offsets are untouched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Humanize {
+    pub amount: Duration,
+    pub seed: u64,
+}
+
+impl Humanize {
+    /// Signed jitter in nanoseconds for one event firing.
+    fn jitter_nanos(&self, cycle: u64, track: usize, event: usize) -> i128 {
+        let span = self.amount.as_nanos() as u64;
+        if span == 0 {
+            return 0;
+        }
+        let mut x = self.seed
+            ^ cycle.wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            ^ (track as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F)
+            ^ (event as u64).wrapping_mul(0x1656_67B1_9E37_79F9);
+        // splitmix64 finalizer
+        x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        x ^= x >> 31;
+        (x % (2 * span + 1)) as i128 - span as i128
+    }
+
+    /// Firing time for an event, kept inside the cycle.
+    fn due(
+        &self,
+        offset: Duration,
+        loop_length: Duration,
+        cycle: u64,
+        track: usize,
+        event: usize,
+    ) -> Duration {
+        let last = loop_length
+            .saturating_sub(Duration::from_nanos(1))
+            .as_nanos() as i128;
+        let due =
+            (offset.as_nanos() as i128 + self.jitter_nanos(cycle, track, event)).clamp(0, last);
+        Duration::from_nanos(due as u64)
+    }
 }
 
 #[derive(Clone)]
@@ -71,6 +237,42 @@ pub struct LoopEngine<A: AudioBus, C: Clock> {
     metronome_queue: VecDeque<Duration>,
     overdub_buffer: Vec<RecordedEvent>,
     paused: bool,
+    pad_labels: BTreeMap<char, String>,
+    humanize: Option<Humanize>,
+    practice: Option<PracticeCycles>,
+    quantize: Option<QuantizeGrid>,
+    /// Metronome ticks before recording starts
+    count_in_beats: u8,
+    /// Beats per bar and beat note value for new loops and their count-in
+    time_signature: TimeSignature,
+    /// Pads whose recorded hits are skipped during playback
+    muted_keys: HashSet<char>,
+    /// Pads that alone are played back while any are soloed
+    soloed_keys: HashSet<char>,
+    /// Whether a first take starts playing once committed; otherwise it
+    /// waits, paused at the loop start, for the next Space
+    auto_play_after_record: bool,
+    /// Whether an overdub still being played at the loop end lengthens the
+    /// loop to the next bar instead of wrapping
+    extend_overdubs: bool,
+    /// Whether hits in the last count-in beat are kept for the take
+    buffer_count_in: bool,
+    /// Hits kept from the last count-in beat, recorded at the loop start
+    /// once recording begins
+    count_in_hits: Vec<RecordedEvent>,
+    /// Playback swing in percent; 0 is straight
+    swing: u8,
+    /// Whether the metronome ticks on every beat while recording and
+    /// playing, not just during the count-in
+    metronome_enabled: bool,
+    /// Beat of the current cycle the metronome last ticked on
+    clicked_beat: Option<u64>,
+    /// Beat length of the loop, set when recording starts or from the bpm
+    /// passed to `restore`
+    beat: Duration,
+    half_length: bool,
+    cycle_count: u64,
+    rest_regions: Vec<RestRegion>,
 }
 
 impl<A: AudioBus, C: Clock> std::fmt::Debug for LoopEngine<A, C> {
@@ -93,6 +295,645 @@ impl<A: AudioBus, C: Clock> LoopEngine<A, C> {
         self.clock.now()
     }
 
+    /// Length of the current loop cycle, or zero when idle.
+    pub fn loop_length(&self) -> Duration {
+        match self.state {
+            LoopState::Idle => Duration::ZERO,
+            LoopState::Ready { loop_length, .. }
+            | LoopState::Recording { loop_length, .. }
+            | LoopState::Playing { loop_length, .. }
+            | LoopState::Paused { loop_length, .. } => loop_length,
+        }
+    }
+
+    /// Time left until the current cycle wraps while recording or playing,
+    /// or `None` when no cycle is running. A take past its end reports zero
+    /// until `update` commits it; playback at half length wraps at the half.
+    pub fn time_remaining(&self) -> Option<Duration> {
+        let now = self.clock.now();
+        match self.state {
+            LoopState::Recording {
+                start_time,
+                loop_length,
+            } => Some(loop_length.saturating_sub(now.saturating_sub(start_time))),
+            LoopState::Playing {
+                cycle_start,
+                loop_length,
+            } => {
+                let cycle_length = self.cycle_length(loop_length);
+                if cycle_length.is_zero() {
+                    return None;
+                }
+                let offset = normalize_offset(now.saturating_sub(cycle_start), cycle_length);
+                Some(cycle_length - offset)
+            }
+            LoopState::Idle | LoopState::Ready { .. } | LoopState::Paused { .. } => None,
+        }
+    }
+
+    /// Summaries of the committed tracks, in playback order.
+    pub fn tracks_snapshot(&self) -> Vec<TrackInfo> {
+        self.tracks
+            .iter()
+            .enumerate()
+            .map(|(index, track)| TrackInfo {
+                index,
+                name: track.name.clone(),
+                event_count: track.events.len(),
+                volume: track.volume,
+                muted: track.muted,
+            })
+            .collect()
+    }
+
+    /// Labels used to name new tracks after the pad of their first event.
+    pub fn set_pad_labels(&mut self, labels: BTreeMap<char, String>) {
+        self.pad_labels = labels;
+    }
+
+    /// Enable or disable call-and-response practice cycles.
+    pub fn set_practice(&mut self, practice: Option<PracticeCycles>) {
+        self.practice = practice;
+    }
+
+    /// Snap newly recorded hits to a grid when their take is committed;
+    /// `None` keeps them as played.
+    pub fn set_quantize(&mut self, quantize: Option<QuantizeGrid>) {
+        self.quantize = quantize;
+    }
+
+    /// Set how many metronome ticks precede recording (clamped to
+    /// `COUNT_IN_MIN..=COUNT_IN_MAX`). Takes effect on the next count-in.
+    pub fn set_count_in_beats(&mut self, beats: u8) {
+        self.count_in_beats = clamp_count_in(beats);
+    }
+
+    /// Metronome ticks before recording starts.
+    pub fn count_in_beats(&self) -> u8 {
+        self.count_in_beats
+    }
+
+    /// Set which pads loop playback skips: every muted pad, and every pad
+    /// outside `soloed` while it is not empty. Live hits are unaffected.
+    pub fn set_key_mutes(&mut self, muted: HashSet<char>, soloed: HashSet<char>) {
+        self.muted_keys = muted;
+        self.soloed_keys = soloed;
+    }
+
+    /// Whether loop playback sounds a pad's recorded hits.
+    pub fn is_key_audible(&self, key: char) -> bool {
+        key_audible(&self.muted_keys, &self.soloed_keys, key)
+    }
+
+    /// Whether loop playback sounds a hit of `key` recorded at `offset`:
+    /// the pad is audible and the hit falls outside every rest region.
+    pub fn is_hit_audible(&self, key: char, offset: Duration) -> bool {
+        let offset = normalize_offset(offset, self.loop_length());
+        self.is_key_audible(key) && !self.rest_regions.iter().any(|rest| rest.contains(offset))
+    }
+
+    /// Choose whether committing the first take starts playback (the
+    /// default) or leaves the loop paused at its start until Space.
+    /// Overdubs always keep playing.
+    pub fn set_auto_play_after_record(&mut self, auto_play: bool) {
+        self.auto_play_after_record = auto_play;
+    }
+
+    /// Let overdubs lengthen the loop: when the last overdub hit falls in
+    /// the final beat as the cycle ends, recording carries on to the next
+    /// bar boundary. Existing tracks keep their offsets and rest in the
+    /// added bars. Restored loops measure the final beat at the bpm passed
+    /// to `restore`.
+    pub fn set_extend_overdubs(&mut self, extend: bool) {
+        self.extend_overdubs = extend;
+    }
+
+    /// Keep pad hits from the last count-in beat: instead of only sounding,
+    /// they land at the start of the take once recording begins, so a hit
+    /// played slightly early still counts. Off by default.
+    pub fn set_buffer_count_in(&mut self, buffer: bool) {
+        self.buffer_count_in = buffer;
+    }
+
+    /// Whether overdubs may lengthen the loop.
+    pub fn extends_overdubs(&self) -> bool {
+        self.extend_overdubs
+    }
+
+    /// Swing playback by a percentage (0 = straight, 50 = triplet feel,
+    /// clamped to 100): every other eighth note sounds late. Recorded
+    /// offsets are untouched, so setting 0 restores straight timing.
+    /// Restored loops swing on the beat of the bpm passed to `restore`.
+    pub fn set_swing(&mut self, percent: u8) {
+        self.swing = percent.min(100);
+    }
+
+    /// Current swing percentage.
+    pub fn swing(&self) -> u8 {
+        self.swing
+    }
+
+    /// Keep the metronome ticking on each beat while recording and playing
+    /// (overdubs included), accenting bar downbeats. Restored loops tick
+    /// on the beat of the bpm passed to `restore`.
+    pub fn set_metronome_enabled(&mut self, enabled: bool) {
+        self.metronome_enabled = enabled;
+    }
+
+    /// Whether the metronome ticks while recording and playing.
+    pub fn metronome_enabled(&self) -> bool {
+        self.metronome_enabled
+    }
+
+    /// Set the time signature new loops are counted in and measured by.
+    /// Takes effect on the next count-in.
+    pub fn set_time_signature(&mut self, signature: TimeSignature) {
+        self.time_signature = signature;
+    }
+
+    /// Time signature new loops are counted in and measured by.
+    pub fn time_signature(&self) -> TimeSignature {
+        self.time_signature
+    }
+
+    /// Toggle practicing the first half of the loop: playback wraps at half
+    /// the loop length while on. Recorded events are left untouched. Returns
+    /// whether it is now on.
+    pub fn toggle_half_length(&mut self) -> bool {
+        self.half_length = !self.half_length;
+        self.half_length
+    }
+
+    /// Whether playback currently wraps at half the loop length.
+    pub fn is_half_length(&self) -> bool {
+        self.half_length
+    }
+
+    /// Length of one playback cycle: the loop, or its first half while
+    /// practicing at half length.
+    fn cycle_length(&self, loop_length: Duration) -> Duration {
+        if self.half_length {
+            loop_length / 2
+        } else {
+            loop_length
+        }
+    }
+
+    /// Enable or disable humanized playback timing.
+    pub fn set_humanize(&mut self, humanize: Option<Humanize>) {
+        self.humanize = humanize;
+    }
+
+    /// Rename a track; `None` clears the name. Returns false if the index is out of range.
+    pub fn set_track_name(&mut self, index: usize, name: Option<String>) -> bool {
+        match self.tracks.get_mut(index) {
+            Some(track) => {
+                track.name = name;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Mute or unmute a track. Returns false if the index is out of range.
+    pub fn set_track_muted(&mut self, index: usize, muted: bool) -> bool {
+        match self.tracks.get_mut(index) {
+            Some(track) => {
+                track.muted = muted;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set a track's playback volume (0.0..=1.0). Returns false if the index is out of range.
+    pub fn set_track_volume(&mut self, index: usize, volume: f32) -> bool {
+        match self.tracks.get_mut(index) {
+            Some(track) => {
+                track.volume = volume.clamp(0.0, 1.0);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a track. Returns false if the index is out of range.
+    pub fn remove_track(&mut self, index: usize) -> bool {
+        if index >= self.tracks.len() {
+            return false;
+        }
+        self.tracks.remove(index);
+        true
+    }
+
+    /// Drop the most recently committed track, leaving the loop state as it
+    /// is. Returns false if there were no tracks.
+    pub fn undo_last_track(&mut self) -> bool {
+        self.tracks.pop().is_some()
+    }
+
+    /// Insert a copy of a track right after it. Returns false if the index is out of range.
+    pub fn duplicate_track(&mut self, index: usize) -> bool {
+        let Some(track) = self.tracks.get(index).cloned() else {
+            return false;
+        };
+        self.tracks.insert(index + 1, track);
+        true
+    }
+
+    /// Move a track to a new position. Returns false if either index is out of range.
+    pub fn move_track(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.tracks.len() || to >= self.tracks.len() {
+            return false;
+        }
+        let track = self.tracks.remove(from);
+        self.tracks.insert(to, track);
+        true
+    }
+
+    /// Move recorded hits, including a take still being recorded, from each
+    /// pad key in `keys` to the key it maps to. All keys move at once, so
+    /// swapping two pads works. Returns how many hits were moved.
+    pub fn rekey_events(&mut self, keys: &BTreeMap<char, char>) -> usize {
+        let mut moved = 0;
+        let tracks = self
+            .tracks
+            .iter_mut()
+            .flat_map(|track| track.events.iter_mut());
+        for event in tracks.chain(self.overdub_buffer.iter_mut()) {
+            if let Some(&to) = keys.get(&event.key) {
+                event.key = to;
+                moved += 1;
+            }
+        }
+        moved
+    }
+
+    /// Stretch or shrink the loop by `factor` (2.0 doubles, 0.5 halves).
+    ///
+    /// Growing repeats every committed track's hits (and rest regions) in
+    /// each added stretch, so the loop sounds the same with room for
+    /// sparser overdubs. Shrinking drops hits past the new end, including
+    /// ones in a take still being recorded. The playhead keeps its place,
+    /// wrapped into the new length. Returns false when there is no loop or
+    /// the new length would be zero.
+    pub fn scale_loop_length(&mut self, factor: f32) -> bool {
+        let old_length = self.loop_length();
+        if old_length.is_zero() || !factor.is_finite() || factor <= 0.0 {
+            return false;
+        }
+        let new_length = old_length.mul_f32(factor);
+        if new_length.is_zero() {
+            return false;
+        }
+
+        let copies = factor.ceil() as u32;
+        for track in &mut self.tracks {
+            let originals = std::mem::take(&mut track.events);
+            track.events = (0..copies)
+                .flat_map(|copy| {
+                    originals.iter().map(move |event| RecordedEvent {
+                        offset: event.offset + old_length * copy,
+                        ..*event
+                    })
+                })
+                .filter(|event| event.offset < new_length)
+                .collect();
+            track.events.sort_by_key(|event| event.offset);
+        }
+        self.tracks.retain(|track| !track.events.is_empty());
+        self.overdub_buffer
+            .retain(|event| event.offset < new_length);
+        let rests = std::mem::take(&mut self.rest_regions);
+        self.rest_regions = (0..copies)
+            .flat_map(|copy| {
+                rests.iter().map(move |rest| RestRegion {
+                    start: rest.start + old_length * copy,
+                    end: (rest.end + old_length * copy).min(new_length),
+                })
+            })
+            .filter(|rest| rest.start < new_length)
+            .collect();
+
+        let now = self.clock.now();
+        match &mut self.state {
+            LoopState::Idle => {}
+            LoopState::Ready { loop_length, .. } | LoopState::Recording { loop_length, .. } => {
+                *loop_length = new_length;
+            }
+            LoopState::Playing {
+                cycle_start,
+                loop_length,
+            } => {
+                let offset = normalize_offset(now.saturating_sub(*cycle_start), new_length);
+                *cycle_start = now - offset;
+                *loop_length = new_length;
+                self.realign_track_positions(offset, new_length);
+            }
+            LoopState::Paused {
+                loop_length,
+                saved_offset,
+                ..
+            } => {
+                *saved_offset = normalize_offset(*saved_offset, new_length);
+                *loop_length = new_length;
+                let offset = *saved_offset;
+                self.realign_track_positions(offset, new_length);
+            }
+        }
+        if self.tracks.is_empty() && matches!(self.state, LoopState::Playing { .. }) {
+            self.state = LoopState::Idle;
+        }
+        true
+    }
+
+    /// Rest regions, ordered by start.
+    pub fn rest_regions(&self) -> &[RestRegion] {
+        &self.rest_regions
+    }
+
+    /// Mark `start..end` of the cycle as a rest. Returns false for an empty
+    /// span or one overlapping an existing rest.
+    pub fn add_rest_region(&mut self, start: Duration, end: Duration) -> bool {
+        if start >= end
+            || self
+                .rest_regions
+                .iter()
+                .any(|rest| start < rest.end && rest.start < end)
+        {
+            return false;
+        }
+        self.rest_regions.push(RestRegion { start, end });
+        self.rest_regions.sort_by_key(|rest| rest.start);
+        true
+    }
+
+    /// Remove the rest containing `offset`. Returns false if there is none.
+    pub fn remove_rest_region_at(&mut self, offset: Duration) -> bool {
+        let before = self.rest_regions.len();
+        self.rest_regions.retain(|rest| !rest.contains(offset));
+        self.rest_regions.len() != before
+    }
+
+    /// Remove every rest region.
+    pub fn clear_rest_regions(&mut self) {
+        self.rest_regions.clear();
+    }
+
+    /// Toggle a rest over the bar under the playhead of a loop `beats` beats
+    /// long. Bars hold one time signature's worth of beats, so a trailing
+    /// partial bar is shorter. Returns whether the bar is now a rest, or
+    /// `None` when the loop is not playing or paused.
+    pub fn toggle_rest_bar(&mut self, beats: u32) -> Option<bool> {
+        let (playhead, loop_length) = self.playhead()?;
+        if self.remove_rest_region_at(playhead) {
+            return Some(false);
+        }
+        let bar = loop_length * u32::from(self.time_signature.numerator) / beats.max(1);
+        if bar.is_zero() {
+            return None;
+        }
+        let index = (playhead.as_nanos() / bar.as_nanos()) as u32;
+        let start = bar * index;
+        Some(self.add_rest_region(start, (start + bar).min(loop_length)))
+    }
+
+    /// Position of the playhead within the cycle while playing or paused.
+    fn playhead(&self) -> Option<(Duration, Duration)> {
+        match self.state {
+            LoopState::Playing {
+                cycle_start,
+                loop_length,
+            } => {
+                let elapsed = self.clock.now().saturating_sub(cycle_start);
+                let offset = normalize_offset(elapsed, self.cycle_length(loop_length));
+                Some((offset, loop_length))
+            }
+            LoopState::Paused {
+                saved_offset,
+                loop_length,
+                ..
+            } => Some((saved_offset, loop_length)),
+            _ => None,
+        }
+    }
+
+    /// Remove the recorded event closest to the playhead, if it is within
+    /// `tolerance` (measured around the loop boundary). A track left without
+    /// events is dropped. Returns false when nothing was close enough.
+    pub fn delete_nearest_event(&mut self, tolerance: Duration) -> bool {
+        let Some((playhead, loop_length)) = self.playhead() else {
+            return false;
+        };
+        let distance = |offset: Duration| {
+            let offset = normalize_offset(offset, loop_length);
+            let direct = offset.abs_diff(playhead);
+            direct.min(loop_length.saturating_sub(direct))
+        };
+        let nearest = self
+            .tracks
+            .iter()
+            .enumerate()
+            .flat_map(|(t, track)| {
+                track
+                    .events
+                    .iter()
+                    .enumerate()
+                    .map(move |(e, event)| (t, e, distance(event.offset)))
+            })
+            .min_by_key(|(_, _, d)| *d);
+        let Some((track_index, event_index, d)) = nearest else {
+            return false;
+        };
+        if d > tolerance {
+            return false;
+        }
+        let track = &mut self.tracks[track_index];
+        track.events.remove(event_index);
+        if event_index < track.next_event_index {
+            track.next_event_index -= 1;
+        }
+        if track.events.is_empty() {
+            self.tracks.remove(track_index);
+        }
+        true
+    }
+
+    /// Capture the committed tracks so they can be restored later.
+    pub fn snapshot(&self) -> LoopSnapshot {
+        LoopSnapshot {
+            loop_length: self.loop_length(),
+            tracks: self
+                .tracks
+                .iter()
+                .map(|track| TrackSnapshot {
+                    name: track.name.clone(),
+                    events: track.events.clone(),
+                    volume: track.volume,
+                    muted: track.muted,
+                })
+                .collect(),
+        }
+    }
+
+    /// Replace the loop content with a snapshot.
+    ///
+    /// A snapshot with tracks starts playing from the top of the cycle;
+    /// an empty one leaves the engine idle. Quantize, swing, overdub growth
+    /// and the click follow the beat of `bpm`.
+    pub fn restore(&mut self, snapshot: LoopSnapshot, bpm: u16) {
+        self.metronome_queue.clear();
+        self.overdub_buffer.clear();
+        self.paused = false;
+        self.cycle_count = 0;
+        self.beat = self.time_signature.beat_interval(bpm);
+        self.load_tracks(snapshot.tracks);
+        self.state = if self.tracks.is_empty() || snapshot.loop_length.is_zero() {
+            self.tracks.clear();
+            LoopState::Idle
+        } else {
+            LoopState::Playing {
+                cycle_start: self.clock.now(),
+                loop_length: snapshot.loop_length,
+            }
+        };
+    }
+
+    /// Capture everything needed to pick the loop up where it is now.
+    pub fn export_state(&self) -> LoopEngineSnapshot {
+        let now = self.clock.now();
+        let position = |start: Duration, loop_length: Duration| {
+            normalize_offset(now.saturating_sub(start), loop_length)
+        };
+        let phase = match self.state {
+            LoopState::Idle | LoopState::Ready { .. } => LoopPhase::Idle,
+            LoopState::Playing {
+                cycle_start,
+                loop_length,
+            } => LoopPhase::Playing {
+                position: position(cycle_start, loop_length),
+            },
+            LoopState::Recording {
+                start_time,
+                loop_length,
+            } => LoopPhase::Recording {
+                position: position(start_time, loop_length),
+            },
+            LoopState::Paused {
+                saved_offset,
+                was_recording,
+                ..
+            } => LoopPhase::Paused {
+                position: saved_offset,
+                was_recording,
+            },
+        };
+        // A count-in drops the tracks once it ends, so there is nothing to keep
+        let content = if phase == LoopPhase::Idle {
+            LoopSnapshot::default()
+        } else {
+            self.snapshot()
+        };
+        let recording = matches!(
+            phase,
+            LoopPhase::Recording { .. }
+                | LoopPhase::Paused {
+                    was_recording: true,
+                    ..
+                }
+        );
+        LoopEngineSnapshot {
+            content,
+            phase,
+            take: if recording {
+                self.overdub_buffer.clone()
+            } else {
+                Vec::new()
+            },
+            beat: self.beat,
+            cycle_count: self.cycle_count,
+            rest_regions: self.rest_regions.clone(),
+            half_length: self.half_length,
+        }
+    }
+
+    /// Replace the engine state with an exported one, placing the cycle so
+    /// that `now` falls at the exported position.
+    ///
+    /// Settings such as swing, quantize or key mutes are left as they are.
+    pub fn import_state(&mut self, snapshot: LoopEngineSnapshot, now: Duration) {
+        let loop_length = snapshot.content.loop_length;
+        self.metronome_queue.clear();
+        self.overdub_buffer = snapshot.take;
+        self.overdub_buffer.sort_by_key(|event| event.offset);
+        self.beat = snapshot.beat;
+        self.cycle_count = snapshot.cycle_count;
+        self.rest_regions = snapshot.rest_regions;
+        self.half_length = snapshot.half_length;
+        self.load_tracks(snapshot.content.tracks);
+        self.paused = false;
+        let phase = match snapshot.phase {
+            // Only a take in progress runs without committed tracks
+            LoopPhase::Playing { .. }
+            | LoopPhase::Paused {
+                was_recording: false,
+                ..
+            } if self.tracks.is_empty() => LoopPhase::Idle,
+            _ if loop_length.is_zero() => LoopPhase::Idle,
+            phase => phase,
+        };
+        self.state = match phase {
+            LoopPhase::Idle => {
+                self.tracks.clear();
+                self.overdub_buffer.clear();
+                LoopState::Idle
+            }
+            LoopPhase::Playing { position } => {
+                self.realign_track_positions(position, loop_length);
+                LoopState::Playing {
+                    cycle_start: now.saturating_sub(position),
+                    loop_length,
+                }
+            }
+            LoopPhase::Recording { position } => {
+                self.realign_track_positions(position, loop_length);
+                LoopState::Recording {
+                    start_time: now.saturating_sub(position),
+                    loop_length,
+                }
+            }
+            LoopPhase::Paused {
+                position,
+                was_recording,
+            } => {
+                self.paused = true;
+                self.realign_track_positions(position, loop_length);
+                LoopState::Paused {
+                    cycle_start: now.saturating_sub(position),
+                    loop_length,
+                    saved_offset: position,
+                    was_recording,
+                }
+            }
+        };
+    }
+
+    /// Replace the committed tracks, dropping empty ones.
+    fn load_tracks(&mut self, tracks: Vec<TrackSnapshot>) {
+        self.tracks = tracks
+            .into_iter()
+            .filter(|track| !track.events.is_empty())
+            .map(|mut snapshot| {
+                snapshot.events.sort_by_key(|event| event.offset);
+                let mut track = LoopTrack::new(snapshot.events);
+                track.name = snapshot.name;
+                track.volume = snapshot.volume.clamp(0.0, 1.0);
+                track.muted = snapshot.muted;
+                track
+            })
+            .collect();
+    }
+
     fn realign_track_positions(&mut self, saved_offset: Duration, loop_length: Duration) {
         for track in &mut self.tracks {
             let idx = track
@@ -105,18 +946,47 @@ impl<A: AudioBus, C: Clock> LoopEngine<A, C> {
     }
 
     fn commit_recording(&mut self, loop_length: Duration, now: Duration) {
-        let events = std::mem::take(&mut self.overdub_buffer);
+        // Overdubs land on a loop that is already sounding and keep playing
+        let first_take = self.tracks.is_empty();
+        let mut events = std::mem::take(&mut self.overdub_buffer);
+        if let Some(grid) = self.quantize {
+            let step = grid.step(self.beat);
+            for event in &mut events {
+                // A hit snapped onto the loop end belongs to the next downbeat
+                event.offset = normalize_offset(quantize_offset(event.offset, step), loop_length);
+            }
+            events.sort_by_key(|event| event.offset);
+        }
         if !events.is_empty() {
-            self.tracks.push(LoopTrack::new(events));
+            let name = events
+                .first()
+                .and_then(|event| self.pad_labels.get(&event.key))
+                .cloned();
+            let mut track = LoopTrack::new(events);
+            track.name = name;
+            self.tracks.push(track);
         }
         for track in &mut self.tracks {
             track.reset();
         }
-        self.paused = false;
-        self.state = LoopState::Playing {
-            cycle_start: now,
-            loop_length,
-        };
+        self.cycle_count = 0;
+        self.clicked_beat = None;
+        if self.auto_play_after_record || !first_take {
+            self.paused = false;
+            self.state = LoopState::Playing {
+                cycle_start: now,
+                loop_length,
+            };
+        } else {
+            // Armed at the loop start; Space resumes it like any pause
+            self.paused = true;
+            self.state = LoopState::Paused {
+                cycle_start: now,
+                loop_length,
+                saved_offset: Duration::ZERO,
+                was_recording: false,
+            };
+        }
     }
     pub fn new(clock: C, audio: A) -> Self {
         Self {
@@ -127,6 +997,25 @@ impl<A: AudioBus, C: Clock> LoopEngine<A, C> {
             metronome_queue: VecDeque::new(),
             overdub_buffer: Vec::new(),
             paused: false,
+            pad_labels: BTreeMap::new(),
+            humanize: None,
+            practice: None,
+            quantize: None,
+            count_in_beats: DEFAULT_COUNT_IN_BEATS,
+            time_signature: TimeSignature::default(),
+            muted_keys: HashSet::new(),
+            soloed_keys: HashSet::new(),
+            auto_play_after_record: true,
+            extend_overdubs: false,
+            buffer_count_in: false,
+            count_in_hits: Vec::new(),
+            swing: 0,
+            metronome_enabled: false,
+            clicked_beat: None,
+            beat: Duration::ZERO,
+            half_length: false,
+            cycle_count: 0,
+            rest_regions: Vec::new(),
         }
     }
 
@@ -134,7 +1023,16 @@ impl<A: AudioBus, C: Clock> LoopEngine<A, C> {
         self.state
     }
 
+    /// Start, pause or resume the loop. A new loop spans `bars` bars of the
+    /// engine's time signature.
     pub fn handle_space(&mut self, bpm: u16, bars: u16) {
+        let beats = u32::from(bars) * u32::from(self.time_signature.numerator);
+        self.handle_space_beats(bpm, beats);
+    }
+
+    /// Like [`handle_space`](Self::handle_space), with a new loop's length
+    /// given in beats of the time signature so it need not fill whole bars.
+    pub fn handle_space_beats(&mut self, bpm: u16, beats: u32) {
         match self.state {
             LoopState::Idle => {}
             LoopState::Playing {
@@ -196,32 +1094,61 @@ impl<A: AudioBus, C: Clock> LoopEngine<A, C> {
                 self.audio.pause_all();
                 return;
             }
-            _ => return,
+            LoopState::Ready { loop_length, .. } => {
+                // Space during the count-in skips the remaining ticks
+                self.metronome_queue.clear();
+                self.tracks.clear();
+                self.overdub_buffer = std::mem::take(&mut self.count_in_hits);
+                self.paused = false;
+                self.state = LoopState::Recording {
+                    start_time: self.clock.now(),
+                    loop_length,
+                };
+                return;
+            }
         }
-        let loop_length = loop_length_from(bpm, bars);
-        let interval = beat_interval_ms(bpm);
+        let loop_length = self.time_signature.beats_length(bpm, beats);
+        let interval = self.time_signature.beat_interval(bpm);
+        self.beat = interval;
         let now = self.clock.now();
         self.metronome_queue.clear();
+        self.count_in_hits.clear();
         let mut next_tick = now + interval;
-        for _ in 0..4 {
+        for _ in 0..self.count_in_beats {
             self.metronome_queue.push_back(next_tick);
             next_tick += interval;
         }
         self.state = LoopState::Ready {
-            ticks_remaining: 4,
+            ticks_remaining: self.count_in_beats,
             loop_length,
         };
-        self.audio.play_metronome_beep();
+        // The count-in starts on the bar's downbeat
+        self.audio.play_metronome_tick(true);
         self.update();
     }
 
     pub fn record_event(&mut self, key: char) {
+        self.record_hit(key, false);
+    }
+
+    /// Record a pad hit played forwards or backwards; playback repeats the
+    /// same direction.
+    pub fn record_hit(&mut self, key: char, reversed: bool) {
         match self.state {
-            LoopState::Recording { start_time, .. } => {
+            LoopState::Recording {
+                start_time,
+                loop_length,
+            } => {
                 let now = self.clock.now();
-                let offset = now.saturating_sub(start_time);
-                self.audio.play_pad(key);
-                self.overdub_buffer.push(RecordedEvent { key, offset });
+                // A hit landing after the cycle end (before `update` commits)
+                // wraps to the top instead of scheduling past the loop
+                let offset = normalize_offset(now.saturating_sub(start_time), loop_length);
+                self.play_hit(key, reversed);
+                self.overdub_buffer.push(RecordedEvent {
+                    key,
+                    offset,
+                    reversed,
+                });
                 self.overdub_buffer.sort_by_key(|event| event.offset);
             }
             LoopState::Playing {
@@ -243,19 +1170,44 @@ impl<A: AudioBus, C: Clock> LoopEngine<A, C> {
                         Duration::from_nanos(remainder as u64)
                     }
                 };
-                self.audio.play_pad(key);
+                self.play_hit(key, reversed);
                 self.state = LoopState::Recording {
                     start_time: cycle_start,
                     loop_length,
                 };
                 self.paused = false;
                 self.overdub_buffer.clear();
-                self.overdub_buffer.push(RecordedEvent { key, offset });
+                self.overdub_buffer.push(RecordedEvent {
+                    key,
+                    offset,
+                    reversed,
+                });
+            }
+            LoopState::Ready {
+                ticks_remaining: 1, ..
+            } if self.buffer_count_in
+                && !self.count_in_hits.iter().any(|event| event.key == key) =>
+            {
+                // Sounded by the caller like any count-in hit; each key is
+                // kept once
+                self.count_in_hits.push(RecordedEvent {
+                    key,
+                    offset: Duration::ZERO,
+                    reversed,
+                });
             }
             _ => {}
         }
     }
 
+    fn play_hit(&self, key: char, reversed: bool) {
+        if reversed {
+            self.audio.play_pad_reversed(key);
+        } else {
+            self.audio.play_pad(key);
+        }
+    }
+
     pub fn handle_cancel(&mut self) {
         match self.state {
             LoopState::Ready { .. }
@@ -275,6 +1227,7 @@ impl<A: AudioBus, C: Clock> LoopEngine<A, C> {
     pub fn handle_control_space(&mut self) {
         self.metronome_queue.clear();
         self.tracks.clear();
+        self.rest_regions.clear();
         self.overdub_buffer.clear();
         self.paused = false;
         self.state = LoopState::Idle;
@@ -285,6 +1238,7 @@ impl<A: AudioBus, C: Clock> LoopEngine<A, C> {
         self.metronome_queue.clear();
         self.tracks.clear();
         self.overdub_buffer.clear();
+        self.rest_regions.clear();
         self.paused = false;
     }
 
@@ -303,7 +1257,7 @@ impl<A: AudioBus, C: Clock> LoopEngine<A, C> {
                     *ticks_remaining -= 1;
                     if *ticks_remaining == 0 {
                         self.tracks.clear();
-                        self.overdub_buffer.clear();
+                        self.overdub_buffer = std::mem::take(&mut self.count_in_hits);
                         self.paused = false;
                         self.state = LoopState::Recording {
                             start_time: now,
@@ -311,17 +1265,33 @@ impl<A: AudioBus, C: Clock> LoopEngine<A, C> {
                         };
                         break;
                     } else {
-                        self.audio.play_metronome_beep();
+                        // Accent each bar's first beat within the count-in
+                        let tick = self.count_in_beats.saturating_sub(*ticks_remaining);
+                        let downbeat =
+                            u16::from(tick).is_multiple_of(self.time_signature.numerator);
+                        self.audio.play_metronome_tick(downbeat);
                     }
                 }
             }
             LoopState::Recording {
                 start_time,
-                loop_length,
+                ref mut loop_length,
             } => {
                 let elapsed = now.saturating_sub(start_time);
-                if elapsed >= loop_length {
-                    self.commit_recording(loop_length, now);
+                if elapsed >= *loop_length {
+                    match overdub_extension(
+                        self.extend_overdubs && !self.tracks.is_empty(),
+                        &self.overdub_buffer,
+                        self.beat,
+                        self.time_signature,
+                        *loop_length,
+                    ) {
+                        Some(extended) => *loop_length = extended,
+                        None => {
+                            let loop_length = *loop_length;
+                            self.commit_recording(loop_length, now);
+                        }
+                    }
                 }
             }
             LoopState::Playing {
@@ -329,17 +1299,50 @@ impl<A: AudioBus, C: Clock> LoopEngine<A, C> {
                 loop_length,
             } => {
                 let elapsed = now.saturating_sub(*cycle_start);
+                let cycle_length = if self.half_length {
+                    loop_length / 2
+                } else {
+                    loop_length
+                };
+                let silent_cycle = self
+                    .practice
+                    .is_some_and(|practice| practice.is_silent(self.cycle_count));
                 if !self.paused {
-                    for track in &mut self.tracks {
+                    for (track_index, track) in self.tracks.iter_mut().enumerate() {
                         while track.next_event_index < track.events.len() {
                             let event = &track.events[track.next_event_index];
-                            let event_offset = if event.offset >= loop_length {
+                            let mut event_offset = if event.offset >= loop_length {
                                 normalize_offset(event.offset, loop_length)
                             } else {
                                 event.offset
                             };
+                            let resting = self
+                                .rest_regions
+                                .iter()
+                                .any(|rest| rest.contains(event_offset));
+                            event_offset = apply_swing(event_offset, self.swing, self.beat / 2);
+                            if let Some(humanize) = &self.humanize {
+                                event_offset = humanize.due(
+                                    event_offset,
+                                    loop_length,
+                                    self.cycle_count,
+                                    track_index,
+                                    track.next_event_index,
+                                );
+                            }
                             if elapsed >= event_offset {
-                                self.audio.play_scheduled(event.key);
+                                if !track.muted
+                                    && !resting
+                                    && !silent_cycle
+                                    && key_audible(&self.muted_keys, &self.soloed_keys, event.key)
+                                {
+                                    if event.reversed {
+                                        self.audio.play_scheduled_reversed(event.key, track.volume);
+                                    } else {
+                                        self.audio
+                                            .play_scheduled_with_gain(event.key, track.volume);
+                                    }
+                                }
                                 track.next_event_index += 1;
                             } else {
                                 break;
@@ -347,8 +1350,10 @@ impl<A: AudioBus, C: Clock> LoopEngine<A, C> {
                         }
                     }
                 }
-                if elapsed >= loop_length {
+                if elapsed >= cycle_length {
                     *cycle_start = now;
+                    self.cycle_count = self.cycle_count.wrapping_add(1);
+                    self.clicked_beat = None;
                     for track in &mut self.tracks {
                         track.reset();
                     }
@@ -359,5 +1364,55 @@ impl<A: AudioBus, C: Clock> LoopEngine<A, C> {
             }
             LoopState::Idle => {}
         }
+        if self.metronome_enabled {
+            self.click_beat(now);
+        }
+    }
+
+    /// Tick the metronome once when the current cycle reaches a new beat.
+    fn click_beat(&mut self, now: Duration) {
+        let start = match self.state {
+            LoopState::Recording { start_time, .. } => start_time,
+            LoopState::Playing { cycle_start, .. } => cycle_start,
+            LoopState::Paused { .. } => return,
+            LoopState::Idle | LoopState::Ready { .. } => {
+                self.clicked_beat = None;
+                return;
+            }
+        };
+        if self.beat.is_zero() {
+            return;
+        }
+        let beat = (now.saturating_sub(start).as_nanos() / self.beat.as_nanos()) as u64;
+        if self.clicked_beat != Some(beat) {
+            self.clicked_beat = Some(beat);
+            let downbeat = beat.is_multiple_of(u64::from(self.time_signature.numerator));
+            self.audio.play_metronome_tick(downbeat);
+        }
     }
 }
+
+/// Length an overdub ending now grows the loop to: the next bar boundary
+/// past `loop_length`, when `enabled`, the beat is known and the take's last
+/// hit fell in the final beat.
+fn overdub_extension(
+    enabled: bool,
+    take: &[RecordedEvent],
+    beat: Duration,
+    signature: TimeSignature,
+    loop_length: Duration,
+) -> Option<Duration> {
+    let bar = beat * u32::from(signature.numerator);
+    let last = take.iter().map(|event| event.offset).max()?;
+    if !enabled || bar.is_zero() || last + beat < loop_length {
+        return None;
+    }
+    let bars = loop_length.as_nanos() / bar.as_nanos() + 1;
+    Some(Duration::from_nanos((bars * bar.as_nanos()) as u64))
+}
+
+/// Whether a pad sounds given the muted pads and, when any are soloed, the
+/// soloed ones.
+fn key_audible(muted: &HashSet<char>, soloed: &HashSet<char>, key: char) -> bool {
+    !muted.contains(&key) && (soloed.is_empty() || soloed.contains(&key))
+}
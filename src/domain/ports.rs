@@ -12,8 +12,28 @@ pub trait Clock: Clone {
 /// Infrastructure layer provides concrete implementations (e.g., SenderAudioBus).
 pub trait AudioBus: Clone {
     fn play_metronome_beep(&self);
+    /// Play a metronome tick, accented on the downbeat. Defaults to the
+    /// plain beep.
+    fn play_metronome_tick(&self, downbeat: bool) {
+        let _ = downbeat;
+        self.play_metronome_beep();
+    }
     fn play_pad(&self, key: char);
     fn play_scheduled(&self, key: char);
+    /// Play a scheduled event at a track gain. Defaults to ignoring the gain.
+    fn play_scheduled_with_gain(&self, key: char, gain: f32) {
+        let _ = gain;
+        self.play_scheduled(key);
+    }
+    /// Play a pad hit backwards. Defaults to forward playback.
+    fn play_pad_reversed(&self, key: char) {
+        self.play_pad(key);
+    }
+    /// Play a scheduled event backwards at a track gain. Defaults to forward
+    /// playback.
+    fn play_scheduled_reversed(&self, key: char, gain: f32) {
+        self.play_scheduled_with_gain(key, gain);
+    }
     fn pause_all(&self) {}
     fn resume_all(&self) {}
 }
@@ -8,6 +8,11 @@
 //! - Bar/measure calculations
 //! - Tempo-related calculations (loop length, beat intervals)
 
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::domain::timing::{BEATS_PER_BAR, bars_to_beats, beat_interval_ms};
+
 /// Minimum valid BPM value.
 pub const BPM_MIN: u16 = 20;
 
@@ -29,3 +34,255 @@ pub fn clamp_bpm(v: u16) -> u16 {
 pub fn clamp_bars(v: u16) -> u16 {
     v.clamp(BARS_MIN, BARS_MAX)
 }
+
+/// Count-in length when none is chosen.
+pub const DEFAULT_COUNT_IN_BEATS: u8 = 4;
+
+/// Minimum valid count-in length in beats.
+pub const COUNT_IN_MIN: u8 = 1;
+
+/// Maximum valid count-in length in beats.
+pub const COUNT_IN_MAX: u8 = 16;
+
+/// Clamp count-in beats to valid range.
+pub fn clamp_count_in(v: u8) -> u8 {
+    v.clamp(COUNT_IN_MIN, COUNT_IN_MAX)
+}
+
+/// Maximum valid loop length in beats.
+pub const BEATS_MAX: u16 = BARS_MAX * BEATS_PER_BAR;
+
+/// Loop length as entered by the user: whole bars (the default) or beats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopLength {
+    Bars(u16),
+    Beats(u16),
+}
+
+impl LoopLength {
+    /// Parse a length field: "4" is bars, "6b" is beats. Values are clamped
+    /// to the valid range, and beats filling whole bars of `beats_per_bar`
+    /// become bars.
+    ///
+    /// # Example
+    /// ```
+    /// use termigroove::domain::tempo::LoopLength;
+    ///
+    /// assert_eq!(LoopLength::parse("6b", 4), Some(LoopLength::Beats(6)));
+    /// assert_eq!(LoopLength::parse("8b", 4), Some(LoopLength::Bars(2)));
+    /// assert_eq!(LoopLength::parse("8b", 3), Some(LoopLength::Beats(8)));
+    /// assert_eq!(LoopLength::parse("3", 4), Some(LoopLength::Bars(3)));
+    /// ```
+    pub fn parse(text: &str, beats_per_bar: u16) -> Option<Self> {
+        let text = text.trim();
+        match text.strip_suffix(['b', 'B']) {
+            Some(beats) => Some(Self::from_beats(beats.trim().parse().ok()?, beats_per_bar)),
+            None => Some(Self::Bars(clamp_bars(text.parse().ok()?))),
+        }
+    }
+
+    /// Length of `beats` beats, normalized to bars when they fill whole bars
+    /// of `beats_per_bar`.
+    pub fn from_beats(beats: u16, beats_per_bar: u16) -> Self {
+        let beats = beats.clamp(1, BEATS_MAX);
+        let beats_per_bar = beats_per_bar.max(1);
+        if beats.is_multiple_of(beats_per_bar) {
+            Self::Bars(clamp_bars(beats / beats_per_bar))
+        } else {
+            Self::Beats(beats)
+        }
+    }
+
+    /// Total beats in the loop when each bar has `beats_per_bar` beats.
+    pub fn beats_per(self, beats_per_bar: u16) -> u32 {
+        match self {
+            Self::Bars(bars) => u32::from(bars) * u32::from(beats_per_bar),
+            Self::Beats(beats) => u32::from(beats),
+        }
+    }
+
+    /// Bars of `beats_per_bar` touched by the loop, counting a trailing
+    /// partial bar.
+    pub fn whole_bars(self, beats_per_bar: u16) -> u16 {
+        match self {
+            Self::Bars(bars) => bars,
+            Self::Beats(beats) => beats.div_ceil(beats_per_bar.max(1)),
+        }
+    }
+
+    /// Length an overdub running past the end grows the loop to: the next
+    /// bar boundary of `beats_per_bar`, as whole bars.
+    ///
+    /// # Example
+    /// ```
+    /// use termigroove::domain::tempo::LoopLength;
+    ///
+    /// assert_eq!(LoopLength::Bars(2).next_bar_boundary(4), LoopLength::Bars(3));
+    /// // Four beats of 3/4 run into the second bar
+    /// assert_eq!(LoopLength::Beats(4).next_bar_boundary(3), LoopLength::Bars(2));
+    /// ```
+    pub fn next_bar_boundary(self, beats_per_bar: u16) -> Self {
+        match self {
+            Self::Bars(bars) => Self::Bars(clamp_bars(bars + 1)),
+            Self::Beats(_) => Self::Bars(clamp_bars(self.whole_bars(beats_per_bar))),
+        }
+    }
+
+    /// Length multiplied by `factor`, staying in bars while it fills whole
+    /// bars of `beats_per_bar`. `None` when the result is not a whole number
+    /// of beats or is out of range.
+    ///
+    /// # Example
+    /// ```
+    /// use termigroove::domain::tempo::LoopLength;
+    ///
+    /// assert_eq!(LoopLength::Bars(4).scaled(2.0, 4), Some(LoopLength::Bars(8)));
+    /// assert_eq!(LoopLength::Bars(1).scaled(0.5, 4), Some(LoopLength::Beats(2)));
+    /// // Half a bar of 3/4 is not a whole number of beats
+    /// assert_eq!(LoopLength::Bars(1).scaled(0.5, 3), None);
+    /// ```
+    pub fn scaled(self, factor: f32, beats_per_bar: u16) -> Option<Self> {
+        if let Self::Bars(bars) = self {
+            let scaled = f32::from(bars) * factor;
+            if scaled.fract() == 0.0
+                && (f32::from(BARS_MIN)..=f32::from(BARS_MAX)).contains(&scaled)
+            {
+                return Some(Self::Bars(scaled as u16));
+            }
+        }
+        let beats = self.beats_per(beats_per_bar) as f32 * factor;
+        (beats.fract() == 0.0 && (1.0..=f32::from(BEATS_MAX)).contains(&beats))
+            .then(|| Self::from_beats(beats as u16, beats_per_bar))
+    }
+}
+
+impl std::fmt::Display for LoopLength {
+    /// Field text that parses back to the same length ("4" or "6b").
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bars(bars) => write!(f, "{bars}"),
+            Self::Beats(beats) => write!(f, "{beats}b"),
+        }
+    }
+}
+
+/// Derive a BPM from a loop's duration and the number of bars it spans.
+///
+/// Assumes 4 beats per bar (matching `timing::loop_length_from`). The result
+/// is rounded to the nearest whole BPM and clamped to the valid range; a zero
+/// duration clamps to `BPM_MAX`.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use termigroove::domain::tempo::bpm_from_length;
+///
+/// // 4 bars lasting 8 seconds = 120 BPM
+/// assert_eq!(bpm_from_length(Duration::from_secs(8), 4), 120);
+/// ```
+pub fn bpm_from_length(duration: Duration, bars: u16) -> u16 {
+    bpm_from_beats(duration, bars_to_beats(clamp_bars(bars)))
+}
+
+/// Derive a BPM from a loop's duration and the number of beats it spans.
+/// Rounded and clamped like [`bpm_from_length`].
+pub fn bpm_from_beats(duration: Duration, beats: u32) -> u16 {
+    let seconds = duration.as_secs_f64();
+    if seconds <= 0.0 {
+        return BPM_MAX;
+    }
+    let bpm = (beats.max(1) as f64 * 60.0 / seconds).round();
+    clamp_bpm(bpm.min(u16::MAX as f64) as u16)
+}
+
+/// Taps kept for the tap-tempo average.
+pub const TAP_WINDOW: usize = 4;
+
+/// Gap after which a tap starts a new measurement.
+pub const TAP_RESET_GAP: Duration = Duration::from_secs(2);
+
+/// Tap-tempo meter: averages the intervals between the last few taps.
+#[derive(Debug, Clone, Default)]
+pub struct TapTempo {
+    taps: VecDeque<Duration>,
+}
+
+impl TapTempo {
+    /// Register a tap at `now` (any monotonic clock). Returns the averaged
+    /// BPM once there are two taps in a row less than `TAP_RESET_GAP` apart.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use termigroove::domain::tempo::TapTempo;
+    ///
+    /// let mut taps = TapTempo::default();
+    /// assert_eq!(taps.tap(Duration::from_millis(0)), None);
+    /// assert_eq!(taps.tap(Duration::from_millis(500)), Some(120));
+    /// ```
+    pub fn tap(&mut self, now: Duration) -> Option<u16> {
+        if self
+            .taps
+            .back()
+            .is_some_and(|last| now.saturating_sub(*last) > TAP_RESET_GAP)
+        {
+            self.taps.clear();
+        }
+        self.taps.push_back(now);
+        if self.taps.len() > TAP_WINDOW {
+            self.taps.pop_front();
+        }
+        let (first, last) = (self.taps.front()?, self.taps.back()?);
+        let intervals = self.taps.len() as u32 - 1;
+        (intervals > 0).then(|| bpm_from_beats(last.saturating_sub(*first), intervals))
+    }
+}
+
+/// A short run of metronome ticks at a tempo, to audition it before
+/// applying it.
+#[derive(Debug, Clone)]
+pub struct ClickPreview {
+    interval: Duration,
+    next: Duration,
+    played: u16,
+    beats: u16,
+}
+
+impl ClickPreview {
+    /// Preview `beats` ticks at `bpm` (clamped), the first due at `now`.
+    pub fn new(bpm: u16, beats: u16, now: Duration) -> Self {
+        Self {
+            interval: beat_interval_ms(clamp_bpm(bpm)),
+            next: now,
+            played: 0,
+            beats,
+        }
+    }
+
+    /// Ticks due by `now`, in order; `true` marks the accented first one.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use termigroove::domain::tempo::ClickPreview;
+    ///
+    /// let mut preview = ClickPreview::new(120, 4, Duration::ZERO);
+    /// assert_eq!(preview.due(Duration::ZERO), vec![true]);
+    /// assert!(preview.due(Duration::from_millis(499)).is_empty());
+    /// assert_eq!(preview.due(Duration::from_millis(1_000)), vec![false, false]);
+    /// ```
+    pub fn due(&mut self, now: Duration) -> Vec<bool> {
+        let mut ticks = Vec::new();
+        while !self.is_done() && now >= self.next {
+            ticks.push(self.played == 0);
+            self.played += 1;
+            self.next += self.interval;
+        }
+        ticks
+    }
+
+    /// Whether every tick has played.
+    pub fn is_done(&self) -> bool {
+        self.played >= self.beats
+    }
+}
@@ -11,13 +11,85 @@
 
 use std::time::Duration;
 
-/// Calculate loop length from BPM and number of bars.
+/// Beats in one bar (common time signature).
+pub const BEATS_PER_BAR: u16 = 4;
+
+/// Time signature: beats per bar over the note value of one beat. BPM
+/// always counts quarter notes, so in 6/8 each beat is half a BPM beat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeSignature {
+    pub numerator: u16,
+    pub denominator: u16,
+}
+
+impl Default for TimeSignature {
+    /// Common time, 4/4.
+    fn default() -> Self {
+        Self {
+            numerator: BEATS_PER_BAR,
+            denominator: 4,
+        }
+    }
+}
+
+impl TimeSignature {
+    /// A time signature with 1 to 16 beats of a half, quarter, eighth or
+    /// sixteenth note, or `None` when out of range.
+    pub fn new(numerator: u16, denominator: u16) -> Option<Self> {
+        ((1..=16).contains(&numerator) && matches!(denominator, 2 | 4 | 8 | 16)).then_some(Self {
+            numerator,
+            denominator,
+        })
+    }
+
+    /// Parse a signature such as "3/4" or "6/8".
+    ///
+    /// # Example
+    /// ```
+    /// use termigroove::domain::timing::TimeSignature;
+    ///
+    /// assert_eq!(TimeSignature::parse("6/8"), TimeSignature::new(6, 8));
+    /// assert_eq!(TimeSignature::parse("5/5"), None);
+    /// ```
+    pub fn parse(text: &str) -> Option<Self> {
+        let (numerator, denominator) = text.trim().split_once('/')?;
+        Self::new(
+            numerator.trim().parse().ok()?,
+            denominator.trim().parse().ok()?,
+        )
+    }
+
+    /// Length of one beat (one `denominator` note) at a quarter-note BPM.
+    pub fn beat_interval(self, bpm: u16) -> Duration {
+        Duration::from_secs_f64(60.0 / bpm as f64 * self.quarters_per_beat())
+    }
+
+    /// Length of `beats` beats at a quarter-note BPM.
+    pub fn beats_length(self, bpm: u16, beats: u32) -> Duration {
+        Duration::from_secs_f64(60.0 / bpm as f64 * self.quarters_per_beat() * beats as f64)
+    }
+
+    /// Quarter notes in one beat (0.5 in 6/8).
+    fn quarters_per_beat(self) -> f64 {
+        4.0 / f64::from(self.denominator)
+    }
+}
+
+impl std::fmt::Display for TimeSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+/// Calculate loop length from BPM, number of bars and beats per bar.
 ///
-/// Assumes 4 beats per bar (common time signature).
+/// Beats are quarter notes; see [`loop_length_from_signature`] for other
+/// note values.
 ///
 /// # Arguments
 /// * `bpm` - Beats per minute (must be > 0)
 /// * `bars` - Number of bars in the loop
+/// * `beats_per_bar` - Beats in each bar (4 in common time)
 ///
 /// # Returns
 /// The total duration of the loop
@@ -27,14 +99,62 @@ use std::time::Duration;
 /// use std::time::Duration;
 /// use termigroove::domain::timing::loop_length_from;
 ///
-/// // 120 BPM, 4 bars = 8 seconds
-/// let length = loop_length_from(120, 4);
-/// assert_eq!(length, Duration::from_secs(8));
+/// // 120 BPM, 4 bars of 4/4 = 8 seconds; of 3/4 = 6 seconds
+/// assert_eq!(loop_length_from(120, 4, 4), Duration::from_secs(8));
+/// assert_eq!(loop_length_from(120, 4, 3), Duration::from_secs(6));
 /// ```
-pub fn loop_length_from(bpm: u16, bars: u16) -> Duration {
-    let beats_per_bar = 4.0;
+pub fn loop_length_from(bpm: u16, bars: u16, beats_per_bar: u16) -> Duration {
+    loop_length_from_beats(bpm, u32::from(bars) * u32::from(beats_per_bar))
+}
+
+/// Calculate loop length from a quarter-note BPM, number of bars and a
+/// time signature given as numerator and denominator.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use termigroove::domain::timing::loop_length_from_signature;
+///
+/// // 120 BPM, 2 bars of 6/8: twelve eighth notes of 250 ms
+/// assert_eq!(loop_length_from_signature(120, 2, 6, 8), Duration::from_secs(3));
+/// ```
+pub fn loop_length_from_signature(
+    bpm: u16,
+    bars: u16,
+    numerator: u16,
+    denominator: u16,
+) -> Duration {
+    let signature = TimeSignature {
+        numerator,
+        denominator: denominator.max(1),
+    };
+    signature.beats_length(bpm, u32::from(bars) * u32::from(numerator))
+}
+
+/// Calculate loop length from BPM and a number of beats.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use termigroove::domain::timing::loop_length_from_beats;
+///
+/// // 120 BPM, 6 beats = 3 seconds
+/// assert_eq!(loop_length_from_beats(120, 6), Duration::from_secs(3));
+/// ```
+pub fn loop_length_from_beats(bpm: u16, beats: u32) -> Duration {
     let beat_seconds = 60.0 / bpm as f64;
-    Duration::from_secs_f64(beat_seconds * beats_per_bar * bars as f64)
+    Duration::from_secs_f64(beat_seconds * beats as f64)
+}
+
+/// Number of beats in `bars` bars.
+pub fn bars_to_beats(bars: u16) -> u32 {
+    u32::from(bars) * u32::from(BEATS_PER_BAR)
+}
+
+/// Bar count spanned by `beats` beats; fractional when the beats do not
+/// fill whole bars (6 beats = 1.5 bars).
+pub fn beats_to_bars(beats: u32) -> f64 {
+    beats as f64 / f64::from(BEATS_PER_BAR)
 }
 
 /// Calculate the duration of a single beat from BPM.
@@ -58,6 +178,137 @@ pub fn beat_interval_ms(bpm: u16) -> Duration {
     Duration::from_secs_f64(60.0 / bpm as f64)
 }
 
+/// Musical position of an offset as 1-based (bar, beat), e.g. (2, 3) for the
+/// third beat of the second bar.
+///
+/// Offsets past the loop end wrap around. A zero-length loop, or one with no
+/// bars or beats, reports (1, 1).
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use termigroove::domain::timing::position_bar_beat;
+///
+/// // 2 bars of 4/4 over 4 seconds: each beat lasts 500 ms
+/// let position = position_bar_beat(Duration::from_millis(3_100), Duration::from_secs(4), 2, 4);
+/// assert_eq!(position, (2, 3));
+/// ```
+pub fn position_bar_beat(
+    offset: Duration,
+    loop_length: Duration,
+    bars: u16,
+    beats_per_bar: u16,
+) -> (u16, u16) {
+    let total_beats = u128::from(bars) * u128::from(beats_per_bar);
+    if loop_length.is_zero() || total_beats == 0 {
+        return (1, 1);
+    }
+    let offset = normalize_offset(offset, loop_length);
+    let beat_index = (offset.as_nanos() * total_beats / loop_length.as_nanos()) as u16;
+    (
+        beat_index / beats_per_bar + 1,
+        beat_index % beats_per_bar + 1,
+    )
+}
+
+/// Note value recorded hits snap to when quantizing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuantizeGrid {
+    /// 1/4 notes: one grid line per beat
+    Quarter,
+    /// 1/8 notes
+    Eighth,
+    /// 1/16 notes
+    Sixteenth,
+}
+
+impl QuantizeGrid {
+    /// Parse a note value such as "1/8".
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim() {
+            "1/4" => Some(QuantizeGrid::Quarter),
+            "1/8" => Some(QuantizeGrid::Eighth),
+            "1/16" => Some(QuantizeGrid::Sixteenth),
+            _ => None,
+        }
+    }
+
+    /// Grid lines per beat.
+    pub fn divisions_per_beat(self) -> u32 {
+        match self {
+            QuantizeGrid::Quarter => 1,
+            QuantizeGrid::Eighth => 2,
+            QuantizeGrid::Sixteenth => 4,
+        }
+    }
+
+    /// Spacing between grid lines for a beat length.
+    pub fn step(self, beat: Duration) -> Duration {
+        beat / self.divisions_per_beat()
+    }
+}
+
+/// Snap an offset to the nearest multiple of `grid`; halfway rounds up.
+///
+/// A zero grid leaves the offset unchanged. The result may equal the loop
+/// length when the offset sits near the end of the cycle.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use termigroove::domain::timing::quantize_offset;
+///
+/// let grid = Duration::from_millis(250);
+/// assert_eq!(
+///     quantize_offset(Duration::from_millis(260), grid),
+///     Duration::from_millis(250)
+/// );
+/// ```
+pub fn quantize_offset(offset: Duration, grid: Duration) -> Duration {
+    if grid.is_zero() {
+        return offset;
+    }
+    let grid_nanos = grid.as_nanos();
+    let lines = (offset.as_nanos() + grid_nanos / 2) / grid_nanos;
+    Duration::from_nanos((lines * grid_nanos) as u64)
+}
+
+/// Delay every other subdivision for a swung feel.
+///
+/// `grid` is one subdivision (e.g. an eighth note) and `swing` a percentage
+/// in 0..=100. Within each pair of subdivisions the second one starts later
+/// by `swing`% of two thirds of a subdivision, and times in between stretch
+/// or squeeze to match, so order is kept and each pair's start never moves.
+/// 0% is straight; 50% puts the off-beat on the triplet.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use termigroove::domain::timing::apply_swing;
+///
+/// let eighth = Duration::from_millis(300);
+/// assert_eq!(
+///     apply_swing(Duration::from_millis(300), 50, eighth),
+///     Duration::from_millis(400)
+/// );
+/// ```
+pub fn apply_swing(offset: Duration, swing: u8, grid: Duration) -> Duration {
+    if swing == 0 || grid.is_zero() {
+        return offset;
+    }
+    let grid = grid.as_nanos();
+    let offset = offset.as_nanos();
+    let pair_start = offset / (2 * grid) * (2 * grid);
+    let position = offset - pair_start;
+    let shift = 2 * grid * u128::from(swing.min(100)) / 300;
+    let swung = if position < grid {
+        position * (grid + shift) / grid
+    } else {
+        grid + shift + (position - grid) * (grid - shift) / grid
+    };
+    Duration::from_nanos((pair_start + swung) as u64)
+}
+
 /// Normalize an elapsed time offset to be within a loop cycle.
 ///
 /// This function wraps elapsed time around the loop length, ensuring
@@ -4,3 +4,4 @@ pub mod domain;
 pub mod presentation;
 pub mod selection;
 pub mod state;
+pub mod ui;
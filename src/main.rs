@@ -1,41 +1,56 @@
-mod application;
-mod audio;
-mod domain;
-mod presentation;
-mod selection;
-mod state;
-mod ui;
-
 use anyhow::Result;
-use application::dto::input_action::InputAction;
-use application::service::app_service::AppService;
-use application::state::ApplicationState;
-use audio::{SenderAudioBus, SystemClock, spawn_audio_thread};
-use domain::r#loop::LoopEngine;
-use presentation::Mode;
-use presentation::ViewModel;
-use presentation::effect_handler::apply_effects;
 use ratatui::crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+        supports_keyboard_enhancement,
+    },
 };
 use ratatui::widgets::{Block, BorderType, Borders};
 use ratatui::{Terminal, backend::CrosstermBackend};
 use ratatui_explorer::FileExplorer;
 use ratatui_explorer::Theme as ExplorerTheme;
 use std::io;
+use std::sync::mpsc;
+use std::time::Instant;
+use termigroove::application::config::AppConfig;
+use termigroove::application::dto::input_action::InputAction;
+use termigroove::application::keymap::{self, KeyAction, KeyContext};
+use termigroove::application::service::app_service::AppService;
+use termigroove::application::state::ApplicationState;
+use termigroove::audio::{
+    AudioHandle, SenderAudioBus, SystemClock, spawn_audio_thread_with_events,
+};
+use termigroove::domain::r#loop::LoopEngine;
+use termigroove::presentation::Mode;
+use termigroove::presentation::ViewModel;
+use termigroove::presentation::effect_handler::apply_effects;
+use termigroove::ui;
 
 fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    // Ask for key release events where the terminal can send them
+    if supports_keyboard_enhancement().unwrap_or(false) {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+        )?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend)?;
     Ok(terminal)
 }
 
 fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+    if supports_keyboard_enhancement().unwrap_or(false) {
+        execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
+    }
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
@@ -51,12 +66,15 @@ fn main() -> Result<()> {
     let mut terminal = setup_terminal()?;
 
     // Initialize infrastructure
-    let audio_tx = spawn_audio_thread();
-    let bus = SenderAudioBus::new(audio_tx.clone());
+    let (event_tx, event_rx) = mpsc::channel();
+    let audio =
+        AudioHandle::with_events(spawn_audio_thread_with_events(event_tx.clone()), event_tx);
+    let bus = SenderAudioBus::from_handle(audio.clone());
     let loop_engine = LoopEngine::new(SystemClock::new(), bus);
 
     // Initialize application and presentation state
     let mut app_state = ApplicationState::new(loop_engine);
+    app_state.set_config(AppConfig::from_env());
     let theme = ExplorerTheme::default()
         .add_default_title()
         .with_block(
@@ -70,11 +88,18 @@ fn main() -> Result<()> {
         });
     let file_explorer = FileExplorer::with_theme(theme)?;
     let mut view_model = ViewModel::new(file_explorer);
+    let size = terminal.size()?;
+    view_model.apply_terminal_size(size.width, size.height);
 
     // Initialize application service
-    let app_service = AppService::new(audio_tx.clone());
+    let app_service = AppService::new(audio.sender());
+
+    // Startup config (default kit) may select files or enter Pads directly
+    let boot_effects = app_service.boot(&mut app_state, &mut view_model);
+    apply_effects(&mut view_model, &audio, boot_effects);
 
     // Minimal event/render loop with exit on 'q'
+    let mut last_tick = Instant::now();
     loop {
         terminal.draw(|f| ui::draw_ui(f, &view_model, &app_state))?;
 
@@ -83,9 +108,15 @@ fn main() -> Result<()> {
                 Event::Key(key) => {
                     // Convert to InputAction and handle via AppService
                     let input_action = InputAction::from(Event::Key(key));
+                    let quit = matches!(
+                        &input_action,
+                        InputAction::KeyPressed { key, modifiers }
+                            if keymap::lookup(KeyContext::Browse, key, *modifiers)
+                                == Some(KeyAction::Quit)
+                    );
                     match app_service.handle_input(&mut app_state, &mut view_model, input_action) {
                         Ok(effects) => {
-                            apply_effects(&mut view_model, &audio_tx, effects);
+                            apply_effects(&mut view_model, &audio, effects);
                         }
                         Err(e) => {
                             // Handle error - could add error effect in future
@@ -94,8 +125,10 @@ fn main() -> Result<()> {
                     }
 
                     // Handle quit when in Browse mode and 'q' pressed
-                    if let KeyCode::Char('q') = key.code
+                    if quit
                         && matches!(view_model.mode, Mode::Browse)
+                        && !view_model.help_open
+                        && !view_model.filter_open
                     {
                         break;
                     }
@@ -106,7 +139,7 @@ fn main() -> Result<()> {
                     if let Ok(effects) =
                         app_service.handle_input(&mut app_state, &mut view_model, input_action)
                     {
-                        apply_effects(&mut view_model, &audio_tx, effects);
+                        apply_effects(&mut view_model, &audio, effects);
                     }
                 }
                 other => {
@@ -115,15 +148,25 @@ fn main() -> Result<()> {
                     if let Ok(effects) =
                         app_service.handle_input(&mut app_state, &mut view_model, input_action)
                     {
-                        apply_effects(&mut view_model, &audio_tx, effects);
+                        apply_effects(&mut view_model, &audio, effects);
                     }
                 }
             }
         }
 
+        // Pad level meters, sample reports and load failures: take fresh
+        // ones, then let the meters fall
+        for event in event_rx.try_iter() {
+            let effects = app_service.handle_audio_event(&mut app_state, event);
+            apply_effects(&mut view_model, &audio, effects);
+        }
+        let now = Instant::now();
+        app_state.pads.decay_levels(now - last_tick);
+        last_tick = now;
+
         // Update loop engine
         let loop_effects = app_service.update_loop(&mut app_state);
-        apply_effects(&mut view_model, &audio_tx, loop_effects);
+        apply_effects(&mut view_model, &audio, loop_effects);
     }
 
     // Restore terminal
@@ -4,36 +4,45 @@
 //! layer to the presentation layer (e.g., updating status messages) and
 //! infrastructure layer (e.g., sending audio commands).
 
+use crate::application::error::AppError;
 use crate::application::service::Effect;
-use crate::audio::AudioCommand;
+use crate::audio::AudioHandle;
 use crate::presentation::ViewModel;
-use std::sync::mpsc::Sender;
 
 /// Apply effects to the presentation layer and infrastructure.
 ///
 /// This function processes effects produced by application services and
 /// applies them to the appropriate layers:
 /// - `StatusMessage` effects update the view model
+/// - `Announce` effects replace the footer text, where screen readers pick
+///   them up
 /// - `AudioCommand` effects are sent to the audio thread
+/// - `ResetAudio` respawns the audio thread and reports the outcome
+///
+/// Afterwards `audio_lost` reflects whether the audio thread is still
+/// taking commands, including ones the loop engine sent between calls.
 ///
 /// # Arguments
 ///
 /// * `view_model` - Mutable reference to the view model (for status messages)
-/// * `audio_tx` - Sender for audio commands
+/// * `audio` - Handle to the audio thread
 /// * `effects` - Vector of effects to apply
-pub fn apply_effects(
-    view_model: &mut ViewModel,
-    audio_tx: &Sender<AudioCommand>,
-    effects: Vec<Effect>,
-) {
+pub fn apply_effects(view_model: &mut ViewModel, audio: &AudioHandle, effects: Vec<Effect>) {
     for effect in effects {
         match effect {
-            Effect::StatusMessage(message) => {
+            Effect::StatusMessage(message) | Effect::Announce(message) => {
                 view_model.status_message = message;
             }
             Effect::AudioCommand(cmd) => {
-                let _ = audio_tx.send(cmd);
+                audio.send(cmd);
+            }
+            Effect::ResetAudio => {
+                view_model.status_message = match audio.respawn() {
+                    Ok(()) => "Audio engine restarted".to_string(),
+                    Err(e) => format!("{}: {}", AppError::AudioUnavailable, e),
+                };
             }
         }
     }
+    view_model.audio_lost = audio.is_disconnected();
 }
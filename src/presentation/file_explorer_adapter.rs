@@ -9,18 +9,60 @@ use crate::application::ports::{FileEntry, FileNavigator};
 use ratatui::crossterm::event::{Event, KeyCode as CrosstermKeyCode, KeyEvent, KeyModifiers};
 use ratatui_explorer::FileExplorer;
 
+/// Whether an explorer entry name passes a name filter: a case-insensitive
+/// substring match, with the empty filter matching everything.
+pub fn name_matches(name: &str, filter: &str) -> bool {
+    name.to_lowercase().contains(&filter.to_lowercase())
+}
+
 /// Adapter that implements `FileNavigator` for `ratatui_explorer::FileExplorer`.
 ///
 /// This adapter wraps a `FileExplorer` and translates between the application
-/// layer's `KeyCode` and the underlying `crossterm` events.
+/// layer's `KeyCode` and the underlying `crossterm` events. `ratatui_explorer`
+/// has no filtering of its own, so with a name filter the adapter steps Up
+/// and Down over matching entries only and hides the rest.
 pub struct FileExplorerAdapter<'a> {
     explorer: &'a mut FileExplorer,
+    filter: &'a str,
 }
 
 impl<'a> FileExplorerAdapter<'a> {
-    /// Create a new adapter wrapping the given file explorer.
-    pub fn new(explorer: &'a mut FileExplorer) -> Self {
-        Self { explorer }
+    /// Create a new adapter wrapping the given file explorer, showing only
+    /// entries that match `filter`.
+    pub fn new(explorer: &'a mut FileExplorer, filter: &'a str) -> Self {
+        Self { explorer, filter }
+    }
+
+    /// Indices of the entries the filter shows, in listing order.
+    fn matching_indices(&self) -> Vec<usize> {
+        self.explorer
+            .files()
+            .iter()
+            .enumerate()
+            .filter(|(_, file)| name_matches(file.name(), self.filter))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Move to the next (or previous) matching entry, wrapping around.
+    fn step_match(&mut self, forward: bool) {
+        let matches = self.matching_indices();
+        let current = self.explorer.selected_idx();
+        let next = if forward {
+            matches
+                .iter()
+                .find(|&&idx| idx > current)
+                .or(matches.first())
+        } else {
+            matches
+                .iter()
+                .rev()
+                .find(|&&idx| idx < current)
+                .or(matches.last())
+        };
+        if let Some(&idx) = next {
+            self.explorer.set_selected_idx(idx);
+        }
     }
 
     /// Convert our KeyCode to crossterm Event for the FileExplorer.
@@ -35,6 +77,8 @@ impl<'a> FileExplorerAdapter<'a> {
             KeyCode::Esc => CrosstermKeyCode::Esc,
             KeyCode::Backspace => CrosstermKeyCode::Backspace,
             KeyCode::Delete => CrosstermKeyCode::Delete,
+            KeyCode::PageUp => CrosstermKeyCode::PageUp,
+            KeyCode::PageDown => CrosstermKeyCode::PageDown,
             KeyCode::Char(c) => CrosstermKeyCode::Char(c),
             KeyCode::Other(_) => {
                 return Err(anyhow::anyhow!(
@@ -51,16 +95,36 @@ impl<'a> FileExplorerAdapter<'a> {
 
 impl FileNavigator for FileExplorerAdapter<'_> {
     fn handle_navigation_key(&mut self, key: KeyCode) -> anyhow::Result<()> {
+        if !self.filter.is_empty() && matches!(key, KeyCode::Up | KeyCode::Down) {
+            self.step_match(key == KeyCode::Down);
+            return Ok(());
+        }
         let event = Self::keycode_to_event(key)?;
         self.explorer.handle(&event)?;
+        // A directory change lists new entries for the filter to pick from
+        self.apply_filter();
         Ok(())
     }
 
     fn selected_entry(&self) -> Option<FileEntry> {
         let idx = self.explorer.selected_idx();
-        self.explorer.files().get(idx).map(|entry| FileEntry {
-            path: entry.path().to_path_buf(),
-            is_dir: entry.is_dir(),
-        })
+        self.explorer
+            .files()
+            .get(idx)
+            .filter(|entry| name_matches(entry.name(), self.filter))
+            .map(|entry| FileEntry {
+                path: entry.path().to_path_buf(),
+                is_dir: entry.is_dir(),
+            })
+    }
+
+    fn apply_filter(&mut self) -> bool {
+        let matches = self.matching_indices();
+        if !matches.contains(&self.explorer.selected_idx())
+            && let Some(&first) = matches.first()
+        {
+            self.explorer.set_selected_idx(first);
+        }
+        !matches.is_empty()
     }
 }
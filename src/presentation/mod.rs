@@ -7,4 +7,7 @@ pub mod effect_handler;
 pub(crate) mod file_explorer_adapter;
 pub mod view_model;
 
-pub use view_model::{FocusPane, Mode, PopupFocus, ViewModel};
+pub use view_model::{
+    BodySplit, ConfirmPrompt, FocusPane, MARQUEE_STEP, MarqueeState, Mode, PadsPanel, PopupFocus,
+    ViewModel,
+};
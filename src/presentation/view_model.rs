@@ -10,9 +10,18 @@
 use crate::application::ports::FileNavigator;
 use crate::presentation::file_explorer_adapter::FileExplorerAdapter;
 use ratatui_explorer::FileExplorer;
+use std::cell::{Cell, RefCell};
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use tui_input::Input as TextInput;
 
+/// Terminal height below which Pads mode shows a one-line transport in
+/// place of the summary box, leaving the rows to the pad grid.
+pub const COMPACT_TRANSPORT_MAX_HEIGHT: u16 = 27;
+
+/// Time between one-column steps of a scrolling footer message.
+pub const MARQUEE_STEP: Duration = Duration::from_millis(150);
+
 /// Application mode - controls which screen is displayed.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Mode {
@@ -33,6 +42,78 @@ pub enum FocusPane {
     RightSelected,
 }
 
+/// Width split between the explorer and selection panes in Browse mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BodySplit {
+    /// 75/25: most room for the explorer
+    #[default]
+    Wide,
+    /// 60/40
+    Balanced,
+    /// 50/50: long selected file names fit
+    Even,
+}
+
+impl BodySplit {
+    /// Explorer and selection pane widths, in percent.
+    pub fn percentages(self) -> (u16, u16) {
+        match self {
+            BodySplit::Wide => (75, 25),
+            BodySplit::Balanced => (60, 40),
+            BodySplit::Even => (50, 50),
+        }
+    }
+
+    /// Preset after this one, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            BodySplit::Wide => BodySplit::Balanced,
+            BodySplit::Balanced => BodySplit::Even,
+            BodySplit::Even => BodySplit::Wide,
+        }
+    }
+}
+
+/// Which surface has keyboard focus in Pads mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PadsPanel {
+    /// Pad grid
+    #[default]
+    Pads,
+    /// Tempo summary box (Enter edits BPM/bars)
+    Summary,
+    /// Track list of the arrange view
+    Tracks,
+}
+
+/// Yes/no question waiting for an answer before an action runs.
+/// [`DiscardLoop`](Self::DiscardLoop) also offers to save the session first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmPrompt {
+    /// Leaving Pads mode would discard the recorded loop
+    DiscardLoop,
+    /// Clear every per-pad effect override
+    ResetEffects,
+}
+
+impl ConfirmPrompt {
+    /// Question shown in the confirm popup.
+    pub fn message(self) -> &'static str {
+        match self {
+            ConfirmPrompt::DiscardLoop => "Discard current loop? (y/n, s saves it first)",
+            ConfirmPrompt::ResetEffects => "Reset all pad effects? (y/n)",
+        }
+    }
+
+    /// Status shown when the prompt is declined.
+    pub fn declined_message(self) -> &'static str {
+        match self {
+            ConfirmPrompt::DiscardLoop => "Kept loop",
+            ConfirmPrompt::ResetEffects => "Kept pad effects",
+        }
+    }
+}
+
 /// Popup focus states for BPM/Bars configuration dialog.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum PopupFocus {
@@ -45,10 +126,54 @@ pub enum PopupFocus {
     PopupFieldBpm,
     /// Bars input field
     PopupFieldBars,
+    /// Count-in beats input field
+    PopupFieldCountIn,
     /// OK button
     PopupOk,
     /// Cancel button
     PopupCancel,
+    /// Metronome preview button
+    PopupClick,
+}
+
+/// Scroll position of a footer message too long to fit. Rendering takes
+/// the view model by shared reference, so the position lives in cells and
+/// is advanced as frames are drawn.
+#[derive(Debug, Default)]
+pub struct MarqueeState {
+    /// Message the offset belongs to; a new message starts from the left
+    text: RefCell<String>,
+    offset: Cell<usize>,
+    last_step: Cell<Option<Instant>>,
+}
+
+impl MarqueeState {
+    /// Column offset to draw `text` at, stepping one column once
+    /// `MARQUEE_STEP` has passed since the last step.
+    pub fn advance(&self, text: &str, now: Instant) -> usize {
+        if *self.text.borrow() != text {
+            self.text.replace(text.to_string());
+            self.offset.set(0);
+            self.last_step.set(Some(now));
+            return 0;
+        }
+        let due = self
+            .last_step
+            .get()
+            .is_none_or(|last| now.duration_since(last) >= MARQUEE_STEP);
+        if due {
+            self.offset.set(self.offset.get() + 1);
+            self.last_step.set(Some(now));
+        }
+        self.offset.get()
+    }
+
+    /// Forget the scrolled message, e.g. once it fits again.
+    pub fn reset(&self) {
+        self.text.borrow_mut().clear();
+        self.offset.set(0);
+        self.last_step.set(None);
+    }
 }
 
 /// Presentation view model containing UI-specific state.
@@ -58,8 +183,13 @@ pub struct ViewModel {
     pub mode: Mode,
     /// Current focus pane (LeftExplorer or RightSelected)
     pub focus: FocusPane,
+    /// Focused surface in Pads mode; arrows act within it
+    pub pads_panel_focus: PadsPanel,
     /// Status message displayed in footer
     pub status_message: String,
+    /// Whether the audio thread stopped taking commands; the footer warns
+    /// until it is restarted
+    pub audio_lost: bool,
     /// File explorer widget for directory navigation
     pub file_explorer: FileExplorer,
     /// Current item in left pane
@@ -74,6 +204,36 @@ pub struct ViewModel {
     pub draft_bpm: TextInput,
     /// Draft bars input field
     pub draft_bars: TextInput,
+    /// Draft count-in beats input field
+    pub draft_count_in: TextInput,
+    /// Whether the arrange (track list) view replaces the pad grid
+    pub is_arrange_open: bool,
+    /// Selected row in the arrange view
+    pub arrange_cursor: usize,
+    /// Whether a text field owns the keyboard; pads are not triggered
+    pub text_capture: bool,
+    /// Text being edited while `text_capture` is on
+    pub text_draft: TextInput,
+    /// Open yes/no confirm popup, if any
+    pub confirm: Option<ConfirmPrompt>,
+    /// Whether the key binding help overlay is shown
+    pub help_open: bool,
+    /// Pad waiting for its new key in mapping mode
+    pub remap_from: Option<char>,
+    /// Pad waiting for its choke group digit in pad-edit mode
+    pub choke_edit: Option<char>,
+    /// Explorer/selection pane widths in Browse mode
+    pub body_split: BodySplit,
+    /// Whether the terminal is too short for the full summary box
+    pub compact_transport: bool,
+    /// Whether the Browse header is a one-line title instead of big text
+    pub header_collapsed: bool,
+    /// Scroll position of an overlong footer message
+    pub marquee: MarqueeState,
+    /// Name filter of the file explorer; empty shows every entry
+    pub explorer_filter: TextInput,
+    /// Whether the `/` filter input owns the keyboard in Browse mode
+    pub filter_open: bool,
 }
 
 impl ViewModel {
@@ -82,7 +242,9 @@ impl ViewModel {
         Self {
             mode: Mode::Browse,
             focus: FocusPane::LeftExplorer,
+            pads_panel_focus: PadsPanel::Pads,
             status_message: "Ready".to_string(),
+            audio_lost: false,
             file_explorer,
             current_left_item: None,
             current_left_is_dir: false,
@@ -90,6 +252,21 @@ impl ViewModel {
             popup_focus: PopupFocus::None,
             draft_bpm: TextInput::new(120.to_string()),
             draft_bars: TextInput::new(16.to_string()),
+            draft_count_in: TextInput::new(4.to_string()),
+            is_arrange_open: false,
+            arrange_cursor: 0,
+            text_capture: false,
+            text_draft: TextInput::default(),
+            confirm: None,
+            help_open: false,
+            remap_from: None,
+            choke_edit: None,
+            body_split: BodySplit::default(),
+            compact_transport: false,
+            header_collapsed: false,
+            marquee: MarqueeState::default(),
+            explorer_filter: TextInput::default(),
+            filter_open: false,
         }
     }
 
@@ -109,6 +286,60 @@ impl ViewModel {
         }
     }
 
+    /// Update size-dependent layout choices for a new terminal size.
+    pub fn apply_terminal_size(&mut self, _width: u16, height: u16) {
+        self.compact_transport = height < COMPACT_TRANSPORT_MAX_HEIGHT;
+    }
+
+    /// Switch to the next Browse pane split and describe it.
+    pub fn cycle_body_split(&mut self) -> String {
+        self.body_split = self.body_split.next();
+        let (left, right) = self.body_split.percentages();
+        format!("Split: {}/{}", left, right)
+    }
+
+    /// Collapse the Browse header to one line, or expand it back, and
+    /// describe the result.
+    pub fn toggle_header(&mut self) -> String {
+        self.header_collapsed = !self.header_collapsed;
+        if self.header_collapsed {
+            "Header: compact".to_string()
+        } else {
+            "Header: full".to_string()
+        }
+    }
+
+    /// Panels Tab cycles through in Pads mode. The arrange track list takes
+    /// the pad grid's place while it is open.
+    pub fn pads_panels(&self) -> [PadsPanel; 2] {
+        let body = if self.is_arrange_open {
+            PadsPanel::Tracks
+        } else {
+            PadsPanel::Pads
+        };
+        [body, PadsPanel::Summary]
+    }
+
+    /// Move Pads-mode focus to the next available panel.
+    pub fn cycle_pads_panel(&mut self) {
+        let panels = self.pads_panels();
+        let next = panels
+            .iter()
+            .position(|panel| *panel == self.pads_panel_focus)
+            .map(|i| (i + 1) % panels.len())
+            .unwrap_or(0);
+        self.pads_panel_focus = panels[next];
+    }
+
+    /// Get status message based on the focused Pads-mode panel.
+    pub fn pads_focus_status_message(&self) -> String {
+        match self.pads_panel_focus {
+            PadsPanel::Pads => "Focus: Pads".to_string(),
+            PadsPanel::Summary => "Focus: Summary (Enter = edit tempo)".to_string(),
+            PadsPanel::Tracks => "Focus: Tracks".to_string(),
+        }
+    }
+
     /// Check if BPM popup is open.
     pub fn is_bpm_popup_open(&self) -> bool {
         self.is_popup_open
@@ -139,12 +370,24 @@ impl ViewModel {
         &self.draft_bars
     }
 
-    /// Open BPM/Bars popup.
-    pub fn open_bpm_bars_popup(&mut self, bpm: u16, bars: u16) {
+    /// Get mutable reference to draft count-in input.
+    pub fn draft_count_in_mut(&mut self) -> &mut TextInput {
+        &mut self.draft_count_in
+    }
+
+    /// Get immutable reference to draft count-in input.
+    pub fn draft_count_in(&self) -> &TextInput {
+        &self.draft_count_in
+    }
+
+    /// Open BPM/Bars popup. `bars` is the length field text, e.g. `4` or
+    /// `6b` for a length in beats; `count_in` is the count-in in beats.
+    pub fn open_bpm_bars_popup(&mut self, bpm: u16, bars: impl std::fmt::Display, count_in: u8) {
         self.is_popup_open = true;
         self.popup_focus = PopupFocus::PopupFieldBpm;
         self.draft_bpm = TextInput::new(bpm.to_string());
         self.draft_bars = TextInput::new(bars.to_string());
+        self.draft_count_in = TextInput::new(count_in.to_string());
     }
 
     /// Close BPM/Bars popup.
@@ -153,11 +396,12 @@ impl ViewModel {
         self.popup_focus = PopupFocus::None;
         self.draft_bpm.reset();
         self.draft_bars.reset();
+        self.draft_count_in.reset();
     }
 
     /// Focus summary box.
     pub fn focus_summary_box(&mut self) {
-        self.popup_focus = PopupFocus::SummaryBox;
+        self.pads_panel_focus = PadsPanel::Summary;
     }
 
     /// Move popup focus up.
@@ -165,7 +409,10 @@ impl ViewModel {
         self.popup_focus = match self.popup_focus {
             PopupFocus::PopupFieldBpm => PopupFocus::PopupOk,
             PopupFocus::PopupFieldBars => PopupFocus::PopupFieldBpm,
-            PopupFocus::PopupOk | PopupFocus::PopupCancel => PopupFocus::PopupFieldBars,
+            PopupFocus::PopupFieldCountIn => PopupFocus::PopupFieldBars,
+            PopupFocus::PopupOk | PopupFocus::PopupCancel | PopupFocus::PopupClick => {
+                PopupFocus::PopupFieldCountIn
+            }
             _ => PopupFocus::SummaryBox,
         };
     }
@@ -174,27 +421,77 @@ impl ViewModel {
     pub fn popup_focus_down(&mut self) {
         self.popup_focus = match self.popup_focus {
             PopupFocus::PopupFieldBpm => PopupFocus::PopupFieldBars,
-            PopupFocus::PopupFieldBars => PopupFocus::PopupOk,
-            PopupFocus::PopupOk | PopupFocus::PopupCancel => PopupFocus::PopupFieldBpm,
+            PopupFocus::PopupFieldBars => PopupFocus::PopupFieldCountIn,
+            PopupFocus::PopupFieldCountIn => PopupFocus::PopupOk,
+            PopupFocus::PopupOk | PopupFocus::PopupCancel | PopupFocus::PopupClick => {
+                PopupFocus::PopupFieldBpm
+            }
             _ => PopupFocus::SummaryBox,
         };
     }
 
-    /// Toggle between OK and Cancel buttons in popup.
-    pub fn popup_toggle_ok_cancel(&mut self) {
-        self.popup_focus = match self.popup_focus {
-            PopupFocus::PopupOk => PopupFocus::PopupCancel,
-            PopupFocus::PopupCancel => PopupFocus::PopupOk,
+    /// Move along the popup's OK, Cancel and Click buttons, wrapping at
+    /// the ends. From a field, focus lands on OK.
+    pub fn popup_cycle_buttons(&mut self, forward: bool) {
+        self.popup_focus = match (self.popup_focus, forward) {
+            (PopupFocus::PopupOk, true) | (PopupFocus::PopupClick, false) => {
+                PopupFocus::PopupCancel
+            }
+            (PopupFocus::PopupCancel, true) | (PopupFocus::PopupOk, false) => {
+                PopupFocus::PopupClick
+            }
             _ => PopupFocus::PopupOk,
         };
     }
 
+    /// Check if the arrange view is open.
+    pub fn is_arrange_open(&self) -> bool {
+        self.is_arrange_open
+    }
+
+    /// Toggle the arrange view, resetting its cursor when opened.
+    pub fn toggle_arrange(&mut self) {
+        if self.is_arrange_open {
+            self.close_arrange();
+        } else {
+            self.is_arrange_open = true;
+            self.arrange_cursor = 0;
+            self.pads_panel_focus = PadsPanel::Tracks;
+        }
+    }
+
+    /// Close the arrange view, handing its focus back to the pad grid.
+    pub fn close_arrange(&mut self) {
+        self.is_arrange_open = false;
+        if self.pads_panel_focus == PadsPanel::Tracks {
+            self.pads_panel_focus = PadsPanel::Pads;
+        }
+    }
+
+    /// Route keys to a text field prefilled with `initial`.
+    pub fn begin_text_capture(&mut self, initial: &str) {
+        self.text_capture = true;
+        self.text_draft = TextInput::new(initial.to_string());
+    }
+
+    /// Hand the keyboard back to the pads, returning the edited text.
+    pub fn end_text_capture(&mut self) -> String {
+        self.text_capture = false;
+        std::mem::take(&mut self.text_draft).value().to_string()
+    }
+
     /// Get a FileNavigator adapter for the file explorer.
     ///
     /// This returns an adapter that implements the `FileNavigator` trait,
     /// allowing the application layer to interact with the file explorer
     /// without depending on the concrete `FileExplorer` type.
     pub fn as_navigator(&mut self) -> impl FileNavigator + '_ {
-        FileExplorerAdapter::new(&mut self.file_explorer)
+        FileExplorerAdapter::new(&mut self.file_explorer, self.explorer_filter.value())
+    }
+
+    /// Drop the explorer name filter and close its input.
+    pub fn clear_filter(&mut self) {
+        self.explorer_filter = TextInput::default();
+        self.filter_open = false;
     }
 }
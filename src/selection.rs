@@ -1,14 +1,80 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Debug, Default)]
 pub struct SelectionModel {
     pub items: Vec<PathBuf>,
     pub set: HashSet<PathBuf>,
+    /// Format details read when a file is added; absent when its header
+    /// could not be parsed. Copies share their original's entry.
+    pub info: HashMap<PathBuf, SampleInfo>,
     pub right_idx: usize,
     pub status: String,
 }
 
+/// Length and sample rate of a selected WAV file, read from its header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampleInfo {
+    pub duration: Duration,
+    pub sample_rate: u32,
+}
+
+impl SampleInfo {
+    /// Parse the RIFF header of a WAV file without decoding its samples.
+    /// `None` for other formats and truncated or malformed headers.
+    pub fn read(path: &Path) -> Option<Self> {
+        let mut file = File::open(path).ok()?;
+        let mut riff = [0u8; 12];
+        file.read_exact(&mut riff).ok()?;
+        if &riff[0..4] != b"RIFF" || &riff[8..12] != b"WAVE" {
+            return None;
+        }
+        let mut format = None;
+        loop {
+            let mut chunk = [0u8; 8];
+            file.read_exact(&mut chunk).ok()?;
+            let size = u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]);
+            match &chunk[0..4] {
+                b"fmt " => {
+                    let mut fmt = [0u8; 16];
+                    file.read_exact(&mut fmt).ok()?;
+                    let sample_rate = u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]);
+                    let block_align = u16::from_le_bytes([fmt[12], fmt[13]]);
+                    format = Some((sample_rate, block_align));
+                    // Chunks are padded to an even length
+                    let rest = i64::from(size).checked_sub(16)? + i64::from(size % 2);
+                    file.seek(SeekFrom::Current(rest)).ok()?;
+                }
+                b"data" => {
+                    let (sample_rate, block_align) = format?;
+                    if sample_rate == 0 || block_align == 0 {
+                        return None;
+                    }
+                    let frames = u64::from(size / u32::from(block_align));
+                    return Some(Self {
+                        duration: Duration::from_secs_f64(frames as f64 / f64::from(sample_rate)),
+                        sample_rate,
+                    });
+                }
+                _ => {
+                    file.seek(SeekFrom::Current(i64::from(size) + i64::from(size % 2)))
+                        .ok()?;
+                }
+            }
+        }
+    }
+
+    /// Short description for the selection list, e.g. "0.8s, 44.1kHz".
+    pub fn summary(&self) -> String {
+        let khz = format!("{:.2}", f64::from(self.sample_rate) / 1000.0);
+        let khz = khz.trim_end_matches('0').trim_end_matches('.');
+        format!("{:.1}s, {khz}kHz", self.duration.as_secs_f64())
+    }
+}
+
 impl SelectionModel {
     fn clamp_right_idx(&mut self) {
         if self.items.is_empty() {
@@ -20,6 +86,9 @@ impl SelectionModel {
 
     pub fn add_file(&mut self, path: PathBuf) {
         if self.set.insert(path.clone()) {
+            if let Some(info) = SampleInfo::read(&path) {
+                self.info.insert(path.clone(), info);
+            }
             self.items.push(path.clone());
             self.right_idx = self.items.len().saturating_sub(1);
             self.status = format!("Added {}", get_file_name(&path));
@@ -35,21 +104,46 @@ impl SelectionModel {
         }
         let idx = self.right_idx.min(self.items.len() - 1);
         let path = self.items.remove(idx);
-        self.set.remove(&path);
+        // A duplicated file stays selected while a copy remains
+        if !self.items.contains(&path) {
+            self.set.remove(&path);
+            self.info.remove(&path);
+        }
         self.status = format!("Removed {}", get_file_name(&path));
         self.clamp_right_idx();
     }
 
+    /// Unselect a file, including any copies made by `fill_to`.
     pub fn remove_file(&mut self, path: &Path) {
-        if self.set.remove(path)
-            && let Some(pos) = self.items.iter().position(|p| p == path)
-        {
-            self.items.remove(pos);
+        if self.set.remove(path) {
+            self.items.retain(|p| p != path);
+            self.info.remove(path);
             self.status = format!("Removed {}", get_file_name(path));
             self.clamp_right_idx();
         }
     }
 
+    /// Repeat the selected files in order until there are `capacity`
+    /// entries, so each copy can land on its own pad. Returns how many
+    /// copies were added.
+    pub fn fill_to(&mut self, capacity: usize) -> usize {
+        let originals = self.items.len();
+        if originals == 0 || originals >= capacity {
+            return 0;
+        }
+        let copies: Vec<PathBuf> = self
+            .items
+            .iter()
+            .cycle()
+            .take(capacity - originals)
+            .cloned()
+            .collect();
+        let added = copies.len();
+        self.items.extend(copies);
+        self.status = format!("Added {added} copies");
+        added
+    }
+
     pub fn move_up(&mut self) {
         if self.items.is_empty() {
             return;
@@ -67,6 +161,37 @@ impl SelectionModel {
             self.right_idx += 1;
         }
     }
+
+    /// Move the cursor up by `step` items, stopping at the first.
+    pub fn page_up(&mut self, step: usize) {
+        self.right_idx = self.right_idx.saturating_sub(step);
+        self.clamp_right_idx();
+    }
+
+    /// Move the cursor down by `step` items, stopping at the last.
+    pub fn page_down(&mut self, step: usize) {
+        self.right_idx = self.right_idx.saturating_add(step);
+        self.clamp_right_idx();
+    }
+
+    /// Like `move_up`, jumping from the first item to the last.
+    pub fn move_up_wrapping(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        self.right_idx = match self.right_idx {
+            0 => self.items.len() - 1,
+            idx => idx - 1,
+        };
+    }
+
+    /// Like `move_down`, jumping from the last item to the first.
+    pub fn move_down_wrapping(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        self.right_idx = (self.right_idx + 1) % self.items.len();
+    }
 }
 
 fn get_file_name(p: &Path) -> String {
@@ -6,101 +6,448 @@ use ratatui::{
     style::{Color, Modifier, Style},
     symbols::border::DOUBLE,
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Padding, Paragraph, Widget, WidgetRef},
+    widgets::{
+        Block, BorderType, Borders, List, ListItem, ListState, Padding, Paragraph, Widget,
+        WidgetRef,
+    },
 };
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tui_big_text::{BigText, PixelSize};
 use tui_popup::{Popup, SizedWidgetRef};
 
-use crate::application::state::ApplicationState;
-use crate::domain::r#loop::LoopState;
+use crate::application::dto::loop_state::{LoopStateDto, LoopStatusDto};
+use crate::application::keymap::{KeyContext, help_entries, help_entries_in};
+use crate::application::state::{ApplicationState, PadMute, is_supported_audio};
+use crate::audio::BusLevel;
+use crate::domain::r#loop::{LoopState, RestRegion};
+use crate::domain::tempo::LoopLength;
+use crate::domain::timing::{TimeSignature, normalize_offset, position_bar_beat};
 use crate::presentation::ViewModel;
-use crate::presentation::{FocusPane, Mode, PopupFocus};
+use crate::presentation::file_explorer_adapter::name_matches;
+use crate::presentation::{BodySplit, ConfirmPrompt, FocusPane, Mode, PadsPanel, PopupFocus};
 
 const HEADER_TITLE: &str = "WELCOME TO TERMIGROOVE";
 const HEADER_SUBTITLE: &str = "Load your samples...";
+/// Header big text sizes from largest to smallest, with the columns one
+/// 8x8-pixel glyph takes at that size.
+const HEADER_PIXEL_SIZES: [(PixelSize, u16); 2] = [(PixelSize::Full, 8), (PixelSize::Quadrant, 4)];
 const RIGHT_TITLE: &str = "Selected (Enter = To Pads)";
+const ARRANGE_TITLE: &str = "Arrange (Ctrl+T = Back to Pads)";
+/// How long a pad stays highlighted after a press, in ms, on terminals
+/// that do not report key releases.
+const PAD_HIGHLIGHT_MS: u128 = 150;
+/// Narrowest a pad cell may get before the grid wraps to more rows.
+const MIN_PAD_WIDTH: u16 = 8;
+/// How long the border stays lit after a downbeat.
+const DOWNBEAT_FLASH_WINDOW: Duration = Duration::from_millis(100);
+/// Minimum time between flashes; fast tempos flash on every Nth bar instead.
+const DOWNBEAT_FLASH_MIN_INTERVAL: Duration = Duration::from_millis(500);
+/// Shortest beat pulse; fast tempos pulse on every Nth beat instead.
+const BEAT_PULSE_MIN_PERIOD: Duration = Duration::from_millis(250);
+/// Width of one key/description column in the help overlay.
+const HELP_COLUMN_WIDTH: usize = 32;
+/// Width of the key part of a help column.
+const HELP_KEY_WIDTH: usize = 13;
+/// Key/description columns per row in the help overlay.
+const HELP_COLUMNS: usize = 3;
 
 pub fn draw_ui(frame: &mut Frame, view_model: &ViewModel, app_state: &ApplicationState) {
     match view_model.mode {
         Mode::Browse => {
-            let (header_area, body_area, footer_area) = vertical_layout(frame);
-            render_header(frame, header_area);
-            let (left_area, right_area) = body_layout(body_area);
-            frame.render_widget(&view_model.file_explorer.widget(), left_area);
+            let (header_area, body_area, footer_area) =
+                vertical_layout(frame, view_model.header_collapsed);
+            render_header(frame, header_area, view_model.header_collapsed);
+            let (left_area, right_area) = body_layout(body_area, view_model.body_split);
+            if view_model.filter_open || !view_model.explorer_filter.value().is_empty() {
+                render_filtered_explorer(frame, left_area, view_model);
+            } else {
+                frame.render_widget(&view_model.file_explorer.widget(), left_area);
+            }
             render_right(frame, right_area, view_model, app_state);
-            render_footer(frame, footer_area, view_model);
+            render_footer(frame, footer_area, view_model, app_state);
         }
         Mode::Pads => {
             let size = frame.area();
+            let summary_height = if view_model.compact_transport {
+                Constraint::Length(1)
+            } else {
+                Constraint::Percentage(30)
+            };
+            let pulse_height = Constraint::Length(u16::from(app_state.config.beat_pulse));
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Percentage(30),
+                    summary_height,
                     Constraint::Min(1),
+                    pulse_height,
                     Constraint::Length(1),
                 ])
                 .split(size);
             let summary_area = chunks[0];
             let body_area = chunks[1];
-            let footer_area = chunks[2];
-            render_summary_box(frame, summary_area, view_model, app_state);
-            render_pads(frame, body_area, view_model, app_state);
-            render_footer(frame, footer_area, view_model);
+            let footer_area = chunks[3];
+            if view_model.compact_transport {
+                render_compact_transport(frame, summary_area, view_model, app_state);
+            } else {
+                render_summary_box(frame, summary_area, view_model, app_state);
+            }
+            if view_model.is_arrange_open() {
+                render_arrange(frame, body_area, view_model, app_state);
+            } else {
+                render_pads(frame, body_area, app_state);
+            }
+            if app_state.config.beat_pulse {
+                render_beat_pulse(
+                    frame,
+                    chunks[2],
+                    &app_state.loop_state_dto(),
+                    app_state.get_bpm(),
+                    app_state.time_signature(),
+                );
+            }
+            render_footer(frame, footer_area, view_model, app_state);
+            if app_state.config.downbeat_flash
+                && is_downbeat_flash(
+                    &app_state.loop_state_dto(),
+                    app_state.loop_length_setting(),
+                    app_state.time_signature().numerator,
+                )
+            {
+                render_downbeat_flash(frame, size);
+            }
             if view_model.is_bpm_popup_open() {
                 render_popup(frame, size, view_model, app_state);
             }
+            if let Some(prompt) = view_model.confirm {
+                render_confirm_popup(frame, size, prompt);
+            }
+        }
+    }
+    if view_model.help_open {
+        render_help_popup(frame, frame.area());
+    }
+}
+
+/// Summary text for the loop length: the bar count, or fractional bars of
+/// `beats_per_bar` with the beat count for a length entered in beats
+/// ("1.5 (6 beats)" in 4/4).
+pub fn bars_label(length: LoopLength, beats_per_bar: u16) -> String {
+    match length {
+        LoopLength::Bars(bars) => bars.to_string(),
+        LoopLength::Beats(beats) => {
+            let bars = f64::from(beats) / f64::from(beats_per_bar.max(1));
+            // Two decimals at most: 4 beats of 3/4 read "1.33"
+            let bars = (bars * 100.0).round() / 100.0;
+            format!("{bars} ({beats} beats)")
         }
     }
 }
 
+/// Playhead as "bar.beat" (e.g. "2.3") in 4/4, or "—" when there is no
+/// running loop to place it in.
+pub fn position_label(loop_state: &LoopStateDto, length: LoopLength) -> String {
+    position_label_in(loop_state, length, TimeSignature::default())
+}
+
+/// Like [`position_label`], counting bars of `signature`.
+pub fn position_label_in(
+    loop_state: &LoopStateDto,
+    length: LoopLength,
+    signature: TimeSignature,
+) -> String {
+    let Some(offset) = loop_state.current_offset else {
+        return "—".to_string();
+    };
+    if loop_state.loop_length.is_zero() {
+        return "—".to_string();
+    }
+    // A length in beats leaves the last bar partial; measure against the
+    // full bars so beats keep their real length
+    let beats_per_bar = signature.numerator.max(1);
+    let beats = length.beats_per(beats_per_bar).max(1);
+    let bars = beats.div_ceil(u32::from(beats_per_bar)) as u16;
+    let span = loop_state.loop_length * u32::from(bars) * u32::from(beats_per_bar) / beats;
+    let (bar, beat) = position_bar_beat(offset, span, bars, beats_per_bar);
+    format!("{bar}.{beat}")
+}
+
+/// Whether the frame falls inside the flash window right after a bar downbeat
+/// of a loop `length` long, in bars of `beats_per_bar`.
+///
+/// Only playing or recording loops flash. A length in beats keeps full-length
+/// bars and a shorter last one. When bars are shorter than
+/// `DOWNBEAT_FLASH_MIN_INTERVAL`, only every Nth downbeat flashes so the
+/// strobe rate stays capped.
+pub fn is_downbeat_flash(
+    loop_state: &LoopStateDto,
+    length: LoopLength,
+    beats_per_bar: u16,
+) -> bool {
+    let beats = length.beats_per(beats_per_bar);
+    if !matches!(
+        loop_state.status,
+        LoopStatusDto::Playing | LoopStatusDto::Recording
+    ) || beats == 0
+        || loop_state.loop_length.is_zero()
+    {
+        return false;
+    }
+    let Some(offset) = loop_state.current_offset else {
+        return false;
+    };
+    let bar_nanos =
+        loop_state.loop_length.as_nanos() * u128::from(beats_per_bar) / u128::from(beats);
+    if bar_nanos == 0 {
+        return false;
+    }
+    let offset_nanos = offset.as_nanos() % loop_state.loop_length.as_nanos();
+    let bar_index = offset_nanos / bar_nanos;
+    let phase = offset_nanos % bar_nanos;
+    let every = DOWNBEAT_FLASH_MIN_INTERVAL
+        .as_nanos()
+        .div_ceil(bar_nanos)
+        .max(1);
+    bar_index.is_multiple_of(every) && phase < DOWNBEAT_FLASH_WINDOW.as_nanos()
+}
+
+/// Size of the beat pulse, from 1.0 right on a beat down to 0.0 just
+/// before the next one, or `None` when no loop is running.
+///
+/// The beat length follows the current `bpm`, so the pulse stays on time
+/// after tempo changes. When beats are shorter than
+/// `BEAT_PULSE_MIN_PERIOD`, one pulse spans every Nth beat.
+pub fn beat_pulse(loop_state: &LoopStateDto, bpm: u16, signature: TimeSignature) -> Option<f64> {
+    if !matches!(
+        loop_state.status,
+        LoopStatusDto::Playing | LoopStatusDto::Recording
+    ) || loop_state.loop_length.is_zero()
+    {
+        return None;
+    }
+    let offset = normalize_offset(loop_state.current_offset?, loop_state.loop_length);
+    let beat = signature.beat_interval(bpm).as_nanos();
+    if beat == 0 {
+        return None;
+    }
+    let every = BEAT_PULSE_MIN_PERIOD.as_nanos().div_ceil(beat).max(1);
+    let period = beat * every;
+    let phase = (offset.as_nanos() % period) as f64 / period as f64;
+    Some(1.0 - phase)
+}
+
+/// Centered bar that snaps to full width on each beat and shrinks until the
+/// next. Shows a single dot while no loop is running.
+pub fn render_beat_pulse(
+    frame: &mut Frame,
+    area: Rect,
+    loop_state: &LoopStateDto,
+    bpm: u16,
+    signature: TimeSignature,
+) {
+    let line = match beat_pulse(loop_state, bpm, signature) {
+        Some(size) => {
+            let width = ((size * f64::from(area.width)).round() as usize).max(1);
+            Line::from(Span::styled(
+                "█".repeat(width),
+                Style::default().fg(Color::LightCyan),
+            ))
+        }
+        None => Line::from(Span::styled("·", Style::default().fg(Color::DarkGray))),
+    };
+    frame.render_widget(Paragraph::new(line).alignment(Alignment::Center), area);
+}
+
+/// Countdown until a recording loop commits, e.g. "1.2s left".
+///
+/// `None` outside the Recording state; clamped at zero past the loop end.
+pub fn recording_time_left(loop_state: &LoopStateDto) -> Option<String> {
+    if loop_state.status != LoopStatusDto::Recording {
+        return None;
+    }
+    let offset = loop_state.current_offset?;
+    let left = loop_state.loop_length.saturating_sub(offset);
+    Some(format!("{:.1}s left", left.as_secs_f64()))
+}
+
+/// Time until a playing loop wraps to its start, e.g. "0:01.3 to wrap".
+///
+/// `None` outside the Playing state; recording shows `recording_time_left`.
+pub fn wrap_countdown(loop_state: &LoopStateDto) -> Option<String> {
+    if loop_state.status != LoopStatusDto::Playing {
+        return None;
+    }
+    let tenths = loop_state.remaining?.as_millis() / 100;
+    Some(format!(
+        "{}:{:02}.{} to wrap",
+        tenths / 600,
+        tenths % 600 / 10,
+        tenths % 10
+    ))
+}
+
+/// Summary hint for a loop that is running with nothing recorded yet,
+/// e.g. after a count-in with no pad hits.
+pub fn empty_loop_hint(loop_state: &LoopStateDto) -> Option<&'static str> {
+    (loop_state.status == LoopStatusDto::Playing && loop_state.track_count == 0)
+        .then_some("Empty loop — play pads to overdub")
+}
+
+fn render_downbeat_flash(frame: &mut Frame, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).border_style(
+        Style::default()
+            .fg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD),
+    );
+    frame.render_widget(block, area);
+}
+
 fn vertical_layout(
     frame: &mut Frame,
+    header_collapsed: bool,
 ) -> (
     ratatui::prelude::Rect,
     ratatui::prelude::Rect,
     ratatui::prelude::Rect,
 ) {
     let size = frame.area();
+    let header_height = if header_collapsed { 1 } else { 8 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(8), // header
-            Constraint::Min(1),    // body
-            Constraint::Length(1), // footer
+            Constraint::Length(header_height), // header
+            Constraint::Min(1),                // body
+            Constraint::Length(1),             // footer
         ])
         .split(size);
     (chunks[0], chunks[1], chunks[2])
 }
 
-fn body_layout(area: ratatui::prelude::Rect) -> (ratatui::prelude::Rect, ratatui::prelude::Rect) {
+fn body_layout(
+    area: ratatui::prelude::Rect,
+    split: BodySplit,
+) -> (ratatui::prelude::Rect, ratatui::prelude::Rect) {
+    let (left, right) = split.percentages();
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(75), Constraint::Percentage(25)])
+        .constraints([Constraint::Percentage(left), Constraint::Percentage(right)])
         .split(area);
     (chunks[0], chunks[1])
 }
 
-fn render_header(frame: &mut Frame, area: ratatui::prelude::Rect) {
+fn render_header(frame: &mut Frame, area: ratatui::prelude::Rect, collapsed: bool) {
+    if collapsed {
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled(
+                HEADER_TITLE,
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" — "),
+            Span::raw(HEADER_SUBTITLE),
+        ]))
+        .alignment(Alignment::Center);
+        frame.render_widget(title, area);
+        return;
+    }
+
     // Split header area: big text + subtitle line
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(7), Constraint::Length(1)])
         .split(area);
 
-    let big = BigText::builder()
-        .pixel_size(PixelSize::Full)
-        .style(Style::default().fg(Color::Green))
-        .lines(vec![HEADER_TITLE.into()])
-        .alignment(Alignment::Center)
-        .build();
-    frame.render_widget(big, chunks[0]);
+    match header_pixel_size(chunks[0].width) {
+        Some(pixel_size) => {
+            let big = BigText::builder()
+                .pixel_size(pixel_size)
+                .style(Style::default().fg(Color::Green))
+                .lines(vec![HEADER_TITLE.into()])
+                .alignment(Alignment::Center)
+                .build();
+            frame.render_widget(big, chunks[0]);
+        }
+        // Too narrow for any big text; a plain title would not clip
+        None => {
+            let title = Paragraph::new(Line::from(Span::styled(
+                HEADER_TITLE,
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            )))
+            .alignment(Alignment::Center);
+            let middle = Rect {
+                y: chunks[0].y + chunks[0].height / 2,
+                height: 1.min(chunks[0].height),
+                ..chunks[0]
+            };
+            frame.render_widget(title, middle);
+        }
+    }
 
     let subtitle =
         Paragraph::new(Line::from(Span::raw(HEADER_SUBTITLE))).alignment(Alignment::Center);
     frame.render_widget(subtitle, chunks[1]);
 }
 
+/// Largest big text size whose header title fits in `width` columns, or
+/// `None` when only plain text fits.
+pub fn header_pixel_size(width: u16) -> Option<PixelSize> {
+    let chars = HEADER_TITLE.chars().count() as u16;
+    HEADER_PIXEL_SIZES
+        .into_iter()
+        .find(|(_, glyph_width)| chars * glyph_width <= width)
+        .map(|(size, _)| size)
+}
+
+/// Explorer listing narrowed to the names matching the `/` filter, with the
+/// filter text in the title. `ratatui_explorer` cannot filter itself, so
+/// this replaces its widget while a filter is set.
+fn render_filtered_explorer(
+    frame: &mut Frame,
+    area: ratatui::prelude::Rect,
+    view_model: &ViewModel,
+) {
+    let filter = view_model.explorer_filter.value();
+    let cursor = if view_model.filter_open { "▏" } else { "" };
+    let block = Block::default()
+        .title(format!(" Filter: {filter}{cursor} "))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+    let explorer = &view_model.file_explorer;
+    let matches: Vec<(usize, &str)> = explorer
+        .files()
+        .iter()
+        .enumerate()
+        .filter(|(_, file)| name_matches(file.name(), filter))
+        .map(|(idx, file)| (idx, file.name()))
+        .collect();
+    if matches.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            "No matches (Esc clears)",
+            Style::default().fg(Color::DarkGray),
+        ))
+        .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+    let selected = matches
+        .iter()
+        .position(|(idx, _)| *idx == explorer.selected_idx());
+    let rows = area.height.saturating_sub(2) as usize;
+    let items: Vec<ListItem> = matches
+        .iter()
+        .map(|(_, name)| ListItem::new(Line::from(name.to_string())))
+        .collect();
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default()
+        .with_offset(selection_offset(selected.unwrap_or(0), matches.len(), rows))
+        .with_selected(selected);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
 fn render_right(
     frame: &mut Frame,
     area: ratatui::prelude::Rect,
@@ -137,53 +484,158 @@ fn render_right(
                 .and_then(|s| s.to_str())
                 .unwrap_or("?")
                 .to_string();
+            let name = match app_state.selection.info.get(p) {
+                Some(info) => format!("{name} ({})", info.summary()),
+                None => name,
+            };
+            // Flag files that Enter will reject before the user gets there
+            let (name, color) = if is_supported_audio(p) {
+                (name, Color::Green)
+            } else {
+                (format!("⚠ {name}"), Color::Yellow)
+            };
             ListItem::new(Line::from(Span::styled(
                 name,
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
             )))
         })
         .collect();
 
+    // Unknown color names fall back to the default green
+    let highlight_color = app_state
+        .config
+        .highlight_color()
+        .parse::<Color>()
+        .unwrap_or(Color::Green);
     let list = List::new(items)
         .block(right_block)
         .highlight_style(
             Style::default()
-                .bg(Color::Green)
+                .bg(highlight_color)
                 .fg(Color::Black)
                 .add_modifier(Modifier::BOLD | Modifier::REVERSED),
         )
-        .highlight_symbol("▶ ");
+        .highlight_symbol(app_state.config.highlight_symbol());
 
     let mut list_state = ListState::default();
     if !app_state.selection.items.is_empty() {
-        list_state.select(Some(app_state.selection.right_idx));
+        let selected = app_state.selection.right_idx;
+        let rows = area.height.saturating_sub(2) as usize;
+        list_state = list_state.with_offset(selection_offset(
+            selected,
+            app_state.selection.items.len(),
+            rows,
+        ));
+        list_state.select(Some(selected));
     } else {
         list_state.select(None);
     }
     frame.render_stateful_widget(list, area, &mut list_state);
 }
 
-fn render_footer(frame: &mut Frame, area: ratatui::prelude::Rect, view_model: &ViewModel) {
-    let footer = Paragraph::new(Line::from(vec![Span::raw(
-        view_model.status_message.clone(),
-    )]))
-    .alignment(Alignment::Center)
-    .block(Block::default().borders(Borders::NONE));
-    frame.render_widget(footer, area);
+/// First visible row of a list of `len` items showing `rows` at a time,
+/// keeping the `selected` item in view and centered where the list allows.
+pub fn selection_offset(selected: usize, len: usize, rows: usize) -> usize {
+    selected
+        .saturating_sub(rows / 2)
+        .min(len.saturating_sub(rows))
 }
 
-fn render_pads(
+/// Footer prefix while the audio thread is gone.
+pub const AUDIO_LOST_WARNING: &str = "NO AUDIO (Ctrl+R in Pads restarts) ";
+
+fn render_footer(
     frame: &mut Frame,
     area: ratatui::prelude::Rect,
-    _view_model: &ViewModel,
+    view_model: &ViewModel,
     app_state: &ApplicationState,
 ) {
-    // Determine grid based on number of pads
-    let total = app_state.pads.key_to_slot.len().max(1);
-    let cols = total.clamp(1, 10) as u16; // cap columns for readability
-    let rows = ((total as f32) / (cols as f32)).ceil() as u16;
+    let mut spans = Vec::new();
+    if view_model.audio_lost {
+        spans.push(Span::styled(
+            AUDIO_LOST_WARNING,
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+    if let Some((loaded, total)) = app_state.pads.preload_progress {
+        spans.push(Span::styled(
+            format!("Loading {loaded}/{total} "),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if app_state.master_muted {
+        spans.push(Span::styled(
+            "MUTED ",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+    if view_model.mode == Mode::Pads {
+        spans.push(Span::styled(
+            format!("[{}] ", app_state.config.pad_layout.name()),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    let message = &view_model.status_message;
+    let room = (area.width as usize).saturating_sub(spans.iter().map(Span::width).sum());
+    let scrolling = app_state.config.marquee && message.chars().count() > room;
+    if scrolling {
+        let offset = view_model.marquee.advance(message, Instant::now());
+        spans.push(Span::raw(marquee_window(message, room, offset)));
+    } else {
+        view_model.marquee.reset();
+        spans.push(Span::raw(message.clone()));
+    }
+    let footer = Paragraph::new(Line::from(spans))
+        .alignment(if scrolling {
+            Alignment::Left
+        } else {
+            Alignment::Center
+        })
+        .block(Block::default().borders(Borders::NONE));
+    frame.render_widget(footer, area);
+}
+
+/// Columns of blank space between the end of a scrolling message and its
+/// start coming round again.
+const MARQUEE_GAP: usize = 4;
+
+/// The `width` characters of `text` visible when scrolled `offset` columns
+/// to the left, wrapping round after a short gap.
+pub fn marquee_window(text: &str, width: usize, offset: usize) -> String {
+    let chars: Vec<char> = text
+        .chars()
+        .chain(std::iter::repeat_n(' ', MARQUEE_GAP))
+        .collect();
+    chars
+        .iter()
+        .cycle()
+        .skip(offset % chars.len())
+        .take(width)
+        .collect()
+}
+
+/// Pad grid as (rows, cols) for `total` pads. A preferred column count
+/// (e.g. 4 for an MPC-style 4x4) is used when set, otherwise the pads fill
+/// one row and wrap; either way there are no more columns than pads or
+/// than `max_cols`.
+pub fn compute_grid(total: usize, preferred_cols: Option<u16>, max_cols: u16) -> (u16, u16) {
+    let total = total.max(1);
+    let limit = total.min(usize::from(max_cols.max(1)));
+    let cols = preferred_cols.map_or(total, usize::from).clamp(1, limit) as u16;
+    (total.div_ceil(usize::from(cols)) as u16, cols)
+}
+
+fn render_pads(frame: &mut Frame, area: ratatui::prelude::Rect, app_state: &ApplicationState) {
+    // Determine grid based on number of pads, keeping cells wide enough
+    // to read
+    let (rows, cols) = compute_grid(
+        app_state.pads.key_to_slot.len(),
+        app_state.config.pad_grid_columns,
+        app_state
+            .config
+            .max_pad_columns()
+            .min(area.width / MIN_PAD_WIDTH),
+    );
 
     // Build column constraints
     let mut col_constraints = Vec::with_capacity(cols as usize);
@@ -197,12 +649,12 @@ fn render_pads(
         .constraints(vec![Constraint::Percentage(100 / rows); rows as usize])
         .split(area);
 
-    // Flatten key/slot items in a stable order
+    // Lay pads out in keyboard order so each sits where its key does
     let items: Vec<(char, String)> = app_state
         .pads
-        .key_to_slot
-        .iter()
-        .map(|(k, slot)| (*k, slot.file_name.clone()))
+        .keys_in_layout_order()
+        .into_iter()
+        .map(|k| (k, app_state.pads.key_to_slot[&k].file_name.clone()))
         .collect();
 
     let mut idx: usize = 0;
@@ -225,14 +677,14 @@ fn render_pads(
             let mut block = Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Green));
-            // Active highlight (revert after 150ms from last press)
-            let is_active = app_state
-                .pads
-                .last_press_ms
-                .get(key)
-                .map(|t| now_ms.saturating_sub(*t) <= 150)
-                .unwrap_or(false);
-            if is_active {
+            if app_state.pads.focused_pad == Some(*key) {
+                block = block.border_style(
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                );
+            }
+            if app_state.pads.is_lit(*key, now_ms, PAD_HIGHLIGHT_MS) {
                 block = block.border_style(
                     Style::default()
                         .fg(Color::Green)
@@ -241,18 +693,53 @@ fn render_pads(
                 );
             }
 
-            // Compose key + filename lines
-            let key_line = Line::from(Span::styled(
-                key.to_string(),
+            // Compose key + filename lines, flagging loop mute/solo, gate,
+            // choke group and mixing bus
+            let mut key_spans = vec![Span::styled(
+                keycap_label(*key),
                 Style::default()
                     .fg(Color::Green)
                     .add_modifier(Modifier::BOLD),
-            ));
+            )];
+            match app_state.pads.mute_of(*key) {
+                PadMute::Muted => key_spans.push(Span::styled(
+                    " M",
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                )),
+                PadMute::Soloed => key_spans.push(Span::styled(
+                    " S",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                PadMute::Normal => {}
+            }
+            if app_state.pads.is_gated(*key) {
+                key_spans.push(Span::styled(" G", Style::default().fg(Color::Cyan)));
+            }
+            if let Some(group) = app_state.pads.key_to_slot[key].choke_group {
+                key_spans.push(Span::styled(
+                    format!(" C{group}"),
+                    Style::default().fg(Color::Magenta),
+                ));
+            }
+            let bus = app_state.pads.bus_for(*key);
+            if let Some(label) = bus_label(bus, app_state.bus_level(bus)) {
+                key_spans.push(Span::styled(
+                    format!(" {label}"),
+                    Style::default().fg(Color::Blue),
+                ));
+            }
+            let key_line = Line::from(key_spans);
             let name_line = Line::from(Span::styled(
                 truncate_middle(file_name, 18),
                 Style::default().fg(Color::Green),
             ));
-            let para = Paragraph::new(vec![key_line, name_line])
+            let meter_line = Line::from(Span::styled(
+                level_meter(app_state.pads.level_for(*key), PAD_METER_WIDTH),
+                Style::default().fg(Color::Green),
+            ));
+            let para = Paragraph::new(vec![key_line, name_line, meter_line])
                 .alignment(Alignment::Center)
                 .block(block);
             frame.render_widget(para, cell);
@@ -260,6 +747,148 @@ fn render_pads(
     }
 }
 
+/// Badge for a pad's mixing bus, e.g. "B2", "B1 60%" or "B1 off" when
+/// muted. Pads on bus 0 at its default level show none.
+pub fn bus_label(bus: u8, level: BusLevel) -> Option<String> {
+    if level.muted {
+        Some(format!("B{bus} off"))
+    } else if level.volume < 1.0 {
+        Some(format!("B{bus} {}%", (level.volume * 100.0).round() as u32))
+    } else if bus != 0 {
+        Some(format!("B{bus}"))
+    } else {
+        None
+    }
+}
+
+/// Cells in a pad's level meter.
+const PAD_METER_WIDTH: usize = 8;
+
+/// Level meter of `width` cells, filled in proportion to `level`
+/// (0.0..=1.0); silent pads show an empty meter.
+pub fn level_meter(level: f32, width: usize) -> String {
+    let filled = (level.clamp(0.0, 1.0) * width as f32).round() as usize;
+    "▮".repeat(filled) + &" ".repeat(width - filled)
+}
+
+/// Label shown on a pad's keycap.
+///
+/// Letters are shown as uppercase single chars; punctuation keys get a short
+/// descriptor next to the char so they are easy to spot.
+pub fn keycap_label(c: char) -> String {
+    let name = match c {
+        ';' => "semi",
+        ',' => "comma",
+        '.' => "dot",
+        '/' => "slash",
+        _ => return c.to_uppercase().to_string(),
+    };
+    format!("{c} {name}")
+}
+
+fn render_arrange(
+    frame: &mut Frame,
+    area: ratatui::prelude::Rect,
+    view_model: &ViewModel,
+    app_state: &ApplicationState,
+) {
+    let mut block = Block::default()
+        .title(ARRANGE_TITLE)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green))
+        .padding(Padding {
+            left: 1,
+            right: 1,
+            top: 0,
+            bottom: 0,
+        });
+    let rests = app_state.rest_regions();
+    if !rests.is_empty() {
+        let width = area.width.saturating_sub(4) as usize;
+        let strip = rest_strip(rests, app_state.loop_state_dto().loop_length, width);
+        block = block.title_bottom(Line::from(Span::styled(
+            strip,
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let tracks = app_state.tracks_snapshot();
+    if tracks.is_empty() {
+        let empty = Paragraph::new(Line::from(Span::styled(
+            "No tracks recorded yet",
+            Style::default().fg(Color::Green),
+        )))
+        .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let renaming = view_model.text_capture.then_some(view_model.arrange_cursor);
+    let items: Vec<ListItem> = tracks
+        .iter()
+        .map(|track| {
+            let name = if renaming == Some(track.index) {
+                format!("{}▏", view_model.text_draft.value())
+            } else {
+                track.name.clone().unwrap_or_default()
+            };
+            let style = if track.muted {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(Color::Green)
+            };
+            let mut spans = vec![
+                Span::styled(
+                    format!("Track {:<3}", track.index + 1),
+                    style.add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!(" {:<12}", name), style),
+                Span::styled(format!(" {:>3} events", track.event_count), style),
+                Span::styled(
+                    format!("  vol {:>3}%", (track.volume * 100.0).round() as u32),
+                    style,
+                ),
+            ];
+            if track.muted {
+                spans.push(Span::styled("  [muted]", style));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(Color::Green)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        )
+        .highlight_symbol("▶ ");
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(view_model.arrange_cursor.min(tracks.len() - 1)));
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
+
+/// Timeline strip of `width` cells: `░` where the cell's midpoint falls in
+/// a rest region, `─` elsewhere.
+pub fn rest_strip(rests: &[RestRegion], loop_length: Duration, width: usize) -> String {
+    if loop_length.is_zero() {
+        return "─".repeat(width);
+    }
+    (0..width)
+        .map(|cell| {
+            let mid = loop_length.mul_f64((cell as f64 + 0.5) / width as f64);
+            if rests.iter().any(|rest| rest.contains(mid)) {
+                '░'
+            } else {
+                '─'
+            }
+        })
+        .collect()
+}
+
 fn render_summary_box(
     frame: &mut Frame,
     area: Rect,
@@ -292,8 +921,10 @@ fn render_summary_box(
         view_model.popup_focus(),
         PopupFocus::PopupFieldBpm
             | PopupFocus::PopupFieldBars
+            | PopupFocus::PopupFieldCountIn
             | PopupFocus::PopupOk
             | PopupFocus::PopupCancel
+            | PopupFocus::PopupClick
     );
     let selected_fill = Color::Rgb(40, 80, 40);
 
@@ -303,7 +934,7 @@ fn render_summary_box(
             Style::default().fg(Color::Green),
             Some(Style::default().bg(selected_fill)),
         )
-    } else if matches!(view_model.popup_focus(), PopupFocus::SummaryBox) {
+    } else if view_model.pads_panel_focus == PadsPanel::Summary {
         (
             Borders::ALL,
             Style::default()
@@ -315,8 +946,9 @@ fn render_summary_box(
         (Borders::NONE, Style::default(), None)
     };
 
-    let content_lines = 3;
-    let minimal_height = content_lines + 2;
+    let content_lines = 4;
+    // One more line for the loop progress bar
+    let minimal_height = content_lines + 1 + 2;
     let focus_rect = Rect {
         x: ring_rect.x,
         y: ring_rect.y,
@@ -352,19 +984,182 @@ fn render_summary_box(
         Line::from(Span::styled("bpm:", Style::default().fg(Color::Green))),
         Line::from(Span::styled("bars:", Style::default().fg(Color::Green))),
         Line::from(Span::styled("state:", Style::default().fg(Color::Green))),
+        Line::from(Span::styled("pos:", Style::default().fg(Color::Green))),
     ])
     .alignment(Alignment::Left);
 
+    let mut bpm_spans = Vec::new();
+    if app_state.metronome_enabled() {
+        bpm_spans.push(Span::styled(
+            "CLICK ",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if app_state.tempo_locked {
+        bpm_spans.push(Span::styled(
+            "LOCKED ",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    bpm_spans.push(Span::styled(
+        app_state.get_bpm().to_string(),
+        Style::default().fg(Color::Green),
+    ));
     let mut value_lines = vec![
+        Line::from(bpm_spans),
         Line::from(Span::styled(
-            app_state.get_bpm().to_string(),
-            Style::default().fg(Color::Green),
-        )),
-        Line::from(Span::styled(
-            app_state.get_bars().to_string(),
+            bars_label(
+                app_state.loop_length_setting(),
+                app_state.time_signature().numerator,
+            ),
             Style::default().fg(Color::Green),
         )),
     ];
+    let (label, style) = loop_state_label(app_state);
+    value_lines.push(Line::from(Span::styled(label, style)));
+    value_lines.push(Line::from(Span::styled(
+        position_label_in(
+            &app_state.loop_state_dto(),
+            app_state.loop_length_setting(),
+            app_state.time_signature(),
+        ),
+        Style::default().fg(Color::Green),
+    )));
+    let values = Paragraph::new(value_lines).alignment(Alignment::Right);
+
+    // Render content
+    frame.render_widget(labels, left);
+    frame.render_widget(values, right);
+
+    // Progress bar on the first free line below the readouts, if any; an
+    // empty loop shows how to fill it instead
+    if content_rect.height > content_lines {
+        let progress_area = Rect {
+            y: content_rect.y + content_lines,
+            height: 1,
+            ..content_rect
+        };
+        if let Some(hint) = empty_loop_hint(&app_state.loop_state_dto()) {
+            let hint = Paragraph::new(Span::styled(hint, Style::default().fg(Color::Yellow)))
+                .alignment(Alignment::Center);
+            frame.render_widget(hint, progress_area);
+            return;
+        }
+        let gridlines = if app_state.config.timeline_grid {
+            let beats_per_bar = app_state.time_signature().numerator;
+            timeline_gridlines(
+                app_state.loop_length_setting().beats_per(beats_per_bar),
+                beats_per_bar,
+                progress_area.width as usize,
+            )
+        } else {
+            Vec::new()
+        };
+        render_loop_timeline(
+            frame,
+            progress_area,
+            &app_state.loop_state_dto(),
+            &gridlines,
+        );
+    }
+}
+
+/// How far the playhead is through the current cycle, 0.0..1.0, or `None`
+/// when no loop is running.
+pub fn loop_progress(loop_state: &LoopStateDto) -> Option<f64> {
+    let offset = loop_state.current_offset?;
+    if loop_state.loop_length.is_zero() {
+        return None;
+    }
+    let offset = normalize_offset(offset, loop_state.loop_length);
+    Some(offset.as_secs_f64() / loop_state.loop_length.as_secs_f64())
+}
+
+/// Fewest cells between two gridlines on the loop timeline; denser lines
+/// are left out.
+const MIN_GRID_SPACING: usize = 2;
+
+/// A beat or bar line on the loop timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridLine {
+    pub column: usize,
+    /// Whether the line starts a bar rather than a beat
+    pub bar: bool,
+}
+
+/// Gridlines across a timeline `width` cells wide for a loop of `beats`
+/// beats with `beats_per_bar` to a bar; a loop shorter than a bar gets beat
+/// lines only. The loop start has no line. When beats would sit closer
+/// than `MIN_GRID_SPACING` cells only bar lines are drawn, and when bars
+/// would too, none.
+pub fn timeline_gridlines(beats: u32, beats_per_bar: u16, width: usize) -> Vec<GridLine> {
+    let beats_per_bar = u32::from(beats_per_bar.max(1));
+    let bars = beats.div_ceil(beats_per_bar) as usize;
+    let show_beats = width >= beats as usize * MIN_GRID_SPACING;
+    if beats == 0 || (!show_beats && width < bars * MIN_GRID_SPACING) {
+        return Vec::new();
+    }
+    (1..beats)
+        .map(|beat| GridLine {
+            column: beat as usize * width / beats as usize,
+            bar: beat % beats_per_bar == 0,
+        })
+        .filter(|line| show_beats || line.bar)
+        .collect()
+}
+
+/// Horizontal bar that fills as the loop plays and empties when it wraps.
+/// Draws an empty track while no loop is running.
+pub fn render_loop_progress(frame: &mut Frame, area: Rect, loop_state: &LoopStateDto) {
+    render_loop_timeline(frame, area, loop_state, &[]);
+}
+
+/// The loop progress bar with faint beat (`╎`) and bar (`│`) marks at
+/// `gridlines`.
+pub fn render_loop_timeline(
+    frame: &mut Frame,
+    area: Rect,
+    loop_state: &LoopStateDto,
+    gridlines: &[GridLine],
+) {
+    let width = area.width as usize;
+    let filled = loop_progress(loop_state)
+        .map(|ratio| ((ratio * width as f64) as usize).min(width))
+        .unwrap_or(0);
+    let color = match loop_state.status {
+        LoopStatusDto::Recording => Color::Red,
+        LoopStatusDto::Paused => Color::Yellow,
+        _ => Color::Green,
+    };
+    if gridlines.is_empty() {
+        let bar = Line::from(vec![
+            Span::styled("━".repeat(filled), Style::default().fg(color)),
+            Span::styled(
+                "─".repeat(width - filled),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(bar), area);
+        return;
+    }
+    let cells: Vec<Span> = (0..width)
+        .map(|x| match gridlines.iter().find(|line| line.column == x) {
+            Some(line) if line.bar => Span::styled("│", Style::default().fg(Color::Gray)),
+            Some(_) => Span::styled("╎", Style::default().fg(Color::DarkGray)),
+            None if x < filled => Span::styled("━", Style::default().fg(color)),
+            None => Span::styled("─", Style::default().fg(Color::DarkGray)),
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(Line::from(cells)), area);
+}
+
+/// Loop state word for the transport, with the remaining record or cycle
+/// time while recording or playing, and its style.
+fn loop_state_label(app_state: &ApplicationState) -> (String, Style) {
     let (label, style) = match app_state.loop_state() {
         LoopState::Paused { .. } => (
             "PAUSED",
@@ -385,12 +1180,62 @@ fn render_summary_box(
         LoopState::Ready { .. } => ("ready", Style::default().fg(Color::Green)),
         LoopState::Idle => ("idle", Style::default().fg(Color::White)),
     };
-    value_lines.push(Line::from(Span::styled(label, style)));
-    let values = Paragraph::new(value_lines).alignment(Alignment::Right);
+    let loop_state = app_state.loop_state_dto();
+    let label = match recording_time_left(&loop_state).or_else(|| wrap_countdown(&loop_state)) {
+        Some(left) => format!("{label} · {left}"),
+        None => label.to_string(),
+    };
+    (label, style)
+}
 
-    // Render content
-    frame.render_widget(labels, left);
-    frame.render_widget(values, right);
+/// One-line stand-in for the summary box on short terminals.
+fn render_compact_transport(
+    frame: &mut Frame,
+    area: Rect,
+    view_model: &ViewModel,
+    app_state: &ApplicationState,
+) {
+    let green = Style::default().fg(Color::Green);
+    let (state, state_style) = loop_state_label(app_state);
+    let mut spans = Vec::new();
+    if app_state.tempo_locked {
+        spans.push(Span::styled(
+            "LOCKED ",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    spans.extend([
+        Span::styled(format!("bpm {}", app_state.get_bpm()), green),
+        Span::raw(" · "),
+        Span::styled(
+            format!(
+                "bars {}",
+                bars_label(
+                    app_state.loop_length_setting(),
+                    app_state.time_signature().numerator
+                )
+            ),
+            green,
+        ),
+        Span::raw(" · "),
+        Span::styled(state, state_style),
+        Span::raw(" · "),
+        Span::styled(
+            position_label_in(
+                &app_state.loop_state_dto(),
+                app_state.loop_length_setting(),
+                app_state.time_signature(),
+            ),
+            green,
+        ),
+    ]);
+    let mut line = Line::from(spans);
+    if view_model.pads_panel_focus == PadsPanel::Summary {
+        line = line.style(Style::default().add_modifier(Modifier::REVERSED));
+    }
+    frame.render_widget(Paragraph::new(line).alignment(Alignment::Center), area);
 }
 
 fn render_popup(
@@ -410,6 +1255,133 @@ fn render_popup(
     frame.render_widget_ref(popup, area);
 }
 
+fn render_confirm_popup(frame: &mut Frame, area: Rect, prompt: ConfirmPrompt) {
+    let popup = Popup::new(ConfirmContent { prompt })
+        .title(Line::from("Confirm").centered())
+        .style(Style::default().bg(Color::Rgb(51, 114, 50)))
+        .border_set(DOUBLE)
+        .border_style(Style::default().fg(Color::White))
+        .borders(Borders::ALL);
+
+    frame.render_widget_ref(popup, area);
+}
+
+fn render_help_popup(frame: &mut Frame, area: Rect) {
+    let popup = Popup::new(HelpContent::new())
+        .title(Line::from("Keys (? or Esc to close)").centered())
+        .style(Style::default().bg(Color::Rgb(51, 114, 50)))
+        .border_set(DOUBLE)
+        .border_style(Style::default().fg(Color::White))
+        .borders(Borders::ALL);
+
+    frame.render_widget_ref(popup, area);
+}
+
+/// Every key binding, one titled section per mode.
+#[derive(Debug)]
+struct HelpContent {
+    sections: Vec<(&'static str, Vec<(String, &'static str)>)>,
+}
+
+impl HelpContent {
+    fn new() -> Self {
+        let browse = help_entries_in(&[
+            KeyContext::Explorer,
+            KeyContext::Selection,
+            KeyContext::Browse,
+        ]);
+        let mut pads = vec![
+            ("a-z".to_string(), "play pad"),
+            ("Shift+a-z".to_string(), "mute/solo pad"),
+            ("Alt+a-z".to_string(), "focus pad"),
+        ];
+        pads.extend(help_entries(KeyContext::Pads));
+        Self {
+            sections: vec![
+                ("Browse", browse),
+                ("Pads", pads),
+                ("Arrange", help_entries(KeyContext::Arrange)),
+            ],
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        for (title, entries) in &self.sections {
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::from(Span::styled(
+                *title,
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for row in entries.chunks(HELP_COLUMNS) {
+                let mut spans = Vec::new();
+                for (keys, description) in row {
+                    let description_width = HELP_COLUMN_WIDTH - HELP_KEY_WIDTH - 1;
+                    spans.push(Span::styled(
+                        format!("{keys:>HELP_KEY_WIDTH$} "),
+                        Style::default()
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                    spans.push(Span::raw(format!("{description:<description_width$}")));
+                }
+                lines.push(Line::from(spans));
+            }
+        }
+        lines
+    }
+}
+
+impl SizedWidgetRef for HelpContent {
+    fn width(&self) -> usize {
+        HELP_COLUMN_WIDTH * HELP_COLUMNS + 2
+    }
+
+    fn height(&self) -> usize {
+        self.lines().len()
+    }
+}
+
+impl ratatui::widgets::WidgetRef for HelpContent {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.lines())
+            .style(Style::default().fg(Color::White))
+            .block(Block::default().padding(Padding::horizontal(1)))
+            .render(area, buf);
+    }
+}
+
+#[derive(Debug)]
+struct ConfirmContent {
+    prompt: ConfirmPrompt,
+}
+
+impl SizedWidgetRef for ConfirmContent {
+    fn width(&self) -> usize {
+        self.prompt.message().chars().count() + 4
+    }
+
+    fn height(&self) -> usize {
+        3
+    }
+}
+
+impl ratatui::widgets::WidgetRef for ConfirmContent {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(Line::from(Span::styled(
+            self.prompt.message(),
+            Style::default().fg(Color::White),
+        )))
+        .alignment(Alignment::Center)
+        .block(Block::default().padding(Padding::vertical(1)))
+        .render(area, buf);
+    }
+}
+
 #[derive(Debug)]
 struct PopupContent<'a> {
     view_model: &'a ViewModel,
@@ -421,7 +1393,7 @@ impl<'a> SizedWidgetRef for PopupContent<'a> {
     }
 
     fn height(&self) -> usize {
-        10
+        13
     }
 }
 
@@ -451,7 +1423,7 @@ impl<'a> ratatui::widgets::WidgetRef for PopupContent<'a> {
         let sections = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(6),
+                Constraint::Length(9),
                 Constraint::Length(1),
                 Constraint::Min(0),
             ])
@@ -462,7 +1434,11 @@ impl<'a> ratatui::widgets::WidgetRef for PopupContent<'a> {
 
         let rows = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(3), Constraint::Length(3)])
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(3),
+            ])
             .split(inputs_area);
 
         render_popup_input_row(
@@ -479,14 +1455,22 @@ impl<'a> ratatui::widgets::WidgetRef for PopupContent<'a> {
             self.view_model.draft_bars().value(),
             matches!(self.view_model.popup_focus(), PopupFocus::PopupFieldBars,),
         );
+        render_popup_input_row(
+            buf,
+            rows[2],
+            "count",
+            self.view_model.draft_count_in().value(),
+            matches!(self.view_model.popup_focus(), PopupFocus::PopupFieldCountIn),
+        );
 
         let button_row = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
-                Constraint::Percentage(42),
+                Constraint::Percentage(20),
+                Constraint::Length(10),
                 Constraint::Length(10),
                 Constraint::Length(10),
-                Constraint::Percentage(38),
+                Constraint::Min(0),
             ])
             .split(buttons_area);
 
@@ -502,6 +1486,12 @@ impl<'a> ratatui::widgets::WidgetRef for PopupContent<'a> {
             "[ Cancel ]",
             matches!(self.view_model.popup_focus(), PopupFocus::PopupCancel),
         );
+        render_popup_button(
+            buf,
+            button_row[3],
+            "[ Click ]",
+            matches!(self.view_model.popup_focus(), PopupFocus::PopupClick),
+        );
     }
 }
 
@@ -3,9 +3,12 @@ use std::sync::mpsc;
 
 use ratatui::widgets::{Block, BorderType, Borders};
 use ratatui_explorer::{FileExplorer, Theme as ExplorerTheme};
+use std::time::Duration;
+use termigroove::application::error::AppError;
 use termigroove::application::state::ApplicationState;
-use termigroove::audio::{SenderAudioBus, SystemClock};
-use termigroove::domain::r#loop::LoopEngine;
+use termigroove::application::state::PadsEntry;
+use termigroove::audio::{AudioCommand, SenderAudioBus, SystemClock};
+use termigroove::domain::r#loop::{LoopEngine, LoopSnapshot, RecordedEvent};
 use termigroove::presentation::PopupFocus;
 use termigroove::presentation::ViewModel;
 use termigroove::selection::SelectionModel;
@@ -46,6 +49,82 @@ fn remove_at_cursor_repositions_cursor() {
     assert_eq!(m.right_idx, 1); // now points to b
 }
 
+#[test]
+fn wrapping_moves_jump_between_the_ends() {
+    let mut m = SelectionModel::default();
+    for name in ["a.wav", "b.wav", "c.wav"] {
+        m.add_file(PathBuf::from("/tmp").join(name));
+    }
+    assert_eq!(m.right_idx, 2);
+    m.move_down();
+    assert_eq!(m.right_idx, 2);
+    m.move_down_wrapping();
+    assert_eq!(m.right_idx, 0);
+
+    m.move_up();
+    assert_eq!(m.right_idx, 0);
+    m.move_up_wrapping();
+    assert_eq!(m.right_idx, 2);
+    m.move_up_wrapping();
+    assert_eq!(m.right_idx, 1);
+
+    let mut empty = SelectionModel::default();
+    empty.move_down_wrapping();
+    empty.move_up_wrapping();
+    assert_eq!(empty.right_idx, 0);
+}
+
+#[test]
+fn paging_moves_by_the_step_and_stops_at_the_ends() {
+    let mut m = SelectionModel::default();
+    for idx in 0..25 {
+        m.add_file(PathBuf::from(format!("/tmp/page{idx}.wav")));
+    }
+    m.right_idx = 0;
+    m.page_down(10);
+    assert_eq!(m.right_idx, 10);
+    m.page_down(10);
+    m.page_down(10);
+    assert_eq!(m.right_idx, 24);
+    m.page_up(10);
+    assert_eq!(m.right_idx, 14);
+    m.page_up(100);
+    assert_eq!(m.right_idx, 0);
+
+    let mut empty = SelectionModel::default();
+    empty.page_down(10);
+    assert_eq!(empty.right_idx, 0);
+}
+
+#[test]
+fn fill_to_repeats_the_selection_up_to_capacity() {
+    let mut m = SelectionModel::default();
+    for name in ["a.wav", "b.wav", "c.wav"] {
+        m.add_file(PathBuf::from("/tmp").join(name));
+    }
+    assert_eq!(m.fill_to(8), 5);
+    let names: Vec<_> = m
+        .items
+        .iter()
+        .map(|p| p.file_name().unwrap().to_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "a.wav", "b.wav", "c.wav", "a.wav", "b.wav", "c.wav", "a.wav", "b.wav"
+        ]
+    );
+    assert_eq!(m.fill_to(8), 0);
+
+    // Removing one copy keeps the file selected; unselecting drops them all
+    m.right_idx = 0;
+    m.remove_at_cursor();
+    assert!(m.set.contains(&PathBuf::from("/tmp/a.wav")));
+    m.add_file(PathBuf::from("/tmp/a.wav"));
+    assert!(!m.items.contains(&PathBuf::from("/tmp/a.wav")));
+    assert_eq!(m.items.len(), 5);
+}
+
 #[test]
 fn move_up_down_bounds() {
     let mut m = SelectionModel::default();
@@ -113,7 +192,7 @@ fn bpm_bars_defaults_and_clamping() {
 #[test]
 fn open_and_close_popup_apply_and_discard() {
     let (mut app_state, mut view_model) = setup_test_state();
-    view_model.open_bpm_bars_popup(app_state.get_bpm(), app_state.get_bars());
+    view_model.open_bpm_bars_popup(app_state.get_bpm(), app_state.get_bars(), 4);
     assert!(view_model.is_bpm_popup_open());
     assert_eq!(view_model.popup_focus(), PopupFocus::PopupFieldBpm);
 
@@ -132,7 +211,7 @@ fn open_and_close_popup_apply_and_discard() {
 
     let bpm_before = app_state.get_bpm();
     let bars_before = app_state.get_bars();
-    view_model.open_bpm_bars_popup(app_state.get_bpm(), app_state.get_bars());
+    view_model.open_bpm_bars_popup(app_state.get_bpm(), app_state.get_bars(), 4);
     set_input_text(view_model.draft_bpm_mut(), "240");
     set_input_text(view_model.draft_bars_mut(), "32");
     // Don't apply (simulating discard)
@@ -147,14 +226,14 @@ fn opening_popup_copies_current_values_into_drafts() {
 
     app_state.set_bpm(140);
     app_state.set_bars(12);
-    view_model.open_bpm_bars_popup(app_state.get_bpm(), app_state.get_bars());
+    view_model.open_bpm_bars_popup(app_state.get_bpm(), app_state.get_bars(), 4);
     assert_eq!(view_model.draft_bpm().value(), "140");
     assert_eq!(view_model.draft_bars().value(), "12");
     view_model.close_bpm_bars_popup();
 
     app_state.set_bpm(200);
     app_state.set_bars(8);
-    view_model.open_bpm_bars_popup(app_state.get_bpm(), app_state.get_bars());
+    view_model.open_bpm_bars_popup(app_state.get_bpm(), app_state.get_bars(), 4);
     assert_eq!(view_model.draft_bpm().value(), "200");
     assert_eq!(view_model.draft_bars().value(), "8");
 }
@@ -162,7 +241,7 @@ fn opening_popup_copies_current_values_into_drafts() {
 #[test]
 fn close_popup_apply_clamps_and_resets_state() {
     let (mut app_state, mut view_model) = setup_test_state();
-    view_model.open_bpm_bars_popup(app_state.get_bpm(), app_state.get_bars());
+    view_model.open_bpm_bars_popup(app_state.get_bpm(), app_state.get_bars(), 4);
 
     set_input_text(view_model.draft_bpm_mut(), "999");
     set_input_text(view_model.draft_bars_mut(), "0");
@@ -183,9 +262,650 @@ fn close_popup_apply_clamps_and_resets_state() {
     assert_eq!(view_model.draft_bars().value(), "");
 }
 
+#[test]
+fn undo_tempo_change_restores_values_and_tracks() {
+    let (mut app_state, _view_model) = setup_test_state();
+    let recorded = LoopSnapshot {
+        loop_length: Duration::from_secs(2),
+        tracks: vec![
+            vec![
+                RecordedEvent {
+                    key: 'q',
+                    offset: Duration::from_millis(0),
+                    reversed: false,
+                },
+                RecordedEvent {
+                    key: 'w',
+                    offset: Duration::from_millis(750),
+                    reversed: false,
+                },
+            ]
+            .into(),
+            vec![RecordedEvent {
+                key: 'e',
+                offset: Duration::from_millis(1_250),
+                reversed: false,
+            }]
+            .into(),
+        ],
+    };
+    app_state.restore_loop(recorded.clone());
+
+    assert!(app_state.apply_tempo(Some(90), Some(4)));
+    assert_eq!(app_state.get_bpm(), 90);
+    assert_eq!(app_state.get_bars(), 4);
+    assert!(app_state.loop_snapshot().tracks.is_empty());
+
+    assert!(app_state.undo_tempo_change());
+    assert_eq!(app_state.get_bpm(), 120);
+    assert_eq!(app_state.get_bars(), 16);
+    assert_eq!(app_state.loop_snapshot(), recorded);
+
+    // History is exhausted after a single undo
+    assert!(!app_state.undo_tempo_change());
+}
+
+#[test]
+fn unchanged_tempo_is_not_recorded_for_undo() {
+    let (mut app_state, _view_model) = setup_test_state();
+    assert!(!app_state.apply_tempo(Some(120), Some(16)));
+    assert!(!app_state.undo_tempo_change());
+}
+
 fn set_input_text(input: &mut TextInput, value: &str) {
     input.reset();
     for ch in value.chars() {
         let _ = input.handle(InputRequest::InsertChar(ch));
     }
 }
+
+#[test]
+fn bus_routing_and_levels_produce_audio_commands_and_restore() {
+    use termigroove::audio::{AudioCommand, BusLevel};
+
+    let (mut app_state, _view_model) = setup_test_state();
+    assert_eq!(app_state.bus_level(0), BusLevel::default());
+
+    assert_eq!(
+        app_state.assign_pad_bus('q', 1),
+        AudioCommand::SetPadBus { key: 'q', bus: 1 }
+    );
+    assert_eq!(
+        app_state.set_bus_volume(1, 0.5),
+        AudioCommand::SetBusLevel {
+            bus: 1,
+            level: BusLevel {
+                volume: 0.5,
+                muted: false
+            }
+        }
+    );
+    let muted = BusLevel {
+        volume: 0.5,
+        muted: true,
+    };
+    assert_eq!(
+        app_state.toggle_bus_mute(1),
+        AudioCommand::SetBusLevel {
+            bus: 1,
+            level: muted
+        }
+    );
+
+    let restore = app_state.pad_restore_commands();
+    assert!(restore.contains(&AudioCommand::SetPadBus { key: 'q', bus: 1 }));
+    assert!(restore.contains(&AudioCommand::SetBusLevel {
+        bus: 1,
+        level: muted
+    }));
+
+    // Routing back to bus 0 drops the override
+    app_state.assign_pad_bus('q', 0);
+    assert!(app_state.pads.bus.is_empty());
+}
+
+#[test]
+fn pad_restore_commands_reapply_the_master_gain() {
+    use termigroove::audio::AudioCommand;
+
+    let (mut app_state, _) = setup_test_state();
+    assert!(
+        !app_state
+            .pad_restore_commands()
+            .iter()
+            .any(|cmd| matches!(cmd, AudioCommand::SetMasterGain(_)))
+    );
+    app_state.config.master_gain = Some(0.6);
+    assert!(
+        app_state
+            .pad_restore_commands()
+            .contains(&AudioCommand::SetMasterGain(0.6))
+    );
+}
+
+#[test]
+fn pad_restore_commands_reapply_a_perceptual_volume_curve() {
+    use termigroove::audio::VolumeCurve;
+
+    let (mut app_state, _) = setup_test_state();
+    assert!(
+        !app_state
+            .pad_restore_commands()
+            .iter()
+            .any(|cmd| matches!(cmd, AudioCommand::SetVolumeCurve(_)))
+    );
+    app_state.config.volume_curve = VolumeCurve::Perceptual;
+    assert!(
+        app_state
+            .pad_restore_commands()
+            .contains(&AudioCommand::SetVolumeCurve(VolumeCurve::Perceptual))
+    );
+}
+
+#[test]
+fn pad_levels_jump_up_and_decay_to_silence() {
+    use termigroove::application::state::PadsState;
+
+    let mut pads = PadsState::default();
+    assert_eq!(pads.level_for('q'), 0.0);
+
+    pads.apply_pad_level('q', 0.8);
+    assert_eq!(pads.level_for('q'), 0.8);
+    // Quieter reports do not pull the meter down; it falls on its own
+    pads.apply_pad_level('q', 0.2);
+    assert_eq!(pads.level_for('q'), 0.8);
+    pads.decay_levels(Duration::from_millis(100));
+    assert!((pads.level_for('q') - 0.6).abs() < 1e-6);
+    // A louder hit jumps straight back up
+    pads.apply_pad_level('q', 1.0);
+    assert_eq!(pads.level_for('q'), 1.0);
+
+    // A pad reported silent keeps falling until it reaches zero
+    pads.apply_pad_level('q', 0.0);
+    pads.decay_levels(Duration::from_millis(400));
+    assert!((pads.level_for('q') - 0.2).abs() < 1e-6);
+    pads.decay_levels(Duration::from_millis(400));
+    assert_eq!(pads.level_for('q'), 0.0);
+    assert!(pads.levels.is_empty());
+
+    // Pads that never sounded stay silent
+    pads.apply_pad_level('w', 0.0);
+    assert!(pads.levels.is_empty());
+}
+
+fn temp_sample(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("termigroove-app-state-formats");
+    std::fs::create_dir_all(&dir).expect("create sample dir");
+    let path = dir.join(name);
+    std::fs::write(&path, b"").expect("create sample file");
+    path
+}
+
+/// Write a 16-bit PCM WAV file with silent samples and a metadata chunk
+/// between `fmt ` and `data`.
+fn temp_wav(name: &str, sample_rate: u32, channels: u16, frames: u32) -> PathBuf {
+    let block_align = channels * 2;
+    let data_len = frames * u32::from(block_align);
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(4 + 24 + 12 + 8 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&channels.to_le_bytes());
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(sample_rate * u32::from(block_align)).to_le_bytes());
+    bytes.extend_from_slice(&block_align.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"LIST");
+    bytes.extend_from_slice(&3u32.to_le_bytes());
+    bytes.extend_from_slice(b"abc\0");
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    bytes.resize(bytes.len() + data_len as usize, 0);
+    let path = temp_sample(name);
+    std::fs::write(&path, bytes).expect("write wav");
+    path
+}
+
+#[test]
+fn add_file_reads_wav_duration_and_sample_rate() {
+    use termigroove::selection::SampleInfo;
+
+    let kick = temp_wav("info-kick.wav", 44_100, 1, 35_280);
+    let pad = temp_wav("info-pad.wav", 48_000, 2, 96_000);
+    let broken = temp_sample("info-broken.wav");
+
+    let mut m = SelectionModel::default();
+    for path in [&kick, &pad, &broken] {
+        m.add_file(path.clone());
+    }
+    let info = m.info[&kick];
+    assert_eq!(info.sample_rate, 44_100);
+    assert_eq!(info.duration, Duration::from_millis(800));
+    assert_eq!(info.summary(), "0.8s, 44.1kHz");
+    assert_eq!(m.info[&pad].summary(), "2.0s, 48kHz");
+    // An unreadable header falls back to the bare name
+    assert!(!m.info.contains_key(&broken));
+    assert_eq!(SampleInfo::read(&broken), None);
+
+    m.add_file(kick.clone());
+    assert!(!m.info.contains_key(&kick));
+}
+
+#[test]
+fn supported_audio_accepts_wav_flac_ogg_and_mp3_in_any_case() {
+    use termigroove::application::state::is_supported_audio;
+
+    for name in ["a.wav", "b.FLAC", "c.ogg", "d.Mp3"] {
+        assert!(is_supported_audio(&PathBuf::from(name)), "{name}");
+    }
+    for name in ["e.txt", "f.aiff", "noext"] {
+        assert!(!is_supported_audio(&PathBuf::from(name)), "{name}");
+    }
+}
+
+/// Pads a batch preload loads, in order.
+fn preload_keys(entry: &PadsEntry) -> Vec<char> {
+    match &entry.preload {
+        AudioCommand::PreloadBatch { pads } => pads.iter().map(|(key, _)| *key).collect(),
+        other => panic!("expected a batch preload, got {other:?}"),
+    }
+}
+
+#[test]
+fn enter_pads_maps_a_mixed_format_selection() {
+    let (mut app_state, _view_model) = setup_test_state();
+    for name in ["kick.wav", "snare.flac", "hat.ogg", "vox.mp3"] {
+        app_state.selection.add_file(temp_sample(name));
+    }
+    let entry = app_state.enter_pads().expect("enter pads");
+    assert_eq!(preload_keys(&entry), vec!['q', 'w', 'e', 'r']);
+    assert_eq!(app_state.pads.key_to_slot.len(), 4);
+}
+
+#[test]
+fn enter_pads_rejects_non_audio_in_a_mixed_selection() {
+    let (mut app_state, _view_model) = setup_test_state();
+    app_state.selection.add_file(temp_sample("snare.flac"));
+    let notes = temp_sample("notes.txt");
+    app_state.selection.add_file(notes.clone());
+    let err = app_state.enter_pads().expect_err("txt is not audio");
+    assert_eq!(err, AppError::UnsupportedFile(notes));
+    assert_eq!(
+        err.to_string(),
+        "Unsupported file (only wav/flac/ogg/mp3): notes.txt"
+    );
+}
+
+#[test]
+fn enter_pads_without_selection_reports_no_selection() {
+    let (mut app_state, _view_model) = setup_test_state();
+    let err = app_state.enter_pads().expect_err("nothing selected");
+    assert_eq!(err, AppError::NoSelection);
+    assert!(app_state.pads.key_to_slot.is_empty());
+}
+
+#[test]
+fn sample_rate_mismatch_flags_only_far_apart_rates() {
+    use termigroove::application::state::sample_rate_mismatch;
+
+    assert_eq!(sample_rate_mismatch([]), None);
+    assert_eq!(sample_rate_mismatch([44_100, 44_100]), None);
+    // Common CD/video pairing is close enough
+    assert_eq!(sample_rate_mismatch([44_100, 48_000]), None);
+    assert_eq!(
+        sample_rate_mismatch([48_000, 22_050, 44_100]),
+        Some((22_050, 48_000))
+    );
+    assert_eq!(sample_rate_mismatch([8_000, 96_000]), Some((8_000, 96_000)));
+}
+
+#[test]
+fn sample_rate_hint_waits_for_every_pad_to_report() {
+    let (mut app_state, _view_model) = setup_test_state();
+    app_state.selection.add_file(temp_sample("lofi.wav"));
+    app_state.selection.add_file(temp_sample("hifi.flac"));
+    app_state.enter_pads().expect("enter pads");
+
+    assert_eq!(app_state.pads.apply_sample_rate('q', 22_050), None);
+    // Pads that are not mapped are ignored
+    assert_eq!(app_state.pads.apply_sample_rate('z', 8_000), None);
+    let hint = app_state
+        .pads
+        .apply_sample_rate('w', 48_000)
+        .expect("rates are far apart");
+    assert!(hint.contains("22050 to 48000 Hz"));
+    // Reported once, not on every repeat
+    assert_eq!(app_state.pads.apply_sample_rate('w', 48_000), None);
+}
+
+#[test]
+fn hit_log_records_live_hits_only_while_enabled() {
+    let (mut app_state, _view_model) = setup_test_state();
+
+    app_state.log_live_hit('q');
+    assert!(app_state.hit_log().is_none());
+
+    app_state.start_hit_log();
+    for key in ['q', 'w', 'q'] {
+        app_state.log_live_hit(key);
+        std::thread::sleep(Duration::from_millis(2));
+    }
+    let log = app_state.stop_hit_log().expect("log was running");
+    let keys: Vec<char> = log.hits().iter().map(|hit| hit.key).collect();
+    assert_eq!(keys, vec!['q', 'w', 'q']);
+    assert!(
+        log.hits()
+            .windows(2)
+            .all(|pair| pair[0].time < pair[1].time)
+    );
+    assert_eq!(log.to_text().lines().count(), 3);
+
+    app_state.log_live_hit('e');
+    assert!(app_state.hit_log().is_none());
+    assert_eq!(log.hits().len(), 3);
+}
+
+#[test]
+fn time_signature_sets_a_one_bar_count_in_and_loop_length() {
+    use termigroove::domain::r#loop::LoopState;
+    use termigroove::domain::timing::TimeSignature;
+
+    let (mut app_state, _view_model) = setup_test_state();
+    assert_eq!(app_state.time_signature(), TimeSignature::default());
+
+    app_state.set_time_signature(TimeSignature::new(3, 4).unwrap());
+    app_state.set_bpm(120);
+    app_state.set_bars(2);
+    assert_eq!(app_state.get_count_in(), 3);
+
+    app_state.handle_loop_space();
+    match app_state.loop_state() {
+        LoopState::Ready {
+            ticks_remaining,
+            loop_length,
+        } => {
+            assert_eq!(ticks_remaining, 3);
+            assert_eq!(loop_length, Duration::from_secs(3));
+        }
+        other => panic!("expected count-in, got {other:?}"),
+    }
+}
+
+#[test]
+fn fill_selection_to_pads_maps_a_copy_to_every_pad() {
+    use termigroove::application::state::pad_capacity;
+
+    let (mut app_state, _view_model) = setup_test_state();
+    for name in ["fill-kick.wav", "fill-snare.wav", "fill-hat.wav"] {
+        app_state.selection.add_file(temp_sample(name));
+    }
+
+    let added = app_state.fill_selection_to_pads();
+    assert_eq!(added, pad_capacity() - 3);
+    assert_eq!(app_state.selection.items.len(), pad_capacity());
+    assert_eq!(app_state.selection.items[3], app_state.selection.items[0]);
+    assert_eq!(app_state.fill_selection_to_pads(), 0);
+
+    let entry = app_state.enter_pads().expect("enter pads");
+    assert_eq!(preload_keys(&entry).len(), pad_capacity());
+    assert_eq!(app_state.pads.key_to_slot.len(), pad_capacity());
+    assert_eq!(
+        app_state.pads.key_to_slot[&'q'].path,
+        app_state.pads.key_to_slot[&'r'].path
+    );
+}
+
+#[test]
+fn remap_pad_moves_the_sample_settings_and_loop_hits() {
+    use termigroove::application::state::PadMute;
+    use termigroove::audio::AudioCommand;
+
+    let (mut app_state, _) = setup_test_state();
+    let kick = temp_sample("remap-kick.wav");
+    let snare = temp_sample("remap-snare.wav");
+    app_state.selection.add_file(kick.clone());
+    app_state.selection.add_file(snare);
+    app_state.enter_pads().expect("enter pads");
+    app_state.pads.pan.insert('q', -0.5);
+    app_state.pads.set_choke_group('q', Some(1));
+    assert!(
+        app_state
+            .pad_restore_commands()
+            .contains(&AudioCommand::SetPadChoke {
+                key: 'q',
+                group: Some(1)
+            })
+    );
+    app_state.cycle_pad_mute('q');
+    let hit = |key, ms| RecordedEvent {
+        key,
+        offset: Duration::from_millis(ms),
+        reversed: false,
+    };
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: Duration::from_secs(2),
+        tracks: vec![vec![hit('q', 0), hit('w', 500), hit('q', 1_000)].into()],
+    });
+
+    let remap = app_state.remap_pad('q', 'a').expect("remap");
+    assert_eq!(remap.moved_hits, 2);
+    assert!(remap.commands.contains(&AudioCommand::Stop { key: 'q' }));
+    assert!(remap.commands.contains(&AudioCommand::Preload {
+        key: 'a',
+        path: kick.clone(),
+    }));
+    assert!(remap.commands.contains(&AudioCommand::SetPadPan {
+        key: 'a',
+        pan: -0.5
+    }));
+    assert!(remap.commands.contains(&AudioCommand::SetPadChoke {
+        key: 'a',
+        group: Some(1)
+    }));
+    assert!(remap.commands.contains(&AudioCommand::SetPadChoke {
+        key: 'q',
+        group: None
+    }));
+    assert!(!app_state.pads.key_to_slot.contains_key(&'q'));
+    assert_eq!(app_state.pads.key_to_slot[&'a'].path, kick);
+    assert_eq!(app_state.pads.pan_for('a'), -0.5);
+    assert_eq!(app_state.pads.mute_of('a'), PadMute::Muted);
+    let keys: Vec<char> = app_state.loop_snapshot().tracks[0]
+        .events
+        .iter()
+        .map(|event| event.key)
+        .collect();
+    assert_eq!(keys, ['a', 'w', 'a']);
+}
+
+#[test]
+fn reverse_pad_mapping_swaps_samples_and_loop_hits() {
+    use termigroove::audio::AudioCommand;
+
+    let (mut app_state, _) = setup_test_state();
+    let kick = temp_sample("reverse-kick.wav");
+    let snare = temp_sample("reverse-snare.wav");
+    let hat = temp_sample("reverse-hat.wav");
+    for path in [&kick, &snare, &hat] {
+        app_state.selection.add_file(path.clone());
+    }
+    app_state.enter_pads().expect("enter pads");
+    let hit = |key, ms| RecordedEvent {
+        key,
+        offset: Duration::from_millis(ms),
+        reversed: false,
+    };
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: Duration::from_secs(2),
+        tracks: vec![vec![hit('q', 0), hit('w', 500), hit('e', 1_000)].into()],
+    });
+
+    let remap = app_state.reverse_pad_mapping();
+
+    assert_eq!(app_state.pads.key_to_slot[&'q'].path, hat);
+    assert_eq!(app_state.pads.key_to_slot[&'w'].path, snare);
+    assert_eq!(app_state.pads.key_to_slot[&'e'].path, kick);
+    assert_eq!(
+        remap.commands,
+        vec![
+            AudioCommand::Stop { key: 'e' },
+            AudioCommand::Stop { key: 'q' },
+            AudioCommand::Preload {
+                key: 'q',
+                path: hat
+            },
+            AudioCommand::Preload {
+                key: 'e',
+                path: kick
+            },
+        ]
+    );
+    assert_eq!(remap.moved_hits, 2);
+    let keys: Vec<char> = app_state.loop_snapshot().tracks[0]
+        .events
+        .iter()
+        .map(|event| event.key)
+        .collect();
+    assert_eq!(keys, ['e', 'w', 'q']);
+}
+
+#[test]
+fn cycling_pad_layouts_rekeys_pads_in_sample_order() {
+    use termigroove::application::state::PadLayout;
+
+    let (mut app_state, _) = setup_test_state();
+    let kick = temp_sample("layout-kick.wav");
+    let snare = temp_sample("layout-snare.wav");
+    let hat = temp_sample("layout-hat.wav");
+    for path in [&kick, &snare, &hat] {
+        app_state.selection.add_file(path.clone());
+    }
+    app_state.enter_pads().expect("enter pads");
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: Duration::from_secs(2),
+        tracks: vec![
+            vec![RecordedEvent {
+                key: 'w',
+                offset: Duration::from_millis(500),
+                reversed: false,
+            }]
+            .into(),
+        ],
+    });
+    let mapping = |app_state: &ApplicationState| {
+        app_state
+            .pads
+            .key_to_slot
+            .iter()
+            .map(|(key, slot)| (*key, slot.path.clone()))
+            .collect::<Vec<_>>()
+    };
+    let keyed = |keys: [char; 3]| {
+        let mut pads = vec![
+            (keys[0], kick.clone()),
+            (keys[1], snare.clone()),
+            (keys[2], hat.clone()),
+        ];
+        pads.sort();
+        pads
+    };
+    assert_eq!(mapping(&app_state), keyed(['q', 'w', 'e']));
+
+    let remap = app_state.cycle_pad_layout();
+    assert_eq!(app_state.config.pad_layout, PadLayout::HomeRow);
+    assert_eq!(mapping(&app_state), keyed(['a', 's', 'd']));
+    assert!(remap.commands.contains(&AudioCommand::Preload {
+        key: 's',
+        path: snare.clone()
+    }));
+    // The recorded snare hit follows its sample
+    assert_eq!(remap.moved_hits, 1);
+    assert_eq!(app_state.loop_snapshot().tracks[0].events[0].key, 's');
+
+    app_state.cycle_pad_layout();
+    assert_eq!(app_state.config.pad_layout, PadLayout::Columns);
+    assert_eq!(mapping(&app_state), keyed(['q', 'a', 'z']));
+
+    app_state.cycle_pad_layout();
+    assert_eq!(app_state.config.pad_layout, PadLayout::Rows);
+    assert_eq!(mapping(&app_state), keyed(['q', 'w', 'e']));
+    assert_eq!(app_state.loop_snapshot().tracks[0].events[0].key, 'w');
+}
+
+#[test]
+fn remap_pad_rejects_taken_and_non_pad_keys() {
+    let (mut app_state, _) = setup_test_state();
+    app_state.selection.add_file(temp_sample("remap-a.wav"));
+    app_state.selection.add_file(temp_sample("remap-b.wav"));
+    app_state.enter_pads().expect("enter pads");
+
+    assert_eq!(
+        app_state.remap_pad('q', 'w').unwrap_err(),
+        AppError::PadKeyTaken('w')
+    );
+    assert_eq!(
+        app_state.remap_pad('q', '1').unwrap_err(),
+        AppError::NotPadKey('1')
+    );
+    assert_eq!(
+        app_state.remap_pad('z', 'a').unwrap_err(),
+        AppError::PadUnmapped('z')
+    );
+    assert_eq!(
+        AppError::PadKeyTaken('w').to_string(),
+        "W already has a pad"
+    );
+    // Failed attempts leave the mapping alone
+    assert!(app_state.pads.key_to_slot.contains_key(&'q'));
+    assert_eq!(app_state.pads.key_to_slot.len(), 2);
+}
+
+#[test]
+fn pad_is_active_within_the_window_after_a_press() {
+    let (mut app_state, _) = setup_test_state();
+    app_state.pads.last_press_ms.insert('q', 1_000);
+
+    assert!(app_state.pads.is_active('q', 1_000, 150));
+    assert!(app_state.pads.is_active('q', 1_150, 150));
+    assert!(!app_state.pads.is_active('q', 1_151, 150));
+    // A press stamped after `now` (clock skew) still counts
+    assert!(app_state.pads.is_active('q', 900, 150));
+    assert!(!app_state.pads.is_active('w', 1_000, 150));
+}
+
+#[test]
+fn pad_stays_lit_until_release_once_releases_are_reported() {
+    let (mut app_state, _) = setup_test_state();
+    app_state.pads.last_press_ms.insert('q', 1_000);
+    app_state.pads.active_keys.insert('q');
+
+    // Timed fallback until the terminal shows it reports releases
+    assert!(!app_state.pads.is_lit('q', 2_000, 150));
+
+    app_state.pads.release('w');
+    assert!(app_state.pads.reports_releases);
+    assert!(app_state.pads.is_lit('q', 2_000, 150));
+    app_state.pads.release('q');
+    assert!(!app_state.pads.is_lit('q', 1_000, 150));
+}
+
+#[test]
+fn enter_pads_tracks_batch_preload_progress_until_done() {
+    let (mut app_state, _view_model) = setup_test_state();
+    for name in [
+        "progress-kick.wav",
+        "progress-snare.wav",
+        "progress-hat.wav",
+    ] {
+        app_state.selection.add_file(temp_sample(name));
+    }
+    app_state.enter_pads().expect("enter pads");
+    assert_eq!(app_state.pads.preload_progress, Some((0, 3)));
+
+    app_state.pads.apply_preload_progress(2, 3);
+    assert_eq!(app_state.pads.preload_progress, Some((2, 3)));
+    app_state.pads.apply_preload_progress(3, 3);
+    assert_eq!(app_state.pads.preload_progress, None);
+}
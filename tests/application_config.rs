@@ -0,0 +1,337 @@
+use termigroove::application::config::{AppConfig, EmptyPadsSpace, LeavePadsVoices};
+use termigroove::application::state::PadLayout;
+use termigroove::audio::{MetronomeConfig, VolumeCurve};
+use termigroove::domain::timing::{QuantizeGrid, TimeSignature};
+
+#[test]
+fn config_defaults_when_lookup_is_empty() {
+    let config = AppConfig::from_lookup(|_| None);
+    assert_eq!(config.empty_pads_space, EmptyPadsSpace::Hint);
+    assert!(!config.downbeat_flash);
+}
+
+#[test]
+fn config_reads_empty_pads_space() {
+    let config = AppConfig::from_lookup(|key| {
+        (key == "TERMIGROOVE_EMPTY_PADS_SPACE").then(|| "record".to_string())
+    });
+    assert_eq!(config.empty_pads_space, EmptyPadsSpace::RecordEmptyLoop);
+
+    // Unknown values keep the default
+    let config = AppConfig::from_lookup(|_| Some("sometimes".to_string()));
+    assert_eq!(config.empty_pads_space, EmptyPadsSpace::Hint);
+}
+
+#[test]
+fn config_reads_leave_pads_voices() {
+    assert_eq!(
+        AppConfig::from_lookup(|_| None).leave_pads,
+        LeavePadsVoices::Stop
+    );
+    let config =
+        AppConfig::from_lookup(|key| (key == "TERMIGROOVE_LEAVE_PADS").then(|| "ring".to_string()));
+    assert_eq!(config.leave_pads, LeavePadsVoices::Ring);
+}
+
+#[test]
+fn config_reads_downbeat_flash() {
+    let config = AppConfig::from_lookup(|key| {
+        (key == "TERMIGROOVE_DOWNBEAT_FLASH").then(|| "on".to_string())
+    });
+    assert!(config.downbeat_flash);
+}
+
+#[test]
+fn config_reads_beat_pulse() {
+    assert!(!AppConfig::from_lookup(|_| None).beat_pulse);
+    let config =
+        AppConfig::from_lookup(|key| (key == "TERMIGROOVE_BEAT_PULSE").then(|| "on".to_string()));
+    assert!(config.beat_pulse);
+}
+
+#[test]
+fn config_reads_timeline_grid() {
+    assert!(!AppConfig::from_lookup(|_| None).timeline_grid);
+    let config = AppConfig::from_lookup(|key| {
+        (key == "TERMIGROOVE_TIMELINE_GRID").then(|| "true".to_string())
+    });
+    assert!(config.timeline_grid);
+}
+
+#[test]
+fn config_reads_debounce_window() {
+    assert_eq!(AppConfig::from_lookup(|_| None).debounce_ms, None);
+    let config =
+        AppConfig::from_lookup(|key| (key == "TERMIGROOVE_DEBOUNCE_MS").then(|| "40".to_string()));
+    assert_eq!(config.debounce_ms, Some(40));
+}
+
+#[test]
+fn config_reads_selection_wrapping() {
+    assert!(!AppConfig::from_lookup(|_| None).wrap_selection);
+    let config = AppConfig::from_lookup(|key| {
+        (key == "TERMIGROOVE_WRAP_SELECTION").then(|| "on".to_string())
+    });
+    assert!(config.wrap_selection);
+}
+
+#[test]
+fn config_reads_default_kit() {
+    let config = AppConfig::from_lookup(|key| match key {
+        "TERMIGROOVE_DEFAULT_KIT_DIR" => Some("/kits/808".to_string()),
+        "TERMIGROOVE_KIT_AUTO_PADS" => Some("true".to_string()),
+        _ => None,
+    });
+    assert_eq!(
+        config.default_kit_dir,
+        Some(std::path::PathBuf::from("/kits/808"))
+    );
+    assert!(config.kit_auto_pads);
+}
+
+#[test]
+fn config_reads_humanize() {
+    let config = AppConfig::from_lookup(|key| match key {
+        "TERMIGROOVE_HUMANIZE_MS" => Some("15".to_string()),
+        "TERMIGROOVE_HUMANIZE_SEED" => Some("1234".to_string()),
+        _ => None,
+    });
+    assert_eq!(config.humanize_ms, 15);
+    assert_eq!(config.humanize_seed, Some(1234));
+}
+
+#[test]
+fn config_reads_trim_silence() {
+    let config = AppConfig::from_lookup(|_| None);
+    assert_eq!(config.trim_silence_threshold(), None);
+
+    let config =
+        AppConfig::from_lookup(|key| (key == "TERMIGROOVE_TRIM_SILENCE").then(|| "on".to_string()));
+    assert_eq!(
+        config.trim_silence_threshold(),
+        Some(termigroove::application::config::DEFAULT_TRIM_THRESHOLD)
+    );
+
+    let config = AppConfig::from_lookup(|key| match key {
+        "TERMIGROOVE_TRIM_SILENCE" => Some("on".to_string()),
+        "TERMIGROOVE_TRIM_THRESHOLD" => Some("0.05".to_string()),
+        _ => None,
+    });
+    assert_eq!(config.trim_silence_threshold(), Some(0.05));
+}
+
+#[test]
+fn config_reads_master_gain_within_unity() {
+    let gain = |value: &str| {
+        AppConfig::from_lookup(|key| (key == "TERMIGROOVE_MASTER_GAIN").then(|| value.to_string()))
+            .master_gain
+    };
+    assert_eq!(gain("0.7"), Some(0.7));
+    assert_eq!(gain("1.5"), None);
+    assert_eq!(gain("loud"), None);
+}
+
+#[test]
+fn config_reads_pad_layout() {
+    let layout = |value: &str| {
+        AppConfig::from_lookup(|key| (key == "TERMIGROOVE_PAD_LAYOUT").then(|| value.to_string()))
+            .pad_layout
+    };
+    assert_eq!(AppConfig::default().pad_layout, PadLayout::Rows);
+    assert_eq!(layout("home"), PadLayout::HomeRow);
+    assert_eq!(layout("Columns"), PadLayout::Columns);
+    assert_eq!(layout("dvorak"), PadLayout::Rows);
+}
+
+#[test]
+fn config_reads_volume_curve() {
+    let curve = |value: &str| {
+        AppConfig::from_lookup(|key| (key == "TERMIGROOVE_VOLUME_CURVE").then(|| value.to_string()))
+            .volume_curve
+    };
+    assert_eq!(AppConfig::default().volume_curve, VolumeCurve::Linear);
+    assert_eq!(curve("Perceptual"), VolumeCurve::Perceptual);
+    assert_eq!(curve("linear"), VolumeCurve::Linear);
+    assert_eq!(curve("loud"), VolumeCurve::Linear);
+}
+
+#[test]
+fn config_reads_metronome_device() {
+    let config = AppConfig::from_lookup(|key| {
+        (key == "TERMIGROOVE_METRONOME_DEVICE").then(|| "Headphones".to_string())
+    });
+    assert_eq!(config.metronome_device.as_deref(), Some("Headphones"));
+}
+
+#[test]
+fn config_reads_metronome_sound() {
+    assert_eq!(AppConfig::default().metronome, None);
+
+    let config = AppConfig::from_lookup(|key| match key {
+        "TERMIGROOVE_METRONOME_FREQ" => Some("800".to_string()),
+        "TERMIGROOVE_METRONOME_VOLUME" => Some("0.15".to_string()),
+        "TERMIGROOVE_METRONOME_ACCENT_FREQ" => Some("1600".to_string()),
+        _ => None,
+    });
+    assert_eq!(
+        config.metronome,
+        Some(MetronomeConfig {
+            freq: 800.0,
+            volume: 0.15,
+            accent_freq: 1_600.0,
+        })
+    );
+
+    // Without an accent the downbeat matches the beat; bad volumes are ignored
+    let config = AppConfig::from_lookup(|key| match key {
+        "TERMIGROOVE_METRONOME_FREQ" => Some("600".to_string()),
+        "TERMIGROOVE_METRONOME_VOLUME" => Some("2".to_string()),
+        _ => None,
+    });
+    let metronome = config.metronome.expect("configured");
+    assert_eq!(metronome.accent_freq, 600.0);
+    assert_eq!(metronome.volume, MetronomeConfig::default().volume);
+}
+
+#[test]
+fn config_reads_max_pad_columns_ignoring_zero() {
+    assert_eq!(AppConfig::default().max_pad_columns(), 10);
+    let config = AppConfig::from_lookup(|key| {
+        (key == "TERMIGROOVE_MAX_PAD_COLUMNS").then(|| "6".to_string())
+    });
+    assert_eq!(config.max_pad_columns(), 6);
+    let config = AppConfig::from_lookup(|key| {
+        (key == "TERMIGROOVE_MAX_PAD_COLUMNS").then(|| "0".to_string())
+    });
+    assert_eq!(config.max_pad_columns(), 10);
+}
+
+#[test]
+fn config_reads_pad_grid_columns_ignoring_zero() {
+    let columns = |value: &str| {
+        AppConfig::from_lookup(|key| {
+            (key == "TERMIGROOVE_PAD_GRID_COLUMNS").then(|| value.to_string())
+        })
+        .pad_grid_columns
+    };
+    assert_eq!(AppConfig::default().pad_grid_columns, None);
+    assert_eq!(columns("4"), Some(4));
+    assert_eq!(columns("0"), None);
+}
+
+#[test]
+fn config_reads_page_step_ignoring_zero() {
+    assert_eq!(AppConfig::default().selection_page_step(), 10);
+    let config =
+        AppConfig::from_lookup(|key| (key == "TERMIGROOVE_PAGE_STEP").then(|| "5".to_string()));
+    assert_eq!(config.selection_page_step(), 5);
+    let config =
+        AppConfig::from_lookup(|key| (key == "TERMIGROOVE_PAGE_STEP").then(|| "0".to_string()));
+    assert_eq!(config.selection_page_step(), 10);
+}
+
+#[test]
+fn config_reads_highlight_symbol_and_color() {
+    let config = AppConfig::default();
+    assert_eq!(config.highlight_symbol(), "▶ ");
+    assert_eq!(config.highlight_color(), "green");
+    let config = AppConfig::from_lookup(|key| match key {
+        "TERMIGROOVE_HIGHLIGHT_SYMBOL" => Some(">".to_string()),
+        "TERMIGROOVE_HIGHLIGHT_COLOR" => Some(" cyan ".to_string()),
+        _ => None,
+    });
+    // A separating space is added after the glyph
+    assert_eq!(config.highlight_symbol(), "> ");
+    assert_eq!(config.highlight_color(), "cyan");
+}
+
+#[test]
+fn config_reads_practice_cycles_ignoring_zero_counts() {
+    let read = |value: &str| {
+        let value = value.to_string();
+        AppConfig::from_lookup(move |key| (key == "TERMIGROOVE_PRACTICE").then(|| value.clone()))
+            .practice_cycles
+    };
+    assert_eq!(read("3:1"), Some((3, 1)));
+    assert_eq!(read(" 1 : 1 "), Some((1, 1)));
+    assert_eq!(read("0:1"), None);
+    assert_eq!(read("2"), None);
+}
+
+#[test]
+fn config_reads_quantize_grid_defaulting_to_off() {
+    let read = |value: &str| {
+        let value = value.to_string();
+        AppConfig::from_lookup(move |key| (key == "TERMIGROOVE_QUANTIZE").then(|| value.clone()))
+            .quantize
+    };
+    assert_eq!(AppConfig::default().quantize, None);
+    assert_eq!(read("1/16"), Some(QuantizeGrid::Sixteenth));
+    assert_eq!(read("off"), None);
+}
+
+#[test]
+fn config_reads_marquee_switch() {
+    assert!(!AppConfig::default().marquee);
+    let config =
+        AppConfig::from_lookup(|key| (key == "TERMIGROOVE_MARQUEE").then(|| "on".to_string()));
+    assert!(config.marquee);
+}
+
+#[test]
+fn config_reads_swing_percentage() {
+    let swing = |value: &str| {
+        AppConfig::from_lookup(|key| (key == "TERMIGROOVE_SWING").then(|| value.to_string())).swing
+    };
+    assert_eq!(swing("60"), Some(60));
+    assert_eq!(swing("25%"), Some(25));
+    assert_eq!(swing("150"), None);
+}
+
+#[test]
+fn config_reads_extend_overdubs_switch() {
+    assert!(!AppConfig::default().extend_overdubs);
+    let config = AppConfig::from_lookup(|key| {
+        (key == "TERMIGROOVE_EXTEND_OVERDUBS").then(|| "on".to_string())
+    });
+    assert!(config.extend_overdubs);
+}
+
+#[test]
+fn config_reads_buffer_count_in_switch() {
+    assert!(!AppConfig::default().buffer_count_in);
+    let config = AppConfig::from_lookup(|key| {
+        (key == "TERMIGROOVE_BUFFER_COUNT_IN").then(|| "on".to_string())
+    });
+    assert!(config.buffer_count_in);
+}
+
+#[test]
+fn config_reads_announce_pads_switch() {
+    assert!(!AppConfig::default().announce_pads);
+    let config = AppConfig::from_lookup(|key| {
+        (key == "TERMIGROOVE_ANNOUNCE_PADS").then(|| "true".to_string())
+    });
+    assert!(config.announce_pads);
+}
+
+#[test]
+fn config_reads_time_signature() {
+    assert_eq!(AppConfig::default().time_signature, None);
+    let config = AppConfig::from_lookup(|key| {
+        (key == "TERMIGROOVE_TIME_SIGNATURE").then(|| "6/8".to_string())
+    });
+    assert_eq!(config.time_signature, TimeSignature::new(6, 8));
+    let config = AppConfig::from_lookup(|key| {
+        (key == "TERMIGROOVE_TIME_SIGNATURE").then(|| "waltz".to_string())
+    });
+    assert_eq!(config.time_signature, None);
+}
+
+#[test]
+fn config_reads_auto_play_switch() {
+    assert!(!AppConfig::default().hold_after_record);
+    let config =
+        AppConfig::from_lookup(|key| (key == "TERMIGROOVE_AUTO_PLAY").then(|| "off".to_string()));
+    assert!(config.hold_after_record);
+}
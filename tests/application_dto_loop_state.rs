@@ -214,6 +214,7 @@ fn test_dto_reverse_conversion_idle() {
         saved_offset: None,
         was_recording: None,
         track_count: 0,
+        remaining: None,
     };
 
     let state: LoopState = dto.into();
@@ -231,6 +232,7 @@ fn test_dto_reverse_conversion_ready() {
         saved_offset: None,
         was_recording: None,
         track_count: 0,
+        remaining: None,
     };
 
     let state: LoopState = dto.into();
@@ -258,6 +260,7 @@ fn test_dto_reverse_conversion_paused() {
         saved_offset: Some(saved_offset),
         was_recording: Some(true),
         track_count: 1,
+        remaining: None,
     };
 
     let state: LoopState = dto.into();
@@ -295,3 +298,58 @@ fn test_dto_conversion_current_offset_calculation() {
         assert!(offset_after >= offset_before);
     }
 }
+
+#[test]
+fn time_remaining_counts_down_mid_cycle() {
+    let clock = FakeClock::new(500);
+    let audio_bus = AudioBusMock::new();
+    let mut engine = LoopEngine::new(clock.clone(), audio_bus);
+    assert_eq!(engine.time_remaining(), None);
+
+    engine.handle_space(TEST_BPM, TEST_BARS);
+    assert_eq!(engine.time_remaining(), None);
+    advance_engine(&clock, &mut engine, 4);
+    assert_eq!(engine.time_remaining(), Some(Duration::from_secs(2)));
+    advance_engine(&clock, &mut engine, 1);
+    assert_eq!(engine.time_remaining(), Some(Duration::from_millis(1_500)));
+
+    engine.record_event('q');
+    advance_engine(&clock, &mut engine, 3);
+    assert!(matches!(engine.state(), LoopState::Playing { .. }));
+    advance_engine(&clock, &mut engine, 1);
+    assert_eq!(engine.time_remaining(), Some(Duration::from_millis(1_500)));
+    assert_eq!(
+        LoopStateDto::from(&engine).remaining,
+        Some(Duration::from_millis(1_500))
+    );
+
+    engine.handle_space(TEST_BPM, TEST_BARS);
+    assert_eq!(engine.time_remaining(), None);
+    assert_eq!(LoopStateDto::from(&engine).remaining, None);
+}
+
+#[test]
+fn time_remaining_at_the_cycle_boundary() {
+    let clock = FakeClock::new(500);
+    let audio_bus = AudioBusMock::new();
+    let mut engine = LoopEngine::new(clock.clone(), audio_bus);
+    engine.handle_space(TEST_BPM, TEST_BARS);
+    advance_engine(&clock, &mut engine, 4);
+    engine.record_event('q');
+
+    // A take at its end has nothing left until `update` commits it
+    for _ in 0..4 {
+        clock.advance();
+    }
+    assert!(matches!(engine.state(), LoopState::Recording { .. }));
+    assert_eq!(engine.time_remaining(), Some(Duration::ZERO));
+
+    // Playback at the boundary has a whole cycle ahead
+    engine.update();
+    assert!(matches!(engine.state(), LoopState::Playing { .. }));
+    assert_eq!(engine.time_remaining(), Some(Duration::from_secs(2)));
+    for _ in 0..4 {
+        clock.advance();
+    }
+    assert_eq!(engine.time_remaining(), Some(Duration::from_secs(2)));
+}
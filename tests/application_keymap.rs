@@ -0,0 +1,111 @@
+//! Tests for the key binding table.
+
+use termigroove::application::dto::input_action::{KeyCode, KeyModifiers};
+use termigroove::application::keymap::{KeyAction, KeyContext, bindings, help_line, lookup};
+
+const CTRL: KeyModifiers = KeyModifiers {
+    control: true,
+    shift: false,
+    alt: false,
+};
+const SHIFT: KeyModifiers = KeyModifiers {
+    control: false,
+    shift: true,
+    alt: false,
+};
+
+#[test]
+fn pads_table_routes_known_keys_to_their_actions() {
+    let none = KeyModifiers::default();
+    let cases = [
+        (KeyCode::Esc, none, KeyAction::BackToBrowse),
+        (KeyCode::Char(' '), none, KeyAction::LoopControl),
+        (KeyCode::Char(' '), CTRL, KeyAction::ClearLoop),
+        (KeyCode::Char('r'), CTRL, KeyAction::PanicReset),
+        (KeyCode::Char('z'), CTRL, KeyAction::UndoTempo),
+        (KeyCode::Left, SHIFT, KeyAction::PanLeft),
+        (KeyCode::Tab, none, KeyAction::CyclePanel),
+        (KeyCode::Backspace, none, KeyAction::DeleteNearestEvent),
+        (KeyCode::Char('='), none, KeyAction::FitBpm),
+    ];
+    for (key, modifiers, action) in cases {
+        assert_eq!(
+            lookup(KeyContext::Pads, &key, modifiers),
+            Some(action),
+            "{key:?} {modifiers:?}"
+        );
+    }
+}
+
+#[test]
+fn unbound_pad_letters_and_missing_modifiers_do_not_match() {
+    let none = KeyModifiers::default();
+    // Plain letters are left to pad triggering
+    assert_eq!(lookup(KeyContext::Pads, &KeyCode::Char('r'), none), None);
+    assert_eq!(lookup(KeyContext::Pads, &KeyCode::Char('m'), CTRL), None);
+    assert_eq!(lookup(KeyContext::Pads, &KeyCode::Char('q'), none), None);
+}
+
+#[test]
+fn arrange_table_routes_track_keys() {
+    let none = KeyModifiers::default();
+    assert_eq!(
+        lookup(KeyContext::Arrange, &KeyCode::Up, SHIFT),
+        Some(KeyAction::MoveTrackUp)
+    );
+    assert_eq!(
+        lookup(KeyContext::Arrange, &KeyCode::Up, none),
+        Some(KeyAction::SelectPrevTrack)
+    );
+    assert_eq!(
+        lookup(KeyContext::Arrange, &KeyCode::Char('m'), none),
+        Some(KeyAction::ToggleTrackMute)
+    );
+    // Arrange keys do not leak into the pad grid
+    assert_eq!(lookup(KeyContext::Pads, &KeyCode::Char('m'), none), None);
+}
+
+#[test]
+fn help_line_is_generated_from_the_table() {
+    let help = help_line(KeyContext::Arrange);
+    assert!(help.starts_with("Esc back, Shift+↑/Shift+↓ move, ↑/↓ select, m mute"));
+    assert!(help.contains("d/Del delete"));
+    // Every binding shows up in its context's help
+    for binding in bindings() {
+        assert!(help_line(binding.context).contains(binding.description));
+    }
+}
+
+#[test]
+fn browse_tables_route_pane_keys_by_focus() {
+    use termigroove::application::keymap::help_entries_in;
+
+    let none = KeyModifiers::default();
+    assert_eq!(
+        lookup(KeyContext::Browse, &KeyCode::Enter, none),
+        Some(KeyAction::EnterPads)
+    );
+    // Space selects in the explorer and unselects in the selected list
+    assert_eq!(
+        lookup(KeyContext::Explorer, &KeyCode::Char(' '), none),
+        Some(KeyAction::SelectFile)
+    );
+    assert_eq!(
+        lookup(KeyContext::Selection, &KeyCode::Char(' '), none),
+        Some(KeyAction::Unselect)
+    );
+    assert_eq!(
+        lookup(KeyContext::Selection, &KeyCode::Char('p'), none),
+        None
+    );
+
+    // Both panes navigate with the arrows; the help lists each chord once
+    let help = help_entries_in(&[
+        KeyContext::Explorer,
+        KeyContext::Selection,
+        KeyContext::Browse,
+    ]);
+    assert_eq!(help[0], ("↑/↓/←/→".to_string(), "navigate"));
+    assert!(help.contains(&("Space/Del/d".to_string(), "unselect")));
+    assert_eq!(help.last(), Some(&("q".to_string(), "quit")));
+}
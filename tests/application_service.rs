@@ -6,11 +6,17 @@
 use ratatui::widgets::{Block, BorderType, Borders};
 use ratatui_explorer::{FileExplorer, Theme as ExplorerTheme};
 use std::sync::mpsc;
+use termigroove::application::config::EmptyPadsSpace;
 use termigroove::application::dto::input_action::{InputAction, KeyCode, KeyModifiers};
+use termigroove::application::error::AppError;
 use termigroove::application::service::{app_service::AppService, effect::Effect};
-use termigroove::application::state::ApplicationState;
-use termigroove::audio::{AudioCommand, SenderAudioBus, SystemClock};
-use termigroove::domain::r#loop::LoopEngine;
+use termigroove::application::state::{ApplicationState, PadMute, PlayMode};
+use termigroove::audio::{
+    AudioCommand, AudioEvent, Bounce, BounceHit, PadEffects, SenderAudioBus, SystemClock,
+};
+use termigroove::domain::r#loop::{
+    LoopEngine, LoopSnapshot, LoopState, RecordedEvent, TrackSnapshot,
+};
 use termigroove::presentation::ViewModel;
 
 fn setup_test_state() -> (ApplicationState, ViewModel, mpsc::Sender<AudioCommand>) {
@@ -28,6 +34,15 @@ fn setup_test_state() -> (ApplicationState, ViewModel, mpsc::Sender<AudioCommand
     (app_state, view_model, tx)
 }
 
+/// Create (or reuse) an empty sample file so `enter_pads` maps it.
+fn sample_file(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join("termigroove-app-service-tests");
+    std::fs::create_dir_all(&dir).expect("create sample dir");
+    let path = dir.join(name);
+    std::fs::write(&path, b"").expect("create sample file");
+    path
+}
+
 #[test]
 fn app_service_is_stateless() {
     // Create two instances - they should behave identically
@@ -43,9 +58,7 @@ fn app_service_is_stateless() {
 fn handle_input_with_space_key_in_pads_mode() {
     let (mut app_state, mut view_model, tx) = setup_test_state();
     // Enter pads mode first
-    app_state
-        .selection
-        .add_file(std::path::PathBuf::from("test.wav"));
+    app_state.selection.add_file(sample_file("test.wav"));
     let _ = app_state.enter_pads();
     view_model.mode = termigroove::presentation::Mode::Pads;
 
@@ -67,9 +80,7 @@ fn handle_input_with_space_key_in_pads_mode() {
 fn handle_input_with_char_key_in_pads_mode_produces_audio_effect() {
     let (mut app_state, mut view_model, tx) = setup_test_state();
     // Enter pads mode and set up a pad mapping
-    app_state
-        .selection
-        .add_file(std::path::PathBuf::from("test.wav"));
+    app_state.selection.add_file(sample_file("test.wav"));
     let _ = app_state.enter_pads();
     view_model.mode = termigroove::presentation::Mode::Pads;
 
@@ -104,9 +115,7 @@ fn handle_input_with_char_key_in_pads_mode_produces_audio_effect() {
 #[test]
 fn handle_input_with_esc_in_pads_mode() {
     let (mut app_state, mut view_model, tx) = setup_test_state();
-    app_state
-        .selection
-        .add_file(std::path::PathBuf::from("test.wav"));
+    app_state.selection.add_file(sample_file("test.wav"));
     let _ = app_state.enter_pads();
     view_model.mode = termigroove::presentation::Mode::Pads;
     assert!(matches!(
@@ -193,9 +202,7 @@ fn handle_input_with_tab_in_browse_mode() {
 #[test]
 fn handle_input_with_control_space_in_pads_mode() {
     let (mut app_state, mut view_model, tx) = setup_test_state();
-    app_state
-        .selection
-        .add_file(std::path::PathBuf::from("test.wav"));
+    app_state.selection.add_file(sample_file("test.wav"));
     let _ = app_state.enter_pads();
     view_model.mode = termigroove::presentation::Mode::Pads;
 
@@ -225,9 +232,7 @@ fn handle_input_with_control_space_in_pads_mode() {
 #[test]
 fn handle_input_with_enter_in_browse_mode() {
     let (mut app_state, mut view_model, tx) = setup_test_state();
-    app_state
-        .selection
-        .add_file(std::path::PathBuf::from("test.wav"));
+    app_state.selection.add_file(sample_file("test.wav"));
 
     let service = AppService::new(tx);
     let input_action = InputAction::KeyPressed {
@@ -418,6 +423,69 @@ fn handle_input_with_down_key_in_right_selected_focus() {
     assert!(effects.is_empty());
 }
 
+#[test]
+fn down_key_wraps_the_selection_when_configured() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    view_model.focus = termigroove::presentation::FocusPane::RightSelected;
+    for name in ["file1.wav", "file2.wav"] {
+        app_state.selection.add_file(std::path::PathBuf::from(name));
+    }
+    let service = AppService::new(tx);
+    let mut press = |app_state: &mut ApplicationState, key| {
+        service
+            .handle_input(
+                app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input");
+    };
+
+    // Clamps by default
+    press(&mut app_state, KeyCode::Down);
+    assert_eq!(app_state.selection.right_idx, 1);
+
+    app_state.config.wrap_selection = true;
+    press(&mut app_state, KeyCode::Down);
+    assert_eq!(app_state.selection.right_idx, 0);
+    press(&mut app_state, KeyCode::Up);
+    assert_eq!(app_state.selection.right_idx, 1);
+}
+
+#[test]
+fn page_keys_move_the_selection_by_the_configured_step() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    view_model.focus = termigroove::presentation::FocusPane::RightSelected;
+    for idx in 0..30 {
+        app_state
+            .selection
+            .add_file(std::path::PathBuf::from(format!("file{idx}.wav")));
+    }
+    app_state.selection.right_idx = 0;
+    app_state.config.page_step = Some(8);
+    let service = AppService::new(tx);
+    let mut press = |app_state: &mut ApplicationState, key| {
+        service
+            .handle_input(
+                app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input");
+    };
+
+    press(&mut app_state, KeyCode::PageDown);
+    assert_eq!(app_state.selection.right_idx, 8);
+    press(&mut app_state, KeyCode::PageUp);
+    assert_eq!(app_state.selection.right_idx, 0);
+}
+
 #[test]
 fn handle_input_with_delete_key_in_right_selected_focus() {
     let (mut app_state, mut view_model, tx) = setup_test_state();
@@ -483,3 +551,2527 @@ fn handle_input_with_d_key_in_right_selected_focus() {
             .any(|e| matches!(e, Effect::StatusMessage(_)))
     );
 }
+
+#[test]
+fn shift_arrows_pan_the_last_pressed_pad() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("test.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+
+    let service = AppService::new(tx);
+    let shift = KeyModifiers {
+        control: false,
+        shift: true,
+        alt: false,
+    };
+
+    // Without a prior trigger there is nothing to pan
+    let effects = service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyPressed {
+                key: KeyCode::Left,
+                modifiers: shift,
+            },
+        )
+        .expect("handle input");
+    assert!(
+        !effects
+            .iter()
+            .any(|e| matches!(e, Effect::AudioCommand(AudioCommand::SetPadPan { .. })))
+    );
+
+    service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyPressed {
+                key: KeyCode::Char('q'),
+                modifiers: KeyModifiers::default(),
+            },
+        )
+        .expect("handle input");
+
+    let effects = service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyPressed {
+                key: KeyCode::Left,
+                modifiers: shift,
+            },
+        )
+        .expect("handle input");
+    assert!(
+        effects.contains(&Effect::AudioCommand(AudioCommand::SetPadPan {
+            key: 'q',
+            pan: -0.1
+        }))
+    );
+    assert_eq!(app_state.pads.pan_for('q'), -0.1);
+}
+
+#[test]
+fn control_r_resets_audio_then_preloads_every_mapped_pad() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    app_state.selection.add_file(sample_file("snare.wav"));
+    let _ = app_state.enter_pads();
+    let _ = app_state.adjust_pad_pan('w', 0.5);
+    view_model.mode = termigroove::presentation::Mode::Pads;
+
+    let service = AppService::new(tx);
+    let effects = service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyPressed {
+                key: KeyCode::Char('r'),
+                modifiers: KeyModifiers {
+                    control: true,
+                    shift: false,
+                    alt: false,
+                },
+            },
+        )
+        .expect("handle input");
+
+    assert_eq!(
+        effects,
+        vec![
+            Effect::ResetAudio,
+            Effect::AudioCommand(AudioCommand::Preload {
+                key: 'q',
+                path: sample_file("kick.wav"),
+            }),
+            Effect::AudioCommand(AudioCommand::Preload {
+                key: 'w',
+                path: sample_file("snare.wav"),
+            }),
+            Effect::AudioCommand(AudioCommand::SetPadPan { key: 'w', pan: 0.5 }),
+        ]
+    );
+    // The pad itself must not be triggered by the combo
+    assert!(app_state.pads.last_press_ms.is_empty());
+}
+
+#[test]
+fn space_with_no_mapped_pads_shows_hint_by_default() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    assert!(app_state.pads.key_to_slot.is_empty());
+
+    let service = AppService::new(tx);
+    let effects = service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyPressed {
+                key: KeyCode::Char(' '),
+                modifiers: KeyModifiers::default(),
+            },
+        )
+        .expect("handle input");
+
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage("Map a pad first.".to_string())]
+    );
+    assert_eq!(app_state.loop_state(), LoopState::Idle);
+}
+
+#[test]
+fn space_with_no_mapped_pads_records_empty_loop_when_configured() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    app_state.config.empty_pads_space = EmptyPadsSpace::RecordEmptyLoop;
+
+    let service = AppService::new(tx);
+    service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyPressed {
+                key: KeyCode::Char(' '),
+                modifiers: KeyModifiers::default(),
+            },
+        )
+        .expect("handle input");
+
+    assert!(matches!(app_state.loop_state(), LoopState::Ready { .. }));
+}
+
+#[test]
+fn popup_plus_minus_and_shift_arrows_step_focused_field_with_clamping() {
+    use termigroove::presentation::{Mode, PopupFocus};
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    view_model.mode = Mode::Pads;
+    let service = AppService::new(tx);
+    let press = |app_state: &mut ApplicationState,
+                 view_model: &mut ViewModel,
+                 key: KeyCode,
+                 shift: bool| {
+        service
+            .handle_input(
+                app_state,
+                view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers {
+                        shift,
+                        ..KeyModifiers::default()
+                    },
+                },
+            )
+            .expect("handle input");
+    };
+
+    view_model.open_bpm_bars_popup(299, 2, 4);
+    assert_eq!(view_model.popup_focus(), PopupFocus::PopupFieldBpm);
+
+    press(&mut app_state, &mut view_model, KeyCode::Char('+'), false);
+    assert_eq!(view_model.draft_bpm().value(), "300");
+    press(&mut app_state, &mut view_model, KeyCode::Up, true);
+    assert_eq!(view_model.draft_bpm().value(), "300"); // clamped at BPM_MAX
+    press(&mut app_state, &mut view_model, KeyCode::Char('-'), false);
+    assert_eq!(view_model.draft_bpm().value(), "299");
+    assert_eq!(view_model.popup_focus(), PopupFocus::PopupFieldBpm);
+
+    // Plain Down still moves focus
+    press(&mut app_state, &mut view_model, KeyCode::Down, false);
+    assert_eq!(view_model.popup_focus(), PopupFocus::PopupFieldBars);
+    press(&mut app_state, &mut view_model, KeyCode::Down, true);
+    assert_eq!(view_model.draft_bars().value(), "1");
+    press(&mut app_state, &mut view_model, KeyCode::Char('-'), false);
+    assert_eq!(view_model.draft_bars().value(), "1"); // clamped at BARS_MIN
+    assert_eq!(view_model.draft_bpm().value(), "299");
+}
+
+#[test]
+fn tempo_popup_click_previews_the_draft_bpm_until_closed() {
+    use termigroove::presentation::{Mode, PopupFocus};
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    view_model.mode = Mode::Pads;
+    let service = AppService::new(tx);
+    let press = |app_state: &mut ApplicationState, view_model: &mut ViewModel, key| {
+        service
+            .handle_input(
+                app_state,
+                view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input")
+    };
+
+    view_model.open_bpm_bars_popup(120, 2, 4);
+    press(&mut app_state, &mut view_model, KeyCode::Backspace);
+    press(&mut app_state, &mut view_model, KeyCode::Backspace);
+    press(&mut app_state, &mut view_model, KeyCode::Backspace);
+    press(&mut app_state, &mut view_model, KeyCode::Char('9'));
+    press(&mut app_state, &mut view_model, KeyCode::Char('0'));
+    for _ in 0..3 {
+        press(&mut app_state, &mut view_model, KeyCode::Down);
+    }
+    press(&mut app_state, &mut view_model, KeyCode::Left);
+    assert_eq!(view_model.popup_focus(), PopupFocus::PopupClick);
+
+    let effects = press(&mut app_state, &mut view_model, KeyCode::Enter);
+    assert!(
+        effects.contains(&Effect::AudioCommand(AudioCommand::PlayMetronome {
+            downbeat: true
+        }))
+    );
+    assert!(effects.contains(&Effect::StatusMessage(
+        "Click preview at 90 BPM".to_string()
+    )));
+    assert!(app_state.is_click_previewing());
+    // The loop and the applied tempo are untouched
+    assert!(view_model.is_popup_open);
+    assert_eq!(app_state.get_bpm(), 120);
+    assert_eq!(app_state.loop_state(), LoopState::Idle);
+
+    press(&mut app_state, &mut view_model, KeyCode::Esc);
+    assert!(!app_state.is_click_previewing());
+}
+
+#[test]
+fn enter_skips_missing_files_and_warns() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    let kick = sample_file("kick.wav");
+    let missing = std::env::temp_dir().join("termigroove-app-service-tests/deleted.wav");
+    let _ = std::fs::remove_file(&missing);
+    app_state.selection.add_file(missing.clone());
+    app_state.selection.add_file(kick.clone());
+
+    let service = AppService::new(tx);
+    let effects = service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyPressed {
+                key: KeyCode::Enter,
+                modifiers: KeyModifiers::default(),
+            },
+        )
+        .expect("handle input");
+
+    assert!(matches!(
+        view_model.mode,
+        termigroove::presentation::Mode::Pads
+    ));
+    // The remaining file takes the first pad
+    assert_eq!(app_state.pads.key_to_slot.len(), 1);
+    assert_eq!(app_state.pads.key_to_slot[&'q'].path, kick);
+    assert!(
+        effects.contains(&Effect::AudioCommand(AudioCommand::PreloadBatch {
+            pads: vec![('q', kick)],
+        }))
+    );
+    assert!(effects.contains(&Effect::StatusMessage(
+        "Skipped missing files: deleted.wav".to_string()
+    )));
+}
+
+#[test]
+fn enter_with_only_missing_files_stays_in_browse() {
+    let (mut app_state, _view_model, _tx) = setup_test_state();
+    let missing = std::env::temp_dir().join("termigroove-app-service-tests/gone.wav");
+    let _ = std::fs::remove_file(&missing);
+    app_state.selection.add_file(missing.clone());
+
+    let err = app_state.enter_pads().expect_err("nothing to map");
+    assert_eq!(err, AppError::FilesMissing(vec![missing]));
+    assert_eq!(err.to_string(), "Selected files no longer exist: gone.wav");
+    assert!(app_state.pads.key_to_slot.is_empty());
+}
+
+#[test]
+fn alt_pad_key_focuses_the_pad_without_playing_it() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    app_state.selection.add_file(sample_file("snare.wav"));
+    let _ = app_state.enter_pads();
+    // Count the repeated W press
+    app_state.set_debounce_ms(0);
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let service = AppService::new(tx);
+    let mut press = |c, control, alt| {
+        service
+            .handle_input(
+                &mut app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key: KeyCode::Char(c),
+                    modifiers: KeyModifiers {
+                        control,
+                        shift: false,
+                        alt,
+                    },
+                },
+            )
+            .expect("handle input")
+    };
+
+    press('w', false, false);
+    let effects = press('q', false, true);
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage("Pad Q focused".to_string())]
+    );
+    // Per-pad commands now act on Q although W was played last
+    let effects = press('p', true, false);
+    assert!(effects.iter().any(|e| matches!(
+        e,
+        Effect::AudioCommand(AudioCommand::Preview { key: 'q', .. })
+    )));
+    // Unmapped keys are ignored
+    assert!(press('z', false, true).is_empty());
+
+    // Playing a pad hands the target back to the last played one
+    press('w', false, false);
+    let effects = press('p', true, false);
+    assert!(effects.iter().any(|e| matches!(
+        e,
+        Effect::AudioCommand(AudioCommand::Preview { key: 'w', .. })
+    )));
+    assert_eq!(app_state.pads.focused_pad, None);
+}
+
+#[test]
+fn control_p_previews_last_pad_with_its_effect_chain() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("test.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let service = AppService::new(tx);
+    let ctrl_p = InputAction::KeyPressed {
+        key: KeyCode::Char('p'),
+        modifiers: KeyModifiers {
+            control: true,
+            shift: false,
+            alt: false,
+        },
+    };
+
+    let effects = service
+        .handle_input(&mut app_state, &mut view_model, ctrl_p.clone())
+        .expect("handle input");
+    assert!(
+        !effects
+            .iter()
+            .any(|e| matches!(e, Effect::AudioCommand(AudioCommand::Preview { .. })))
+    );
+
+    service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyPressed {
+                key: KeyCode::Char('q'),
+                modifiers: KeyModifiers::default(),
+            },
+        )
+        .expect("handle input");
+    let _ = app_state.adjust_pad_pan('q', 0.3);
+
+    let effects = service
+        .handle_input(&mut app_state, &mut view_model, ctrl_p)
+        .expect("handle input");
+    assert!(
+        effects.contains(&Effect::AudioCommand(AudioCommand::Preview {
+            key: 'q',
+            effects: PadEffects {
+                pan: 0.3,
+                reverse: false,
+            },
+        }))
+    );
+    // Previewing neither records nor triggers the pad
+    assert!(
+        !effects
+            .iter()
+            .any(|e| matches!(e, Effect::AudioCommand(AudioCommand::Play { .. })))
+    );
+    assert!(matches!(app_state.loop_state(), LoopState::Idle));
+}
+
+#[test]
+fn alt_m_toggles_master_mute() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("test.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let service = AppService::new(tx);
+    let alt_m = InputAction::KeyPressed {
+        key: KeyCode::Char('m'),
+        modifiers: KeyModifiers {
+            control: false,
+            shift: false,
+            alt: true,
+        },
+    };
+
+    let effects = service
+        .handle_input(&mut app_state, &mut view_model, alt_m.clone())
+        .expect("handle input");
+    assert!(app_state.master_muted);
+    assert!(effects.contains(&Effect::AudioCommand(AudioCommand::SetMasterMute(true))));
+    // The combo must not trigger the 'm' pad
+    assert!(app_state.pads.last_press_ms.is_empty());
+
+    let effects = service
+        .handle_input(&mut app_state, &mut view_model, alt_m)
+        .expect("handle input");
+    assert!(!app_state.master_muted);
+    assert!(effects.contains(&Effect::AudioCommand(AudioCommand::SetMasterMute(false))));
+}
+
+fn kit_dir(name: &str, files: &[&str]) -> std::path::PathBuf {
+    let dir = std::env::temp_dir()
+        .join("termigroove-app-service-tests")
+        .join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("create kit dir");
+    for file in files {
+        std::fs::write(dir.join(file), b"").expect("create kit file");
+    }
+    dir
+}
+
+#[test]
+fn slash_filters_the_explorer_and_esc_clears_it() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    let dir = kit_dir("filter", &["hat.wav", "kick.wav", "kick2.wav", "snare.wav"]);
+    view_model
+        .file_explorer
+        .set_cwd(&dir)
+        .expect("change directory");
+    let service = AppService::new(tx);
+    let press = |app_state: &mut ApplicationState, view_model: &mut ViewModel, key| {
+        service
+            .handle_input(
+                app_state,
+                view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input")
+    };
+
+    press(&mut app_state, &mut view_model, KeyCode::Char('/'));
+    assert!(view_model.filter_open);
+    let mut effects = Vec::new();
+    for c in "kick".chars() {
+        effects = press(&mut app_state, &mut view_model, KeyCode::Char(c));
+    }
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage("Filter: kick".to_string())]
+    );
+    assert_eq!(view_model.current_left_item, Some(dir.join("kick.wav")));
+    press(&mut app_state, &mut view_model, KeyCode::Down);
+    assert_eq!(view_model.current_left_item, Some(dir.join("kick2.wav")));
+
+    // Enter hands the keys back with the filter kept, so Space selects
+    press(&mut app_state, &mut view_model, KeyCode::Enter);
+    assert!(!view_model.filter_open);
+    press(&mut app_state, &mut view_model, KeyCode::Char(' '));
+    assert_eq!(app_state.selection.items, vec![dir.join("kick2.wav")]);
+
+    press(&mut app_state, &mut view_model, KeyCode::Char('/'));
+    let effects = press(&mut app_state, &mut view_model, KeyCode::Char('z'));
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage(
+            "No names match \"kickz\"".to_string()
+        )]
+    );
+    assert_eq!(view_model.current_left_item, None);
+
+    let effects = press(&mut app_state, &mut view_model, KeyCode::Esc);
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage("Filter cleared".to_string())]
+    );
+    assert!(!view_model.filter_open);
+    assert_eq!(view_model.explorer_filter.value(), "");
+}
+
+#[test]
+fn boot_selects_default_kit_and_enters_pads() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    let dir = kit_dir("kit-auto", &["snare.wav", "kick.WAV", "notes.txt"]);
+    app_state.config.default_kit_dir = Some(dir.clone());
+    app_state.config.kit_auto_pads = true;
+
+    let effects = AppService::new(tx).boot(&mut app_state, &mut view_model);
+
+    assert_eq!(
+        app_state.selection.items,
+        vec![dir.join("kick.WAV"), dir.join("snare.wav")]
+    );
+    assert!(matches!(
+        view_model.mode,
+        termigroove::presentation::Mode::Pads
+    ));
+    assert!(
+        effects.contains(&Effect::AudioCommand(AudioCommand::PreloadBatch {
+            pads: vec![('q', dir.join("kick.WAV")), ('w', dir.join("snare.wav"))],
+        }))
+    );
+}
+
+#[test]
+fn boot_without_auto_pads_stays_in_browse() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    let dir = kit_dir("kit-select", &["kick.wav"]);
+    app_state.config.default_kit_dir = Some(dir);
+
+    let effects = AppService::new(tx).boot(&mut app_state, &mut view_model);
+
+    assert_eq!(app_state.selection.items.len(), 1);
+    assert!(matches!(
+        view_model.mode,
+        termigroove::presentation::Mode::Browse
+    ));
+    assert!(!effects.iter().any(|e| matches!(e, Effect::AudioCommand(_))));
+}
+
+#[test]
+fn boot_warns_on_missing_or_empty_kit_dir() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    let service = AppService::new(tx);
+    app_state.config.kit_auto_pads = true;
+
+    let empty = kit_dir("kit-empty", &["readme.txt"]);
+    app_state.config.default_kit_dir = Some(empty.clone());
+    let effects = service.boot(&mut app_state, &mut view_model);
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage(format!(
+            "Kit directory {} has no supported audio files (wav, flac, ogg, mp3)",
+            empty.display()
+        ))]
+    );
+
+    app_state.config.default_kit_dir = Some(empty.join("missing"));
+    let effects = service.boot(&mut app_state, &mut view_model);
+    assert!(matches!(
+        &effects[..],
+        [Effect::StatusMessage(msg)] if msg.contains("unavailable")
+    ));
+    assert!(app_state.selection.items.is_empty());
+    assert!(matches!(
+        view_model.mode,
+        termigroove::presentation::Mode::Browse
+    ));
+}
+
+#[test]
+fn control_l_replays_last_triggered_pad() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    app_state.selection.add_file(sample_file("snare.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let service = AppService::new(tx);
+    let ctrl_l = InputAction::KeyPressed {
+        key: KeyCode::Char('l'),
+        modifiers: KeyModifiers {
+            control: true,
+            shift: false,
+            alt: false,
+        },
+    };
+
+    // Nothing triggered yet: no-op
+    let effects = service
+        .handle_input(&mut app_state, &mut view_model, ctrl_l.clone())
+        .expect("handle input");
+    assert!(effects.is_empty());
+
+    for key in ['q', 'w'] {
+        service
+            .handle_input(
+                &mut app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key: KeyCode::Char(key),
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input");
+    }
+    assert_eq!(app_state.pads.last_triggered, Some('w'));
+
+    let effects = service
+        .handle_input(&mut app_state, &mut view_model, ctrl_l)
+        .expect("handle input");
+    assert_eq!(
+        effects,
+        vec![Effect::AudioCommand(AudioCommand::Play { key: 'w' })]
+    );
+}
+
+#[test]
+fn control_b_bounces_loop_to_free_pad_and_clears_tracks_keeping_tempo() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    app_state.selection.add_file(sample_file("snare.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    app_state.set_bpm(100);
+    let loop_length = std::time::Duration::from_secs(2);
+    let event = |key, ms| RecordedEvent {
+        key,
+        offset: std::time::Duration::from_millis(ms),
+        reversed: false,
+    };
+    app_state.restore_loop(LoopSnapshot {
+        loop_length,
+        tracks: vec![
+            TrackSnapshot {
+                volume: 0.5,
+                ..TrackSnapshot::from(vec![event('w', 1_000)])
+            },
+            TrackSnapshot::from(vec![event('q', 0)]),
+            TrackSnapshot {
+                muted: true,
+                ..TrackSnapshot::from(vec![event('q', 500)])
+            },
+        ],
+    });
+    let service = AppService::new(tx);
+    let effects = service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyPressed {
+                key: KeyCode::Char('b'),
+                modifiers: KeyModifiers {
+                    control: true,
+                    shift: false,
+                    alt: false,
+                },
+            },
+        )
+        .expect("handle input");
+
+    // Render: unmuted hits in time order, into the first unmapped pad
+    let bounce = Bounce {
+        length: loop_length,
+        hits: vec![
+            BounceHit {
+                key: 'q',
+                offset: std::time::Duration::ZERO,
+                gain: 1.0,
+                reversed: false,
+            },
+            BounceHit {
+                key: 'w',
+                offset: std::time::Duration::from_millis(1_000),
+                gain: 0.5,
+                reversed: false,
+            },
+        ],
+    };
+    assert_eq!(
+        effects.first(),
+        Some(&Effect::AudioCommand(AudioCommand::Bounce {
+            key: 'e',
+            bounce: bounce.clone(),
+        }))
+    );
+    assert_eq!(app_state.pads.key_to_slot[&'e'].file_name, "Bounce 1");
+    // Tracks cleared for the next section, tempo kept
+    assert!(app_state.tracks_snapshot().is_empty());
+    assert_eq!(app_state.loop_state(), LoopState::Idle);
+    assert_eq!(app_state.get_bpm(), 100);
+    // A panic reset re-renders the bounce instead of preloading a file
+    let restore = app_state.pad_restore_commands();
+    assert!(restore.contains(&AudioCommand::Bounce { key: 'e', bounce }));
+    assert!(
+        !restore
+            .iter()
+            .any(|cmd| matches!(cmd, AudioCommand::Preload { key: 'e', .. }))
+    );
+}
+
+#[test]
+fn bounced_pad_leaves_out_muted_pads_and_rests() {
+    let (mut app_state, _view_model, _tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    app_state.selection.add_file(sample_file("snare.wav"));
+    let _ = app_state.enter_pads();
+    app_state.set_bars(2);
+    let event = |key, ms| RecordedEvent {
+        key,
+        offset: std::time::Duration::from_millis(ms),
+        reversed: false,
+    };
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: std::time::Duration::from_secs(2),
+        tracks: vec![TrackSnapshot::from(vec![
+            event('q', 0),
+            event('q', 1_500),
+            event('w', 1_500),
+        ])],
+    });
+    // Mute the snare and rest the first bar, under the playhead
+    assert_eq!(app_state.cycle_pad_mute('w'), Some(PadMute::Muted));
+    assert_eq!(app_state.toggle_rest_bar(), Some(true));
+
+    let (key, bounce) = match app_state.bounce_loop().expect("bounce") {
+        (key, AudioCommand::Bounce { bounce, .. }) => (key, bounce),
+        other => panic!("expected a bounce, got {other:?}"),
+    };
+    assert_eq!(key, 'e');
+    assert_eq!(
+        bounce.hits,
+        vec![BounceHit {
+            key: 'q',
+            offset: std::time::Duration::from_millis(1_500),
+            gain: 1.0,
+            reversed: false,
+        }]
+    );
+}
+
+#[test]
+fn control_b_without_tracks_reports_nothing_to_bounce() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let service = AppService::new(tx);
+    let effects = service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyPressed {
+                key: KeyCode::Char('b'),
+                modifiers: KeyModifiers {
+                    control: true,
+                    shift: false,
+                    alt: false,
+                },
+            },
+        )
+        .expect("handle input");
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage("Nothing to bounce".to_string())]
+    );
+    assert_eq!(app_state.pads.key_to_slot.len(), 1);
+}
+
+#[test]
+fn control_w_exports_the_playing_loop_to_a_timestamped_wav() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let service = AppService::new(tx);
+    let press = |app_state: &mut ApplicationState, view_model: &mut ViewModel| {
+        service
+            .handle_input(
+                app_state,
+                view_model,
+                InputAction::KeyPressed {
+                    key: KeyCode::Char('w'),
+                    modifiers: KeyModifiers {
+                        control: true,
+                        ..KeyModifiers::default()
+                    },
+                },
+            )
+            .expect("handle input")
+    };
+
+    let effects = press(&mut app_state, &mut view_model);
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage(
+            "Start loop playback before exporting".to_string()
+        )]
+    );
+
+    let loop_length = std::time::Duration::from_secs(2);
+    app_state.restore_loop(LoopSnapshot {
+        loop_length,
+        tracks: vec![TrackSnapshot::from(vec![RecordedEvent {
+            key: 'q',
+            offset: std::time::Duration::from_millis(500),
+            reversed: false,
+        }])],
+    });
+    let effects = press(&mut app_state, &mut view_model);
+    let Some(Effect::AudioCommand(AudioCommand::ExportWav { path, bounce })) = effects.first()
+    else {
+        panic!("expected an export command, got {effects:?}");
+    };
+    let name = path.to_str().expect("utf-8 path");
+    assert!(name.starts_with("termigroove-") && name.ends_with(".wav"));
+    assert_eq!(
+        *bounce,
+        Bounce {
+            length: loop_length,
+            hits: vec![BounceHit {
+                key: 'q',
+                offset: std::time::Duration::from_millis(500),
+                gain: 1.0,
+                reversed: false,
+            }],
+        }
+    );
+    assert_eq!(
+        effects.get(1),
+        Some(&Effect::StatusMessage(format!("Exporting loop to {name}")))
+    );
+    // Exporting leaves the loop playing
+    assert_eq!(app_state.tracks_count(), 1);
+}
+
+#[test]
+fn exported_loop_leaves_out_muted_pads_and_rests() {
+    let (mut app_state, _view_model, _tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    app_state.selection.add_file(sample_file("snare.wav"));
+    let _ = app_state.enter_pads();
+    app_state.set_bars(2);
+    let event = |key, ms| RecordedEvent {
+        key,
+        offset: std::time::Duration::from_millis(ms),
+        reversed: false,
+    };
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: std::time::Duration::from_secs(2),
+        tracks: vec![TrackSnapshot::from(vec![
+            event('q', 0),
+            event('q', 1_500),
+            event('w', 1_500),
+        ])],
+    });
+    // Mute the snare and rest the first bar, under the playhead
+    assert_eq!(app_state.cycle_pad_mute('w'), Some(PadMute::Muted));
+    assert_eq!(app_state.toggle_rest_bar(), Some(true));
+
+    let Ok(AudioCommand::ExportWav { bounce, .. }) =
+        app_state.export_loop(std::path::PathBuf::from("loop.wav"))
+    else {
+        panic!("expected an export command");
+    };
+    assert_eq!(
+        bounce.hits,
+        vec![BounceHit {
+            key: 'q',
+            offset: std::time::Duration::from_millis(1_500),
+            gain: 1.0,
+            reversed: false,
+        }]
+    );
+}
+
+#[test]
+fn letter_keys_edit_text_instead_of_triggering_pads_while_capturing() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    app_state.selection.add_file(sample_file("snare.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: std::time::Duration::from_secs(2),
+        tracks: vec![TrackSnapshot::from(vec![RecordedEvent {
+            key: 'q',
+            offset: std::time::Duration::ZERO,
+            reversed: false,
+        }])],
+    });
+    view_model.toggle_arrange();
+    let service = AppService::new(tx);
+    let mut press = |key| {
+        service
+            .handle_input(
+                &mut app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input")
+    };
+
+    press(KeyCode::Char('n'));
+    let mut typed = Vec::new();
+    for c in "qwe".chars() {
+        typed.extend(press(KeyCode::Char(c)));
+    }
+    assert!(
+        !typed
+            .iter()
+            .any(|effect| matches!(effect, Effect::AudioCommand(_)))
+    );
+    press(KeyCode::Enter);
+
+    assert!(app_state.pads.last_press_ms.is_empty());
+    assert!(!view_model.text_capture);
+    assert_eq!(app_state.tracks_snapshot()[0].name.as_deref(), Some("qwe"));
+}
+
+#[test]
+fn esc_while_capturing_text_cancels_without_leaving_pads() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    view_model.begin_text_capture("old");
+    let service = AppService::new(tx);
+    let effects = service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyPressed {
+                key: KeyCode::Esc,
+                modifiers: KeyModifiers::default(),
+            },
+        )
+        .expect("handle input");
+
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage("Rename cancelled".to_string())]
+    );
+    assert!(!view_model.text_capture);
+    assert_eq!(view_model.mode, termigroove::presentation::Mode::Pads);
+}
+
+#[test]
+fn p_in_explorer_queues_a_bounded_preview_loop_and_navigation_stops_it() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    let path = sample_file("loop.wav");
+    view_model.current_left_item = Some(path.clone());
+    view_model.current_left_is_dir = false;
+    let service = AppService::new(tx);
+    let mut press = |key| {
+        service
+            .handle_input(
+                &mut app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input")
+    };
+
+    let effects = press(KeyCode::Char('p'));
+    let plays: Vec<_> = effects
+        .iter()
+        .filter(|effect| matches!(effect, Effect::AudioCommand(AudioCommand::PlayPath { .. })))
+        .collect();
+    assert_eq!(
+        plays,
+        vec![&Effect::AudioCommand(AudioCommand::PlayPath {
+            path: path.clone(),
+            repeats: 4,
+        })]
+    );
+    assert_eq!(
+        effects.first(),
+        Some(&Effect::AudioCommand(AudioCommand::StopAudition))
+    );
+
+    let effects = press(KeyCode::Down);
+    assert_eq!(
+        effects.first(),
+        Some(&Effect::AudioCommand(AudioCommand::StopAudition))
+    );
+    // Only stopped once
+    let effects = press(KeyCode::Down);
+    assert!(!effects.contains(&Effect::AudioCommand(AudioCommand::StopAudition)));
+}
+
+#[test]
+fn a_preview_that_plays_out_is_not_stopped_again() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    view_model.current_left_item = Some(sample_file("loop.wav"));
+    view_model.current_left_is_dir = false;
+    let service = AppService::new(tx);
+    let down = InputAction::KeyPressed {
+        key: KeyCode::Down,
+        modifiers: KeyModifiers::default(),
+    };
+
+    let _ = service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyPressed {
+                key: KeyCode::Char('p'),
+                modifiers: KeyModifiers::default(),
+            },
+        )
+        .expect("handle input");
+    assert!(app_state.auditioning);
+
+    let _ = service.handle_audio_event(&mut app_state, AudioEvent::AuditionEnded);
+    assert!(!app_state.auditioning);
+    let effects = service
+        .handle_input(&mut app_state, &mut view_model, down)
+        .expect("handle input");
+    assert!(!effects.contains(&Effect::AudioCommand(AudioCommand::StopAudition)));
+}
+
+#[test]
+fn tab_cycles_pads_panels_and_arrows_act_within_the_focused_one() {
+    use termigroove::presentation::PadsPanel;
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let event = |ms| RecordedEvent {
+        key: 'q',
+        offset: std::time::Duration::from_millis(ms),
+        reversed: false,
+    };
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: std::time::Duration::from_secs(2),
+        tracks: vec![
+            TrackSnapshot::from(vec![event(0)]),
+            TrackSnapshot::from(vec![event(500)]),
+        ],
+    });
+    let service = AppService::new(tx);
+    let mut press = |view_model: &mut ViewModel, key| {
+        service
+            .handle_input(
+                &mut app_state,
+                view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input")
+    };
+
+    // Pad grid <-> summary; arrows no longer jump to the summary
+    assert_eq!(view_model.pads_panel_focus, PadsPanel::Pads);
+    press(&mut view_model, KeyCode::Right);
+    assert_eq!(view_model.pads_panel_focus, PadsPanel::Pads);
+    press(&mut view_model, KeyCode::Enter);
+    assert!(!view_model.is_bpm_popup_open());
+    press(&mut view_model, KeyCode::Tab);
+    assert_eq!(view_model.pads_panel_focus, PadsPanel::Summary);
+    press(&mut view_model, KeyCode::Tab);
+    assert_eq!(view_model.pads_panel_focus, PadsPanel::Pads);
+
+    // The track list replaces the grid while arranging
+    view_model.toggle_arrange();
+    assert_eq!(view_model.pads_panel_focus, PadsPanel::Tracks);
+    press(&mut view_model, KeyCode::Down);
+    assert_eq!(view_model.arrange_cursor, 1);
+    press(&mut view_model, KeyCode::Tab);
+    assert_eq!(view_model.pads_panel_focus, PadsPanel::Summary);
+    press(&mut view_model, KeyCode::Up);
+    assert_eq!(view_model.arrange_cursor, 1);
+    press(&mut view_model, KeyCode::Tab);
+    assert_eq!(view_model.pads_panel_focus, PadsPanel::Tracks);
+
+    // Summary focus is what Enter edits
+    press(&mut view_model, KeyCode::Tab);
+    press(&mut view_model, KeyCode::Enter);
+    assert!(view_model.is_bpm_popup_open());
+}
+
+#[test]
+fn ratchet_reverse_pad_alternates_forward_and_reversed_hits() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    assert!(app_state.pads.toggle_ratchet_reverse('q'));
+    let service = AppService::new(tx);
+
+    let mut played = Vec::new();
+    for _ in 0..3 {
+        // Bypass the debounce so each press counts as a fresh trigger
+        app_state.pads.last_press_ms.clear();
+        let effects = service
+            .handle_input(
+                &mut app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key: KeyCode::Char('q'),
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input");
+        played.extend(effects);
+    }
+
+    assert_eq!(
+        played,
+        vec![
+            Effect::AudioCommand(AudioCommand::Play { key: 'q' }),
+            Effect::AudioCommand(AudioCommand::PlayReversed {
+                key: 'q',
+                gain: 1.0
+            }),
+            Effect::AudioCommand(AudioCommand::Play { key: 'q' }),
+        ]
+    );
+}
+
+#[test]
+fn popup_length_with_b_suffix_sets_loop_length_in_beats() {
+    use termigroove::domain::tempo::LoopLength;
+    use termigroove::presentation::{Mode, PopupFocus};
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    view_model.mode = Mode::Pads;
+    let service = AppService::new(tx);
+    let mut press = |key: KeyCode| {
+        service
+            .handle_input(
+                &mut app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input");
+    };
+
+    // Focus the summary and open the tempo popup
+    press(KeyCode::Tab);
+    press(KeyCode::Enter);
+    press(KeyCode::Down);
+    for _ in 0..2 {
+        press(KeyCode::Backspace);
+    }
+    for key in ['6', 'b'] {
+        press(KeyCode::Char(key));
+    }
+    // Past the count-in field to OK
+    press(KeyCode::Down);
+    press(KeyCode::Down);
+    press(KeyCode::Enter);
+
+    assert!(!view_model.is_bpm_popup_open());
+    assert_eq!(app_state.loop_length_setting(), LoopLength::Beats(6));
+    assert_eq!(app_state.get_bars(), 2);
+    app_state.handle_loop_space();
+    // 6 beats at the default 120 BPM
+    match app_state.loop_state() {
+        LoopState::Ready { loop_length, .. } => {
+            assert_eq!(loop_length, std::time::Duration::from_secs(3))
+        }
+        other => panic!("expected count-in, got {other:?}"),
+    }
+
+    // Reopening shows the length in beats again
+    view_model.open_bpm_bars_popup(
+        app_state.get_bpm(),
+        app_state.loop_length_setting(),
+        app_state.get_count_in(),
+    );
+    assert_eq!(view_model.draft_bars().value(), "6b");
+    assert_eq!(view_model.popup_focus(), PopupFocus::PopupFieldBpm);
+}
+
+#[test]
+fn tempo_lock_blocks_the_popup_and_bpm_changes_until_unlocked() {
+    use termigroove::presentation::Mode;
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = Mode::Pads;
+    let service = AppService::new(tx);
+    let press = |app_state: &mut ApplicationState,
+                 view_model: &mut ViewModel,
+                 key: KeyCode,
+                 modifiers: KeyModifiers| {
+        service
+            .handle_input(
+                app_state,
+                view_model,
+                InputAction::KeyPressed { key, modifiers },
+            )
+            .expect("handle input")
+    };
+    let alt = KeyModifiers {
+        alt: true,
+        ..KeyModifiers::default()
+    };
+    let locked = vec![Effect::StatusMessage(
+        "Tempo locked (Alt+U to unlock)".to_string(),
+    )];
+
+    assert_eq!(
+        press(&mut app_state, &mut view_model, KeyCode::Char('l'), alt),
+        locked
+    );
+    assert!(app_state.tempo_locked);
+    // The pad key is remembered, so '=' would otherwise fit the BPM to it
+    app_state.pads.last_press_ms.insert('q', 1);
+
+    press(
+        &mut app_state,
+        &mut view_model,
+        KeyCode::Tab,
+        KeyModifiers::default(),
+    );
+    assert_eq!(
+        press(
+            &mut app_state,
+            &mut view_model,
+            KeyCode::Enter,
+            KeyModifiers::default()
+        ),
+        locked
+    );
+    assert!(!view_model.is_bpm_popup_open());
+    assert_eq!(
+        press(
+            &mut app_state,
+            &mut view_model,
+            KeyCode::Char('='),
+            KeyModifiers::default()
+        ),
+        locked
+    );
+    assert_eq!(app_state.get_bpm(), 120);
+
+    assert_eq!(
+        press(&mut app_state, &mut view_model, KeyCode::Char('u'), alt),
+        vec![Effect::StatusMessage("Tempo unlocked".to_string())]
+    );
+    press(
+        &mut app_state,
+        &mut view_model,
+        KeyCode::Enter,
+        KeyModifiers::default(),
+    );
+    assert!(view_model.is_bpm_popup_open());
+}
+
+#[test]
+fn esc_with_recorded_loop_asks_before_discarding_it() {
+    use termigroove::presentation::{ConfirmPrompt, Mode};
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = Mode::Pads;
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: std::time::Duration::from_secs(2),
+        tracks: vec![TrackSnapshot::from(vec![RecordedEvent {
+            key: 'q',
+            offset: std::time::Duration::ZERO,
+            reversed: false,
+        }])],
+    });
+    let service = AppService::new(tx);
+    let press = |app_state: &mut ApplicationState, view_model: &mut ViewModel, key| {
+        service
+            .handle_input(
+                app_state,
+                view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input")
+    };
+
+    press(&mut app_state, &mut view_model, KeyCode::Esc);
+    assert_eq!(view_model.confirm, Some(ConfirmPrompt::DiscardLoop));
+    assert_eq!(view_model.mode, Mode::Pads);
+
+    // 'n' keeps the loop and stays in Pads mode
+    press(&mut app_state, &mut view_model, KeyCode::Char('n'));
+    assert_eq!(view_model.confirm, None);
+    assert_eq!(view_model.mode, Mode::Pads);
+    assert_eq!(app_state.tracks_count(), 1);
+
+    // 'y' discards it and leaves
+    press(&mut app_state, &mut view_model, KeyCode::Esc);
+    let effects = press(&mut app_state, &mut view_model, KeyCode::Char('y'));
+    assert_eq!(view_model.confirm, None);
+    assert_eq!(view_model.mode, Mode::Browse);
+    assert_eq!(
+        effects,
+        vec![
+            Effect::AudioCommand(AudioCommand::StopAll),
+            Effect::StatusMessage("Back to browse".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn saving_before_discarding_keeps_the_loop_in_the_session() {
+    use termigroove::presentation::Mode;
+
+    let path = std::env::temp_dir().join("termigroove-app-service-tests/discard/session.json");
+    let _ = std::fs::remove_file(&path);
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = Mode::Pads;
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: std::time::Duration::from_secs(2),
+        tracks: vec![TrackSnapshot::from(vec![RecordedEvent {
+            key: 'q',
+            offset: std::time::Duration::ZERO,
+            reversed: false,
+        }])],
+    });
+    let service = AppService::new(tx);
+
+    let mut effects = Vec::new();
+    service.save_session_and_leave(&mut app_state, &mut view_model, &path, &mut effects);
+    assert_eq!(view_model.mode, Mode::Browse);
+    assert_eq!(
+        effects.last(),
+        Some(&Effect::StatusMessage(format!(
+            "Session saved to {}",
+            path.display()
+        )))
+    );
+
+    let (mut restored, mut view_model, tx) = setup_test_state();
+    let mut effects = Vec::new();
+    AppService::new(tx).open_session(&mut restored, &mut view_model, &path, &mut effects);
+    assert_eq!(restored.tracks_count(), 1);
+}
+
+#[test]
+fn esc_with_empty_loop_returns_to_browse_immediately() {
+    use termigroove::presentation::Mode;
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = Mode::Pads;
+    let service = AppService::new(tx);
+    let effects = service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyPressed {
+                key: KeyCode::Esc,
+                modifiers: KeyModifiers::default(),
+            },
+        )
+        .expect("handle input");
+    assert_eq!(view_model.confirm, None);
+    assert_eq!(view_model.mode, Mode::Browse);
+    // Anything still ringing is cut on the way out
+    assert!(effects.contains(&Effect::AudioCommand(AudioCommand::StopAll)));
+}
+
+#[test]
+fn leaving_pads_stops_held_pads_unless_set_to_ring() {
+    use termigroove::application::config::LeavePadsVoices;
+    use termigroove::presentation::Mode;
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    let service = AppService::new(tx);
+    let mut leave_holding_q = |app_state: &mut ApplicationState| {
+        view_model.mode = Mode::Pads;
+        app_state.pads.active_keys.insert('q');
+        let effects = service
+            .handle_input(
+                app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key: KeyCode::Esc,
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input");
+        assert_eq!(view_model.mode, Mode::Browse);
+        // The release never arrives in Browse, so the pad is no longer held
+        assert!(app_state.pads.active_keys.is_empty());
+        effects
+    };
+
+    let effects = leave_holding_q(&mut app_state);
+    assert!(effects.contains(&Effect::AudioCommand(AudioCommand::StopAll)));
+
+    app_state.config.leave_pads = LeavePadsVoices::Ring;
+    let effects = leave_holding_q(&mut app_state);
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage("Back to browse".to_string())]
+    );
+}
+
+#[test]
+fn control_x_confirms_then_resets_every_pad_effect() {
+    use termigroove::presentation::{ConfirmPrompt, Mode};
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    app_state.selection.add_file(sample_file("snare.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = Mode::Pads;
+    let _ = app_state.adjust_pad_pan('q', -0.5);
+    let _ = app_state.adjust_pad_pan('w', 0.3);
+    app_state.pads.toggle_ratchet_reverse('q');
+    let service = AppService::new(tx);
+    let press = |app_state: &mut ApplicationState, view_model: &mut ViewModel, key, control| {
+        service
+            .handle_input(
+                app_state,
+                view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers {
+                        control,
+                        ..KeyModifiers::default()
+                    },
+                },
+            )
+            .expect("handle input")
+    };
+
+    press(&mut app_state, &mut view_model, KeyCode::Char('x'), true);
+    assert_eq!(view_model.confirm, Some(ConfirmPrompt::ResetEffects));
+    let effects = press(&mut app_state, &mut view_model, KeyCode::Char('y'), false);
+
+    assert!(app_state.pads.pan.is_empty());
+    assert!(app_state.pads.ratchet_reverse.is_empty());
+    assert_eq!(
+        effects,
+        vec![
+            Effect::AudioCommand(AudioCommand::SetPadPan { key: 'q', pan: 0.0 }),
+            Effect::AudioCommand(AudioCommand::SetPadPan { key: 'w', pan: 0.0 }),
+            Effect::StatusMessage("Pad effects reset".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn alt_d_drops_only_the_last_recorded_layer() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let event = |key| RecordedEvent {
+        key,
+        offset: std::time::Duration::ZERO,
+        reversed: false,
+    };
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: std::time::Duration::from_secs(2),
+        tracks: vec![
+            TrackSnapshot::from(vec![event('q')]),
+            TrackSnapshot::from(vec![event('w')]),
+            TrackSnapshot::from(vec![event('e')]),
+        ],
+    });
+    view_model.arrange_cursor = 2;
+    let service = AppService::new(tx);
+    let press = |app_state: &mut ApplicationState, view_model: &mut ViewModel| {
+        service
+            .handle_input(
+                app_state,
+                view_model,
+                InputAction::KeyPressed {
+                    key: KeyCode::Char('d'),
+                    modifiers: KeyModifiers {
+                        alt: true,
+                        ..KeyModifiers::default()
+                    },
+                },
+            )
+            .expect("handle input")
+    };
+
+    let effects = press(&mut app_state, &mut view_model);
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage("Track 3 removed, 2 left".to_string())]
+    );
+    let keys: Vec<char> = app_state
+        .loop_snapshot()
+        .tracks
+        .iter()
+        .map(|track| track.events[0].key)
+        .collect();
+    assert_eq!(keys, vec!['q', 'w']);
+    assert!(matches!(app_state.loop_state(), LoopState::Playing { .. }));
+    assert_eq!(view_model.arrange_cursor, 1);
+
+    press(&mut app_state, &mut view_model);
+    press(&mut app_state, &mut view_model);
+    let effects = press(&mut app_state, &mut view_model);
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage("No tracks to remove".to_string())]
+    );
+}
+
+#[test]
+fn saved_session_restores_pads_tempo_and_tracks() {
+    use termigroove::domain::tempo::LoopLength;
+    use termigroove::presentation::Mode;
+
+    let path = std::env::temp_dir().join("termigroove-app-service-tests/session/session.json");
+    let _ = std::fs::remove_file(&path);
+    let (mut app_state, _view_model, _tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    app_state.selection.add_file(sample_file("snare.wav"));
+    let _ = app_state.enter_pads();
+    app_state.apply_tempo_length(Some(90), Some(LoopLength::Beats(6)));
+    let snapshot = LoopSnapshot {
+        loop_length: std::time::Duration::from_secs(4),
+        tracks: vec![TrackSnapshot {
+            name: Some("kick".to_string()),
+            volume: 0.5,
+            ..TrackSnapshot::from(vec![
+                RecordedEvent {
+                    key: 'q',
+                    offset: std::time::Duration::ZERO,
+                    reversed: false,
+                },
+                RecordedEvent {
+                    key: 'w',
+                    offset: std::time::Duration::from_micros(1_500_250),
+                    reversed: true,
+                },
+            ])
+        }],
+    };
+    app_state.restore_loop(snapshot.clone());
+    app_state.save_session(&path).expect("save session");
+
+    let (mut restored, mut view_model, tx) = setup_test_state();
+    let service = AppService::new(tx);
+    let mut effects = Vec::new();
+    service.open_session(&mut restored, &mut view_model, &path, &mut effects);
+
+    assert_eq!(view_model.mode, Mode::Pads);
+    assert_eq!(
+        effects,
+        vec![
+            Effect::AudioCommand(AudioCommand::Preload {
+                key: 'q',
+                path: sample_file("kick.wav"),
+            }),
+            Effect::AudioCommand(AudioCommand::Preload {
+                key: 'w',
+                path: sample_file("snare.wav"),
+            }),
+            Effect::StatusMessage("Session restored: 2 pads, 1 tracks".to_string()),
+        ]
+    );
+    assert_eq!(restored.pads.key_to_slot[&'w'].file_name, "snare.wav");
+    assert_eq!(restored.selection.items.len(), 2);
+    assert_eq!(restored.get_bpm(), 90);
+    assert_eq!(restored.loop_length_setting(), LoopLength::Beats(6));
+    assert_eq!(restored.loop_snapshot(), snapshot);
+    assert!(matches!(restored.loop_state(), LoopState::Playing { .. }));
+}
+
+#[test]
+fn saved_session_restores_bounced_pads_and_rests() {
+    let path = std::env::temp_dir().join("termigroove-app-service-tests/session/bounce.json");
+    let _ = std::fs::remove_file(&path);
+    let (mut app_state, _view_model, _tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    let hit = |key| {
+        TrackSnapshot::from(vec![RecordedEvent {
+            key,
+            offset: std::time::Duration::ZERO,
+            reversed: false,
+        }])
+    };
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: std::time::Duration::from_secs(2),
+        tracks: vec![hit('q')],
+    });
+    let (bounce_key, bounce) = match app_state.bounce_loop().expect("bounce") {
+        (key, AudioCommand::Bounce { bounce, .. }) => (key, bounce),
+        other => panic!("expected a bounce, got {other:?}"),
+    };
+    // A new loop playing the bounce, with its first bar resting
+    let snapshot = LoopSnapshot {
+        loop_length: std::time::Duration::from_secs(2),
+        tracks: vec![hit(bounce_key)],
+    };
+    app_state.restore_loop(snapshot.clone());
+    assert_eq!(app_state.toggle_rest_bar(), Some(true));
+    let rests = app_state.rest_regions().to_vec();
+    app_state.save_session(&path).expect("save session");
+
+    let (mut restored, mut view_model, tx) = setup_test_state();
+    let mut effects = Vec::new();
+    AppService::new(tx).open_session(&mut restored, &mut view_model, &path, &mut effects);
+
+    assert_eq!(
+        effects,
+        vec![
+            Effect::AudioCommand(AudioCommand::Preload {
+                key: 'q',
+                path: sample_file("kick.wav"),
+            }),
+            Effect::AudioCommand(AudioCommand::Bounce {
+                key: bounce_key,
+                bounce,
+            }),
+            Effect::StatusMessage("Session restored: 2 pads, 1 tracks".to_string()),
+        ]
+    );
+    assert_eq!(restored.pads.key_to_slot[&bounce_key].file_name, "Bounce 1");
+    assert_eq!(restored.loop_snapshot(), snapshot);
+    assert_eq!(restored.rest_regions(), rests.as_slice());
+}
+
+#[test]
+fn saved_session_keeps_its_time_signature() {
+    use termigroove::domain::tempo::LoopLength;
+    use termigroove::domain::timing::TimeSignature;
+
+    let path = std::env::temp_dir().join("termigroove-app-service-tests/session/waltz.json");
+    let _ = std::fs::remove_file(&path);
+    let (mut app_state, _view_model, _tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    let waltz = TimeSignature::new(3, 4).expect("signature");
+    app_state.set_time_signature(waltz);
+    // Eight beats of 3/4 are not whole bars
+    app_state.apply_tempo_length(None, Some(LoopLength::Beats(8)));
+    assert_eq!(app_state.loop_length_setting(), LoopLength::Beats(8));
+    assert_eq!(app_state.get_bars(), 3);
+    app_state.save_session(&path).expect("save session");
+
+    let (mut restored, mut view_model, tx) = setup_test_state();
+    let service = AppService::new(tx);
+    let mut effects = Vec::new();
+    service.open_session(&mut restored, &mut view_model, &path, &mut effects);
+
+    assert_eq!(restored.time_signature(), waltz);
+    assert_eq!(restored.loop_length_setting(), LoopLength::Beats(8));
+}
+
+#[test]
+fn opening_a_missing_session_stays_in_browse() {
+    use termigroove::presentation::Mode;
+
+    let path = std::env::temp_dir().join("termigroove-app-service-tests/no-such-session.json");
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    let service = AppService::new(tx);
+    let mut effects = Vec::new();
+    service.open_session(&mut app_state, &mut view_model, &path, &mut effects);
+
+    assert_eq!(view_model.mode, Mode::Browse);
+    assert!(matches!(
+        effects.as_slice(),
+        [Effect::StatusMessage(message)] if message.starts_with("Session load failed")
+    ));
+}
+
+#[test]
+fn alt_t_taps_the_tempo_into_the_bpm() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("tap.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: std::time::Duration::from_secs(2),
+        tracks: vec![TrackSnapshot::from(vec![RecordedEvent {
+            key: 'q',
+            offset: std::time::Duration::ZERO,
+            reversed: false,
+        }])],
+    });
+    let service = AppService::new(tx);
+    let mut tap = || {
+        service
+            .handle_input(
+                &mut app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key: KeyCode::Char('t'),
+                    modifiers: KeyModifiers {
+                        alt: true,
+                        ..KeyModifiers::default()
+                    },
+                },
+            )
+            .expect("handle input")
+    };
+
+    assert_eq!(
+        tap(),
+        vec![Effect::StatusMessage(
+            "Tap again to set the tempo".to_string()
+        )]
+    );
+    // Back-to-back taps are as fast as it gets: the tempo clamps at the top
+    assert_eq!(
+        tap(),
+        vec![Effect::StatusMessage("Tap tempo: 300 bpm".to_string())]
+    );
+    assert_eq!(app_state.get_bpm(), 300);
+    // Like a popup edit, the new tempo resets the loop and can be undone
+    assert_eq!(app_state.tracks_count(), 0);
+    assert!(app_state.undo_tempo_change());
+    assert_eq!(app_state.get_bpm(), 120);
+    assert_eq!(app_state.tracks_count(), 1);
+}
+
+#[test]
+fn resize_switches_to_the_compact_transport_on_short_terminals() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    let service = AppService::new(tx);
+    let resize = |app_state: &mut ApplicationState, view_model: &mut ViewModel, height| {
+        service
+            .handle_input(
+                app_state,
+                view_model,
+                InputAction::Resize { width: 100, height },
+            )
+            .expect("handle input")
+    };
+
+    assert!(resize(&mut app_state, &mut view_model, 40).is_empty());
+    assert!(!view_model.compact_transport);
+    assert!(resize(&mut app_state, &mut view_model, 20).is_empty());
+    assert!(view_model.compact_transport);
+    resize(&mut app_state, &mut view_model, 40);
+    assert!(!view_model.compact_transport);
+}
+
+#[test]
+fn tempo_popup_sets_the_count_in_length() {
+    use termigroove::presentation::Mode;
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = Mode::Pads;
+    let service = AppService::new(tx);
+    let mut press = |key: KeyCode| {
+        service
+            .handle_input(
+                &mut app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input");
+    };
+
+    press(KeyCode::Tab);
+    press(KeyCode::Enter);
+    press(KeyCode::Down);
+    press(KeyCode::Down);
+    press(KeyCode::Backspace);
+    press(KeyCode::Char('8'));
+    press(KeyCode::Down);
+    press(KeyCode::Enter);
+
+    assert!(!view_model.is_bpm_popup_open());
+    assert_eq!(app_state.get_count_in(), 8);
+    app_state.handle_loop_space();
+    match app_state.loop_state() {
+        LoopState::Ready {
+            ticks_remaining, ..
+        } => assert_eq!(ticks_remaining, 8),
+        other => panic!("expected count-in, got {other:?}"),
+    }
+}
+
+#[test]
+fn shift_letter_cycles_pad_mute_and_solo() {
+    use termigroove::application::state::PadMute;
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let service = AppService::new(tx);
+    let shift_q = InputAction::KeyPressed {
+        key: KeyCode::Char('Q'),
+        modifiers: KeyModifiers {
+            control: false,
+            shift: true,
+            alt: false,
+        },
+    };
+
+    for (mute, message) in [
+        (PadMute::Muted, "Pad Q muted in the loop"),
+        (PadMute::Soloed, "Pad Q soloed in the loop"),
+        (PadMute::Normal, "Pad Q back in the loop"),
+    ] {
+        let effects = service
+            .handle_input(&mut app_state, &mut view_model, shift_q.clone())
+            .expect("handle input");
+        assert_eq!(app_state.pads.mute_of('q'), mute);
+        assert!(effects.contains(&Effect::StatusMessage(message.to_string())));
+        assert!(app_state.pads.last_press_ms.is_empty());
+    }
+
+    // A muted pad still sounds when played live
+    service
+        .handle_input(&mut app_state, &mut view_model, shift_q)
+        .expect("handle input");
+    let effects = service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyPressed {
+                key: KeyCode::Char('q'),
+                modifiers: KeyModifiers::default(),
+            },
+        )
+        .expect("handle input");
+    assert!(
+        effects
+            .iter()
+            .any(|e| matches!(e, Effect::AudioCommand(AudioCommand::Play { .. })))
+    );
+}
+
+/// Open the tempo popup, clear the field `downs` rows below BPM, then move
+/// to OK and press it. Returns the effects of the OK press.
+fn ok_with_cleared_field(
+    app_state: &mut ApplicationState,
+    view_model: &mut ViewModel,
+    downs: usize,
+) -> Vec<Effect> {
+    use termigroove::presentation::Mode;
+
+    let (tx, _rx) = mpsc::channel();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = Mode::Pads;
+    let service = AppService::new(tx);
+    let mut press = |key: KeyCode| {
+        service
+            .handle_input(
+                app_state,
+                view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input")
+    };
+
+    press(KeyCode::Tab);
+    press(KeyCode::Enter);
+    for _ in 0..downs {
+        press(KeyCode::Down);
+    }
+    for _ in 0..4 {
+        press(KeyCode::Backspace);
+    }
+    for _ in downs..3 {
+        press(KeyCode::Down);
+    }
+    press(KeyCode::Enter)
+}
+
+#[test]
+fn tempo_popup_ok_with_empty_bpm_keeps_the_popup_open() {
+    use termigroove::presentation::PopupFocus;
+
+    let (mut app_state, mut view_model, _tx) = setup_test_state();
+    let bpm = app_state.get_bpm();
+    let effects = ok_with_cleared_field(&mut app_state, &mut view_model, 0);
+
+    assert!(effects.contains(&Effect::StatusMessage("BPM cannot be empty".to_string())));
+    assert!(view_model.is_bpm_popup_open());
+    assert_eq!(view_model.popup_focus(), PopupFocus::PopupFieldBpm);
+    assert_eq!(app_state.get_bpm(), bpm);
+}
+
+#[test]
+fn tempo_popup_ok_with_empty_bars_keeps_the_popup_open() {
+    use termigroove::presentation::PopupFocus;
+
+    let (mut app_state, mut view_model, _tx) = setup_test_state();
+    let bars = app_state.get_bars();
+    let effects = ok_with_cleared_field(&mut app_state, &mut view_model, 1);
+
+    assert!(effects.contains(&Effect::StatusMessage("Bars cannot be empty".to_string())));
+    assert!(view_model.is_bpm_popup_open());
+    assert_eq!(view_model.popup_focus(), PopupFocus::PopupFieldBars);
+    assert_eq!(app_state.get_bars(), bars);
+
+    // Fixing the field lets OK through
+    let (tx, _rx) = mpsc::channel();
+    let service = AppService::new(tx);
+    for key in [
+        KeyCode::Char('2'),
+        KeyCode::Down,
+        KeyCode::Down,
+        KeyCode::Enter,
+    ] {
+        service
+            .handle_input(
+                &mut app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input");
+    }
+    assert!(!view_model.is_bpm_popup_open());
+    assert_eq!(app_state.get_bars(), 2);
+}
+
+#[test]
+fn question_mark_toggles_help_and_swallows_other_keys() {
+    use termigroove::presentation::{FocusPane, Mode};
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    let service = AppService::new(tx);
+    let press = |app_state: &mut ApplicationState, view_model: &mut ViewModel, key| {
+        service
+            .handle_input(
+                app_state,
+                view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input")
+    };
+
+    press(&mut app_state, &mut view_model, KeyCode::Char('?'));
+    assert!(view_model.help_open);
+    press(&mut app_state, &mut view_model, KeyCode::Tab);
+    assert_eq!(view_model.focus, FocusPane::LeftExplorer);
+    press(&mut app_state, &mut view_model, KeyCode::Char('?'));
+    assert!(!view_model.help_open);
+
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = Mode::Pads;
+    press(&mut app_state, &mut view_model, KeyCode::Char('?'));
+    let effects = press(&mut app_state, &mut view_model, KeyCode::Char('q'));
+    assert!(effects.is_empty());
+    assert!(app_state.pads.last_press_ms.is_empty());
+    // Esc only closes the overlay; it does not leave Pads mode
+    press(&mut app_state, &mut view_model, KeyCode::Esc);
+    assert!(!view_model.help_open);
+    assert_eq!(view_model.mode, Mode::Pads);
+}
+
+#[test]
+fn control_k_remaps_the_last_played_pad() {
+    use termigroove::presentation::Mode;
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = Mode::Pads;
+    let service = AppService::new(tx);
+    let mut press = |key: KeyCode, control: bool| {
+        service
+            .handle_input(
+                &mut app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key,
+                    modifiers: KeyModifiers {
+                        control,
+                        shift: false,
+                        alt: false,
+                    },
+                },
+            )
+            .expect("handle input")
+    };
+
+    let effects = press(KeyCode::Char('k'), true);
+    assert!(effects.contains(&Effect::StatusMessage(
+        "Play a pad first to pick it for remapping".to_string()
+    )));
+
+    press(KeyCode::Char('q'), false);
+    let effects = press(KeyCode::Char('k'), true);
+    assert!(effects.contains(&Effect::StatusMessage(
+        "Remap pad Q: press a free pad key (Esc cancels)".to_string()
+    )));
+    // A non-pad key is refused and mapping mode stays on
+    let effects = press(KeyCode::Char('1'), false);
+    assert!(effects.contains(&Effect::StatusMessage("1 is not a pad key".to_string())));
+
+    let effects = press(KeyCode::Char('s'), false);
+    assert!(effects.contains(&Effect::StatusMessage("Pad Q moved to S".to_string())));
+    assert!(effects.iter().any(|e| matches!(
+        e,
+        Effect::AudioCommand(AudioCommand::Preload { key: 's', .. })
+    )));
+    assert!(view_model.remap_from.is_none());
+    assert!(app_state.pads.key_to_slot.contains_key(&'s'));
+}
+
+#[test]
+fn pad_presses_respect_the_configured_debounce_window() {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use termigroove::application::config::AppConfig;
+    use termigroove::application::state::DEFAULT_DEBOUNCE_MS;
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    assert_eq!(app_state.debounce_ms(), DEFAULT_DEBOUNCE_MS);
+    app_state.set_config(AppConfig {
+        debounce_ms: Some(1_000),
+        ..AppConfig::default()
+    });
+    assert_eq!(app_state.debounce_ms(), 1_000);
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let service = AppService::new(tx);
+
+    // Stamp the previous press just inside, then just outside the window
+    for (ago_ms, plays) in [(950, false), (1_050, true)] {
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        app_state.pads.last_press_ms.insert('q', now_ms - ago_ms);
+        let effects = service
+            .handle_input(
+                &mut app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key: KeyCode::Char('q'),
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input");
+        let played = effects
+            .iter()
+            .any(|e| matches!(e, Effect::AudioCommand(AudioCommand::Play { .. })));
+        assert_eq!(played, plays, "previous press {ago_ms}ms ago");
+    }
+}
+
+#[test]
+fn key_release_clears_the_held_pad() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let service = AppService::new(tx);
+
+    service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyPressed {
+                key: KeyCode::Char('q'),
+                modifiers: KeyModifiers::default(),
+            },
+        )
+        .expect("handle input");
+    assert!(app_state.pads.active_keys.contains(&'q'));
+
+    let effects = service
+        .handle_input(
+            &mut app_state,
+            &mut view_model,
+            InputAction::KeyReleased {
+                key: KeyCode::Char('q'),
+            },
+        )
+        .expect("handle input");
+    assert!(effects.is_empty());
+    assert!(!app_state.pads.active_keys.contains(&'q'));
+    assert!(app_state.pads.reports_releases);
+}
+
+#[test]
+fn gate_pads_sound_only_while_held() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("pad.wav"));
+    let _ = app_state.enter_pads();
+    app_state.set_debounce_ms(0);
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let service = AppService::new(tx);
+    let mut send = |action| {
+        service
+            .handle_input(&mut app_state, &mut view_model, action)
+            .expect("handle input")
+    };
+    let press = |c, control| InputAction::KeyPressed {
+        key: KeyCode::Char(c),
+        modifiers: KeyModifiers {
+            control,
+            ..KeyModifiers::default()
+        },
+    };
+    let release = || InputAction::KeyReleased {
+        key: KeyCode::Char('q'),
+    };
+    let played = |effects: &[Effect]| {
+        effects.iter().any(|e| {
+            matches!(
+                e,
+                Effect::AudioCommand(
+                    AudioCommand::Play { key: 'q' } | AudioCommand::PlayGated { key: 'q', .. }
+                )
+            )
+        })
+    };
+    let stopped = |effects: &[Effect]| {
+        effects.iter().any(|e| {
+            matches!(
+                e,
+                Effect::AudioCommand(
+                    AudioCommand::Release { key: 'q' } | AudioCommand::Stop { key: 'q' }
+                )
+            )
+        })
+    };
+
+    // One-shot pads ignore the release
+    assert!(played(&send(press('q', false))));
+    assert!(!stopped(&send(release())));
+
+    let effects = send(press('o', true));
+    assert!(effects.contains(&Effect::StatusMessage("Pad Q plays while held".to_string())));
+
+    assert!(
+        send(press('q', false)).contains(&Effect::AudioCommand(AudioCommand::PlayGated {
+            key: 'q',
+            reversed: false
+        }))
+    );
+    // Key repeats while held do not restart the pad
+    assert!(!played(&send(press('q', false))));
+    assert_eq!(
+        send(release()),
+        vec![Effect::AudioCommand(AudioCommand::Release { key: 'q' })]
+    );
+
+    send(press('o', true));
+    assert_eq!(
+        app_state.pads.key_to_slot[&'q'].play_mode,
+        PlayMode::OneShot
+    );
+}
+
+#[test]
+fn pad_edit_mode_assigns_the_choke_group_live_hits_use() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("open-hat.wav"));
+    let _ = app_state.enter_pads();
+    app_state.set_debounce_ms(0);
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let service = AppService::new(tx);
+    let mut press = |c, control| {
+        service
+            .handle_input(
+                &mut app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key: KeyCode::Char(c),
+                    modifiers: KeyModifiers {
+                        control,
+                        ..KeyModifiers::default()
+                    },
+                },
+            )
+            .expect("handle input")
+    };
+    let status = |text: &str| Effect::StatusMessage(text.to_string());
+    let play = |effects: Vec<Effect>| {
+        effects.into_iter().find_map(|effect| match effect {
+            Effect::AudioCommand(cmd) => Some(cmd),
+            _ => None,
+        })
+    };
+
+    assert_eq!(
+        play(press('q', false)),
+        Some(AudioCommand::Play { key: 'q' })
+    );
+    assert!(press('n', true).contains(&status(
+        "Choke group for pad Q: press 1-9, 0 for none (Esc cancels)"
+    )));
+    // Non-digits keep waiting instead of playing pads
+    assert!(press('w', false).is_empty());
+    assert_eq!(
+        press('2', false),
+        vec![
+            Effect::AudioCommand(AudioCommand::SetPadChoke {
+                key: 'q',
+                group: Some(2)
+            }),
+            status("Pad Q in choke group 2"),
+        ]
+    );
+    // The audio thread chokes every hit of the pad, so live hits stay plain
+    assert_eq!(
+        play(press('q', false)),
+        Some(AudioCommand::Play { key: 'q' })
+    );
+
+    press('n', true);
+    assert!(press('0', false).contains(&status("Pad Q left its choke group")));
+    assert_eq!(
+        play(press('q', false)),
+        Some(AudioCommand::Play { key: 'q' })
+    );
+}
+
+#[test]
+fn bus_keys_route_the_target_pad_and_set_its_bus_level() {
+    use termigroove::audio::BusLevel;
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("bus-kick.wav"));
+    let _ = app_state.enter_pads();
+    app_state.set_debounce_ms(0);
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let service = AppService::new(tx);
+    let mut press = |key, modifiers| {
+        service
+            .handle_input(
+                &mut app_state,
+                &mut view_model,
+                InputAction::KeyPressed { key, modifiers },
+            )
+            .expect("handle input")
+    };
+    let ctrl = KeyModifiers {
+        control: true,
+        ..KeyModifiers::default()
+    };
+    let alt = KeyModifiers {
+        alt: true,
+        ..KeyModifiers::default()
+    };
+    let status = |text: &str| Effect::StatusMessage(text.to_string());
+
+    assert_eq!(
+        press(KeyCode::Char('a'), ctrl),
+        vec![status("Play a pad first to pick its bus")]
+    );
+    press(KeyCode::Char('q'), KeyModifiers::default());
+    assert_eq!(
+        press(KeyCode::Char('a'), ctrl),
+        vec![
+            Effect::AudioCommand(AudioCommand::SetPadBus { key: 'q', bus: 1 }),
+            status("Pad Q on bus 1"),
+        ]
+    );
+    let quieter = press(KeyCode::Down, alt);
+    assert_eq!(quieter[1], status("Bus 1 volume 90%"));
+    assert_eq!(
+        press(KeyCode::Char('d'), ctrl),
+        vec![
+            Effect::AudioCommand(AudioCommand::SetBusLevel {
+                bus: 1,
+                level: BusLevel {
+                    volume: 0.9,
+                    muted: true
+                }
+            }),
+            status("Bus 1 muted"),
+        ]
+    );
+    // Four buses, then back to the main one
+    for _ in 0..3 {
+        press(KeyCode::Char('a'), ctrl);
+    }
+    assert_eq!(app_state.pads.bus_for('q'), 0);
+}
+
+#[test]
+fn sample_load_failure_is_reported_in_the_footer() {
+    let (mut app_state, _, tx) = setup_test_state();
+    let service = AppService::new(tx);
+
+    let effects = service.handle_audio_event(
+        &mut app_state,
+        AudioEvent::LoadFailed {
+            key: 'q',
+            error: "cannot decode broken.wav: Unrecognized format".to_string(),
+        },
+    );
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage(
+            "Pad Q is silent: cannot decode broken.wav: Unrecognized format".to_string()
+        )]
+    );
+}
+
+#[test]
+fn export_failure_is_reported_in_the_footer() {
+    let (mut app_state, _, tx) = setup_test_state();
+    let service = AppService::new(tx);
+
+    let effects = service.handle_audio_event(
+        &mut app_state,
+        AudioEvent::ExportFailed {
+            path: std::path::PathBuf::from("loop.wav"),
+            error: "cannot write loop.wav: Permission denied".to_string(),
+        },
+    );
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage(
+            "Export to loop.wav failed: cannot write loop.wav: Permission denied".to_string()
+        )]
+    );
+}
+
+#[test]
+fn pad_hits_are_announced_in_words_when_enabled() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state
+        .selection
+        .add_file(sample_file("announce-kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let service = AppService::new(tx);
+    let press = |app_state: &mut ApplicationState, view_model: &mut ViewModel| {
+        service
+            .handle_input(
+                app_state,
+                view_model,
+                InputAction::KeyPressed {
+                    key: KeyCode::Char('q'),
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input")
+    };
+
+    let effects = press(&mut app_state, &mut view_model);
+    assert!(
+        !effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::Announce(_)))
+    );
+
+    app_state.config.announce_pads = true;
+    app_state.pads.last_press_ms.clear();
+    let effects = press(&mut app_state, &mut view_model);
+    assert!(effects.contains(&Effect::AudioCommand(AudioCommand::Play { key: 'q' })));
+    assert!(effects.contains(&Effect::Announce("Played announce-kick on Q".to_string())));
+}
+
+#[test]
+fn brackets_double_and_halve_the_loop() {
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("scale-kick.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    let service = AppService::new(tx);
+    let press = |app_state: &mut ApplicationState, view_model: &mut ViewModel, c| {
+        service
+            .handle_input(
+                app_state,
+                view_model,
+                InputAction::KeyPressed {
+                    key: KeyCode::Char(c),
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input")
+    };
+
+    let effects = press(&mut app_state, &mut view_model, ']');
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage("Record a loop first".to_string())]
+    );
+
+    app_state.set_bars(2);
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: std::time::Duration::from_secs(4),
+        tracks: vec![
+            vec![RecordedEvent {
+                key: 'q',
+                offset: std::time::Duration::from_secs(1),
+                reversed: false,
+            }]
+            .into(),
+        ],
+    });
+    let effects = press(&mut app_state, &mut view_model, ']');
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage("Loop doubled to 4 bars".to_string())]
+    );
+    let effects = press(&mut app_state, &mut view_model, '[');
+    let effects_again = press(&mut app_state, &mut view_model, '[');
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage("Loop halved to 2 bars".to_string())]
+    );
+    assert_eq!(
+        effects_again,
+        vec![Effect::StatusMessage("Loop halved to 1 bar".to_string())]
+    );
+    assert_eq!(app_state.get_bars(), 1);
+    assert_eq!(
+        app_state.loop_snapshot().loop_length,
+        std::time::Duration::from_secs(2)
+    );
+    assert_eq!(app_state.tracks_count(), 1);
+
+    // A locked tempo keeps the loop length too
+    app_state.tempo_locked = true;
+    let effects = press(&mut app_state, &mut view_model, ']');
+    assert_eq!(
+        effects,
+        vec![Effect::StatusMessage(
+            "Tempo locked (Alt+U to unlock)".to_string()
+        )]
+    );
+    assert_eq!(app_state.get_bars(), 1);
+}
+
+#[test]
+fn halving_scales_in_beats_of_the_time_signature() {
+    use termigroove::domain::tempo::LoopLength;
+    use termigroove::domain::timing::TimeSignature;
+
+    let (mut app_state, mut view_model, tx) = setup_test_state();
+    app_state.selection.add_file(sample_file("scale-waltz.wav"));
+    let _ = app_state.enter_pads();
+    view_model.mode = termigroove::presentation::Mode::Pads;
+    app_state.set_time_signature(TimeSignature::new(3, 4).expect("signature"));
+    app_state.set_bars(2);
+    // Two bars of 3/4 at 120 BPM
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: std::time::Duration::from_secs(3),
+        tracks: vec![
+            vec![RecordedEvent {
+                key: 'q',
+                offset: std::time::Duration::ZERO,
+                reversed: false,
+            }]
+            .into(),
+        ],
+    });
+    let service = AppService::new(tx);
+    let mut press = || {
+        service
+            .handle_input(
+                &mut app_state,
+                &mut view_model,
+                InputAction::KeyPressed {
+                    key: KeyCode::Char('['),
+                    modifiers: KeyModifiers::default(),
+                },
+            )
+            .expect("handle input")
+    };
+
+    assert_eq!(
+        press(),
+        vec![Effect::StatusMessage("Loop halved to 1 bar".to_string())]
+    );
+    // Half a bar of 3/4 is a beat and a half: refused, loop untouched
+    assert_eq!(
+        press(),
+        vec![Effect::StatusMessage(
+            "Loop cannot be halved further".to_string()
+        )]
+    );
+    assert_eq!(app_state.loop_length_setting(), LoopLength::Bars(1));
+    assert_eq!(
+        app_state.loop_snapshot().loop_length,
+        std::time::Duration::from_millis(1_500)
+    );
+}
@@ -0,0 +1,167 @@
+use std::time::Duration;
+
+use termigroove::domain::tempo::{
+    BPM_MAX, BPM_MIN, ClickPreview, LoopLength, TapTempo, bpm_from_beats, bpm_from_length,
+};
+use termigroove::domain::timing::{beat_interval_ms, loop_length_from, loop_length_from_beats};
+
+#[test]
+fn test_bpm_from_length_known_values() {
+    // 4 bars over 8 seconds = 16 beats / 8s = 120 BPM
+    assert_eq!(bpm_from_length(Duration::from_secs(8), 4), 120);
+    // 1 bar over 4 seconds = 60 BPM
+    assert_eq!(bpm_from_length(Duration::from_secs(4), 1), 60);
+    // 2 bars over 2.666s ≈ 180 BPM (rounded)
+    assert_eq!(bpm_from_length(Duration::from_secs_f64(8.0 / 3.0), 2), 180);
+    // Slightly imprecise loop lengths round to the nearest BPM
+    assert_eq!(bpm_from_length(Duration::from_millis(8_020), 4), 120);
+}
+
+#[test]
+fn test_bpm_from_length_round_trips_loop_length() {
+    for (bpm, bars) in [(90, 2), (128, 8), (174, 4)] {
+        let length = loop_length_from(bpm, bars, 4);
+        assert_eq!(bpm_from_length(length, bars), bpm);
+    }
+}
+
+#[test]
+fn test_bpm_from_length_clamps_at_edges() {
+    // Very long sample for 1 bar would be below the minimum
+    assert_eq!(bpm_from_length(Duration::from_secs(60), 1), BPM_MIN);
+    // Very short sample for many bars would exceed the maximum
+    assert_eq!(bpm_from_length(Duration::from_millis(100), 16), BPM_MAX);
+    // Zero duration cannot be divided; clamps to the maximum
+    assert_eq!(bpm_from_length(Duration::ZERO, 4), BPM_MAX);
+}
+
+#[test]
+fn test_loop_length_parses_bars_and_beats() {
+    assert_eq!(LoopLength::parse("4", 4), Some(LoopLength::Bars(4)));
+    assert_eq!(LoopLength::parse("6b", 4), Some(LoopLength::Beats(6)));
+    // Beats filling whole bars normalize to bars
+    assert_eq!(LoopLength::parse("8b", 4), Some(LoopLength::Bars(2)));
+    assert_eq!(LoopLength::parse("0b", 4), Some(LoopLength::Beats(1)));
+    assert_eq!(LoopLength::parse("b", 4), None);
+    assert_eq!(LoopLength::parse("", 4), None);
+}
+
+#[test]
+fn test_loop_length_counts_bars_of_the_time_signature() {
+    // In 3/4 eight beats are not whole bars, six are two
+    assert_eq!(LoopLength::parse("8b", 3), Some(LoopLength::Beats(8)));
+    assert_eq!(LoopLength::parse("6b", 3), Some(LoopLength::Bars(2)));
+    assert_eq!(LoopLength::from_beats(8, 3).beats_per(3), 8);
+    assert_eq!(LoopLength::Beats(8).whole_bars(3), 3);
+    assert_eq!(
+        LoopLength::Bars(2).scaled(0.5, 3),
+        Some(LoopLength::Bars(1))
+    );
+    assert_eq!(
+        LoopLength::Bars(1).scaled(2.0, 3),
+        Some(LoopLength::Bars(2))
+    );
+    assert_eq!(
+        LoopLength::Beats(3).scaled(2.0, 3),
+        Some(LoopLength::Bars(2))
+    );
+    // Half a bar of 3/4 is a beat and a half
+    assert_eq!(LoopLength::Bars(1).scaled(0.5, 3), None);
+}
+
+#[test]
+fn test_extended_loop_length_ends_on_a_bar_of_the_signature() {
+    assert_eq!(
+        LoopLength::Bars(1).next_bar_boundary(3),
+        LoopLength::Bars(2)
+    );
+    // Five beats of 3/4 grow to two bars (six beats), not 4/4's two bars
+    assert_eq!(
+        LoopLength::Beats(5).next_bar_boundary(3),
+        LoopLength::Bars(2)
+    );
+    assert_eq!(LoopLength::Beats(5).next_bar_boundary(3).beats_per(3), 6);
+    assert_eq!(
+        LoopLength::Beats(7).next_bar_boundary(3),
+        LoopLength::Bars(3)
+    );
+    assert_eq!(
+        LoopLength::Beats(7).next_bar_boundary(4),
+        LoopLength::Bars(2)
+    );
+}
+
+#[test]
+fn test_loop_length_beats_and_bar_counts() {
+    assert_eq!(LoopLength::Bars(2).beats_per(4), 8);
+    assert_eq!(LoopLength::Beats(6).beats_per(4), 6);
+    assert_eq!(LoopLength::Beats(6).whole_bars(4), 2);
+    assert_eq!(LoopLength::Beats(6).to_string(), "6b");
+    assert_eq!(
+        loop_length_from_beats(120, LoopLength::Beats(6).beats_per(4)),
+        Duration::from_secs(3)
+    );
+    assert_eq!(bpm_from_beats(Duration::from_secs(3), 6), 120);
+}
+
+#[test]
+fn test_loop_length_scales_by_whole_beats() {
+    assert_eq!(
+        LoopLength::Bars(4).scaled(0.5, 4),
+        Some(LoopLength::Bars(2))
+    );
+    assert_eq!(
+        LoopLength::Bars(3).scaled(0.5, 4),
+        Some(LoopLength::Beats(6))
+    );
+    assert_eq!(
+        LoopLength::Beats(6).scaled(2.0, 4),
+        Some(LoopLength::Bars(3))
+    );
+    // Half a beat, and more than the longest loop, cannot be set
+    assert_eq!(LoopLength::Beats(1).scaled(0.5, 4), None);
+    assert_eq!(LoopLength::Bars(256).scaled(2.0, 4), None);
+}
+
+#[test]
+fn tap_tempo_averages_the_last_four_taps() {
+    let mut taps = TapTempo::default();
+    let tap = |taps: &mut TapTempo, ms| taps.tap(Duration::from_millis(ms));
+    assert_eq!(tap(&mut taps, 0), None);
+    assert_eq!(tap(&mut taps, 500), Some(120));
+    // 0, 500, 1100: 1100 ms over two intervals
+    assert_eq!(tap(&mut taps, 1_100), Some(109));
+    assert_eq!(tap(&mut taps, 1_500), Some(120));
+    // The first tap falls out of the window: 500..2100 over three intervals
+    assert_eq!(tap(&mut taps, 2_100), Some(113));
+}
+
+#[test]
+fn tap_tempo_restarts_after_a_long_gap() {
+    let mut taps = TapTempo::default();
+    taps.tap(Duration::from_millis(0));
+    assert_eq!(taps.tap(Duration::from_millis(1_000)), Some(60));
+    // More than two seconds later: a fresh measurement
+    assert_eq!(taps.tap(Duration::from_millis(3_500)), None);
+    assert_eq!(taps.tap(Duration::from_millis(3_900)), Some(150));
+}
+
+#[test]
+fn click_preview_ticks_at_the_draft_bpm_spacing() {
+    let start = Duration::from_secs(10);
+    let interval = beat_interval_ms(90);
+    let mut preview = ClickPreview::new(90, 4, start);
+
+    let mut ticks = Vec::new();
+    for beat in 0..4 {
+        let due = start + interval * beat;
+        assert!(
+            preview.due(due - Duration::from_nanos(1)).is_empty(),
+            "beat {beat} ticked early"
+        );
+        ticks.extend(preview.due(due));
+    }
+    assert_eq!(ticks, vec![true, false, false, false]);
+    assert!(preview.is_done());
+    assert!(preview.due(start + interval * 10).is_empty());
+}
@@ -1,19 +1,23 @@
 use std::time::Duration;
 
-use termigroove::domain::timing::{beat_interval_ms, loop_length_from, normalize_offset};
+use termigroove::domain::timing::{
+    QuantizeGrid, TimeSignature, apply_swing, bars_to_beats, beat_interval_ms, beats_to_bars,
+    loop_length_from, loop_length_from_beats, loop_length_from_signature, normalize_offset,
+    position_bar_beat, quantize_offset,
+};
 
 #[test]
 fn test_loop_length_from() {
     // 120 BPM, 4 bars = 8 seconds (120 beats/min = 2 beats/sec, 4 bars = 16 beats, 16/2 = 8 sec)
-    let length = loop_length_from(120, 4);
+    let length = loop_length_from(120, 4, 4);
     assert_eq!(length, Duration::from_secs(8));
 
     // 60 BPM, 1 bar = 4 seconds (60 beats/min = 1 beat/sec, 1 bar = 4 beats, 4/1 = 4 sec)
-    let length = loop_length_from(60, 1);
+    let length = loop_length_from(60, 1, 4);
     assert_eq!(length, Duration::from_secs(4));
 
     // 180 BPM, 2 bars = 2.666... seconds (180 beats/min = 3 beats/sec, 2 bars = 8 beats, 8/3 ≈ 2.667 sec)
-    let length = loop_length_from(180, 2);
+    let length = loop_length_from(180, 2, 4);
     let expected = Duration::from_secs_f64(8.0 / 3.0);
     assert!((length.as_secs_f64() - expected.as_secs_f64()).abs() < 0.001);
 }
@@ -98,3 +102,143 @@ fn test_normalize_offset_deterministic() {
     let result2 = normalize_offset(elapsed, loop_length);
     assert_eq!(result1, result2);
 }
+
+#[test]
+fn test_beats_and_bars_conversion() {
+    assert_eq!(bars_to_beats(4), 16);
+    assert_eq!(beats_to_bars(16), 4.0);
+    // Beats that do not fill whole bars give fractional bars
+    assert_eq!(beats_to_bars(6), 1.5);
+    assert_eq!(beats_to_bars(3), 0.75);
+}
+
+#[test]
+fn test_loop_length_from_beats() {
+    // 120 BPM, 6 beats = 3 seconds
+    assert_eq!(loop_length_from_beats(120, 6), Duration::from_secs(3));
+    // Whole bars agree with the bar-based length
+    assert_eq!(
+        loop_length_from_beats(90, bars_to_beats(3)),
+        loop_length_from(90, 3, 4)
+    );
+}
+
+#[test]
+fn test_quantize_offset() {
+    let grid = Duration::from_millis(250);
+    let snap = |ms| quantize_offset(Duration::from_millis(ms), grid).as_millis();
+    assert_eq!(snap(0), 0);
+    assert_eq!(snap(124), 0);
+    assert_eq!(snap(125), 250); // halfway rounds up
+    assert_eq!(snap(260), 250);
+    assert_eq!(snap(1_990), 2_000);
+
+    // A zero grid leaves the offset alone
+    let offset = Duration::from_millis(333);
+    assert_eq!(quantize_offset(offset, Duration::ZERO), offset);
+}
+
+#[test]
+fn test_quantize_grid_parse_and_step() {
+    assert_eq!(QuantizeGrid::parse("1/4"), Some(QuantizeGrid::Quarter));
+    assert_eq!(QuantizeGrid::parse(" 1/8 "), Some(QuantizeGrid::Eighth));
+    assert_eq!(QuantizeGrid::parse("1/16"), Some(QuantizeGrid::Sixteenth));
+    assert_eq!(QuantizeGrid::parse("off"), None);
+
+    let beat = beat_interval_ms(120);
+    assert_eq!(QuantizeGrid::Quarter.step(beat), Duration::from_millis(500));
+    assert_eq!(QuantizeGrid::Eighth.step(beat), Duration::from_millis(250));
+    assert_eq!(
+        QuantizeGrid::Sixteenth.step(beat),
+        Duration::from_millis(125)
+    );
+}
+
+#[test]
+fn test_position_bar_beat() {
+    // 2 bars of 4/4 over 4 seconds: 500 ms per beat
+    let loop_length = Duration::from_secs(4);
+    let at = |ms| position_bar_beat(Duration::from_millis(ms), loop_length, 2, 4);
+    assert_eq!(at(0), (1, 1));
+    assert_eq!(at(499), (1, 1));
+    assert_eq!(at(500), (1, 2));
+    assert_eq!(at(1_999), (1, 4));
+    assert_eq!(at(2_000), (2, 1));
+    assert_eq!(at(3_100), (2, 3));
+    assert_eq!(at(3_999), (2, 4));
+    // Past the end wraps to the top
+    assert_eq!(at(4_600), (1, 2));
+
+    // Three beats to the bar
+    assert_eq!(
+        position_bar_beat(Duration::from_millis(2_000), Duration::from_secs(3), 2, 3),
+        (2, 2)
+    );
+}
+
+#[test]
+fn test_position_bar_beat_zero_length() {
+    assert_eq!(
+        position_bar_beat(Duration::from_millis(500), Duration::ZERO, 4, 4),
+        (1, 1)
+    );
+    assert_eq!(
+        position_bar_beat(Duration::from_millis(500), Duration::from_secs(2), 0, 4),
+        (1, 1)
+    );
+}
+
+#[test]
+fn loop_length_follows_the_time_signature() {
+    // 120 BPM: a quarter note lasts 500 ms
+    assert_eq!(loop_length_from(120, 2, 3), Duration::from_secs(3));
+    assert_eq!(
+        loop_length_from_signature(120, 2, 3, 4),
+        Duration::from_secs(3)
+    );
+    assert_eq!(
+        loop_length_from_signature(120, 2, 6, 8),
+        Duration::from_millis(3_000)
+    );
+    assert_eq!(
+        loop_length_from_signature(120, 4, 4, 4),
+        loop_length_from(120, 4, 4)
+    );
+}
+
+#[test]
+fn time_signature_parses_and_sizes_beats() {
+    assert_eq!(TimeSignature::default(), TimeSignature::new(4, 4).unwrap());
+    let six_eight = TimeSignature::parse(" 6 / 8 ").expect("6/8");
+    assert_eq!(six_eight.to_string(), "6/8");
+    assert_eq!(six_eight.beat_interval(120), Duration::from_millis(250));
+    assert_eq!(six_eight.beats_length(120, 6), Duration::from_millis(1_500));
+    for bad in ["", "3", "0/4", "3/3", "17/4", "x/4"] {
+        assert_eq!(TimeSignature::parse(bad), None, "{bad:?}");
+    }
+}
+
+#[test]
+fn test_apply_swing_zero_keeps_straight_timing() {
+    let eighth = Duration::from_millis(250);
+    for ms in [0, 125, 250, 375, 500, 999] {
+        let offset = Duration::from_millis(ms);
+        assert_eq!(apply_swing(offset, 0, eighth), offset);
+    }
+}
+
+#[test]
+fn test_apply_swing_fifty_percent_is_a_triplet_feel() {
+    let ms = |ms| Duration::from_millis(ms);
+    let eighth = ms(300);
+    // Beats stay put; off-beat eighths land two thirds into the beat
+    assert_eq!(apply_swing(ms(0), 50, eighth), ms(0));
+    assert_eq!(apply_swing(ms(300), 50, eighth), ms(400));
+    assert_eq!(apply_swing(ms(600), 50, eighth), ms(600));
+    assert_eq!(apply_swing(ms(900), 50, eighth), ms(1_000));
+    // Times in between stretch and squeeze to match
+    assert_eq!(apply_swing(ms(150), 50, eighth), ms(200));
+    assert_eq!(apply_swing(ms(450), 50, eighth), ms(500));
+    // No grid, no swing
+    assert_eq!(apply_swing(ms(300), 50, Duration::ZERO), ms(300));
+}
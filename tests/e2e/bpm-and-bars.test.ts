@@ -3,15 +3,15 @@ import { test, expect } from "@microsoft/tui-test";
 test.use({ program: { file: "./target/release/termigroove" } });
 
 test.describe("BPM & Bars configuration flow", () => {
-  test("Arrow shows summary, Enter opens popup, OK applies", async ({ terminal }) => {
+  test("Tab focuses summary, Enter opens popup, OK applies", async ({ terminal }) => {
     // Wait for pads mode confirmation
     if (!(await goToPadsScreen(terminal))) {
       test.skip();
       return;
     }
 
-    // Arrow key should focus summary box and show the labels
-    terminal.keyRight(1);
+    // Tab should focus summary box and show the labels
+    terminal.write("\t");
     await expect(terminal.getByText("bpm:", { full: false })).toBeVisible();
     await expect(terminal.getByText("bars:", { full: false })).toBeVisible();
 
@@ -43,7 +43,7 @@ test.describe("BPM & Bars configuration flow", () => {
     }
 
     // Focus summary and open popup
-    terminal.keyRight(1);
+    terminal.write("\t");
     terminal.write("\r");
     await expect(terminal.getByText("Configure tempo & loop", { full: true })).toBeVisible();
 
@@ -60,8 +60,7 @@ test.describe("BPM & Bars configuration flow", () => {
     await expect(terminal.getByText("bpm: 120", { full: true })).toBeVisible();
     await expect(terminal.getByText("bars: 16", { full: true })).toBeVisible();
 
-    // Reopen and test Esc
-    terminal.keyRight(1);
+    // Summary keeps focus; reopen and test Esc
     terminal.write("\r");
     await expect(terminal.getByText("Configure tempo & loop", { full: true })).toBeVisible();
     terminal.write("\b\b\b180");
@@ -55,5 +55,8 @@ test("Loop capture: count-in, record, playback, and stop", async ({ terminal })
   // Exit pads
   terminal.keyEscape();
   await sleep(200);
+  await expect(terminal.getByText("Discard current loop?", { full: false })).toBeVisible();
+  terminal.write("y");
+  await sleep(200);
   await expect(terminal.getByText("Ready", { full: false })).toBeVisible();
 });
@@ -65,6 +65,9 @@ test("Loop overdub layering pause resume clear", async ({ terminal }) => {
   // Back to browse
   terminal.keyEscape();
   await sleep(200);
+  await expect(terminal.getByText("Discard current loop?", { full: false })).toBeVisible();
+  terminal.write("y");
+  await sleep(200);
   await expect(terminal.getByText("Ready", { full: false })).toBeVisible();
 });
 
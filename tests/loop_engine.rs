@@ -5,5 +5,22 @@ mod loop_engine {
     // pub mod loop_clear;
     // pub mod loop_happy_path;
     // pub mod loop_overdub_layers;
+    pub mod loop_auto_play;
+    pub mod loop_count_in;
+    pub mod loop_delete_event;
+    pub mod loop_engine_state;
+    pub mod loop_extend_overdub;
+    pub mod loop_half_length;
+    pub mod loop_humanize;
+    pub mod loop_key_mutes;
+    pub mod loop_metronome_accent;
     pub mod loop_pause_resume;
+    pub mod loop_practice_cycles;
+    pub mod loop_quantize;
+    pub mod loop_record_offset;
+    pub mod loop_rest_regions;
+    pub mod loop_scale_length;
+    pub mod loop_skip_count_in;
+    pub mod loop_swing;
+    pub mod loop_track_management;
 }
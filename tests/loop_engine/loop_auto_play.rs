@@ -0,0 +1,146 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{LoopEngine, LoopState};
+use termigroove::domain::ports::{AudioBus, Clock};
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+}
+
+impl FakeClock {
+    fn new() -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::ZERO)),
+        }
+    }
+
+    fn advance(&self, ms: u64) {
+        *self.now.borrow_mut() += Duration::from_millis(ms);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+#[derive(Clone)]
+struct AudioBusMock {
+    scheduled: Rc<RefCell<Vec<char>>>,
+}
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {}
+
+    fn play_pad(&self, _key: char) {}
+
+    fn play_scheduled(&self, key: char) {
+        self.scheduled.borrow_mut().push(key);
+    }
+}
+
+/// Count in and record one hit over a 1-bar loop at 120 BPM (2 seconds),
+/// stopping right at the commit.
+fn record_one_take(
+    auto_play: bool,
+) -> (LoopEngine<AudioBusMock, FakeClock>, FakeClock, AudioBusMock) {
+    let clock = FakeClock::new();
+    let audio = AudioBusMock {
+        scheduled: Rc::new(RefCell::new(Vec::new())),
+    };
+    let mut engine = LoopEngine::new(clock.clone(), audio.clone());
+    engine.set_auto_play_after_record(auto_play);
+    engine.handle_space(120, 1);
+    for _ in 0..4 {
+        clock.advance(500);
+        engine.update();
+    }
+    assert!(matches!(engine.state(), LoopState::Recording { .. }));
+    clock.advance(100);
+    engine.record_event('q');
+    clock.advance(1_900);
+    engine.update();
+    (engine, clock, audio)
+}
+
+#[test]
+fn committed_take_plays_by_default() {
+    let (engine, _clock, _audio) = record_one_take(true);
+    assert!(matches!(engine.state(), LoopState::Playing { .. }));
+}
+
+#[test]
+fn committed_take_waits_at_the_start_when_auto_play_is_off() {
+    let (mut engine, clock, audio) = record_one_take(false);
+    match engine.state() {
+        LoopState::Paused {
+            saved_offset,
+            was_recording,
+            ..
+        } => {
+            assert_eq!(saved_offset, Duration::ZERO);
+            assert!(!was_recording);
+        }
+        other => panic!("expected the take to wait, got {other:?}"),
+    }
+    assert_eq!(engine.tracks_count(), 1);
+
+    // Nothing sounds while it waits
+    clock.advance(500);
+    engine.update();
+    assert!(audio.scheduled.borrow().is_empty());
+
+    // Space starts it from the top
+    engine.handle_space(120, 1);
+    assert!(matches!(engine.state(), LoopState::Playing { .. }));
+    clock.advance(150);
+    engine.update();
+    assert_eq!(*audio.scheduled.borrow(), vec!['q']);
+}
+
+#[test]
+fn overdubs_keep_playing_when_auto_play_is_off() {
+    let (mut engine, clock, _audio) = record_one_take(false);
+    engine.handle_space(120, 1);
+
+    clock.advance(300);
+    engine.record_event('w');
+    assert!(matches!(engine.state(), LoopState::Recording { .. }));
+    clock.advance(1_700);
+    engine.update();
+    assert!(matches!(engine.state(), LoopState::Playing { .. }));
+    assert_eq!(engine.tracks_count(), 2);
+}
+
+#[test]
+fn pad_press_on_an_empty_playing_loop_starts_an_overdub() {
+    let clock = FakeClock::new();
+    let audio = AudioBusMock {
+        scheduled: Rc::new(RefCell::new(Vec::new())),
+    };
+    let mut engine = LoopEngine::new(clock.clone(), audio.clone());
+    engine.set_auto_play_after_record(true);
+    engine.handle_space(120, 1);
+    for _ in 0..4 {
+        clock.advance(500);
+        engine.update();
+    }
+    // The take commits with no hits and the loop runs empty
+    clock.advance(2_000);
+    engine.update();
+    assert!(matches!(engine.state(), LoopState::Playing { .. }));
+    assert_eq!(engine.tracks_count(), 0);
+
+    clock.advance(300);
+    engine.record_event('q');
+    assert!(matches!(engine.state(), LoopState::Recording { .. }));
+
+    clock.advance(1_700);
+    engine.update();
+    assert!(matches!(engine.state(), LoopState::Playing { .. }));
+    assert_eq!(engine.tracks_count(), 1);
+}
@@ -0,0 +1,157 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{LoopEngine, LoopState};
+use termigroove::domain::ports::{AudioBus, Clock};
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+}
+
+impl FakeClock {
+    fn new() -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::ZERO)),
+        }
+    }
+
+    fn advance(&self, ms: u64) {
+        *self.now.borrow_mut() += Duration::from_millis(ms);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+#[derive(Clone)]
+struct AudioBusMock {
+    ticks: Rc<RefCell<usize>>,
+}
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {
+        *self.ticks.borrow_mut() += 1;
+    }
+
+    fn play_pad(&self, _key: char) {}
+
+    fn play_scheduled(&self, _key: char) {}
+}
+
+/// Count in at 120 BPM (500 ms beats) and return the ticks heard and the
+/// number of beats until recording started.
+fn count_in(beats: u8) -> (usize, usize) {
+    let clock = FakeClock::new();
+    let ticks = Rc::new(RefCell::new(0));
+    let mut engine = LoopEngine::new(
+        clock.clone(),
+        AudioBusMock {
+            ticks: ticks.clone(),
+        },
+    );
+    engine.set_count_in_beats(beats);
+    engine.handle_space(120, 4);
+
+    let mut waited = 0;
+    while !matches!(engine.state(), LoopState::Recording { .. }) {
+        assert!(matches!(engine.state(), LoopState::Ready { .. }));
+        clock.advance(500);
+        engine.update();
+        waited += 1;
+    }
+    let heard = *ticks.borrow();
+    (heard, waited)
+}
+
+#[test]
+fn count_in_defaults_to_four_beats() {
+    let engine = LoopEngine::new(
+        FakeClock::new(),
+        AudioBusMock {
+            ticks: Rc::new(RefCell::new(0)),
+        },
+    );
+    assert_eq!(engine.count_in_beats(), 4);
+    assert_eq!(count_in(4), (4, 4));
+}
+
+#[test]
+fn count_in_length_sets_ticks_before_recording() {
+    assert_eq!(count_in(2), (2, 2));
+    assert_eq!(count_in(8), (8, 8));
+}
+
+#[test]
+fn count_in_length_is_clamped() {
+    // Zero would skip straight past the count-in; it counts one beat instead
+    assert_eq!(count_in(0), (1, 1));
+    assert_eq!(count_in(200), (16, 16));
+}
+
+/// Count in 4 beats at 120 BPM over a 1-bar loop, pressing Q 50 ms before
+/// recording starts, then let the take commit.
+fn press_late_in_count_in(buffer: bool) -> LoopEngine<AudioBusMock, FakeClock> {
+    let clock = FakeClock::new();
+    let mut engine = LoopEngine::new(
+        clock.clone(),
+        AudioBusMock {
+            ticks: Rc::new(RefCell::new(0)),
+        },
+    );
+    engine.set_buffer_count_in(buffer);
+    engine.handle_space(120, 1);
+    clock.advance(1_950);
+    engine.update();
+    assert!(matches!(engine.state(), LoopState::Ready { .. }));
+    engine.record_event('q');
+    clock.advance(50);
+    engine.update();
+    assert!(matches!(engine.state(), LoopState::Recording { .. }));
+    clock.advance(2_000);
+    engine.update();
+    engine
+}
+
+#[test]
+fn late_count_in_press_is_buffered_to_the_loop_start() {
+    let engine = press_late_in_count_in(true);
+    let snapshot = engine.snapshot();
+    assert_eq!(snapshot.tracks.len(), 1);
+    let events = &snapshot.tracks[0].events;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].key, 'q');
+    assert!(events[0].offset < Duration::from_millis(10));
+}
+
+#[test]
+fn count_in_presses_are_not_recorded_by_default() {
+    let engine = press_late_in_count_in(false);
+    assert_eq!(engine.tracks_count(), 0);
+}
+
+#[test]
+fn early_count_in_presses_are_not_buffered() {
+    let clock = FakeClock::new();
+    let mut engine = LoopEngine::new(
+        clock.clone(),
+        AudioBusMock {
+            ticks: Rc::new(RefCell::new(0)),
+        },
+    );
+    engine.set_buffer_count_in(true);
+    engine.handle_space(120, 1);
+    clock.advance(600);
+    engine.update();
+    engine.record_event('q');
+    clock.advance(1_400);
+    engine.update();
+    assert!(matches!(engine.state(), LoopState::Recording { .. }));
+    clock.advance(2_000);
+    engine.update();
+    assert_eq!(engine.tracks_count(), 0);
+}
@@ -0,0 +1,126 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{LoopEngine, LoopSnapshot, RecordedEvent};
+use termigroove::domain::ports::{AudioBus, Clock};
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+    step: Duration,
+}
+
+impl FakeClock {
+    fn new(step_ms: u64) -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::from_millis(0))),
+            step: Duration::from_millis(step_ms),
+        }
+    }
+
+    fn advance(&self) {
+        let mut now = self.now.borrow_mut();
+        *now += self.step;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+#[derive(Clone)]
+struct AudioBusMock {
+    scheduled: Rc<RefCell<Vec<char>>>,
+}
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {}
+
+    fn play_pad(&self, _key: char) {}
+
+    fn play_scheduled(&self, key: char) {
+        self.scheduled.borrow_mut().push(key);
+    }
+}
+
+fn event(key: char, offset_ms: u64) -> RecordedEvent {
+    RecordedEvent {
+        key,
+        offset: Duration::from_millis(offset_ms),
+        reversed: false,
+    }
+}
+
+fn engine() -> (
+    FakeClock,
+    LoopEngine<AudioBusMock, FakeClock>,
+    Rc<RefCell<Vec<char>>>,
+) {
+    let clock = FakeClock::new(10);
+    let scheduled = Rc::new(RefCell::new(Vec::new()));
+    let mut engine = LoopEngine::new(
+        clock.clone(),
+        AudioBusMock {
+            scheduled: scheduled.clone(),
+        },
+    );
+    engine.restore(
+        LoopSnapshot {
+            loop_length: Duration::from_secs(2),
+            tracks: vec![
+                vec![event('q', 0), event('w', 1_000)].into(),
+                vec![event('e', 1_500)].into(),
+            ],
+        },
+        120,
+    );
+    (clock, engine, scheduled)
+}
+
+fn advance_to(clock: &FakeClock, engine: &mut LoopEngine<AudioBusMock, FakeClock>, ms: u64) {
+    while clock.now() < Duration::from_millis(ms) {
+        clock.advance();
+        engine.update();
+    }
+}
+
+#[test]
+fn deletes_event_nearest_the_playhead() {
+    let (clock, mut engine, scheduled) = engine();
+    advance_to(&clock, &mut engine, 950);
+
+    assert!(engine.delete_nearest_event(Duration::from_millis(100)));
+    let snapshot = engine.snapshot();
+    assert_eq!(snapshot.tracks[0].events, vec![event('q', 0)]);
+    assert_eq!(snapshot.tracks[1].events, vec![event('e', 1_500)]);
+
+    // The deleted event no longer plays; the rest of the cycle is intact
+    advance_to(&clock, &mut engine, 1_990);
+    assert_eq!(*scheduled.borrow(), vec!['q', 'e']);
+}
+
+#[test]
+fn nothing_is_deleted_outside_the_tolerance() {
+    let (clock, mut engine, _scheduled) = engine();
+    advance_to(&clock, &mut engine, 500);
+
+    assert!(!engine.delete_nearest_event(Duration::from_millis(100)));
+    assert_eq!(engine.snapshot().tracks.len(), 2);
+}
+
+#[test]
+fn deleting_across_the_loop_boundary_drops_emptied_track() {
+    let (clock, mut engine, _scheduled) = engine();
+    advance_to(&clock, &mut engine, 1_960);
+
+    // 'q' at 0 is 40ms ahead once the cycle wraps
+    assert!(engine.delete_nearest_event(Duration::from_millis(50)));
+    assert_eq!(engine.snapshot().tracks[0].events, vec![event('w', 1_000)]);
+
+    advance_to(&clock, &mut engine, 2_990);
+    assert!(engine.delete_nearest_event(Duration::from_millis(100)));
+    assert_eq!(engine.tracks_count(), 1);
+}
@@ -0,0 +1,181 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{LoopEngine, LoopPhase, LoopState};
+use termigroove::domain::ports::{AudioBus, Clock};
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+}
+
+impl FakeClock {
+    fn starting_at(ms: u64) -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::from_millis(ms))),
+        }
+    }
+
+    fn advance(&self, ms: u64) {
+        *self.now.borrow_mut() += Duration::from_millis(ms);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+#[derive(Clone, Default)]
+struct AudioBusMock {
+    scheduled: Rc<RefCell<Vec<(char, f32)>>>,
+}
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {}
+
+    fn play_pad(&self, _key: char) {}
+
+    fn play_scheduled(&self, key: char) {
+        self.play_scheduled_with_gain(key, 1.0);
+    }
+
+    fn play_scheduled_with_gain(&self, key: char, gain: f32) {
+        self.scheduled.borrow_mut().push((key, gain));
+    }
+}
+
+type Engine = LoopEngine<AudioBusMock, FakeClock>;
+
+/// Record a 1-bar loop at 120 BPM (2 s) with hits at 100 ms and 1100 ms,
+/// overdub a hit at 600 ms, and leave it playing 300 ms into a cycle.
+fn recorded_engine() -> (Engine, FakeClock, AudioBusMock) {
+    let clock = FakeClock::starting_at(0);
+    let audio = AudioBusMock::default();
+    let mut engine = LoopEngine::new(clock.clone(), audio.clone());
+    engine.handle_space(120, 1);
+    for _ in 0..4 {
+        clock.advance(500);
+        engine.update();
+    }
+    clock.advance(100);
+    engine.record_event('q');
+    clock.advance(1_000);
+    engine.record_event('w');
+    clock.advance(900);
+    engine.update();
+    clock.advance(600);
+    engine.update();
+    engine.record_event('e');
+    clock.advance(1_400);
+    engine.update();
+    engine.set_track_volume(1, 0.5);
+    clock.advance(300);
+    engine.update();
+    audio.scheduled.borrow_mut().clear();
+    (engine, clock, audio)
+}
+
+#[test]
+fn imported_engine_schedules_the_same_hits_as_the_original() {
+    let (mut original, clock, audio) = recorded_engine();
+    let snapshot = original.export_state();
+    assert_eq!(
+        snapshot.phase,
+        LoopPhase::Playing {
+            position: Duration::from_millis(300)
+        }
+    );
+
+    // A fresh engine on a clock far ahead of the original's
+    let other_clock = FakeClock::starting_at(90_000);
+    let other_audio = AudioBusMock::default();
+    let mut imported = LoopEngine::new(other_clock.clone(), other_audio.clone());
+    imported.import_state(snapshot.clone(), other_clock.now());
+    assert_eq!(imported.export_state(), snapshot);
+
+    let mut expected = Vec::new();
+    let mut actual = Vec::new();
+    for step in 1..=600u64 {
+        clock.advance(10);
+        other_clock.advance(10);
+        original.update();
+        imported.update();
+        expected.extend(
+            audio
+                .scheduled
+                .borrow_mut()
+                .drain(..)
+                .map(|hit| (step, hit)),
+        );
+        actual.extend(
+            other_audio
+                .scheduled
+                .borrow_mut()
+                .drain(..)
+                .map(|hit| (step, hit)),
+        );
+    }
+    // 3 hits per 2 s cycle over 6 s, the overdub at half volume
+    assert_eq!(expected.len(), 9);
+    assert_eq!(expected[0], (30, ('e', 0.5)));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn paused_and_recording_engines_resume_where_they_were() {
+    let (mut original, clock, _audio) = recorded_engine();
+    original.handle_space(120, 1);
+    let snapshot = original.export_state();
+    assert_eq!(
+        snapshot.phase,
+        LoopPhase::Paused {
+            position: Duration::from_millis(300),
+            was_recording: false
+        }
+    );
+    let other_clock = FakeClock::starting_at(5_000);
+    let mut imported = LoopEngine::new(other_clock.clone(), AudioBusMock::default());
+    imported.import_state(snapshot, other_clock.now());
+    assert!(matches!(
+        imported.state(),
+        LoopState::Paused {
+            saved_offset,
+            ..
+        } if saved_offset == Duration::from_millis(300)
+    ));
+
+    // A take still being recorded comes back with its hits
+    original.handle_space(120, 1);
+    clock.advance(200);
+    original.record_event('r');
+    let snapshot = original.export_state();
+    assert_eq!(
+        snapshot.phase,
+        LoopPhase::Recording {
+            position: Duration::from_millis(500)
+        }
+    );
+    assert_eq!(snapshot.take.len(), 1);
+    imported.import_state(snapshot, other_clock.now());
+    other_clock.advance(1_500);
+    imported.update();
+    assert_eq!(imported.tracks_count(), 3);
+    assert!(matches!(imported.state(), LoopState::Playing { .. }));
+}
+
+#[test]
+fn count_in_and_empty_snapshots_import_as_idle() {
+    let clock = FakeClock::starting_at(0);
+    let mut engine = LoopEngine::new(clock.clone(), AudioBusMock::default());
+    engine.handle_space(120, 1);
+    let snapshot = engine.export_state();
+    assert_eq!(snapshot.phase, LoopPhase::Idle);
+
+    let (mut recorded, _clock, _audio) = recorded_engine();
+    recorded.import_state(snapshot, clock.now());
+    assert_eq!(recorded.state(), LoopState::Idle);
+    assert_eq!(recorded.tracks_count(), 0);
+}
@@ -0,0 +1,132 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{LoopEngine, LoopState};
+use termigroove::domain::ports::{AudioBus, Clock};
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+}
+
+impl FakeClock {
+    fn new() -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::ZERO)),
+        }
+    }
+
+    fn advance(&self, ms: u64) {
+        *self.now.borrow_mut() += Duration::from_millis(ms);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+#[derive(Clone, Default)]
+struct AudioBusMock;
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {}
+
+    fn play_pad(&self, _key: char) {}
+
+    fn play_scheduled(&self, _key: char) {}
+}
+
+/// Count in and record a one-hit take over a 1-bar loop at 120 BPM
+/// (2 seconds), stopping right at the commit.
+fn one_bar_loop(extend: bool) -> (LoopEngine<AudioBusMock, FakeClock>, FakeClock) {
+    let clock = FakeClock::new();
+    let mut engine = LoopEngine::new(clock.clone(), AudioBusMock);
+    engine.set_extend_overdubs(extend);
+    engine.handle_space(120, 1);
+    for _ in 0..4 {
+        clock.advance(500);
+        engine.update();
+    }
+    clock.advance(200);
+    engine.record_event('q');
+    clock.advance(1_800);
+    engine.update();
+    assert!(matches!(engine.state(), LoopState::Playing { .. }));
+    (engine, clock)
+}
+
+fn offsets(engine: &LoopEngine<AudioBusMock, FakeClock>) -> Vec<Vec<(char, u64)>> {
+    engine
+        .snapshot()
+        .tracks
+        .iter()
+        .map(|track| {
+            track
+                .events
+                .iter()
+                .map(|event| (event.key, event.offset.as_millis() as u64))
+                .collect()
+        })
+        .collect()
+}
+
+/// Overdub a phrase that runs past the loop end: hits at 1.9 s and, a bar
+/// later, 3.0 s into the cycle.
+fn long_overdub(engine: &mut LoopEngine<AudioBusMock, FakeClock>, clock: &FakeClock) {
+    clock.advance(1_900);
+    engine.update();
+    engine.record_event('w');
+    clock.advance(100);
+    engine.update();
+    clock.advance(1_000);
+    engine.record_event('e');
+    engine.update();
+    clock.advance(1_000);
+    engine.update();
+}
+
+#[test]
+fn long_overdub_extends_the_loop_to_the_next_bar() {
+    let (mut engine, clock) = one_bar_loop(true);
+
+    long_overdub(&mut engine, &clock);
+
+    assert_eq!(engine.loop_length(), Duration::from_secs(4));
+    assert!(matches!(engine.state(), LoopState::Playing { .. }));
+    // The first take keeps its offset and rests in the added bar
+    assert_eq!(
+        offsets(&engine),
+        [vec![('q', 200)], vec![('w', 1_900), ('e', 3_000)]]
+    );
+}
+
+#[test]
+fn overdubs_wrap_at_the_loop_end_without_the_toggle() {
+    let (mut engine, clock) = one_bar_loop(false);
+
+    long_overdub(&mut engine, &clock);
+
+    // The late hit lands in the next pass as an overdub of its own
+    assert_eq!(engine.loop_length(), Duration::from_secs(2));
+    assert_eq!(
+        offsets(&engine),
+        [vec![('q', 200)], vec![('w', 1_900)], vec![('e', 1_000)]]
+    );
+}
+
+#[test]
+fn overdub_ending_early_keeps_the_loop_length() {
+    let (mut engine, clock) = one_bar_loop(true);
+
+    clock.advance(1_000);
+    engine.update();
+    engine.record_event('w');
+    clock.advance(1_000);
+    engine.update();
+
+    assert_eq!(engine.loop_length(), Duration::from_secs(2));
+    assert_eq!(engine.tracks_count(), 2);
+}
@@ -0,0 +1,99 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{LoopEngine, LoopSnapshot, RecordedEvent};
+use termigroove::domain::ports::{AudioBus, Clock};
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+}
+
+impl FakeClock {
+    fn new() -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::ZERO)),
+        }
+    }
+
+    fn advance(&self, ms: u64) {
+        *self.now.borrow_mut() += Duration::from_millis(ms);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+/// Records each scheduled hit with the time it fired.
+#[derive(Clone)]
+struct AudioBusMock {
+    clock: FakeClock,
+    fired: Rc<RefCell<Vec<(char, u128)>>>,
+}
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {}
+
+    fn play_pad(&self, _key: char) {}
+
+    fn play_scheduled(&self, key: char) {
+        self.fired
+            .borrow_mut()
+            .push((key, self.clock.now().as_millis()));
+    }
+}
+
+fn event(key: char, offset_ms: u64) -> RecordedEvent {
+    RecordedEvent {
+        key,
+        offset: Duration::from_millis(offset_ms),
+        reversed: false,
+    }
+}
+
+fn run(engine: &mut LoopEngine<AudioBusMock, FakeClock>, clock: &FakeClock, ms: u64) {
+    for _ in 0..ms / 10 {
+        clock.advance(10);
+        engine.update();
+    }
+}
+
+#[test]
+fn half_length_wraps_at_half_and_full_length_when_off() {
+    let clock = FakeClock::new();
+    let fired = Rc::new(RefCell::new(Vec::new()));
+    let audio = AudioBusMock {
+        clock: clock.clone(),
+        fired: fired.clone(),
+    };
+    let mut engine = LoopEngine::new(clock.clone(), audio);
+    engine.restore(
+        LoopSnapshot {
+            loop_length: Duration::from_secs(2),
+            tracks: vec![vec![event('q', 0), event('w', 1_500)].into()],
+        },
+        120,
+    );
+
+    // Half length: the downbeat repeats every second and the second half
+    // never plays
+    assert!(engine.toggle_half_length());
+    run(&mut engine, &clock, 3_000);
+    assert_eq!(fired.take(), vec![('q', 10), ('q', 1_010), ('q', 2_010)]);
+
+    // Back to the full loop: the second half plays and the cycle is 2s
+    assert!(!engine.toggle_half_length());
+    run(&mut engine, &clock, 4_000);
+    assert_eq!(
+        fired.take(),
+        vec![('q', 3_010), ('w', 4_500), ('q', 5_010), ('w', 6_500)]
+    );
+
+    // Stored events are untouched
+    assert_eq!(engine.snapshot().tracks[0].events.len(), 2);
+    assert_eq!(engine.snapshot().loop_length, Duration::from_secs(2));
+}
@@ -0,0 +1,130 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{Humanize, LoopEngine, LoopSnapshot, RecordedEvent};
+use termigroove::domain::ports::{AudioBus, Clock};
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+    step: Duration,
+}
+
+impl FakeClock {
+    fn new(step_ms: u64) -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::from_millis(0))),
+            step: Duration::from_millis(step_ms),
+        }
+    }
+
+    fn advance(&self) {
+        let mut now = self.now.borrow_mut();
+        *now += self.step;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+/// Records when each scheduled pad fires, read from the shared clock.
+#[derive(Clone)]
+struct AudioBusMock {
+    clock: FakeClock,
+    fired: Rc<RefCell<Vec<(char, Duration)>>>,
+}
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {}
+
+    fn play_pad(&self, _key: char) {}
+
+    fn play_scheduled(&self, key: char) {
+        self.fired.borrow_mut().push((key, self.clock.now()));
+    }
+}
+
+fn event(key: char, offset_ms: u64) -> RecordedEvent {
+    RecordedEvent {
+        key,
+        offset: Duration::from_millis(offset_ms),
+        reversed: false,
+    }
+}
+
+/// Play a 1s loop for three cycles and return the firing times.
+fn firing_times(humanize: Option<Humanize>) -> Vec<(char, Duration)> {
+    let clock = FakeClock::new(1);
+    let fired = Rc::new(RefCell::new(Vec::new()));
+    let audio = AudioBusMock {
+        clock: clock.clone(),
+        fired: fired.clone(),
+    };
+    let mut engine = LoopEngine::new(clock.clone(), audio);
+    engine.set_humanize(humanize);
+    engine.restore(
+        LoopSnapshot {
+            loop_length: Duration::from_secs(1),
+            tracks: vec![
+                vec![event('q', 100), event('w', 600)].into(),
+                vec![event('e', 300)].into(),
+            ],
+        },
+        120,
+    );
+    for _ in 0..3_000 {
+        clock.advance();
+        engine.update();
+    }
+    fired.take()
+}
+
+#[test]
+fn seeded_humanize_jitters_deterministically_within_range() {
+    let humanize = Some(Humanize {
+        amount: Duration::from_millis(20),
+        seed: 42,
+    });
+    let straight = firing_times(None);
+    let first = firing_times(humanize);
+    let second = firing_times(humanize);
+
+    // Same seed, same timing
+    assert_eq!(first, second);
+    assert_eq!(first.len(), straight.len());
+
+    let mut sorted_straight = straight.clone();
+    let mut sorted_first = first.clone();
+    sorted_straight.sort_by_key(|(key, at)| (at.as_millis() / 1_000, *key));
+    sorted_first.sort_by_key(|(key, at)| (at.as_millis() / 1_000, *key));
+    let mut moved = false;
+    for ((key_a, at_a), (key_b, at_b)) in sorted_straight.iter().zip(&sorted_first) {
+        assert_eq!(key_a, key_b);
+        let diff = at_a.abs_diff(*at_b);
+        // ±20ms of jitter plus one clock step of resolution
+        assert!(diff <= Duration::from_millis(21), "{key_a}: {diff:?}");
+        moved |= !diff.is_zero();
+    }
+    assert!(moved, "humanize should shift at least one event");
+
+    // Jitter is re-rolled each cycle: the same event lands at different
+    // positions within the cycle
+    let q_phases: Vec<u128> = first
+        .iter()
+        .filter(|(key, _)| *key == 'q')
+        .map(|(_, at)| at.as_millis() % 1_000)
+        .collect();
+    assert_eq!(q_phases.len(), 3);
+    assert!(q_phases.windows(2).any(|w| w[0] != w[1]));
+
+    // A different seed produces different timing
+    let other = firing_times(Some(Humanize {
+        amount: Duration::from_millis(20),
+        seed: 7,
+    }));
+    assert_ne!(first, other);
+}
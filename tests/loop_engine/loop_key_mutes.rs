@@ -0,0 +1,115 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{LoopEngine, LoopSnapshot, RecordedEvent};
+use termigroove::domain::ports::{AudioBus, Clock};
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+}
+
+impl FakeClock {
+    fn new() -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::ZERO)),
+        }
+    }
+
+    fn advance(&self, ms: u64) {
+        *self.now.borrow_mut() += Duration::from_millis(ms);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+#[derive(Clone, Default)]
+struct AudioBusMock {
+    live: Rc<RefCell<Vec<char>>>,
+    scheduled: Rc<RefCell<Vec<char>>>,
+}
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {}
+
+    fn play_pad(&self, key: char) {
+        self.live.borrow_mut().push(key);
+    }
+
+    fn play_scheduled(&self, key: char) {
+        self.scheduled.borrow_mut().push(key);
+    }
+}
+
+fn event(key: char, offset_ms: u64) -> RecordedEvent {
+    RecordedEvent {
+        key,
+        offset: Duration::from_millis(offset_ms),
+        reversed: false,
+    }
+}
+
+/// A playing 1-second loop with hits of q, w and e.
+fn playing_loop() -> (LoopEngine<AudioBusMock, FakeClock>, FakeClock, AudioBusMock) {
+    let clock = FakeClock::new();
+    let audio = AudioBusMock::default();
+    let mut engine = LoopEngine::new(clock.clone(), audio.clone());
+    engine.restore(
+        LoopSnapshot {
+            loop_length: Duration::from_secs(1),
+            tracks: vec![vec![event('q', 100), event('w', 200), event('e', 300)].into()],
+        },
+        120,
+    );
+    (engine, clock, audio)
+}
+
+fn keys(set: &[char]) -> HashSet<char> {
+    set.iter().copied().collect()
+}
+
+#[test]
+fn muted_keys_are_skipped_in_playback() {
+    let (mut engine, clock, audio) = playing_loop();
+    engine.set_key_mutes(keys(&['w']), HashSet::new());
+    assert!(!engine.is_key_audible('w'));
+
+    clock.advance(500);
+    engine.update();
+    assert_eq!(*audio.scheduled.borrow(), vec!['q', 'e']);
+}
+
+#[test]
+fn a_solo_silences_every_other_key() {
+    let (mut engine, clock, audio) = playing_loop();
+    engine.set_key_mutes(HashSet::new(), keys(&['e']));
+
+    clock.advance(500);
+    engine.update();
+    assert_eq!(*audio.scheduled.borrow(), vec!['e']);
+}
+
+#[test]
+fn mutes_survive_pause_and_resume_and_spare_live_hits() {
+    let (mut engine, clock, audio) = playing_loop();
+    engine.set_key_mutes(keys(&['q']), HashSet::new());
+
+    clock.advance(50);
+    engine.update();
+    engine.handle_space(120, 1);
+    clock.advance(1_000);
+    engine.handle_space(120, 1);
+    clock.advance(400);
+    engine.update();
+    assert_eq!(*audio.scheduled.borrow(), vec!['w', 'e']);
+
+    // A live hit on the muted pad still sounds (and starts an overdub)
+    engine.record_event('q');
+    assert_eq!(*audio.live.borrow(), vec!['q']);
+}
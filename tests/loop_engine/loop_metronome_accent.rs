@@ -0,0 +1,222 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{LoopEngine, LoopState};
+use termigroove::domain::ports::{AudioBus, Clock};
+use termigroove::domain::timing::TimeSignature;
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+}
+
+impl FakeClock {
+    fn new() -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::ZERO)),
+        }
+    }
+
+    fn advance(&self, ms: u64) {
+        *self.now.borrow_mut() += Duration::from_millis(ms);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+/// Records whether each metronome tick was a downbeat.
+#[derive(Clone)]
+struct AudioBusMock {
+    ticks: Rc<RefCell<Vec<bool>>>,
+}
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {
+        panic!("the engine should say whether a tick is the downbeat");
+    }
+
+    fn play_metronome_tick(&self, downbeat: bool) {
+        self.ticks.borrow_mut().push(downbeat);
+    }
+
+    fn play_pad(&self, _key: char) {}
+
+    fn play_scheduled(&self, _key: char) {}
+}
+
+#[test]
+fn count_in_accents_only_the_first_tick() {
+    let clock = FakeClock::new();
+    let ticks = Rc::new(RefCell::new(Vec::new()));
+    let mut engine = LoopEngine::new(
+        clock.clone(),
+        AudioBusMock {
+            ticks: ticks.clone(),
+        },
+    );
+
+    // 120 BPM: a beat every 500 ms
+    engine.handle_space(120, 1);
+    for _ in 0..4 {
+        clock.advance(500);
+        engine.update();
+    }
+
+    assert!(matches!(engine.state(), LoopState::Recording { .. }));
+    assert_eq!(*ticks.borrow(), vec![true, false, false, false]);
+}
+
+#[test]
+fn count_in_accents_each_bar_of_the_time_signature() {
+    let clock = FakeClock::new();
+    let ticks = Rc::new(RefCell::new(Vec::new()));
+    let mut engine = LoopEngine::new(
+        clock.clone(),
+        AudioBusMock {
+            ticks: ticks.clone(),
+        },
+    );
+    engine.set_time_signature(TimeSignature::new(3, 4).unwrap());
+    engine.set_count_in_beats(6);
+
+    // Two bars of 3/4 at 120 BPM: 3 seconds
+    engine.handle_space(120, 2);
+    for _ in 0..6 {
+        clock.advance(500);
+        engine.update();
+    }
+
+    assert_eq!(
+        *ticks.borrow(),
+        vec![true, false, false, true, false, false]
+    );
+    match engine.state() {
+        LoopState::Recording { loop_length, .. } => {
+            assert_eq!(loop_length, Duration::from_secs(3))
+        }
+        other => panic!("expected recording, got {other:?}"),
+    }
+}
+
+#[test]
+fn compound_time_ticks_on_the_eighth_note() {
+    let clock = FakeClock::new();
+    let ticks = Rc::new(RefCell::new(Vec::new()));
+    let mut engine = LoopEngine::new(
+        clock.clone(),
+        AudioBusMock {
+            ticks: ticks.clone(),
+        },
+    );
+    engine.set_time_signature(TimeSignature::new(6, 8).unwrap());
+    engine.set_count_in_beats(6);
+
+    // Eighth notes at 120 BPM last 250 ms
+    engine.handle_space(120, 1);
+    for _ in 0..5 {
+        clock.advance(250);
+        engine.update();
+    }
+    assert!(matches!(engine.state(), LoopState::Ready { .. }));
+    clock.advance(250);
+    engine.update();
+    assert!(matches!(engine.state(), LoopState::Recording { .. }));
+    assert_eq!(ticks.borrow().len(), 6);
+}
+
+#[test]
+fn enabled_metronome_ticks_through_recording_and_playback() {
+    let clock = FakeClock::new();
+    let ticks = Rc::new(RefCell::new(Vec::new()));
+    let mut engine = LoopEngine::new(
+        clock.clone(),
+        AudioBusMock {
+            ticks: ticks.clone(),
+        },
+    );
+    engine.set_metronome_enabled(true);
+
+    // One bar at 120 BPM after a four-tick count-in
+    engine.handle_space(120, 1);
+    for _ in 0..4 {
+        clock.advance(500);
+        engine.update();
+    }
+    assert!(matches!(engine.state(), LoopState::Recording { .. }));
+    for _ in 0..4 {
+        clock.advance(500);
+        engine.update();
+    }
+    assert!(matches!(engine.state(), LoopState::Playing { .. }));
+    clock.advance(250);
+    engine.update();
+    clock.advance(250);
+    engine.update();
+    assert_eq!(
+        *ticks.borrow(),
+        vec![
+            true, false, false, false, // count-in
+            true, false, false, false, // recording
+            true, false, // playing
+        ]
+    );
+
+    // Pausing mid-beat and resuming does not repeat the beat
+    clock.advance(250);
+    engine.handle_space(120, 1);
+    clock.advance(2_000);
+    engine.update();
+    engine.handle_space(120, 1);
+    engine.update();
+    assert_eq!(ticks.borrow().len(), 10);
+    clock.advance(250);
+    engine.update();
+    assert_eq!(ticks.borrow().len(), 11);
+
+    engine.set_metronome_enabled(false);
+    clock.advance(500);
+    engine.update();
+    assert_eq!(ticks.borrow().len(), 11);
+}
+
+#[test]
+fn restored_loop_ticks_on_the_beat_of_its_bpm() {
+    use termigroove::domain::r#loop::{LoopSnapshot, RecordedEvent};
+
+    let clock = FakeClock::new();
+    let ticks = Rc::new(RefCell::new(Vec::new()));
+    let mut engine = LoopEngine::new(
+        clock.clone(),
+        AudioBusMock {
+            ticks: ticks.clone(),
+        },
+    );
+    engine.set_metronome_enabled(true);
+
+    // One bar at 120 BPM, e.g. loaded from a session
+    engine.restore(
+        LoopSnapshot {
+            loop_length: Duration::from_secs(2),
+            tracks: vec![
+                vec![RecordedEvent {
+                    key: 'q',
+                    offset: Duration::ZERO,
+                    reversed: false,
+                }]
+                .into(),
+            ],
+        },
+        120,
+    );
+    for _ in 0..4 {
+        engine.update();
+        clock.advance(500);
+    }
+
+    assert_eq!(*ticks.borrow(), vec![true, false, false, false]);
+}
@@ -0,0 +1,99 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{LoopEngine, LoopSnapshot, PracticeCycles, RecordedEvent};
+use termigroove::domain::ports::{AudioBus, Clock};
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+}
+
+impl FakeClock {
+    fn new() -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::ZERO)),
+        }
+    }
+
+    fn advance(&self, ms: u64) {
+        *self.now.borrow_mut() += Duration::from_millis(ms);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+/// Records the time of each scheduled hit, read from the shared clock.
+#[derive(Clone)]
+struct AudioBusMock {
+    clock: FakeClock,
+    fired: Rc<RefCell<Vec<Duration>>>,
+}
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {}
+
+    fn play_pad(&self, _key: char) {}
+
+    fn play_scheduled(&self, _key: char) {
+        self.fired.borrow_mut().push(self.clock.now());
+    }
+}
+
+/// Play a 1s loop with one hit for eight cycles and return the cycles that
+/// sounded.
+fn sounding_cycles(practice: Option<PracticeCycles>) -> Vec<u128> {
+    let clock = FakeClock::new();
+    let fired = Rc::new(RefCell::new(Vec::new()));
+    let audio = AudioBusMock {
+        clock: clock.clone(),
+        fired: fired.clone(),
+    };
+    let mut engine = LoopEngine::new(clock.clone(), audio);
+    engine.set_practice(practice);
+    engine.restore(
+        LoopSnapshot {
+            loop_length: Duration::from_secs(1),
+            tracks: vec![
+                vec![RecordedEvent {
+                    key: 'q',
+                    offset: Duration::from_millis(100),
+                    reversed: false,
+                }]
+                .into(),
+            ],
+        },
+        120,
+    );
+    for _ in 0..800 {
+        clock.advance(10);
+        engine.update();
+    }
+    fired
+        .take()
+        .iter()
+        .map(|at| at.as_millis() / 1_000)
+        .collect()
+}
+
+#[test]
+fn loop_plays_every_cycle_without_practice() {
+    assert_eq!(sounding_cycles(None), vec![0, 1, 2, 3, 4, 5, 6, 7]);
+}
+
+#[test]
+fn one_on_one_off_silences_every_other_cycle() {
+    let practice = PracticeCycles { on: 1, off: 1 };
+    assert_eq!(sounding_cycles(Some(practice)), vec![0, 2, 4, 6]);
+}
+
+#[test]
+fn three_on_one_off_silences_every_fourth_cycle() {
+    let practice = PracticeCycles { on: 3, off: 1 };
+    assert_eq!(sounding_cycles(Some(practice)), vec![0, 1, 2, 4, 5, 6]);
+}
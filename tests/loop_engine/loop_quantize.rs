@@ -0,0 +1,97 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{LoopEngine, LoopState, RecordedEvent};
+use termigroove::domain::ports::{AudioBus, Clock};
+use termigroove::domain::timing::QuantizeGrid;
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+}
+
+impl FakeClock {
+    fn new() -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::ZERO)),
+        }
+    }
+
+    fn advance(&self, ms: u64) {
+        *self.now.borrow_mut() += Duration::from_millis(ms);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+#[derive(Clone)]
+struct AudioBusMock;
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {}
+
+    fn play_pad(&self, _key: char) {}
+
+    fn play_scheduled(&self, _key: char) {}
+}
+
+/// Record one bar at 120 bpm with hits at the given ms into the take and
+/// return the committed offsets.
+fn recorded_offsets(quantize: Option<QuantizeGrid>, hits: &[(char, u64)]) -> Vec<(char, u64)> {
+    let clock = FakeClock::new();
+    let mut engine = LoopEngine::new(clock.clone(), AudioBusMock);
+    engine.set_quantize(quantize);
+    engine.handle_space(120, 1);
+    while !matches!(engine.state(), LoopState::Recording { .. }) {
+        clock.advance(10);
+        engine.update();
+    }
+    let mut elapsed = 0;
+    for &(key, at) in hits {
+        clock.advance(at - elapsed);
+        elapsed = at;
+        engine.record_event(key);
+    }
+    while !matches!(engine.state(), LoopState::Playing { .. }) {
+        clock.advance(10);
+        engine.update();
+    }
+    engine.snapshot().tracks[0]
+        .events
+        .iter()
+        .map(|RecordedEvent { key, offset, .. }| (*key, offset.as_millis() as u64))
+        .collect()
+}
+
+#[test]
+fn hits_stay_as_played_without_quantize() {
+    assert_eq!(
+        recorded_offsets(None, &[('q', 20), ('w', 480)]),
+        vec![('q', 20), ('w', 480)]
+    );
+}
+
+#[test]
+fn eighth_grid_snaps_hits_to_the_nearest_line() {
+    // 120 bpm: a beat is 500 ms, an eighth 250 ms
+    assert_eq!(
+        recorded_offsets(
+            Some(QuantizeGrid::Eighth),
+            &[('q', 20), ('w', 360), ('e', 1_120)]
+        ),
+        vec![('q', 0), ('w', 250), ('e', 1_000)]
+    );
+}
+
+#[test]
+fn hit_snapped_onto_the_loop_end_wraps_to_the_downbeat() {
+    assert_eq!(
+        recorded_offsets(Some(QuantizeGrid::Quarter), &[('q', 700), ('w', 1_900)]),
+        vec![('w', 0), ('q', 500)]
+    );
+}
@@ -0,0 +1,80 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{LoopEngine, LoopSnapshot, LoopState, RecordedEvent};
+use termigroove::domain::ports::{AudioBus, Clock};
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+}
+
+impl FakeClock {
+    fn new() -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::ZERO)),
+        }
+    }
+
+    fn set(&self, ms: u64) {
+        *self.now.borrow_mut() = Duration::from_millis(ms);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+#[derive(Clone)]
+struct AudioBusMock;
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {}
+
+    fn play_pad(&self, _key: char) {}
+
+    fn play_scheduled(&self, _key: char) {}
+}
+
+#[test]
+fn hit_at_the_loop_boundary_is_stored_inside_the_cycle() {
+    let clock = FakeClock::new();
+    let mut engine = LoopEngine::new(clock.clone(), AudioBusMock);
+    let loop_length = Duration::from_secs(2);
+    engine.restore(
+        LoopSnapshot {
+            loop_length,
+            tracks: vec![
+                vec![RecordedEvent {
+                    key: 'q',
+                    offset: Duration::ZERO,
+                    reversed: false,
+                }]
+                .into(),
+            ],
+        },
+        120,
+    );
+
+    // Start an overdub, then hit again exactly at the cycle end before the
+    // next update gets to commit the recording
+    clock.set(500);
+    engine.record_event('w');
+    assert!(matches!(engine.state(), LoopState::Recording { .. }));
+    clock.set(2_000);
+    engine.record_event('e');
+    clock.set(2_010);
+    engine.update();
+
+    let snapshot = engine.snapshot();
+    let overdub = &snapshot.tracks[1].events;
+    assert!(overdub.iter().all(|event| event.offset < loop_length));
+    assert!(overdub.contains(&RecordedEvent {
+        key: 'e',
+        offset: Duration::ZERO,
+        reversed: false,
+    }));
+}
@@ -0,0 +1,171 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{LoopEngine, LoopSnapshot, RecordedEvent, RestRegion};
+use termigroove::domain::ports::{AudioBus, Clock};
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+    step: Duration,
+}
+
+impl FakeClock {
+    fn new(step_ms: u64) -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::from_millis(0))),
+            step: Duration::from_millis(step_ms),
+        }
+    }
+
+    fn advance(&self) {
+        let mut now = self.now.borrow_mut();
+        *now += self.step;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+#[derive(Clone)]
+struct AudioBusMock {
+    scheduled: Rc<RefCell<Vec<char>>>,
+}
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {}
+
+    fn play_pad(&self, _key: char) {}
+
+    fn play_scheduled(&self, key: char) {
+        self.scheduled.borrow_mut().push(key);
+    }
+}
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+fn event(key: char, offset_ms: u64) -> RecordedEvent {
+    RecordedEvent {
+        key,
+        offset: ms(offset_ms),
+        reversed: false,
+    }
+}
+
+fn engine() -> (
+    FakeClock,
+    LoopEngine<AudioBusMock, FakeClock>,
+    Rc<RefCell<Vec<char>>>,
+) {
+    let clock = FakeClock::new(10);
+    let scheduled = Rc::new(RefCell::new(Vec::new()));
+    let mut engine = LoopEngine::new(
+        clock.clone(),
+        AudioBusMock {
+            scheduled: scheduled.clone(),
+        },
+    );
+    engine.restore(
+        LoopSnapshot {
+            loop_length: Duration::from_secs(2),
+            tracks: vec![vec![event('q', 0), event('w', 1_000), event('e', 1_500)].into()],
+        },
+        120,
+    );
+    (clock, engine, scheduled)
+}
+
+fn advance_to(clock: &FakeClock, engine: &mut LoopEngine<AudioBusMock, FakeClock>, to_ms: u64) {
+    while clock.now() < ms(to_ms) {
+        clock.advance();
+        engine.update();
+    }
+}
+
+#[test]
+fn rest_regions_are_stored_in_order_and_reject_empty_or_overlapping_spans() {
+    let (_clock, mut engine, _scheduled) = engine();
+
+    assert!(engine.add_rest_region(ms(1_000), ms(1_500)));
+    assert!(engine.add_rest_region(ms(0), ms(500)));
+    assert!(!engine.add_rest_region(ms(600), ms(600)));
+    assert!(!engine.add_rest_region(ms(1_400), ms(1_800)));
+    assert_eq!(
+        engine.rest_regions(),
+        &[
+            RestRegion {
+                start: ms(0),
+                end: ms(500),
+            },
+            RestRegion {
+                start: ms(1_000),
+                end: ms(1_500),
+            },
+        ]
+    );
+
+    assert!(engine.remove_rest_region_at(ms(1_200)));
+    assert!(!engine.remove_rest_region_at(ms(1_200)));
+    engine.clear_rest_regions();
+    assert!(engine.rest_regions().is_empty());
+}
+
+#[test]
+fn events_inside_a_rest_are_skipped_but_kept() {
+    let (clock, mut engine, scheduled) = engine();
+    assert!(engine.add_rest_region(ms(1_000), ms(1_500)));
+
+    advance_to(&clock, &mut engine, 1_990);
+    assert_eq!(*scheduled.borrow(), vec!['q', 'e']);
+    assert_eq!(engine.snapshot().tracks[0].events.len(), 3);
+
+    // Removing the rest brings the event back on the next cycle
+    engine.clear_rest_regions();
+    advance_to(&clock, &mut engine, 3_990);
+    assert_eq!(*scheduled.borrow(), vec!['q', 'e', 'q', 'w', 'e']);
+}
+
+#[test]
+fn toggle_rest_bar_marks_and_unmarks_the_bar_under_the_playhead() {
+    let (clock, mut engine, _scheduled) = engine();
+    assert_eq!(engine.toggle_rest_bar(16), Some(true));
+    assert_eq!(
+        engine.rest_regions(),
+        &[RestRegion {
+            start: ms(0),
+            end: ms(500),
+        }]
+    );
+
+    advance_to(&clock, &mut engine, 1_200);
+    assert_eq!(engine.toggle_rest_bar(16), Some(true));
+    assert_eq!(engine.rest_regions()[1].start, ms(1_000));
+
+    assert_eq!(engine.toggle_rest_bar(16), Some(false));
+    assert_eq!(engine.rest_regions().len(), 1);
+
+    engine.handle_control_space();
+    assert!(engine.rest_regions().is_empty());
+    assert_eq!(engine.toggle_rest_bar(16), None);
+}
+
+#[test]
+fn toggle_rest_bar_keeps_full_bars_in_a_beat_length_loop() {
+    // The 2s loop is 6 beats of 4/4: a 4-beat bar, then a 2-beat one
+    let (clock, mut engine, _scheduled) = engine();
+    advance_to(&clock, &mut engine, 1_200);
+    assert_eq!(engine.toggle_rest_bar(6), Some(true));
+    assert_eq!(engine.rest_regions()[0].start, ms(0));
+    assert!(engine.rest_regions()[0].end > ms(1_333));
+
+    advance_to(&clock, &mut engine, 1_500);
+    assert_eq!(engine.toggle_rest_bar(6), Some(true));
+    assert!(engine.rest_regions()[1].start > ms(1_333));
+    assert_eq!(engine.rest_regions()[1].end, ms(2_000));
+}
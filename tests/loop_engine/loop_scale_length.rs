@@ -0,0 +1,148 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{LoopEngine, LoopSnapshot, LoopState, RecordedEvent};
+use termigroove::domain::ports::{AudioBus, Clock};
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+}
+
+impl FakeClock {
+    fn new() -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::ZERO)),
+        }
+    }
+
+    fn advance(&self, ms: u64) {
+        *self.now.borrow_mut() += Duration::from_millis(ms);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+#[derive(Clone, Default)]
+struct AudioBusMock {
+    scheduled: Rc<RefCell<Vec<char>>>,
+}
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {}
+
+    fn play_pad(&self, _key: char) {}
+
+    fn play_scheduled(&self, key: char) {
+        self.scheduled.borrow_mut().push(key);
+    }
+}
+
+fn event(key: char, offset_ms: u64) -> RecordedEvent {
+    RecordedEvent {
+        key,
+        offset: Duration::from_millis(offset_ms),
+        reversed: false,
+    }
+}
+
+/// A playing 1-second loop with hits of q and w.
+fn playing_loop() -> (LoopEngine<AudioBusMock, FakeClock>, FakeClock, AudioBusMock) {
+    let clock = FakeClock::new();
+    let audio = AudioBusMock::default();
+    let mut engine = LoopEngine::new(clock.clone(), audio.clone());
+    engine.restore(
+        LoopSnapshot {
+            loop_length: Duration::from_secs(1),
+            tracks: vec![vec![event('q', 100), event('w', 600)].into()],
+        },
+        120,
+    );
+    (engine, clock, audio)
+}
+
+fn offsets(engine: &LoopEngine<AudioBusMock, FakeClock>) -> Vec<(char, u64)> {
+    engine.snapshot().tracks[0]
+        .events
+        .iter()
+        .map(|event| (event.key, event.offset.as_millis() as u64))
+        .collect()
+}
+
+#[test]
+fn doubling_repeats_every_hit_in_the_second_half() {
+    let (mut engine, clock, audio) = playing_loop();
+
+    assert!(engine.scale_loop_length(2.0));
+    assert_eq!(engine.loop_length(), Duration::from_secs(2));
+    assert_eq!(
+        offsets(&engine),
+        [('q', 100), ('w', 600), ('q', 1_100), ('w', 1_600)]
+    );
+    assert!(matches!(
+        engine.state(),
+        LoopState::Playing { loop_length, .. } if loop_length == Duration::from_secs(2)
+    ));
+
+    // The copies play in the same pass instead of the loop wrapping at 1 s
+    clock.advance(1_700);
+    engine.update();
+    assert_eq!(*audio.scheduled.borrow(), ['q', 'w', 'q', 'w']);
+}
+
+#[test]
+fn halving_drops_hits_past_the_new_end_and_wraps_the_playhead() {
+    let (mut engine, clock, audio) = playing_loop();
+    clock.advance(700);
+    engine.update();
+    assert_eq!(*audio.scheduled.borrow(), ['q', 'w']);
+
+    assert!(engine.scale_loop_length(0.5));
+    assert_eq!(engine.loop_length(), Duration::from_millis(500));
+    assert_eq!(offsets(&engine), [('q', 100)]);
+
+    // 700 ms in is 200 ms into the half loop: it wraps 300 ms later and q
+    // comes round again 100 ms after that
+    clock.advance(300);
+    engine.update();
+    assert_eq!(*audio.scheduled.borrow(), ['q', 'w']);
+    clock.advance(100);
+    engine.update();
+    assert_eq!(*audio.scheduled.borrow(), ['q', 'w', 'q']);
+}
+
+#[test]
+fn halving_while_recording_shortens_the_take() {
+    let clock = FakeClock::new();
+    let mut engine = LoopEngine::new(clock.clone(), AudioBusMock::default());
+    engine.handle_space_beats(120, 4);
+    while !matches!(engine.state(), LoopState::Recording { .. }) {
+        clock.advance(100);
+        engine.update();
+    }
+    clock.advance(200);
+    engine.record_event('q');
+    clock.advance(1_000);
+    engine.record_event('w');
+
+    assert!(engine.scale_loop_length(0.5));
+    assert!(matches!(
+        engine.state(),
+        LoopState::Recording { loop_length, .. } if loop_length == Duration::from_secs(1)
+    ));
+    clock.advance(100);
+    engine.update();
+    assert_eq!(offsets(&engine), [('q', 200)]);
+}
+
+#[test]
+fn scaling_needs_a_loop() {
+    let mut engine = LoopEngine::new(FakeClock::new(), AudioBusMock::default());
+    assert!(!engine.scale_loop_length(2.0));
+    assert_eq!(engine.state(), LoopState::Idle);
+}
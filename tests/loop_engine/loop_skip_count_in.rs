@@ -0,0 +1,126 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{LoopEngine, LoopState};
+use termigroove::domain::ports::{AudioBus, Clock};
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+    step: Duration,
+}
+
+impl FakeClock {
+    fn new(step_ms: u64) -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::from_millis(0))),
+            step: Duration::from_millis(step_ms),
+        }
+    }
+
+    fn advance(&self) {
+        let mut now = self.now.borrow_mut();
+        *now += self.step;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RecordedCommand {
+    Metronome,
+    Pad { key: char },
+    Scheduled { key: char },
+}
+
+#[derive(Clone)]
+struct AudioBusMock {
+    sent: Rc<RefCell<Vec<RecordedCommand>>>,
+}
+
+impl AudioBusMock {
+    fn new() -> (Self, Rc<RefCell<Vec<RecordedCommand>>>) {
+        let sent = Rc::new(RefCell::new(Vec::new()));
+        (Self { sent: sent.clone() }, sent)
+    }
+}
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {
+        self.sent.borrow_mut().push(RecordedCommand::Metronome);
+    }
+
+    fn play_pad(&self, key: char) {
+        self.sent.borrow_mut().push(RecordedCommand::Pad { key });
+    }
+
+    fn play_scheduled(&self, key: char) {
+        self.sent
+            .borrow_mut()
+            .push(RecordedCommand::Scheduled { key });
+    }
+}
+
+#[test]
+fn space_during_count_in_starts_recording_immediately() {
+    let clock = FakeClock::new(500); // one beat per step at 120 BPM
+    let (audio, sent) = AudioBusMock::new();
+    let mut engine = LoopEngine::new(clock.clone(), audio);
+
+    engine.handle_space(120, 1);
+    assert!(matches!(
+        engine.state(),
+        LoopState::Ready {
+            ticks_remaining: 4,
+            ..
+        }
+    ));
+
+    clock.advance();
+    engine.update();
+    assert!(matches!(
+        engine.state(),
+        LoopState::Ready {
+            ticks_remaining: 3,
+            ..
+        }
+    ));
+    let beeps_before_skip = sent.borrow().len();
+
+    engine.handle_space(120, 1);
+    assert_eq!(
+        engine.state(),
+        LoopState::Recording {
+            start_time: Duration::from_millis(500),
+            loop_length: Duration::from_secs(2),
+        }
+    );
+
+    // The remaining count-in ticks are gone
+    clock.advance();
+    engine.update();
+    assert_eq!(sent.borrow().len(), beeps_before_skip);
+    assert!(
+        sent.borrow()
+            .iter()
+            .all(|cmd| *cmd == RecordedCommand::Metronome)
+    );
+
+    // Events are recorded relative to the skip moment
+    engine.record_event('q');
+    for _ in 0..3 {
+        clock.advance();
+        engine.update();
+    }
+    assert!(matches!(engine.state(), LoopState::Playing { .. }));
+    assert_eq!(engine.tracks_count(), 1);
+    assert_eq!(
+        engine.snapshot().tracks[0].events[0].offset,
+        Duration::from_millis(500)
+    );
+}
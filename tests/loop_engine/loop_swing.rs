@@ -0,0 +1,93 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{LoopEngine, LoopState};
+use termigroove::domain::ports::{AudioBus, Clock};
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+}
+
+impl FakeClock {
+    fn new() -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::ZERO)),
+        }
+    }
+
+    fn advance(&self, ms: u64) {
+        *self.now.borrow_mut() += Duration::from_millis(ms);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+#[derive(Clone, Default)]
+struct AudioBusMock {
+    scheduled: Rc<RefCell<Vec<char>>>,
+}
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {}
+
+    fn play_pad(&self, _key: char) {}
+
+    fn play_scheduled(&self, key: char) {
+        self.scheduled.borrow_mut().push(key);
+    }
+}
+
+/// Record a 1-bar loop at 120 BPM (500 ms beats) with a hit on the first
+/// off-beat eighth (250 ms), then let the next cycle start.
+fn offbeat_loop() -> (LoopEngine<AudioBusMock, FakeClock>, FakeClock, AudioBusMock) {
+    let clock = FakeClock::new();
+    let audio = AudioBusMock::default();
+    let mut engine = LoopEngine::new(clock.clone(), audio.clone());
+    engine.handle_space(120, 1);
+    for _ in 0..4 {
+        clock.advance(500);
+        engine.update();
+    }
+    clock.advance(250);
+    engine.record_event('q');
+    clock.advance(1_750);
+    engine.update();
+    assert!(matches!(engine.state(), LoopState::Playing { .. }));
+    (engine, clock, audio)
+}
+
+#[test]
+fn swing_delays_offbeat_hits_during_playback() {
+    let (mut engine, clock, audio) = offbeat_loop();
+    engine.set_swing(50);
+
+    // At 50% the off-beat moves from 250 ms to the triplet at 333 ms
+    clock.advance(300);
+    engine.update();
+    assert!(audio.scheduled.borrow().is_empty());
+    clock.advance(40);
+    engine.update();
+    assert_eq!(*audio.scheduled.borrow(), ['q']);
+    // The stored offset stays straight
+    assert_eq!(
+        engine.snapshot().tracks[0].events[0].offset,
+        Duration::from_millis(250)
+    );
+}
+
+#[test]
+fn swing_off_plays_straight_again() {
+    let (mut engine, clock, audio) = offbeat_loop();
+    engine.set_swing(50);
+    engine.set_swing(0);
+
+    clock.advance(260);
+    engine.update();
+    assert_eq!(*audio.scheduled.borrow(), ['q']);
+}
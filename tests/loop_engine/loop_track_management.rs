@@ -0,0 +1,199 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use termigroove::domain::r#loop::{
+    LoopEngine, LoopSnapshot, LoopState, RecordedEvent, TrackSnapshot,
+};
+use termigroove::domain::ports::{AudioBus, Clock};
+
+#[derive(Clone)]
+struct FakeClock {
+    now: Rc<RefCell<Duration>>,
+    step: Duration,
+}
+
+impl FakeClock {
+    fn new(step_ms: u64) -> Self {
+        Self {
+            now: Rc::new(RefCell::new(Duration::from_millis(0))),
+            step: Duration::from_millis(step_ms),
+        }
+    }
+
+    fn advance(&self) {
+        let mut now = self.now.borrow_mut();
+        *now += self.step;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum RecordedCommand {
+    Metronome,
+    Pad { key: char },
+    Scheduled { key: char, gain: f32 },
+}
+
+#[derive(Clone)]
+struct AudioBusMock {
+    sent: Rc<RefCell<Vec<RecordedCommand>>>,
+}
+
+impl AudioBusMock {
+    fn new() -> (Self, Rc<RefCell<Vec<RecordedCommand>>>) {
+        let sent = Rc::new(RefCell::new(Vec::new()));
+        (Self { sent: sent.clone() }, sent)
+    }
+}
+
+impl AudioBus for AudioBusMock {
+    fn play_metronome_beep(&self) {
+        self.sent.borrow_mut().push(RecordedCommand::Metronome);
+    }
+
+    fn play_pad(&self, key: char) {
+        self.sent.borrow_mut().push(RecordedCommand::Pad { key });
+    }
+
+    fn play_scheduled(&self, key: char) {
+        self.play_scheduled_with_gain(key, 1.0);
+    }
+
+    fn play_scheduled_with_gain(&self, key: char, gain: f32) {
+        self.sent
+            .borrow_mut()
+            .push(RecordedCommand::Scheduled { key, gain });
+    }
+}
+
+fn advance(clock: &FakeClock, engine: &mut LoopEngine<AudioBusMock, FakeClock>, steps: usize) {
+    for _ in 0..steps {
+        clock.advance();
+        engine.update();
+    }
+}
+
+fn event(key: char, offset_ms: u64) -> RecordedEvent {
+    RecordedEvent {
+        key,
+        offset: Duration::from_millis(offset_ms),
+        reversed: false,
+    }
+}
+
+fn two_track_engine() -> (
+    FakeClock,
+    LoopEngine<AudioBusMock, FakeClock>,
+    Rc<RefCell<Vec<RecordedCommand>>>,
+) {
+    let clock = FakeClock::new(100);
+    let (audio, sent) = AudioBusMock::new();
+    let mut engine = LoopEngine::new(clock.clone(), audio);
+    engine.restore(
+        LoopSnapshot {
+            loop_length: Duration::from_secs(2),
+            tracks: vec![vec![event('q', 0)].into(), vec![event('w', 500)].into()],
+        },
+        120,
+    );
+    assert!(matches!(engine.state(), LoopState::Playing { .. }));
+    (clock, engine, sent)
+}
+
+#[test]
+fn muted_track_is_skipped_and_volume_is_forwarded() {
+    let (clock, mut engine, sent) = two_track_engine();
+    assert!(engine.set_track_muted(0, true));
+    assert!(engine.set_track_volume(1, 0.5));
+
+    advance(&clock, &mut engine, 10); // one second into the cycle
+
+    assert_eq!(
+        *sent.borrow(),
+        vec![RecordedCommand::Scheduled {
+            key: 'w',
+            gain: 0.5
+        }]
+    );
+}
+
+#[test]
+fn duplicate_move_and_remove_reorder_tracks() {
+    let (_clock, mut engine, _sent) = two_track_engine();
+
+    assert!(engine.duplicate_track(0));
+    assert_eq!(engine.tracks_count(), 3);
+    assert_eq!(engine.snapshot().tracks[1].events, vec![event('q', 0)]);
+
+    assert!(engine.move_track(2, 0));
+    assert_eq!(engine.snapshot().tracks[0].events, vec![event('w', 500)]);
+
+    assert!(engine.remove_track(0));
+    assert!(!engine.remove_track(5));
+    assert_eq!(
+        engine.snapshot().tracks,
+        vec![
+            TrackSnapshot::from(vec![event('q', 0)]),
+            TrackSnapshot::from(vec![event('q', 0)])
+        ]
+    );
+}
+
+#[test]
+fn undo_last_track_pops_the_newest_layer() {
+    let (_clock, mut engine, _sent) = two_track_engine();
+
+    assert!(engine.undo_last_track());
+    assert_eq!(
+        engine.snapshot().tracks,
+        vec![TrackSnapshot::from(vec![event('q', 0)])]
+    );
+    assert!(matches!(engine.state(), LoopState::Playing { .. }));
+
+    assert!(engine.undo_last_track());
+    assert!(!engine.undo_last_track());
+    assert_eq!(engine.tracks_count(), 0);
+    assert!(matches!(engine.state(), LoopState::Playing { .. }));
+}
+
+#[test]
+fn undo_last_track_without_tracks_keeps_idle() {
+    let clock = FakeClock::new(100);
+    let (audio, _sent) = AudioBusMock::new();
+    let mut engine = LoopEngine::new(clock, audio);
+
+    assert!(!engine.undo_last_track());
+    assert!(matches!(engine.state(), LoopState::Idle));
+}
+
+#[test]
+fn committed_track_is_named_after_first_pad() {
+    let clock = FakeClock::new(100);
+    let (audio, _sent) = AudioBusMock::new();
+    let mut engine = LoopEngine::new(clock.clone(), audio);
+    engine.set_pad_labels([('q', "kick".to_string()), ('w', "hats".to_string())].into());
+
+    engine.handle_space(120, 1);
+    advance(&clock, &mut engine, 20); // count-in
+    assert!(matches!(engine.state(), LoopState::Recording { .. }));
+    clock.advance();
+    engine.record_event('w');
+    clock.advance();
+    engine.record_event('q');
+    advance(&clock, &mut engine, 20); // let the cycle wrap and commit
+
+    let tracks = engine.tracks_snapshot();
+    assert_eq!(tracks.len(), 1);
+    assert_eq!(tracks[0].name.as_deref(), Some("hats"));
+
+    assert!(engine.set_track_name(0, Some("groove".to_string())));
+    assert!(!engine.set_track_name(3, None));
+    assert_eq!(engine.tracks_snapshot()[0].name.as_deref(), Some("groove"));
+    assert_eq!(engine.snapshot().tracks[0].name.as_deref(), Some("groove"));
+}
@@ -169,3 +169,51 @@ fn test_multiple_navigation_operations() {
         "Should still have a selected entry after multiple navigations"
     );
 }
+
+#[test]
+fn test_filter_steps_over_matching_entries_only() {
+    let dir = std::env::temp_dir().join("termigroove-adapter-filter");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("create dir");
+    for name in ["Kick.wav", "hat.wav", "kick2.wav", "snare.wav"] {
+        std::fs::write(dir.join(name), b"").expect("create file");
+    }
+    let mut view_model = create_test_view_model();
+    view_model
+        .file_explorer
+        .set_cwd(&dir)
+        .expect("change directory");
+    view_model.explorer_filter = tui_input::Input::new("KICK".to_string());
+
+    let mut navigator = view_model.as_navigator();
+    assert!(navigator.apply_filter());
+    let name = |entry: Option<termigroove::application::ports::FileEntry>| {
+        entry.and_then(|e| e.path.file_name().map(|n| n.to_string_lossy().to_string()))
+    };
+    assert_eq!(
+        name(navigator.selected_entry()).as_deref(),
+        Some("Kick.wav")
+    );
+    navigator
+        .handle_navigation_key(KeyCode::Down)
+        .expect("down");
+    assert_eq!(
+        name(navigator.selected_entry()).as_deref(),
+        Some("kick2.wav")
+    );
+    // Wraps past the last match instead of landing on hidden entries
+    navigator
+        .handle_navigation_key(KeyCode::Down)
+        .expect("down");
+    assert_eq!(
+        name(navigator.selected_entry()).as_deref(),
+        Some("Kick.wav")
+    );
+    drop(navigator);
+
+    view_model.explorer_filter = tui_input::Input::new("clap".to_string());
+    let mut navigator = view_model.as_navigator();
+    assert!(!navigator.apply_filter());
+    assert_eq!(navigator.selected_entry(), None);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,163 @@
+//! Rendering tests for the arrange (track list) view.
+
+use std::sync::mpsc;
+use std::time::Duration;
+
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui_explorer::{FileExplorer, Theme as ExplorerTheme};
+use termigroove::application::dto::input_action::{InputAction, KeyCode, KeyModifiers};
+use termigroove::application::service::app_service::AppService;
+use termigroove::application::state::ApplicationState;
+use termigroove::audio::{SenderAudioBus, SystemClock};
+use termigroove::domain::r#loop::{LoopEngine, LoopSnapshot, RecordedEvent, RestRegion};
+use termigroove::presentation::{Mode, ViewModel};
+use termigroove::ui::{draw_ui, rest_strip};
+
+fn setup_test_state() -> (ApplicationState, ViewModel, AppService) {
+    let (tx, _rx) = mpsc::channel();
+    let bus = SenderAudioBus::new(tx.clone());
+    let loop_engine = LoopEngine::new(SystemClock::new(), bus);
+    let app_state = ApplicationState::new(loop_engine);
+    let theme = ExplorerTheme::default().with_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded),
+    );
+    let file_explorer = FileExplorer::with_theme(theme).expect("create file explorer");
+    let mut view_model = ViewModel::new(file_explorer);
+    view_model.mode = Mode::Pads;
+    (app_state, view_model, AppService::new(tx))
+}
+
+fn event(key: char, offset_ms: u64) -> RecordedEvent {
+    RecordedEvent {
+        key,
+        offset: Duration::from_millis(offset_ms),
+        reversed: false,
+    }
+}
+
+fn render(view_model: &ViewModel, app_state: &ApplicationState) -> Vec<String> {
+    let mut terminal = Terminal::new(TestBackend::new(80, 30)).expect("terminal");
+    terminal
+        .draw(|f| draw_ui(f, view_model, app_state))
+        .expect("draw");
+    let buffer = terminal.backend().buffer();
+    (0..buffer.area.height)
+        .map(|y| {
+            (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+        })
+        .collect()
+}
+
+fn press(
+    service: &AppService,
+    app_state: &mut ApplicationState,
+    view_model: &mut ViewModel,
+    key: KeyCode,
+    modifiers: KeyModifiers,
+) {
+    service
+        .handle_input(
+            app_state,
+            view_model,
+            InputAction::KeyPressed { key, modifiers },
+        )
+        .expect("handle input");
+}
+
+#[test]
+fn arrange_view_lists_current_tracks() {
+    let (mut app_state, mut view_model, service) = setup_test_state();
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: Duration::from_secs(2),
+        tracks: vec![
+            vec![event('q', 0), event('w', 500)].into(),
+            vec![event('e', 250)].into(),
+        ],
+    });
+    app_state.toggle_track_mute(1);
+    app_state.rename_track(0, Some("kick".to_string()));
+
+    let ctrl = KeyModifiers {
+        control: true,
+        ..KeyModifiers::default()
+    };
+    press(
+        &service,
+        &mut app_state,
+        &mut view_model,
+        KeyCode::Char('t'),
+        ctrl,
+    );
+    assert!(view_model.is_arrange_open());
+
+    let lines = render(&view_model, &app_state);
+    let track1 = lines
+        .iter()
+        .find(|l| l.contains("Track 1"))
+        .expect("track 1 row");
+    assert!(track1.contains("kick"));
+    assert!(track1.contains("2 events"));
+    assert!(track1.contains("vol 100%"));
+    let track2 = lines
+        .iter()
+        .find(|l| l.contains("Track 2"))
+        .expect("track 2 row");
+    assert!(track2.contains("1 events"));
+    assert!(track2.contains("[muted]"));
+}
+
+#[test]
+fn arrange_navigation_moves_highlight() {
+    let (mut app_state, mut view_model, service) = setup_test_state();
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: Duration::from_secs(2),
+        tracks: vec![vec![event('q', 0)].into(), vec![event('w', 500)].into()],
+    });
+    view_model.toggle_arrange();
+
+    let highlighted = |lines: &[String]| {
+        lines
+            .iter()
+            .find(|l| l.contains("▶"))
+            .cloned()
+            .expect("highlighted row")
+    };
+
+    let lines = render(&view_model, &app_state);
+    assert!(highlighted(&lines).contains("Track 1"));
+
+    press(
+        &service,
+        &mut app_state,
+        &mut view_model,
+        KeyCode::Down,
+        KeyModifiers::default(),
+    );
+    let lines = render(&view_model, &app_state);
+    assert!(highlighted(&lines).contains("Track 2"));
+
+    // Cursor stops at the last track
+    press(
+        &service,
+        &mut app_state,
+        &mut view_model,
+        KeyCode::Down,
+        KeyModifiers::default(),
+    );
+    assert_eq!(view_model.arrange_cursor, 1);
+}
+
+#[test]
+fn rest_strip_shades_cells_inside_rest_regions() {
+    let rests = [RestRegion {
+        start: Duration::from_millis(1_000),
+        end: Duration::from_millis(1_500),
+    }];
+    assert_eq!(rest_strip(&rests, Duration::from_secs(2), 8), "────░░──");
+}
@@ -0,0 +1,71 @@
+//! Tests for the footer warning shown when the audio thread is gone.
+
+use std::sync::mpsc;
+
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui_explorer::{FileExplorer, Theme as ExplorerTheme};
+use termigroove::application::service::Effect;
+use termigroove::application::state::ApplicationState;
+use termigroove::audio::{AudioCommand, AudioHandle, SenderAudioBus, SystemClock};
+use termigroove::domain::r#loop::LoopEngine;
+use termigroove::presentation::ViewModel;
+use termigroove::presentation::effect_handler::apply_effects;
+use termigroove::ui::{AUDIO_LOST_WARNING, draw_ui};
+
+const WIDTH: u16 = 80;
+const HEIGHT: u16 = 20;
+
+fn setup_test_state() -> (ApplicationState, ViewModel) {
+    let (tx, _rx) = mpsc::channel();
+    let bus = SenderAudioBus::new(tx);
+    let loop_engine = LoopEngine::new(SystemClock::new(), bus);
+    let app_state = ApplicationState::new(loop_engine);
+    let theme = ExplorerTheme::default().with_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded),
+    );
+    let file_explorer = FileExplorer::with_theme(theme).expect("create file explorer");
+    let view_model = ViewModel::new(file_explorer);
+    (app_state, view_model)
+}
+
+fn footer_row(terminal: &Terminal<TestBackend>) -> String {
+    let buffer = terminal.backend().buffer();
+    (0..WIDTH)
+        .map(|x| buffer[(x, HEIGHT - 1)].symbol().to_string())
+        .collect()
+}
+
+#[test]
+fn dropped_audio_receiver_shows_a_persistent_footer_warning() {
+    let (app_state, mut view_model) = setup_test_state();
+    let (tx, rx) = mpsc::channel();
+    let audio = AudioHandle::new(tx);
+    let play = || vec![Effect::AudioCommand(AudioCommand::Play { key: 'q' })];
+
+    apply_effects(&mut view_model, &audio, play());
+    assert!(!view_model.audio_lost);
+
+    // The audio thread exits, e.g. after a device error
+    drop(rx);
+    apply_effects(&mut view_model, &audio, play());
+    assert!(view_model.audio_lost);
+    // Later status messages do not hide the warning
+    apply_effects(
+        &mut view_model,
+        &audio,
+        vec![Effect::StatusMessage("Tempo locked".to_string())],
+    );
+    assert!(view_model.audio_lost);
+
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).expect("terminal");
+    terminal
+        .draw(|f| draw_ui(f, &view_model, &app_state))
+        .expect("draw");
+    let footer = footer_row(&terminal);
+    assert!(footer.contains(AUDIO_LOST_WARNING.trim_end()), "{footer}");
+    assert!(footer.contains("Tempo locked"), "{footer}");
+}
@@ -0,0 +1,71 @@
+//! Tests for the beat pulse shown in Pads mode.
+
+use std::time::Duration;
+
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use termigroove::application::dto::loop_state::{LoopStateDto, LoopStatusDto};
+use termigroove::domain::timing::TimeSignature;
+use termigroove::ui::{beat_pulse, render_beat_pulse};
+
+fn loop_state(status: LoopStatusDto, offset_ms: Option<u64>) -> LoopStateDto {
+    LoopStateDto {
+        status,
+        ticks_remaining: None,
+        loop_length: Duration::from_secs(8),
+        current_offset: offset_ms.map(Duration::from_millis),
+        saved_offset: None,
+        was_recording: None,
+        track_count: 1,
+        remaining: None,
+    }
+}
+
+/// Width of the rendered pulse bar in a 40-column strip.
+fn pulse_width(state: &LoopStateDto, bpm: u16) -> usize {
+    let mut terminal = Terminal::new(TestBackend::new(40, 1)).expect("terminal");
+    terminal
+        .draw(|f| render_beat_pulse(f, f.area(), state, bpm, TimeSignature::default()))
+        .expect("draw");
+    let buffer = terminal.backend().buffer();
+    (0..40).filter(|&x| buffer[(x, 0)].symbol() == "█").count()
+}
+
+#[test]
+fn pulse_shrinks_through_each_beat() {
+    // 120 BPM: a beat every 500ms
+    let widths: Vec<usize> = [0, 125, 250, 375, 500, 625]
+        .into_iter()
+        .map(|ms| pulse_width(&loop_state(LoopStatusDto::Playing, Some(ms)), 120))
+        .collect();
+    assert_eq!(widths, [40, 30, 20, 10, 40, 30]);
+}
+
+#[test]
+fn pulse_follows_the_current_tempo() {
+    // At 60 BPM the same offset is only a quarter of the way into the beat
+    let state = loop_state(LoopStatusDto::Recording, Some(250));
+    assert_eq!(beat_pulse(&state, 60, TimeSignature::default()), Some(0.75));
+    assert_eq!(pulse_width(&state, 60), 30);
+    assert_eq!(beat_pulse(&state, 120, TimeSignature::default()), Some(0.5));
+}
+
+#[test]
+fn fast_tempos_pulse_every_other_beat() {
+    // 300 BPM beats are 200ms, shorter than the minimum pulse period
+    let state = loop_state(LoopStatusDto::Playing, Some(200));
+    assert_eq!(beat_pulse(&state, 300, TimeSignature::default()), Some(0.5));
+    let state = loop_state(LoopStatusDto::Playing, Some(400));
+    assert_eq!(beat_pulse(&state, 300, TimeSignature::default()), Some(1.0));
+}
+
+#[test]
+fn pulse_rests_without_a_running_loop() {
+    for state in [
+        loop_state(LoopStatusDto::Idle, None),
+        loop_state(LoopStatusDto::Paused, Some(250)),
+    ] {
+        assert_eq!(beat_pulse(&state, 120, TimeSignature::default()), None);
+        assert_eq!(pulse_width(&state, 120), 0);
+    }
+}
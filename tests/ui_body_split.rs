@@ -0,0 +1,63 @@
+//! Rendering tests for the Browse pane split presets.
+
+use std::sync::mpsc;
+
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui_explorer::{FileExplorer, Theme as ExplorerTheme};
+use termigroove::application::state::ApplicationState;
+use termigroove::audio::{SenderAudioBus, SystemClock};
+use termigroove::domain::r#loop::LoopEngine;
+use termigroove::presentation::{BodySplit, ViewModel};
+use termigroove::ui::draw_ui;
+
+fn setup_test_state() -> (ApplicationState, ViewModel) {
+    let (tx, _rx) = mpsc::channel();
+    let bus = SenderAudioBus::new(tx);
+    let loop_engine = LoopEngine::new(SystemClock::new(), bus);
+    let app_state = ApplicationState::new(loop_engine);
+    let theme = ExplorerTheme::default().with_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded),
+    );
+    let file_explorer = FileExplorer::with_theme(theme).expect("create file explorer");
+    (app_state, ViewModel::new(file_explorer))
+}
+
+/// Column where the selection pane's top-left border corner is drawn.
+fn right_pane_x(view_model: &ViewModel, app_state: &ApplicationState) -> u16 {
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).expect("terminal");
+    terminal
+        .draw(|f| draw_ui(f, view_model, app_state))
+        .expect("draw");
+    let buffer = terminal.backend().buffer();
+    (0..buffer.area.height)
+        .find_map(|y| {
+            let row: String = (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect();
+            row.find("Selected")
+                .map(|byte_idx| row[..byte_idx].chars().count() as u16)
+        })
+        .expect("selection pane rendered")
+        - 1
+}
+
+#[test]
+fn cycling_the_split_resizes_the_selection_pane() {
+    let (app_state, mut view_model) = setup_test_state();
+    assert_eq!(view_model.body_split, BodySplit::Wide);
+    assert_eq!(right_pane_x(&view_model, &app_state), 75);
+
+    assert_eq!(view_model.cycle_body_split(), "Split: 60/40");
+    assert_eq!(right_pane_x(&view_model, &app_state), 60);
+
+    assert_eq!(view_model.cycle_body_split(), "Split: 50/50");
+    assert_eq!(right_pane_x(&view_model, &app_state), 50);
+
+    view_model.cycle_body_split();
+    assert_eq!(view_model.body_split, BodySplit::Wide);
+    assert_eq!(right_pane_x(&view_model, &app_state), 75);
+}
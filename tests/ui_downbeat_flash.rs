@@ -0,0 +1,123 @@
+//! Tests for the downbeat border flash in Pads mode.
+
+use std::sync::mpsc;
+use std::time::Duration;
+
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::style::Color;
+use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui_explorer::{FileExplorer, Theme as ExplorerTheme};
+use termigroove::application::dto::loop_state::{LoopStateDto, LoopStatusDto};
+use termigroove::application::state::ApplicationState;
+use termigroove::audio::{SenderAudioBus, SystemClock};
+use termigroove::domain::r#loop::{LoopEngine, LoopSnapshot, RecordedEvent};
+use termigroove::domain::tempo::LoopLength;
+use termigroove::presentation::{Mode, ViewModel};
+use termigroove::ui::{draw_ui, is_downbeat_flash};
+
+fn setup_test_state() -> (ApplicationState, ViewModel) {
+    let (tx, _rx) = mpsc::channel();
+    let bus = SenderAudioBus::new(tx);
+    let loop_engine = LoopEngine::new(SystemClock::new(), bus);
+    let app_state = ApplicationState::new(loop_engine);
+    let theme = ExplorerTheme::default().with_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded),
+    );
+    let file_explorer = FileExplorer::with_theme(theme).expect("create file explorer");
+    let mut view_model = ViewModel::new(file_explorer);
+    view_model.mode = Mode::Pads;
+    (app_state, view_model)
+}
+
+fn playing(loop_length_ms: u64, offset_ms: u64) -> LoopStateDto {
+    LoopStateDto {
+        status: LoopStatusDto::Playing,
+        ticks_remaining: None,
+        loop_length: Duration::from_millis(loop_length_ms),
+        current_offset: Some(Duration::from_millis(offset_ms)),
+        saved_offset: None,
+        was_recording: None,
+        track_count: 1,
+        remaining: None,
+    }
+}
+
+fn corner_color(view_model: &ViewModel, app_state: &ApplicationState) -> Option<Color> {
+    let mut terminal = Terminal::new(TestBackend::new(80, 30)).expect("terminal");
+    terminal
+        .draw(|f| draw_ui(f, view_model, app_state))
+        .expect("draw");
+    terminal.backend().buffer()[(0, 0)].style().fg
+}
+
+/// Flash check for a loop of whole 4/4 bars.
+fn flash(loop_state: &LoopStateDto, bars: u16) -> bool {
+    is_downbeat_flash(loop_state, LoopLength::Bars(bars), 4)
+}
+
+#[test]
+fn flash_is_lit_only_right_after_each_downbeat() {
+    // 2 bars of 1s each
+    assert!(flash(&playing(2_000, 0), 2));
+    assert!(flash(&playing(2_000, 1_050), 2));
+    assert!(!flash(&playing(2_000, 300), 2));
+    assert!(!flash(&playing(2_000, 1_500), 2));
+    // Offsets past the loop wrap around
+    assert!(flash(&playing(2_000, 4_020), 2));
+
+    let mut paused = playing(2_000, 0);
+    paused.status = LoopStatusDto::Paused;
+    assert!(!flash(&paused, 2));
+}
+
+#[test]
+fn flash_rate_is_capped_for_short_bars() {
+    // 4 bars of 250ms: only every second downbeat flashes
+    assert!(flash(&playing(1_000, 0), 4));
+    assert!(!flash(&playing(1_000, 250), 4));
+    assert!(flash(&playing(1_000, 500), 4));
+}
+
+#[test]
+fn flash_keeps_full_bars_for_a_length_in_beats() {
+    // 6 beats of 500ms in 4/4: a 2s bar, then a half bar
+    let six_beats = LoopLength::Beats(6);
+    assert!(is_downbeat_flash(&playing(3_000, 0), six_beats, 4));
+    assert!(!is_downbeat_flash(&playing(3_000, 1_500), six_beats, 4));
+    assert!(is_downbeat_flash(&playing(3_000, 2_000), six_beats, 4));
+    // 3/4 bars of the same loop last 1.5s
+    assert!(is_downbeat_flash(&playing(3_000, 1_500), six_beats, 3));
+}
+
+#[test]
+fn border_brightens_on_downbeat_frame_when_enabled() {
+    let (mut app_state, view_model) = setup_test_state();
+    app_state.set_bars(1);
+    app_state.config.downbeat_flash = true;
+
+    // Idle: no flash
+    assert_eq!(corner_color(&view_model, &app_state), Some(Color::Green));
+
+    // Restoring starts playback at the top of the cycle, i.e. on the downbeat
+    app_state.restore_loop(LoopSnapshot {
+        loop_length: Duration::from_secs(10),
+        tracks: vec![
+            vec![RecordedEvent {
+                key: 'q',
+                offset: Duration::from_secs(5),
+                reversed: false,
+            }]
+            .into(),
+        ],
+    });
+    assert_eq!(
+        corner_color(&view_model, &app_state),
+        Some(Color::LightYellow)
+    );
+
+    app_state.config.downbeat_flash = false;
+    assert_eq!(corner_color(&view_model, &app_state), Some(Color::Green));
+}
@@ -0,0 +1,83 @@
+//! Tests for scrolling overlong footer messages.
+
+use std::sync::mpsc;
+
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui_explorer::{FileExplorer, Theme as ExplorerTheme};
+use termigroove::application::state::ApplicationState;
+use termigroove::audio::{SenderAudioBus, SystemClock};
+use termigroove::domain::r#loop::LoopEngine;
+use termigroove::presentation::{MARQUEE_STEP, ViewModel};
+use termigroove::ui::{draw_ui, marquee_window};
+
+const WIDTH: u16 = 40;
+const HEIGHT: u16 = 20;
+
+fn setup_test_state() -> (ApplicationState, ViewModel) {
+    let (tx, _rx) = mpsc::channel();
+    let bus = SenderAudioBus::new(tx);
+    let loop_engine = LoopEngine::new(SystemClock::new(), bus);
+    let mut app_state = ApplicationState::new(loop_engine);
+    app_state.config.marquee = true;
+    let theme = ExplorerTheme::default().with_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded),
+    );
+    let file_explorer = FileExplorer::with_theme(theme).expect("create file explorer");
+    let view_model = ViewModel::new(file_explorer);
+    (app_state, view_model)
+}
+
+fn footer_row(terminal: &Terminal<TestBackend>) -> String {
+    let buffer = terminal.backend().buffer();
+    (0..WIDTH)
+        .map(|x| buffer[(x, HEIGHT - 1)].symbol().to_string())
+        .collect()
+}
+
+#[test]
+fn marquee_window_wraps_after_a_gap() {
+    assert_eq!(marquee_window("abcdef", 4, 0), "abcd");
+    assert_eq!(marquee_window("abcdef", 4, 3), "def ");
+    assert_eq!(marquee_window("abcdef", 4, 8), "  ab");
+    assert_eq!(marquee_window("abcdef", 4, 10), "abcd");
+}
+
+#[test]
+fn long_status_message_scrolls_across_frames() {
+    let (app_state, mut view_model) = setup_test_state();
+    view_model.status_message =
+        "Selected files no longer exist: kick.wav, snare.wav, hat.wav".to_string();
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).expect("terminal");
+
+    let mut frames = Vec::new();
+    for _ in 0..3 {
+        terminal
+            .draw(|f| draw_ui(f, &view_model, &app_state))
+            .expect("draw");
+        frames.push(footer_row(&terminal));
+        std::thread::sleep(MARQUEE_STEP);
+    }
+
+    assert!(frames[0].starts_with("Selected files"), "{:?}", frames[0]);
+    assert!(frames[1].starts_with("elected files"), "{:?}", frames[1]);
+    assert!(frames[2].starts_with("lected files"), "{:?}", frames[2]);
+}
+
+#[test]
+fn short_status_message_stays_centered() {
+    let (app_state, mut view_model) = setup_test_state();
+    view_model.status_message = "Ready".to_string();
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).expect("terminal");
+
+    for _ in 0..2 {
+        terminal
+            .draw(|f| draw_ui(f, &view_model, &app_state))
+            .expect("draw");
+        assert_eq!(footer_row(&terminal).trim(), "Ready");
+        assert!(footer_row(&terminal).starts_with(' '));
+    }
+}
@@ -0,0 +1,67 @@
+//! Rendering tests for the Browse header on narrow terminals.
+
+use std::sync::mpsc;
+
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui_explorer::{FileExplorer, Theme as ExplorerTheme};
+use termigroove::application::state::ApplicationState;
+use termigroove::audio::{SenderAudioBus, SystemClock};
+use termigroove::domain::r#loop::LoopEngine;
+use termigroove::presentation::ViewModel;
+use termigroove::ui::{draw_ui, header_pixel_size};
+use tui_big_text::PixelSize;
+
+fn setup_test_state() -> (ApplicationState, ViewModel) {
+    let (tx, _rx) = mpsc::channel();
+    let bus = SenderAudioBus::new(tx);
+    let loop_engine = LoopEngine::new(SystemClock::new(), bus);
+    let app_state = ApplicationState::new(loop_engine);
+    let theme = ExplorerTheme::default().with_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded),
+    );
+    let file_explorer = FileExplorer::with_theme(theme).expect("create file explorer");
+    (app_state, ViewModel::new(file_explorer))
+}
+
+/// The seven big text rows of the header at `width` columns.
+fn header_rows(width: u16) -> Vec<String> {
+    let (app_state, view_model) = setup_test_state();
+    let mut terminal = Terminal::new(TestBackend::new(width, 30)).expect("terminal");
+    terminal
+        .draw(|f| draw_ui(f, &view_model, &app_state))
+        .expect("draw");
+    let buffer = terminal.backend().buffer();
+    (0..7)
+        .map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect())
+        .collect()
+}
+
+#[test]
+fn header_picks_the_largest_size_that_fits() {
+    // "WELCOME TO TERMIGROOVE" is 22 glyphs
+    assert_eq!(header_pixel_size(200), Some(PixelSize::Full));
+    assert_eq!(header_pixel_size(176), Some(PixelSize::Full));
+    assert_eq!(header_pixel_size(175), Some(PixelSize::Quadrant));
+    assert_eq!(header_pixel_size(88), Some(PixelSize::Quadrant));
+    assert_eq!(header_pixel_size(87), None);
+}
+
+#[test]
+fn narrow_terminal_falls_back_to_a_plain_title() {
+    let rows = header_rows(60);
+    assert!(rows.iter().all(|row| !row.contains('█')));
+    assert_eq!(
+        rows.iter()
+            .filter(|row| row.contains("WELCOME TO TERMIGROOVE"))
+            .count(),
+        1
+    );
+
+    let rows = header_rows(100);
+    assert!(rows.iter().any(|row| row.contains('█')));
+    assert!(rows.iter().all(|row| !row.contains("WELCOME")));
+}
@@ -0,0 +1,59 @@
+//! Rendering tests for collapsing the Browse header.
+
+use std::sync::mpsc;
+
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui_explorer::{FileExplorer, Theme as ExplorerTheme};
+use termigroove::application::state::ApplicationState;
+use termigroove::audio::{SenderAudioBus, SystemClock};
+use termigroove::domain::r#loop::LoopEngine;
+use termigroove::presentation::ViewModel;
+use termigroove::ui::draw_ui;
+
+fn setup_test_state() -> (ApplicationState, ViewModel) {
+    let (tx, _rx) = mpsc::channel();
+    let bus = SenderAudioBus::new(tx);
+    let loop_engine = LoopEngine::new(SystemClock::new(), bus);
+    let app_state = ApplicationState::new(loop_engine);
+    let theme = ExplorerTheme::default().with_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded),
+    );
+    let file_explorer = FileExplorer::with_theme(theme).expect("create file explorer");
+    (app_state, ViewModel::new(file_explorer))
+}
+
+/// Rows from the selection pane's top border down to the footer.
+fn body_height(view_model: &ViewModel, app_state: &ApplicationState) -> u16 {
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).expect("terminal");
+    terminal
+        .draw(|f| draw_ui(f, view_model, app_state))
+        .expect("draw");
+    let buffer = terminal.backend().buffer();
+    let top = (0..buffer.area.height)
+        .find(|&y| {
+            let row: String = (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect();
+            row.contains("Selected")
+        })
+        .expect("selection pane rendered");
+    buffer.area.height - 1 - top
+}
+
+#[test]
+fn collapsing_the_header_gives_its_rows_to_the_body() {
+    let (app_state, mut view_model) = setup_test_state();
+    assert!(!view_model.header_collapsed);
+    let full = body_height(&view_model, &app_state);
+    assert_eq!(full, 21);
+
+    assert_eq!(view_model.toggle_header(), "Header: compact");
+    assert_eq!(body_height(&view_model, &app_state), full + 7);
+
+    assert_eq!(view_model.toggle_header(), "Header: full");
+    assert_eq!(body_height(&view_model, &app_state), full);
+}
@@ -0,0 +1,63 @@
+//! Rendering tests for the key binding help overlay.
+
+use std::sync::mpsc;
+
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui_explorer::{FileExplorer, Theme as ExplorerTheme};
+use termigroove::application::state::ApplicationState;
+use termigroove::audio::{SenderAudioBus, SystemClock};
+use termigroove::domain::r#loop::LoopEngine;
+use termigroove::presentation::{Mode, ViewModel};
+use termigroove::ui::draw_ui;
+
+fn setup_test_state() -> (ApplicationState, ViewModel) {
+    let (tx, _rx) = mpsc::channel();
+    let bus = SenderAudioBus::new(tx);
+    let loop_engine = LoopEngine::new(SystemClock::new(), bus);
+    let app_state = ApplicationState::new(loop_engine);
+    let theme = ExplorerTheme::default().with_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded),
+    );
+    let file_explorer = FileExplorer::with_theme(theme).expect("create file explorer");
+    (app_state, ViewModel::new(file_explorer))
+}
+
+fn screen(view_model: &ViewModel, app_state: &ApplicationState) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(120, 50)).expect("terminal");
+    terminal
+        .draw(|f| draw_ui(f, view_model, app_state))
+        .expect("draw");
+    let buffer = terminal.backend().buffer();
+    (0..buffer.area.height)
+        .map(|y| {
+            (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn help_overlay_lists_browse_and_pads_keys_separately() {
+    let (app_state, mut view_model) = setup_test_state();
+    assert!(!screen(&view_model, &app_state).contains("record/play/pause"));
+
+    view_model.help_open = true;
+    for mode in [Mode::Browse, Mode::Pads] {
+        view_model.mode = mode;
+        let screen = screen(&view_model, &app_state);
+        assert!(screen.contains("Keys (? or Esc to close)"));
+        let browse = screen.find(" Browse ").expect("browse section");
+        let pads = screen.find(" Pads ").expect("pads section");
+        assert!(browse < pads);
+        assert!(screen.contains("select file"));
+        assert!(screen.contains("Space record/play/pause"));
+        assert!(screen.contains("Ctrl+R reset audio"));
+        assert!(screen.contains(" Arrange "));
+    }
+}
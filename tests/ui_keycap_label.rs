@@ -0,0 +1,15 @@
+use termigroove::ui::keycap_label;
+
+#[test]
+fn letters_are_uppercase_single_chars() {
+    assert_eq!(keycap_label('q'), "Q");
+    assert_eq!(keycap_label('m'), "M");
+}
+
+#[test]
+fn punctuation_keys_get_descriptors() {
+    assert_eq!(keycap_label(';'), "; semi");
+    assert_eq!(keycap_label(','), ", comma");
+    assert_eq!(keycap_label('.'), ". dot");
+    assert_eq!(keycap_label('/'), "/ slash");
+}
@@ -0,0 +1,154 @@
+//! Tests for the loop progress bar in the summary box.
+
+use std::time::Duration;
+
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::style::Color;
+use termigroove::application::dto::loop_state::{LoopStateDto, LoopStatusDto};
+use termigroove::ui::{
+    GridLine, loop_progress, render_loop_progress, render_loop_timeline, timeline_gridlines,
+};
+
+fn loop_state(status: LoopStatusDto, loop_length_ms: u64, offset_ms: Option<u64>) -> LoopStateDto {
+    LoopStateDto {
+        status,
+        ticks_remaining: None,
+        loop_length: Duration::from_millis(loop_length_ms),
+        current_offset: offset_ms.map(Duration::from_millis),
+        saved_offset: None,
+        was_recording: None,
+        track_count: 1,
+        remaining: None,
+    }
+}
+
+fn filled_cells(state: &LoopStateDto) -> usize {
+    let mut terminal = Terminal::new(TestBackend::new(20, 1)).expect("terminal");
+    terminal
+        .draw(|f| render_loop_progress(f, f.area(), state))
+        .expect("draw");
+    let buffer = terminal.backend().buffer();
+    (0..20).filter(|&x| buffer[(x, 0)].symbol() == "━").count()
+}
+
+#[test]
+fn progress_fills_as_the_loop_plays_and_resets_each_cycle() {
+    let quarter = loop_state(LoopStatusDto::Playing, 4_000, Some(1_000));
+    assert_eq!(loop_progress(&quarter), Some(0.25));
+    assert_eq!(filled_cells(&quarter), 5);
+
+    let late = loop_state(LoopStatusDto::Playing, 4_000, Some(3_000));
+    assert_eq!(filled_cells(&late), 15);
+
+    // An offset past the end has wrapped into the next cycle
+    let wrapped = loop_state(LoopStatusDto::Playing, 4_000, Some(4_400));
+    assert_eq!(filled_cells(&wrapped), 2);
+}
+
+#[test]
+fn progress_is_empty_without_a_running_loop() {
+    let idle = loop_state(LoopStatusDto::Idle, 0, None);
+    assert_eq!(loop_progress(&idle), None);
+    assert_eq!(filled_cells(&idle), 0);
+
+    let counting_in = loop_state(LoopStatusDto::Ready, 4_000, None);
+    assert_eq!(filled_cells(&counting_in), 0);
+}
+
+#[test]
+fn summary_box_shows_the_progress_track() {
+    use ratatui::widgets::{Block, BorderType, Borders};
+    use ratatui_explorer::{FileExplorer, Theme as ExplorerTheme};
+    use std::sync::mpsc;
+    use termigroove::application::state::ApplicationState;
+    use termigroove::audio::{SenderAudioBus, SystemClock};
+    use termigroove::domain::r#loop::LoopEngine;
+    use termigroove::presentation::{Mode, ViewModel};
+    use termigroove::ui::draw_ui;
+
+    let (tx, _rx) = mpsc::channel();
+    let app_state =
+        ApplicationState::new(LoopEngine::new(SystemClock::new(), SenderAudioBus::new(tx)));
+    let theme = ExplorerTheme::default().with_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded),
+    );
+    let mut view_model = ViewModel::new(FileExplorer::with_theme(theme).expect("explorer"));
+    view_model.mode = Mode::Pads;
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 30)).expect("terminal");
+    terminal
+        .draw(|f| draw_ui(f, &view_model, &app_state))
+        .expect("draw");
+    let buffer = terminal.backend().buffer();
+    // The empty track is the only dark gray line; borders are colored
+    let track_cells = (0..30)
+        .flat_map(|y| (0..80).map(move |x| (x, y)))
+        .filter(|&pos| {
+            let cell = &buffer[pos];
+            cell.symbol() == "─" && cell.style().fg == Some(Color::DarkGray)
+        })
+        .count();
+    assert!(track_cells > 40, "no progress track drawn");
+}
+
+fn columns(lines: &[GridLine]) -> Vec<(usize, bool)> {
+    lines.iter().map(|line| (line.column, line.bar)).collect()
+}
+
+#[test]
+fn gridlines_mark_each_beat_and_bar_across_the_width() {
+    assert_eq!(
+        columns(&timeline_gridlines(4, 4, 20)),
+        vec![(5, false), (10, false), (15, false)]
+    );
+    assert_eq!(
+        columns(&timeline_gridlines(8, 4, 40)),
+        vec![
+            (5, false),
+            (10, false),
+            (15, false),
+            (20, true),
+            (25, false),
+            (30, false),
+            (35, false)
+        ]
+    );
+}
+
+#[test]
+fn gridlines_for_a_sub_bar_loop_are_beats_only() {
+    assert_eq!(
+        columns(&timeline_gridlines(3, 4, 30)),
+        vec![(10, false), (20, false)]
+    );
+}
+
+#[test]
+fn gridlines_thin_out_when_the_timeline_is_narrow() {
+    assert_eq!(
+        columns(&timeline_gridlines(16, 4, 20)),
+        vec![(5, true), (10, true), (15, true)]
+    );
+    assert!(timeline_gridlines(64, 4, 20).is_empty());
+    assert!(timeline_gridlines(0, 4, 20).is_empty());
+}
+
+#[test]
+fn gridlines_render_as_faint_marks_over_the_track() {
+    let state = loop_state(LoopStatusDto::Playing, 4_000, Some(2_000));
+    let mut terminal = Terminal::new(TestBackend::new(20, 1)).expect("terminal");
+    terminal
+        .draw(|f| render_loop_timeline(f, f.area(), &state, &timeline_gridlines(4, 4, 20)))
+        .expect("draw");
+    let buffer = terminal.backend().buffer();
+    let marks: Vec<u16> = (0..20)
+        .filter(|&x| buffer[(x, 0)].symbol() == "╎")
+        .collect();
+    assert_eq!(marks, vec![5, 10, 15]);
+    assert_eq!(buffer[(5, 0)].fg, Color::DarkGray);
+    assert_eq!(buffer[(4, 0)].symbol(), "━");
+    assert_eq!(buffer[(12, 0)].symbol(), "─");
+}
@@ -0,0 +1,180 @@
+//! Tests for the pad grid layout in Pads mode.
+
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui_explorer::{FileExplorer, Theme as ExplorerTheme};
+use termigroove::application::state::ApplicationState;
+use termigroove::audio::{SenderAudioBus, SystemClock};
+use termigroove::domain::r#loop::LoopEngine;
+use termigroove::presentation::{Mode, ViewModel};
+use termigroove::ui::draw_ui;
+
+fn setup_test_state() -> (ApplicationState, ViewModel) {
+    let (tx, _rx) = mpsc::channel();
+    let bus = SenderAudioBus::new(tx);
+    let loop_engine = LoopEngine::new(SystemClock::new(), bus);
+    let app_state = ApplicationState::new(loop_engine);
+    let theme = ExplorerTheme::default().with_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded),
+    );
+    let file_explorer = FileExplorer::with_theme(theme).expect("create file explorer");
+    let mut view_model = ViewModel::new(file_explorer);
+    view_model.mode = Mode::Pads;
+    (app_state, view_model)
+}
+
+fn sample_file(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("termigroove-ui-pad-grid-tests");
+    std::fs::create_dir_all(&dir).expect("create sample dir");
+    let path = dir.join(name);
+    std::fs::write(&path, b"").expect("create sample file");
+    path
+}
+
+/// Screen position of the first cell showing `symbol` below the summary.
+fn keycap_position(terminal: &Terminal<TestBackend>, symbol: &str) -> (u16, u16) {
+    let buffer = terminal.backend().buffer();
+    let area = buffer.area;
+    (area.height * 3 / 10..area.height)
+        .flat_map(|y| (0..area.width).map(move |x| (x, y)))
+        .find(|&(x, y)| buffer[(x, y)].symbol() == symbol)
+        .unwrap_or_else(|| panic!("keycap {symbol} not rendered"))
+}
+
+#[test]
+fn pad_grid_wraps_at_configured_column_count_in_stable_order() {
+    let (mut app_state, view_model) = setup_test_state();
+    for name in ["a.wav", "b.wav", "c.wav", "d.wav"] {
+        app_state.selection.add_file(sample_file(name));
+    }
+    app_state.enter_pads().expect("enter pads");
+    app_state.config.max_pad_columns = Some(2);
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 30)).expect("terminal");
+    terminal
+        .draw(|f| draw_ui(f, &view_model, &app_state))
+        .expect("draw");
+
+    // Pads are laid out in keyboard order: Q W / E R
+    let q = keycap_position(&terminal, "Q");
+    let w = keycap_position(&terminal, "W");
+    let e = keycap_position(&terminal, "E");
+    let r = keycap_position(&terminal, "R");
+    assert_eq!(q.1, w.1);
+    assert!(w.0 > q.0);
+    assert!(e.1 > q.1);
+    assert_eq!(e.0, q.0);
+    assert_eq!((r.0, r.1), (w.0, e.1));
+}
+
+#[test]
+fn pads_line_up_with_the_keyboard_rows() {
+    let (mut app_state, view_model) = setup_test_state();
+    for index in 0..12 {
+        app_state
+            .selection
+            .add_file(sample_file(&format!("row-{index:02}.wav")));
+    }
+    app_state.enter_pads().expect("enter pads");
+    app_state.config.pad_grid_columns = Some(10);
+
+    let mut terminal = Terminal::new(TestBackend::new(160, 40)).expect("terminal");
+    terminal
+        .draw(|f| draw_ui(f, &view_model, &app_state))
+        .expect("draw");
+
+    // The top keyboard row reads left to right; the home row starts below Q
+    let top: Vec<(u16, u16)> = ["Q", "W", "E", "R", "T", "Y", "U", "I", "O", "P"]
+        .iter()
+        .map(|key| keycap_position(&terminal, key))
+        .collect();
+    assert!(top.iter().all(|(_, y)| *y == top[0].1));
+    assert!(top.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    let a = keycap_position(&terminal, "A");
+    let s = keycap_position(&terminal, "S");
+    assert_eq!(a.0, top[0].0);
+    assert!(a.1 > top[0].1);
+    assert_eq!((s.0, s.1), (top[1].0, a.1));
+}
+
+#[test]
+fn compute_grid_keeps_preferred_columns_and_fits_otherwise() {
+    use termigroove::ui::compute_grid;
+
+    // Automatic: one row up to the cap, then wrap
+    assert_eq!(compute_grid(3, None, 10), (1, 3));
+    assert_eq!(compute_grid(25, None, 10), (3, 10));
+    assert_eq!(compute_grid(0, None, 10), (1, 1));
+    // Preferred columns, but never more than the pads or the cap allow
+    assert_eq!(compute_grid(16, Some(4), 10), (4, 4));
+    assert_eq!(compute_grid(3, Some(4), 10), (1, 3));
+    assert_eq!(compute_grid(17, Some(4), 2), (9, 2));
+    assert_eq!(compute_grid(5, Some(0), 10), (5, 1));
+}
+
+#[test]
+fn sixteen_pads_render_as_a_square_grid_with_four_columns() {
+    let (mut app_state, view_model) = setup_test_state();
+    for index in 0..16 {
+        app_state
+            .selection
+            .add_file(sample_file(&format!("pad-{index:02}.wav")));
+    }
+    app_state.enter_pads().expect("enter pads");
+    app_state.config.pad_grid_columns = Some(4);
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 60)).expect("terminal");
+    terminal
+        .draw(|f| draw_ui(f, &view_model, &app_state))
+        .expect("draw");
+
+    // Pads are laid out in keyboard order, four to a row: Q W E R / T Y U I / ...
+    let rows: Vec<u16> = ["Q", "W", "E", "R", "T", "Y", "U", "I", "O", "P", "A", "S"]
+        .iter()
+        .map(|key| keycap_position(&terminal, key).1)
+        .collect();
+    assert!(rows[..4].iter().all(|y| *y == rows[0]));
+    assert!(rows[4..8].iter().all(|y| *y == rows[4]));
+    assert!(rows[8..].iter().all(|y| *y == rows[8]));
+    assert!(rows[0] < rows[4] && rows[4] < rows[8]);
+    assert_eq!(
+        keycap_position(&terminal, "Q").0,
+        keycap_position(&terminal, "T").0
+    );
+}
+
+#[test]
+fn level_meter_fills_in_proportion_and_is_empty_when_silent() {
+    use termigroove::ui::level_meter;
+
+    assert_eq!(level_meter(0.0, 4), "    ");
+    assert_eq!(level_meter(0.5, 4), "▮▮  ");
+    assert_eq!(level_meter(1.0, 4), "▮▮▮▮");
+    assert_eq!(level_meter(3.0, 4), "▮▮▮▮");
+}
+
+#[test]
+fn bus_label_shows_routing_and_level() {
+    use termigroove::audio::BusLevel;
+    use termigroove::ui::bus_label;
+
+    let unity = BusLevel::default();
+    assert_eq!(bus_label(0, unity), None);
+    assert_eq!(bus_label(2, unity).as_deref(), Some("B2"));
+    let quiet = BusLevel {
+        volume: 0.6,
+        muted: false,
+    };
+    assert_eq!(bus_label(1, quiet).as_deref(), Some("B1 60%"));
+    let muted = BusLevel {
+        volume: 0.6,
+        muted: true,
+    };
+    assert_eq!(bus_label(0, muted).as_deref(), Some("B0 off"));
+}
@@ -0,0 +1,54 @@
+//! Tests for the bar.beat position shown in the summary.
+
+use std::time::Duration;
+
+use termigroove::application::dto::loop_state::{LoopStateDto, LoopStatusDto};
+use termigroove::domain::tempo::LoopLength;
+use termigroove::domain::timing::TimeSignature;
+use termigroove::ui::{position_label, position_label_in};
+
+fn loop_state(loop_length_ms: u64, offset_ms: Option<u64>) -> LoopStateDto {
+    LoopStateDto {
+        status: LoopStatusDto::Playing,
+        ticks_remaining: None,
+        loop_length: Duration::from_millis(loop_length_ms),
+        current_offset: offset_ms.map(Duration::from_millis),
+        saved_offset: None,
+        was_recording: None,
+        track_count: 1,
+        remaining: None,
+    }
+}
+
+#[test]
+fn position_reads_bar_and_beat() {
+    let state = loop_state(4_000, Some(3_100));
+    assert_eq!(position_label(&state, LoopLength::Bars(2)), "2.3");
+}
+
+#[test]
+fn position_in_a_beat_length_loop_keeps_beats_in_time() {
+    // 6 beats over 3 seconds: the last bar holds beats 1 and 2
+    let state = loop_state(3_000, Some(2_600));
+    assert_eq!(position_label(&state, LoopLength::Beats(6)), "2.2");
+}
+
+#[test]
+fn position_is_a_dash_without_a_running_loop() {
+    assert_eq!(
+        position_label(&loop_state(0, None), LoopLength::Bars(4)),
+        "—"
+    );
+    assert_eq!(
+        position_label(&loop_state(0, Some(100)), LoopLength::Bars(4)),
+        "—"
+    );
+}
+
+#[test]
+fn position_counts_bars_of_the_time_signature() {
+    // 2 bars of 3/4 over 3 seconds: each beat lasts 500 ms
+    let state = loop_state(3_000, Some(2_100));
+    let waltz = TimeSignature::new(3, 4).unwrap();
+    assert_eq!(position_label_in(&state, LoopLength::Bars(2), waltz), "2.2");
+}
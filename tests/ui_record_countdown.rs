@@ -0,0 +1,78 @@
+//! Tests for the remaining record and cycle time and the empty-loop hint
+//! shown in the summary.
+
+use std::time::Duration;
+
+use termigroove::application::dto::loop_state::{LoopStateDto, LoopStatusDto};
+use termigroove::ui::{empty_loop_hint, recording_time_left, wrap_countdown};
+
+fn loop_state(status: LoopStatusDto, loop_length_ms: u64, offset_ms: u64) -> LoopStateDto {
+    LoopStateDto {
+        status,
+        ticks_remaining: None,
+        loop_length: Duration::from_millis(loop_length_ms),
+        current_offset: Some(Duration::from_millis(offset_ms)),
+        saved_offset: None,
+        was_recording: None,
+        track_count: 0,
+        remaining: None,
+    }
+}
+
+#[test]
+fn countdown_shows_remaining_loop_time_while_recording() {
+    let state = loop_state(LoopStatusDto::Recording, 4_000, 2_800);
+    assert_eq!(recording_time_left(&state).as_deref(), Some("1.2s left"));
+}
+
+#[test]
+fn countdown_clamps_at_zero_past_the_loop_end() {
+    let state = loop_state(LoopStatusDto::Recording, 4_000, 4_300);
+    assert_eq!(recording_time_left(&state).as_deref(), Some("0.0s left"));
+}
+
+#[test]
+fn countdown_is_hidden_outside_recording() {
+    let state = loop_state(LoopStatusDto::Playing, 4_000, 2_800);
+    assert_eq!(recording_time_left(&state), None);
+}
+
+#[test]
+fn empty_playing_loop_shows_the_overdub_hint() {
+    let empty = loop_state(LoopStatusDto::Playing, 4_000, 2_800);
+    assert_eq!(
+        empty_loop_hint(&empty),
+        Some("Empty loop — play pads to overdub")
+    );
+
+    let filled = LoopStateDto {
+        track_count: 1,
+        remaining: None,
+        ..empty.clone()
+    };
+    assert_eq!(empty_loop_hint(&filled), None);
+    let recording = loop_state(LoopStatusDto::Recording, 4_000, 2_800);
+    assert_eq!(empty_loop_hint(&recording), None);
+}
+
+#[test]
+fn wrap_countdown_shows_minutes_seconds_and_tenths_while_playing() {
+    let playing = |remaining_ms| LoopStateDto {
+        remaining: Some(Duration::from_millis(remaining_ms)),
+        ..loop_state(LoopStatusDto::Playing, 4_000, 0)
+    };
+    assert_eq!(
+        wrap_countdown(&playing(1_350)).as_deref(),
+        Some("0:01.3 to wrap")
+    );
+    assert_eq!(
+        wrap_countdown(&playing(75_000)).as_deref(),
+        Some("1:15.0 to wrap")
+    );
+
+    let recording = LoopStateDto {
+        remaining: Some(Duration::from_millis(1_350)),
+        ..loop_state(LoopStatusDto::Recording, 4_000, 2_650)
+    };
+    assert_eq!(wrap_countdown(&recording), None);
+}
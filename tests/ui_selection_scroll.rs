@@ -0,0 +1,71 @@
+//! Tests for scrolling the Browse selection pane past its height.
+
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui_explorer::{FileExplorer, Theme as ExplorerTheme};
+use termigroove::application::state::ApplicationState;
+use termigroove::audio::{SenderAudioBus, SystemClock};
+use termigroove::domain::r#loop::LoopEngine;
+use termigroove::presentation::ViewModel;
+use termigroove::ui::{draw_ui, selection_offset};
+
+fn setup_test_state() -> (ApplicationState, ViewModel) {
+    let (tx, _rx) = mpsc::channel();
+    let bus = SenderAudioBus::new(tx);
+    let loop_engine = LoopEngine::new(SystemClock::new(), bus);
+    let app_state = ApplicationState::new(loop_engine);
+    let theme = ExplorerTheme::default().with_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded),
+    );
+    let file_explorer = FileExplorer::with_theme(theme).expect("create file explorer");
+    (app_state, ViewModel::new(file_explorer))
+}
+
+fn screen(terminal: &Terminal<TestBackend>) -> String {
+    let buffer = terminal.backend().buffer();
+    (0..buffer.area.height)
+        .map(|y| {
+            (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn offset_keeps_the_cursor_centered_until_the_list_ends() {
+    assert_eq!(selection_offset(0, 40, 10), 0);
+    assert_eq!(selection_offset(4, 40, 10), 0);
+    assert_eq!(selection_offset(20, 40, 10), 15);
+    assert_eq!(selection_offset(39, 40, 10), 30);
+    // Short lists never scroll
+    assert_eq!(selection_offset(5, 8, 10), 0);
+    assert_eq!(selection_offset(3, 40, 0), 3);
+}
+
+#[test]
+fn cursor_far_down_a_long_selection_stays_visible() {
+    let (mut app_state, view_model) = setup_test_state();
+    for idx in 0..40 {
+        app_state
+            .selection
+            .add_file(PathBuf::from(format!("/tmp/hit-{idx:02}.wav")));
+    }
+    app_state.selection.right_idx = 33;
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).expect("terminal");
+    terminal
+        .draw(|f| draw_ui(f, &view_model, &app_state))
+        .expect("draw");
+
+    let screen = screen(&terminal);
+    assert!(screen.contains("hit-33.wav"));
+    assert!(!screen.contains("hit-00.wav"));
+}
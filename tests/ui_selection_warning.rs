@@ -0,0 +1,131 @@
+//! Rendering tests for unsupported files in the Browse selection pane.
+
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::style::Color;
+use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui_explorer::{FileExplorer, Theme as ExplorerTheme};
+use termigroove::application::state::ApplicationState;
+use termigroove::audio::{SenderAudioBus, SystemClock};
+use termigroove::domain::r#loop::LoopEngine;
+use termigroove::presentation::ViewModel;
+use termigroove::ui::draw_ui;
+
+fn setup_test_state() -> (ApplicationState, ViewModel) {
+    let (tx, _rx) = mpsc::channel();
+    let bus = SenderAudioBus::new(tx);
+    let loop_engine = LoopEngine::new(SystemClock::new(), bus);
+    let app_state = ApplicationState::new(loop_engine);
+    let theme = ExplorerTheme::default().with_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded),
+    );
+    let file_explorer = FileExplorer::with_theme(theme).expect("create file explorer");
+    (app_state, ViewModel::new(file_explorer))
+}
+
+/// Find the row containing `needle` and return its text and the color of its first char.
+fn find_row(terminal: &Terminal<TestBackend>, needle: &str) -> (String, Option<Color>) {
+    let buffer = terminal.backend().buffer();
+    for y in 0..buffer.area.height {
+        let row: String = (0..buffer.area.width)
+            .map(|x| buffer[(x, y)].symbol())
+            .collect();
+        if let Some(byte_idx) = row.find(needle) {
+            let col = row[..byte_idx].chars().count() as u16;
+            return (row, buffer[(col, y)].style().fg);
+        }
+    }
+    panic!("{needle} not rendered");
+}
+
+#[test]
+fn non_audio_selection_renders_with_warning_style() {
+    let (mut app_state, view_model) = setup_test_state();
+    app_state.selection.add_file(PathBuf::from("/tmp/kick.wav"));
+    app_state
+        .selection
+        .add_file(PathBuf::from("/tmp/notes.txt"));
+    // Keep the cursor (highlight) off the rows under test
+    app_state
+        .selection
+        .add_file(PathBuf::from("/tmp/snare.wav"));
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).expect("terminal");
+    terminal
+        .draw(|f| draw_ui(f, &view_model, &app_state))
+        .expect("draw");
+
+    let (row, color) = find_row(&terminal, "kick.wav");
+    assert_eq!(color, Some(Color::Green));
+    assert!(!row.contains('⚠'));
+
+    let (row, color) = find_row(&terminal, "notes.txt");
+    assert_eq!(color, Some(Color::Yellow));
+    assert!(row.contains("⚠ notes.txt"));
+}
+
+#[test]
+fn selection_cursor_uses_configured_highlight_symbol() {
+    let (mut app_state, view_model) = setup_test_state();
+    app_state.selection.add_file(PathBuf::from("/tmp/kick.wav"));
+    app_state.config.highlight_symbol = Some("> ".to_string());
+    app_state.config.highlight_color = Some("yellow".to_string());
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).expect("terminal");
+    terminal
+        .draw(|f| draw_ui(f, &view_model, &app_state))
+        .expect("draw");
+
+    let (row, _) = find_row(&terminal, "kick.wav");
+    assert!(row.contains("> kick.wav"));
+    assert!(!row.contains('▶'));
+    // The cursor row is painted in the configured color
+    let buffer = terminal.backend().buffer();
+    let painted = (0..buffer.area.height)
+        .flat_map(|y| (0..buffer.area.width).map(move |x| (x, y)))
+        .any(|(x, y)| buffer[(x, y)].symbol() == ">" && buffer[(x, y)].bg == Color::Yellow);
+    assert!(painted);
+}
+
+#[test]
+fn selection_rows_show_wav_duration_and_sample_rate() {
+    let dir = std::env::temp_dir().join("termigroove-ui-selection-info");
+    std::fs::create_dir_all(&dir).expect("create sample dir");
+    let path = dir.join("kick.wav");
+    // 0.5s of 22.05kHz mono 8-bit audio
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"RIFF\0\0\0\0WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    for field in [1u16, 1] {
+        bytes.extend_from_slice(&field.to_le_bytes());
+    }
+    bytes.extend_from_slice(&22_050u32.to_le_bytes());
+    bytes.extend_from_slice(&22_050u32.to_le_bytes());
+    for field in [1u16, 8] {
+        bytes.extend_from_slice(&field.to_le_bytes());
+    }
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&11_025u32.to_le_bytes());
+    std::fs::write(&path, bytes).expect("write wav");
+
+    let (mut app_state, view_model) = setup_test_state();
+    app_state.selection.add_file(path);
+    app_state
+        .selection
+        .add_file(PathBuf::from("/tmp/snare.wav"));
+
+    let mut terminal = Terminal::new(TestBackend::new(160, 30)).expect("terminal");
+    terminal
+        .draw(|f| draw_ui(f, &view_model, &app_state))
+        .expect("draw");
+
+    let (row, _) = find_row(&terminal, "kick.wav");
+    assert!(row.contains("kick.wav (0.5s, 22.05kHz)"), "{row}");
+    let (row, _) = find_row(&terminal, "snare.wav");
+    assert!(!row.contains("snare.wav ("));
+}